@@ -0,0 +1,147 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn main() {
+    let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let json_dir = manifest_dir.join("json");
+
+    println!("cargo:rerun-if-changed=json");
+    println!("cargo:rerun-if-env-changed=RINGHOPPER_DEFINITIONS_REVISION");
+
+    // Provenance: a digest of all embedded JSON files plus the revision they came from.
+    let mut files = Vec::new();
+    collect_json_files(&json_dir, &mut files);
+    files.retain(|f| is_embedded(&f.strip_prefix(&json_dir).unwrap().to_string_lossy().replace('\\', "/")));
+    files.sort();
+
+    let mut digest = Fnv1a::new();
+    for f in &files {
+        let relative = f.strip_prefix(&json_dir).unwrap().to_string_lossy().replace('\\', "/");
+        digest.write(relative.as_bytes());
+        digest.write(&[0]);
+        digest.write(&std::fs::read(f).unwrap_or_else(|e| panic!("failed to read {}: {e}", f.display())));
+    }
+
+    println!("cargo:rustc-env=RINGHOPPER_DEFINITIONS_JSON_DIGEST={:016x}", digest.finish());
+    println!("cargo:rustc-env=RINGHOPPER_DEFINITIONS_FILE_COUNT={}", files.len());
+
+    embed_precompiled_definitions(digest.finish());
+
+    let revision = std::env::var("RINGHOPPER_DEFINITIONS_REVISION").ok().or_else(|| git_revision(&manifest_dir));
+    if let Some(revision) = revision {
+        println!("cargo:rustc-env=RINGHOPPER_DEFINITIONS_REVISION={revision}");
+    }
+}
+
+/// Format constants shared with `src/types/binary.rs`.
+mod binary_header {
+    include!("src/types/binary_header.rs");
+}
+
+/// Embed definitions written by `precompile_builtin_definitions` if they match the JSON.
+fn embed_precompiled_definitions(json_digest: u64) {
+    use binary_header::{BINARY_FORMAT_VERSION, BINARY_MAGIC};
+
+    println!("cargo:rustc-check-cfg=cfg(precompiled_definitions)");
+    println!("cargo:rerun-if-env-changed=RINGHOPPER_DEFINITIONS_PRECOMPILED");
+    let Some(path) = std::env::var_os("RINGHOPPER_DEFINITIONS_PRECOMPILED") else {
+        return
+    };
+    if std::env::var_os("CARGO_FEATURE_BINARY").is_none() {
+        println!("cargo:warning=RINGHOPPER_DEFINITIONS_PRECOMPILED is ignored without the binary feature");
+        return
+    }
+
+    let path = PathBuf::from(path);
+    println!("cargo:rerun-if-changed={}", path.display());
+    let data = std::fs::read(&path).unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+    let header_matches = data.len() >= 16
+        && data[0..4] == BINARY_MAGIC
+        && data[4..8] == BINARY_FORMAT_VERSION.to_le_bytes()
+        && data[8..16] == json_digest.to_le_bytes();
+    if !header_matches {
+        println!("cargo:warning={} is outdated and will not be used; regenerate it with precompile_builtin_definitions", path.display());
+        return
+    }
+
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    std::fs::write(out_dir.join("definitions.bin"), data).expect("failed to write precompiled definitions");
+    println!("cargo:rustc-cfg=precompiled_definitions");
+}
+
+/// Whether a JSON file (relative to the json folder) is embedded with the enabled features.
+fn is_embedded(relative: &str) -> bool {
+    // Must match the cfgs in get_tag_definitions and get_engine_definitions in src/types/parse.rs.
+    let feature = match relative.rsplit('/').next().unwrap_or(relative) {
+        "ui_widget_definition.json" | "ui_widget_collection.json" | "virtual_keyboard.json" | "multiplayer_scenario_description.json" => "GROUPS_UI",
+        "preferences_network_game.json" | "input_device_defaults.json" => "GROUPS_PREFERENCES",
+        _ if !relative.starts_with("engine/") => return true,
+        f if f.starts_with("halo pc") => "ENGINES_PC",
+        f if f.starts_with("halo xbox") => "ENGINES_XBOX",
+        f if f.starts_with("halo macintosh") => "ENGINES_MAC",
+        f if f.starts_with("halo mcc") => "ENGINES_MCC",
+        f => panic!("engine file {f} does not belong to an engines feature")
+    };
+    std::env::var_os(format!("CARGO_FEATURE_{feature}")).is_some()
+}
+
+fn collect_json_files(directory: &Path, files: &mut Vec<PathBuf>) {
+    for entry in std::fs::read_dir(directory).unwrap_or_else(|e| panic!("failed to read {}: {e}", directory.display())) {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_json_files(&path, files);
+        }
+        else if path.extension().is_some_and(|e| e == "json") {
+            files.push(path);
+        }
+    }
+}
+
+/// Get the revision of the crate's own git checkout.
+///
+/// Only a `.git` directly in the crate is used, so a crate unpacked inside of some other repository
+/// (e.g. vendored dependencies) does not pick up that repository's revision.
+fn git_revision(manifest_dir: &Path) -> Option<String> {
+    let git_dir = manifest_dir.join(".git");
+    if !git_dir.exists() {
+        return None
+    }
+
+    // HEAD only changes when switching branches, so also watch the branch it points to.
+    if git_dir.is_dir() {
+        let head = git_dir.join("HEAD");
+        println!("cargo:rerun-if-changed={}", head.display());
+        let branch = std::fs::read_to_string(&head).ok().and_then(|h| h.strip_prefix("ref: ").map(|r| r.trim().to_owned()));
+        // Missing files would make cargo rerun this every build, and a branch's ref may only be
+        // in packed-refs.
+        for path in branch.into_iter().chain(Some("packed-refs".to_owned())).map(|p| git_dir.join(p)).filter(|p| p.is_file()) {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+    }
+
+    let output = Command::new("git").arg("rev-parse").arg("HEAD").current_dir(manifest_dir).output().ok()?;
+    if !output.status.success() {
+        return None
+    }
+    let revision = String::from_utf8(output.stdout).ok()?.trim().to_owned();
+    (!revision.is_empty()).then_some(revision)
+}
+
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 ^= *b as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
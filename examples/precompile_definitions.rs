@@ -0,0 +1,11 @@
+//! Writes the built-in definitions in the binary format to the given path.
+//!
+//! ```text
+//! cargo run --example precompile_definitions --features binary -- definitions.bin
+//! RINGHOPPER_DEFINITIONS_PRECOMPILED=$PWD/definitions.bin cargo build --features binary
+//! ```
+
+fn main() {
+    let path = std::env::args_os().nth(1).expect("usage: precompile_definitions <output>");
+    std::fs::write(&path, ringhopper_definitions::precompile_builtin_definitions()).expect("failed to write precompiled definitions");
+}
@@ -7,34 +7,148 @@
 extern crate alloc;
 extern crate serde_json;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 mod types;
 
 use spin::lazy::Lazy;
 pub use types::*;
 
 /// Load all built-in definitions.
-static DEFINITIONS: Lazy<ParsedDefinitions> = Lazy::new(|| {
-    let values = get_all_definitions();
+static DEFINITIONS: Lazy<ParsedDefinitions> = Lazy::new(ParsedDefinitions::load_builtin);
+
+/// Load only the built-in engine definitions.
+static ENGINE_DEFINITIONS: Lazy<ParsedDefinitions> = Lazy::new(|| {
+    let values = get_engine_definitions();
+    let mut parsed = ParsedDefinitions::default();
+    if !ALL_BUILTIN_ENGINES {
+        parsed.set_unknown_engine_policy(UnknownEnginePolicy::TreatAsUnsupported);
+    }
+    parsed.load_from_json(&values);
+    parsed.finalize();
+
+    parsed
+});
+
+/// Load only the built-in tag and map definitions.
+static TAG_DEFINITIONS: Lazy<ParsedDefinitions> = Lazy::new(|| {
+    let values = get_tag_definitions();
     let mut parsed = ParsedDefinitions::default();
     parsed.load_from_json(&values);
-    parsed.finalize_and_assert_valid();
-    parsed.resolve_parent_class_references();
-    parsed.find_const_structs();
+    parsed.finalize_without_engines();
 
     parsed
 });
 
 /// Load all built-in definitions.
+///
+/// These are parsed from JSON on first use. The only exception is if this crate was built with the
+/// `binary` feature and the `RINGHOPPER_DEFINITIONS_PRECOMPILED` environment variable set; see
+/// `precompile_builtin_definitions`.
 pub fn load_all_definitions() -> &'static ParsedDefinitions {
-    &*DEFINITIONS
+    &DEFINITIONS
+}
+
+impl ParsedDefinitions {
+    /// Load all built-in definitions into a new, owned value.
+    ///
+    /// This loads the same definitions as [`load_all_definitions`], but on every call, so they can
+    /// be modified (e.g. with [`ParsedDefinitions::merge`]) without affecting anything else. Like
+    /// [`load_all_definitions`], JSON is parsed and finalized on every call unless precompiled
+    /// definitions were embedded when building this crate.
+    pub fn load_builtin() -> Self {
+        #[cfg(precompiled_definitions)]
+        let parsed = Self::from_binary(include_bytes!(concat!(env!("OUT_DIR"), "/definitions.bin"))).expect("precompiled definitions are invalid");
+
+        #[cfg(not(precompiled_definitions))]
+        let parsed = {
+            let mut parsed = Self::default();
+            if !ALL_BUILTIN_ENGINES {
+                parsed.set_unknown_engine_policy(UnknownEnginePolicy::TreatAsUnsupported);
+            }
+            parsed.load_from_json(&get_all_definitions());
+            parsed.finalize();
+            parsed
+        };
+
+        parsed
+    }
+}
+
+/// Load only the built-in engine definitions.
+///
+/// [`ParsedDefinitions::objects`] and [`ParsedDefinitions::groups`] will be empty. This is much
+/// cheaper than [`load_all_definitions`] if only engine information is needed.
+pub fn load_engines_only() -> &'static ParsedDefinitions {
+    &ENGINE_DEFINITIONS
+}
+
+/// Load only the built-in tag and map definitions.
+///
+/// [`ParsedDefinitions::engines`] will be empty. Since engines are not loaded, any
+/// [`SupportedEngines`] lists and engine limits are left exactly as written in the JSON rather than
+/// being validated and expanded to include engines that inherit them.
+pub fn load_tag_definitions_only() -> &'static ParsedDefinitions {
+    &TAG_DEFINITIONS
+}
+
+/// Load only the given built-in groups, their supergroups, and the structs, enums, and bitfields
+/// they depend on.
+///
+/// Every built-in JSON file is still parsed, and parsing is most of the cost of
+/// [`load_all_definitions`], so this is only slightly faster. It mainly saves memory and keeps
+/// unrelated groups out of the result (e.g. for `&["weapon", "bitmap"]`). Unlike the other
+/// functions, this parses the definitions on each call. See
+/// [`ParsedDefinitions::load_groups_from_json`].
+///
+/// # Panics
+///
+/// Panics if a group does not exist.
+pub fn load_definitions_for_groups(groups: &[&str]) -> ParsedDefinitions {
+    let mut parsed = ParsedDefinitions::default();
+    if !ALL_BUILTIN_ENGINES {
+        parsed.set_unknown_engine_policy(UnknownEnginePolicy::TreatAsUnsupported);
+    }
+    parsed.load_groups_from_json(&get_all_definitions(), groups);
+    parsed
 }
 
 #[cfg(test)]
 mod test {
+    use alloc::borrow::ToOwned;
     use crate::load_all_definitions;
 
     #[test]
     fn loading_all_definitions_succeeds() {
         load_all_definitions();
     }
+
+    #[test]
+    fn builtin_definitions_are_owned() {
+        let mut definitions = crate::ParsedDefinitions::load_builtin();
+        assert_eq!(definitions.fingerprint(), load_all_definitions().fingerprint());
+        definitions.groups.remove("weapon");
+        assert!(load_all_definitions().groups.contains_key("weapon"));
+    }
+
+    #[test]
+    fn loading_subsets_succeeds() {
+        let engines = crate::load_engines_only();
+        assert!(engines.objects.is_empty() && engines.groups.is_empty());
+        assert_eq!(engines.engines.len(), load_all_definitions().engines.len());
+
+        let tags = crate::load_tag_definitions_only();
+        assert!(tags.engines.is_empty());
+        assert_eq!(tags.objects.len(), load_all_definitions().objects.len());
+    }
+
+    #[test]
+    fn provenance_covers_every_embedded_file() {
+        let files: alloc::collections::BTreeSet<_> = crate::get_all_definitions()
+            .iter()
+            .map(|o| o["__json_file"].as_str().unwrap().to_owned())
+            .collect();
+        assert_eq!(crate::builtin_provenance().file_count, files.len());
+    }
 }
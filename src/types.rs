@@ -5,7 +5,9 @@ use core::ops::RangeInclusive;
 use serde_json::Value;
 
 /// Contains all definitions.
-#[derive(Default)]
+#[derive(Clone, Default)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ParsedDefinitions {
     /// Describes all definitions for structs, enums, and bitfields.
     pub objects: BTreeMap<String, NamedObject>,
@@ -14,7 +16,46 @@ pub struct ParsedDefinitions {
     pub groups: BTreeMap<String, TagGroup>,
 
     /// Describes all definitions for engines.
-    pub engines: BTreeMap<String, Engine>
+    pub engines: BTreeMap<String, Engine>,
+
+    /// Describes all named limit profiles.
+    pub limit_profiles: BTreeMap<String, LimitProfile>,
+
+    /// Maps each group's fourcc to its name; see [`ParsedDefinitions::group_by_fourcc`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    group_fourccs: BTreeMap<u32, String>,
+
+    /// What to do with references to engines that are not loaded; see
+    /// [`ParsedDefinitions::set_unknown_engine_policy`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    unknown_engine_policy: UnknownEnginePolicy,
+
+    /// Custom field object kinds; see [`ParsedDefinitions::register_custom_object`].
+    custom_objects: BTreeMap<String, CustomFieldObject>,
+
+    /// Maps each object to the struct fields that reference it, as (struct, field index), for
+    /// [`ParsedDefinitions::refinalize_objects`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    referencers: BTreeMap<String, Vec<(String, usize)>>
+}
+
+/// A named set of limits (e.g. known safe extended limits for modded engines).
+///
+/// Fields can override their limits per profile with `profiles` in their `limit` object. Fields
+/// without an override use their usual limits. Profile limits do not raise the editor limit unless
+/// an `editor` limit is also given.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LimitProfile {
+    /// Name of the profile.
+    pub name: String,
+
+    /// The json file the definition is defined in.
+    pub definition_file: String,
+
+    /// Description of the profile, if any.
+    pub description: Option<String>
 }
 
 /// Allows you to query the size of an object.
@@ -25,6 +66,7 @@ pub trait SizeableObject {
 
 /// Describes a struct, enum, or bitfield type.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum NamedObject {
     /// Describes a struct type.
     Struct(Struct),
@@ -66,9 +108,45 @@ impl NamedObject {
             Self::Bitfield(b) => b.name.as_str(),
         }
     }
+
+    /// Get the kind of the object.
+    pub const fn kind(&self) -> ObjectKind {
+        match self {
+            Self::Struct(_) => ObjectKind::Struct,
+            Self::Enum(_) => ObjectKind::Enum,
+            Self::Bitfield(_) => ObjectKind::Bitfield
+        }
+    }
+
+    /// Returns true if the object has a field (or option, for enums) with the given name.
+    pub fn has_field(&self, field: &str) -> bool {
+        match self {
+            Self::Struct(s) => s.fields.iter().any(|f| f.name == field),
+            Self::Enum(e) => e.options.iter().any(|o| o.name == field),
+            Self::Bitfield(b) => b.fields.iter().any(|f| f.name == field)
+        }
+    }
+}
+
+/// Describes what kind of [`NamedObject`] something is, without its contents.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ObjectKind {
+    /// The object is a [`Struct`].
+    Struct,
+
+    /// The object is an [`Enum`].
+    Enum,
+
+    /// The object is a [`Bitfield`].
+    Bitfield
 }
 
 /// Describes a tag group.
+#[derive(Clone)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TagGroup {
     /// Name of the tag group.
     ///
@@ -100,11 +178,122 @@ pub struct TagGroup {
     pub version: u16,
 
     /// The fourcc of the tag group.
-    pub fourcc_binary: u32
+    pub fourcc_binary: u32,
+
+    /// Related definitions, if any.
+    pub see_also: Vec<SeeAlso>,
+
+    /// File extension of tag files of this group, without the leading period.
+    ///
+    /// This is the group name unless overridden.
+    pub extension: String,
+
+    /// Additional extensions (e.g. legacy extensions) that also resolve to this group.
+    ///
+    /// Tag files should be written with [`TagGroup::extension`]. No built-in group has any, since
+    /// none is known to have had another extension; these are for custom definitions.
+    pub extension_aliases: Vec<String>,
+
+    /// Scenario types this group is meaningful for, or `None` if it applies to all of them.
+    pub scenario_types: Option<Vec<ScenarioType>>,
+
+    /// Long-form documentation for the group, if any.
+    pub documentation: Option<GroupDocumentation>,
+
+    /// Short code used to abbreviate the group in listings, if any.
+    ///
+    /// Built-in groups use their four-character code without trailing spaces (e.g. `weap`).
+    pub short_code: Option<String>,
+
+    /// Position of the group in listings, if any.
+    ///
+    /// Built-in object groups are ordered as in the `ObjectType` enum, with each abstract group
+    /// (e.g. `unit`) just before the groups that inherit it. See
+    /// [`ParsedDefinitions::groups_in_sort_order`].
+    pub sort_order: Option<u32>
+}
+
+/// Long-form documentation for a tag group, separate from per-field comments.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GroupDocumentation {
+    /// Introduction to what the group is and what it is used for.
+    pub overview: String,
+
+    /// Additional notes on how to use the group, one paragraph each.
+    pub usage_notes: Vec<String>
+}
+
+/// Describes a type of scenario (and thus cache file).
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub enum ScenarioType {
+    /// Campaign maps.
+    Singleplayer,
+
+    /// Multiplayer maps.
+    Multiplayer,
+
+    /// User interface maps (i.e. the main menu).
+    UserInterface
+}
+
+impl ScenarioType {
+    /// Get the scenario type from its name in the JSON definitions (e.g. `user interface`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "singleplayer" => Some(Self::Singleplayer),
+            "multiplayer" => Some(Self::Multiplayer),
+            "user interface" => Some(Self::UserInterface),
+            _ => None
+        }
+    }
+
+    /// Get the name of the scenario type as used in the JSON definitions.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Singleplayer => "singleplayer",
+            Self::Multiplayer => "multiplayer",
+            Self::UserInterface => "user interface"
+        }
+    }
+}
+
+impl TagGroup {
+    /// Returns true if this group is meaningful for the given scenario type.
+    pub fn applies_to_scenario_type(&self, scenario_type: ScenarioType) -> bool {
+        self.scenario_types.as_ref().is_none_or(|t| t.contains(&scenario_type))
+    }
+}
+
+/// A cross-reference to a related definition, used for linking related settings in documentation
+/// and editors.
+///
+/// In JSON, this is written as either a group name (e.g. `"weapon"`) or an object name and field
+/// name separated by a period (e.g. `"Weapon.error angle"`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum SeeAlso {
+    /// References a tag group in [`ParsedDefinitions::groups`].
+    Group(String),
+
+    /// References a field of an object.
+    Field {
+        /// References an object in [`ParsedDefinitions::objects`].
+        object: String,
+
+        /// Name of the field (or option, for enums) in the object.
+        field: String
+    }
 }
 
 /// Describes a struct, a composite block that potentially contains multiple fields.
 #[derive(Clone)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Struct {
     /// The name of the struct.
     ///
@@ -115,6 +304,10 @@ pub struct Struct {
     pub definition_file: String,
 
     /// All fields of the struct.
+    ///
+    /// This may be empty, in which case the struct has a size of 0 (e.g. a placeholder for data
+    /// that is not yet understood). Empty structs take no space when inline, and reflexives of
+    /// them still have a count, but no element data.
     pub fields: Vec<StructField>,
 
     /// The struct does not use tag dependencies, tag references, or tag data, and generating it
@@ -122,11 +315,40 @@ pub struct Struct {
     /// excluded, too.
     pub is_const: bool,
 
+    /// The struct contains tag references, directly or through inline structs and reflexives.
+    ///
+    /// Like [`Struct::is_const`], this assumes fields marked as `exclude` are excluded, and it is
+    /// set when finalizing.
+    pub contains_tag_references: bool,
+
+    /// The struct contains data fields (e.g. [`FieldObject::Data`]), directly or through inline
+    /// structs and reflexives.
+    pub contains_data: bool,
+
+    /// The struct contains reflexives, directly or through inline structs.
+    pub contains_reflexives: bool,
+
+    /// Maximum number of nested reflexives below the struct, or 0 if it contains no reflexives.
+    ///
+    /// Reflexives back to a struct that is already being descended into are not counted again.
+    pub max_depth: usize,
+
     /// Flags for the struct, itself.
     pub flags: Flags,
 
     /// The final size of the struct in bytes
-    pub size: usize
+    pub size: usize,
+
+    /// The struct is deliberately packed, so its fields are not expected to be naturally aligned.
+    ///
+    /// See [`ParsedDefinitions::alignment_warnings`].
+    pub packed: bool,
+
+    /// Where instances of the struct live at runtime when read through a reflexive, as defined.
+    ///
+    /// Not every engine has every pool, so use [`Struct::memory_pool`] to get the pool on a given
+    /// engine.
+    pub defined_memory_pool: MemoryPool
 }
 
 impl SizeableObject for Struct {
@@ -136,20 +358,20 @@ impl SizeableObject for Struct {
 }
 
 impl Struct {
-    fn set_offsets_and_verify_sizes(&mut self, parsed_tag_data: &ParsedDefinitions) {
-        let expected_size = self.size;
-        let mut real_size = 0;
+    // Sizes were already verified by validation_problems
+    fn set_offsets(&mut self, parsed_tag_data: &ParsedDefinitions) {
+        let mut offset = 0;
         for f in &mut self.fields {
-            f.relative_offset = real_size;
-            real_size += f.size(parsed_tag_data);
+            f.relative_offset = offset;
+            offset += f.size(parsed_tag_data);
         }
-        assert_eq!(expected_size, real_size, "Size for {name} is incorrect (expected {expected_size}, got {real_size} instead)", name=self.name);
-        assert_eq!(expected_size, self.size(parsed_tag_data), "size() is implemented wrong for {name} (expected {expected_size}, got {real_size} instead)", name=self.name);
+        debug_assert_eq!(offset, self.size, "size for {} was not verified", self.name);
     }
 }
 
 /// Describes a limit for something for a given field.
-#[derive(PartialEq, Eq, Hash, Clone, PartialOrd, Ord)]
+#[derive(PartialEq, Eq, Hash, Clone, PartialOrd, Ord, Debug)]
+#[non_exhaustive]
 pub enum LimitType {
     /// Maximum allowed by the engine
     Engine(String),
@@ -158,11 +380,18 @@ pub enum LimitType {
     Default,
 
     /// Maximum allowed by the editor
-    Editor
+    Editor,
+
+    /// Maximum allowed by a limit profile
+    ///
+    /// References a profile in [`ParsedDefinitions::limit_profiles`].
+    Profile(String)
 }
 
 /// Describes a field on a struct.
 #[derive(Clone)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StructField {
     /// Name of the field.
     ///
@@ -186,8 +415,17 @@ pub struct StructField {
     pub default_value: Option<Vec<StaticValue>>,
 
     /// Number of fields.
+    ///
+    /// For arrays with [`StructField::engine_counts`], this is the default length, which is also
+    /// the length used for the layout.
     pub count: FieldCount,
 
+    /// Array lengths for specific engines, if they differ from [`StructField::count`].
+    ///
+    /// Each length is no greater than the default length, and any remaining elements are unused
+    /// on that engine. Like limits, these also apply to engines that inherit a listed engine.
+    pub engine_counts: BTreeMap<String, usize>,
+
     /// Whether or not the field is nullable.
     pub nullability: Nullability,
 
@@ -204,7 +442,65 @@ pub struct StructField {
     pub flags: Flags,
 
     /// Relative offset to the start of its structs.
-    pub relative_offset: usize
+    pub relative_offset: usize,
+
+    /// What is known about the region, if this is padding.
+    ///
+    /// This is `None` for fields that are not [`StructFieldType::Padding`].
+    pub padding_kind: Option<PaddingKind>,
+
+    /// Editor control hint, if explicitly set.
+    ///
+    /// See [`StructField::editor_widget`] for the hint including implied ones.
+    pub widget: Option<EditorWidget>,
+
+    /// Increment between valid values for numeric fields, if any (e.g. 0.01).
+    pub step: Option<f64>,
+
+    /// Number of decimal places to display for numeric fields, if any.
+    pub precision: Option<u8>,
+
+    /// Where the data of a data or reflexive field lives at runtime, if it overrides the default.
+    ///
+    /// See [`FieldView::memory_pool`].
+    pub memory_pool: Option<MemoryPool>,
+
+    /// Maximum number of bytes the elements of a reflexive may use, if it has an explicit budget.
+    ///
+    /// This is checked against the reflexive's limit on every engine when finalizing. See
+    /// [`ParsedDefinitions::reflexive_byte_ceilings`].
+    pub byte_budget: Option<u64>,
+
+    /// Values must be unit length (e.g. normals and rotation quaternions).
+    ///
+    /// Only set for [`FieldObject::Vector2D`], [`FieldObject::Vector3D`], and
+    /// [`FieldObject::Quaternion`] fields. See [`StructField::is_valid_normal`].
+    pub normalized: bool,
+
+    /// Color space of the field's values, if this is a color and it is known.
+    ///
+    /// See [`StructField::color_range`] for the expected range of the values.
+    pub color_space: Option<ColorSpace>
+}
+
+/// Describes what is known about a padding region.
+///
+/// Notes about the region, if any, are in the field's [`Flags`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum PaddingKind {
+    /// True padding that is not used by anything and is safe to repurpose.
+    ///
+    /// This is the default.
+    #[default]
+    Padding,
+
+    /// Reserved or not-yet-understood data (set with `reserved` in the JSON).
+    ///
+    /// This may be read by some engine or hold meaningful values in some tags, so it should be
+    /// preserved and not repurposed.
+    Reserved
 }
 
 impl SizeableObject for StructField {
@@ -214,7 +510,9 @@ impl SizeableObject for StructField {
 }
 
 /// Describes a struct field.
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum StructFieldType {
     /// This field is a tangible object with a meaning.
     Object(FieldObject),
@@ -246,6 +544,7 @@ impl SizeableObject for StructFieldType {
 
 /// Describes the number of values an object has.
 #[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum FieldCount {
     /// A single field
     One,
@@ -258,7 +557,8 @@ pub enum FieldCount {
 }
 
 impl FieldCount {
-    fn field_count(&self) -> usize {
+    /// Get the number of values (e.g. 2 for bounds).
+    pub const fn field_count(&self) -> usize {
         match self {
             Self::One => 1,
             Self::Bounds => 2,
@@ -268,6 +568,8 @@ impl FieldCount {
 }
 
 /// Describes how an uninitialized field is handled.
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct DefaultBehavior {
     /// Default values for each field.
     ///
@@ -282,7 +584,11 @@ pub struct DefaultBehavior {
 }
 
 /// Describes a static value that is inside of the definitions, such as for default values.
-#[derive(Debug, Clone)]
+///
+/// Values of different variants are never equal; use [`StaticValue::numeric_cmp`] to compare them
+/// by value.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum StaticValue {
     /// Describes a float value.
     Float(f32),
@@ -310,6 +616,8 @@ impl core::fmt::Display for StaticValue {
 
 /// Describes a bitfield (a collection of booleans).
 #[derive(Clone)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Bitfield {
     /// Name of the bitfield.
     ///
@@ -320,11 +628,29 @@ pub struct Bitfield {
     pub definition_file: String,
 
     /// Width in bits.
+    ///
+    /// This is 8, 16, or 32, or 0 for a placeholder bitfield with no fields that takes no space.
     pub width: u8,
 
     /// Fields for the bitfield.
     pub fields: Vec<Field>,
 
+    /// Groups of fields where at most one field in each group may be set at a time.
+    ///
+    /// Each entry references fields in [`Bitfield::fields`] by name.
+    pub exclusive_groups: Vec<Vec<String>>,
+
+    /// Fields that require other fields to be set.
+    ///
+    /// Each key is a field that, when set, requires all of its values to be set, too. All names
+    /// reference fields in [`Bitfield::fields`].
+    pub implications: BTreeMap<String, Vec<String>>,
+
+    /// Name of the bitfield this is a copy of, if any.
+    ///
+    /// A copy must have the same fields in the same order; see [`OptionDrift`].
+    pub copy_of: Option<String>,
+
     /// Flags! Capture all of them to win!
     pub flags: Flags
 }
@@ -335,8 +661,80 @@ impl SizeableObject for Bitfield {
     }
 }
 
+impl Struct {
+    /// Get a field by name.
+    pub fn field(&self, name: &str) -> Option<&StructField> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    /// Iterate through the byte ranges of all padding of the given kind, relative to the start of
+    /// the struct.
+    ///
+    /// Padding of inline structs is not included.
+    pub fn padding_ranges(&self, kind: PaddingKind) -> impl Iterator<Item = core::ops::Range<usize>> + '_ {
+        self.fields.iter().filter_map(move |f| match f.field_type {
+            StructFieldType::Padding(size) if f.padding_kind == Some(kind) => Some(f.relative_offset..f.relative_offset + size),
+            _ => None
+        })
+    }
+}
+
+impl Enum {
+    /// Get an option by name.
+    pub fn option(&self, name: &str) -> Option<&Field> {
+        self.options.iter().find(|o| o.name == name)
+    }
+}
+
+impl Bitfield {
+    /// Get a field by name.
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    /// Get the value of a field by name.
+    pub fn field_value(&self, name: &str) -> Option<u32> {
+        self.field(name).map(|f| f.value)
+    }
+
+    /// Get the combined value of the given fields.
+    ///
+    /// Unknown fields are ignored.
+    pub fn mask_of_fields(&self, fields: &[String]) -> u32 {
+        fields.iter().filter_map(|n| self.field_value(n)).fold(0, |mask, v| mask | v)
+    }
+
+    /// Set any fields implied by fields already set in `mask`, following chains of implications.
+    ///
+    /// This matches how the official tools silently fix up masks.
+    pub fn normalize(&self, mut mask: u32) -> u32 {
+        loop {
+            let mut normalized = mask;
+            for (field, implied) in &self.implications {
+                if self.field_value(field).is_some_and(|v| mask & v != 0) {
+                    normalized |= self.mask_of_fields(implied);
+                }
+            }
+            if normalized == mask {
+                return mask
+            }
+            mask = normalized;
+        }
+    }
+
+    /// Iterate through all exclusive groups that have more than one field set in `mask`.
+    pub fn violated_exclusive_groups(&self, mask: u32) -> impl Iterator<Item = &[String]> {
+        self.exclusive_groups
+            .iter()
+            .filter(move |g| (mask & self.mask_of_fields(g)).count_ones() > 1)
+            .map(|g| g.as_slice())
+    }
+}
+
 /// Describes an enum.
 #[derive(Clone)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Enum {
     /// Name of the enum.
     ///
@@ -347,8 +745,22 @@ pub struct Enum {
     pub definition_file: String,
 
     /// All possible values the enum can be.
+    ///
+    /// This may be empty for placeholder enums. These still take 16 bits like any other enum, but
+    /// no value is a valid option.
     pub options: Vec<Field>,
 
+    /// How the values are interpreted, if they have a meaning beyond their names.
+    pub interpretation: Option<EnumInterpretation>,
+
+    /// Role of the enum if it is shared vocabulary with a stable order; see [`GlobalEnumRole`].
+    pub global_role: Option<GlobalEnumRole>,
+
+    /// Name of the enum this is a copy of, if any.
+    ///
+    /// A copy must have the same options in the same order; see [`OptionDrift`].
+    pub copy_of: Option<String>,
+
     /// Flags for the enum data type, itself.
     pub flags: Flags
 }
@@ -361,6 +773,8 @@ impl SizeableObject for Enum {
 
 /// Describes a field
 #[derive(Clone)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Field {
     /// Name of the field.
     ///
@@ -390,27 +804,40 @@ pub struct Field {
 
 /// A list of engines that support something.
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum SupportedEngines {
     /// This is supported by all engines.
     #[default]
     AllEngines,
 
     /// This is only supported by these engines.
-    SomeEngines(BTreeSet<String>)
+    SomeEngines(BTreeSet<String>),
+
+    /// This is supported by all engines except these engines.
+    ///
+    /// This is set with `unsupported_engines` in the JSON, and, like `supported_engines`, it
+    /// applies to engines that inherit a listed engine, too.
+    AllExcept(BTreeSet<String>)
 }
 
 impl SupportedEngines {
     /// Returns true if the engine is supported.
+    ///
+    /// This is an exact match against the list. Once definitions are finalized, the list already
+    /// includes every engine inheriting a listed engine.
     pub fn supports_engine(&self, engine: &Engine) -> bool {
         match self {
             Self::AllEngines => true,
-            Self::SomeEngines(engines) => engines.contains(&engine.name)
+            Self::SomeEngines(engines) => engines.contains(&engine.name),
+            Self::AllExcept(engines) => !engines.contains(&engine.name)
         }
     }
 }
 
 /// General fields. Some may be applicable to some objects, but not all.
 #[derive(Default, Clone)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Flags {
     /// This field is not readable from tag files
     pub cache_only: bool,
@@ -445,7 +872,85 @@ pub struct Flags {
     pub developer_note: Option<String>,
 
     /// Any description, if present
-    pub description: Option<String>
+    pub description: Option<String>,
+
+    /// The engine this first appeared in, if any.
+    ///
+    /// Once finalized, [`Flags::supported_engines`] only includes this engine and engines that
+    /// inherit it.
+    pub introduced_in: Option<String>,
+
+    /// The engine this was removed in, if any.
+    ///
+    /// Once finalized, [`Flags::supported_engines`] excludes this engine and engines that inherit
+    /// it.
+    pub removed_in: Option<String>,
+
+    /// Example values or snippets, if any.
+    ///
+    /// These are for documentation and editor tooltips only (e.g. typical values for a field) and
+    /// are not validated against the field type.
+    pub examples: Vec<String>,
+
+    /// Related definitions, if any.
+    pub see_also: Vec<SeeAlso>,
+
+    /// Reverse-engineering findings, if any.
+    pub research: Option<Research>,
+
+    /// How the cache builder rewrites this field, if explicitly set (with `cache_rewrite` in the
+    /// JSON).
+    ///
+    /// See [`StructField::cache_rewrite`] for the rewrite including implied ones.
+    pub cache_rewrite: Option<CacheRewrite>,
+
+    /// Whether the field affects gameplay determinism, if known.
+    pub determinism: Option<Determinism>
+}
+
+/// Reverse-engineering findings about a field or padding region.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Research {
+    /// How confident the findings are.
+    pub confidence: ResearchConfidence,
+
+    /// Values that have been observed in real tags or cache files.
+    pub observed_values: Vec<String>,
+
+    /// Links to further information (e.g. discussions or reference material).
+    pub links: Vec<String>,
+
+    /// Any notes about the findings.
+    pub notes: Option<String>
+}
+
+/// Describes how confident a [`Research`] annotation is.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub enum ResearchConfidence {
+    /// An educated guess that has not been verified.
+    #[default]
+    Speculative,
+
+    /// Supported by evidence, but not fully verified.
+    Likely,
+
+    /// Verified (e.g. by testing on the engine).
+    Confirmed
+}
+
+impl ResearchConfidence {
+    /// Get the name used for this confidence level in the JSON definitions.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Speculative => "speculative",
+            Self::Likely => "likely",
+            Self::Confirmed => "confirmed"
+        }
+    }
 }
 
 /// Describes the nullability of a field.
@@ -454,6 +959,7 @@ pub struct Flags {
 ///
 /// For all other values, this property should be ignored.
 #[derive(Copy, Clone, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Nullability {
     /// The field must not be null and must be set.
     ///
@@ -470,7 +976,73 @@ pub enum Nullability {
     Nullable
 }
 
+/// Describes one of the boolean flags in [`Flags`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum FlagType {
+    /// Corresponds to [`Flags::cache_only`].
+    CacheOnly,
+
+    /// Corresponds to [`Flags::non_cached`].
+    NonCached,
+
+    /// Corresponds to [`Flags::uneditable_in_editor`].
+    UneditableInEditor,
+
+    /// Corresponds to [`Flags::hidden_in_editor`].
+    HiddenInEditor,
+
+    /// Corresponds to [`Flags::exclude`].
+    Exclude,
+
+    /// Corresponds to [`Flags::little_endian_in_tags`].
+    LittleEndianInTags,
+
+    /// Corresponds to [`Flags::shifted_by_one`].
+    ShiftedByOne
+}
+
+impl FlagType {
+    /// Every flag type.
+    pub const ALL: [FlagType; 7] = [
+        FlagType::CacheOnly,
+        FlagType::NonCached,
+        FlagType::UneditableInEditor,
+        FlagType::HiddenInEditor,
+        FlagType::Exclude,
+        FlagType::LittleEndianInTags,
+        FlagType::ShiftedByOne
+    ];
+
+    /// Get the key used for this flag in the JSON definitions.
+    pub const fn json_name(self) -> &'static str {
+        match self {
+            FlagType::CacheOnly => "cache_only",
+            FlagType::NonCached => "non_cached",
+            FlagType::UneditableInEditor => "read_only",
+            FlagType::HiddenInEditor => "hidden",
+            FlagType::Exclude => "exclude",
+            FlagType::LittleEndianInTags => "little_endian",
+            FlagType::ShiftedByOne => "shifted_by_one"
+        }
+    }
+}
+
 impl Flags {
+    /// Returns true if the given flag is set.
+    pub const fn has_flag(&self, flag: FlagType) -> bool {
+        match flag {
+            FlagType::CacheOnly => self.cache_only,
+            FlagType::NonCached => self.non_cached,
+            FlagType::UneditableInEditor => self.uneditable_in_editor,
+            FlagType::HiddenInEditor => self.hidden_in_editor,
+            FlagType::Exclude => self.exclude,
+            FlagType::LittleEndianInTags => self.little_endian_in_tags,
+            FlagType::ShiftedByOne => self.shifted_by_one
+        }
+    }
+
     pub(crate) fn combine_with(&mut self, other: &Flags) {
         self.cache_only |= other.cache_only;
         self.non_cached |= other.non_cached;
@@ -479,6 +1051,7 @@ impl Flags {
         self.exclude |= other.exclude;
         self.little_endian_in_tags |= other.little_endian_in_tags;
         self.shifted_by_one |= other.shifted_by_one;
+        self.determinism = self.determinism.or(other.determinism);
     }
 }
 
@@ -486,6 +1059,8 @@ impl Flags {
 ///
 /// Note: This enum will be removed eventually to generify cache file loading/building.
 #[derive(Copy, Clone, PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum EngineCacheParser {
     /// Hint this is an Xbox cache file.
     Xbox,
@@ -495,6 +1070,9 @@ pub enum EngineCacheParser {
 }
 
 /// Describes an engine.
+#[derive(Clone)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Engine {
     /// Internal name of the engine.
     pub name: String,
@@ -542,6 +1120,9 @@ pub struct Engine {
     /// Maximum number of script nodes in the scenario tag.
     pub max_script_nodes: u64,
 
+    /// Layout of compiled scripts in the scenario tag.
+    pub script_node_table: EngineScriptNodeTable,
+
     /// Maximum tag space, in bytes.
     pub max_tag_space: u64,
 
@@ -585,10 +1166,42 @@ pub struct Engine {
     pub grenades: EngineGrenades,
     
     /// Minimum weapons in a globals tag.
-    pub minimum_weapons: u64
+    pub minimum_weapons: u64,
+
+    /// Limits to tag paths.
+    pub tag_paths: EngineTagPaths,
+
+    /// How HUD and UI elements are fit to the screen.
+    pub hud_scaling: HUDScaling,
+
+    /// Notes about the engine for presenting it to users.
+    ///
+    /// Unlike most properties, this is not inherited.
+    pub documentation: EngineDocumentation
+}
+
+/// Describes an engine for presenting it to users (e.g. in an engine picker).
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EngineDocumentation {
+    /// Short description of the engine, if any.
+    pub description: Option<String>,
+
+    /// Release date of the engine, if known, formatted as `YYYY`, `YYYY-MM`, or `YYYY-MM-DD`.
+    pub release_date: Option<String>,
+
+    /// How the engine was distributed (e.g. retail disc, digital download, or mod), if known.
+    pub distribution: Option<String>,
+
+    /// Known quirks of the engine.
+    pub quirks: Vec<String>
 }
 
 /// Describes limits to grenades
+#[derive(Clone)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct EngineGrenades {
     /// Grenade limit for singleplayer
     pub singleplayer: RangeInclusive<u8>,
@@ -601,6 +1214,9 @@ pub struct EngineGrenades {
 }
 
 /// Describes the type of compression used, if any.
+#[derive(Copy, Clone, PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum EngineCompressionType {
     /// Cache files are stored uncompressed.
     Uncompressed,
@@ -612,12 +1228,18 @@ pub enum EngineCompressionType {
 /// Describes additional fields.
 ///
 /// Note: This will be changed to an enum, later.
+#[derive(Clone)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct EngineSupportedResourceMaps {
     /// Supports externally indexed tags.
     pub externally_indexed_tags: bool
 }
 
 /// Per-scenario type cache file size limits.
+#[derive(Clone)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct EngineCacheFileSize {
     /// Maximum cache file size, in bytes, for UI maps.
     pub user_interface: u64,
@@ -630,7 +1252,9 @@ pub struct EngineCacheFileSize {
 }
 
 /// All prerequisite tags for building a cache file.
-#[derive(Default)]
+#[derive(Clone, Default)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct EngineRequiredTags {
     /// All prerequisite tags for any maps.
     pub all: Vec<String>,
@@ -646,6 +1270,9 @@ pub struct EngineRequiredTags {
 }
 
 /// Base memory address for the tag data block.
+#[derive(Clone)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct BaseMemoryAddress {
     /// The base memory address.
     pub address: u64,
@@ -658,6 +1285,9 @@ pub struct BaseMemoryAddress {
 }
 
 /// Describes the build string.
+#[derive(Clone)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Build {
     /// The actual build string.
     ///
@@ -665,6 +1295,8 @@ pub struct Build {
     pub string: String,
 
     /// Additional build strings that this engine can be used for.
+    ///
+    /// These may use `*` as a wildcard; see [`ParsedDefinitions::engine_by_build`].
     pub aliases: Vec<String>,
 
     /// If true, the build string is enforced and cannot differ or else the map will be rejected by
@@ -675,6 +1307,9 @@ pub struct Build {
 /// Describes how bitmaps work on the engine.
 ///
 /// This only applies to cache files. Tag files are unaffected.
+#[derive(Clone)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct EngineBitmapOptions {
     /// If true, uncompressed power-of-two bitmaps are swizzled.
     pub swizzled: bool,
@@ -690,7 +1325,9 @@ pub struct EngineBitmapOptions {
 }
 
 /// Describes a type of objects for a field.
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum FieldObject {
     /// Describes an inline object.
     ///
@@ -1171,9 +1808,65 @@ pub enum FieldObject {
     /// only one (correct) way to access its data, and the only way to find this is by checking its
     /// containing node.
     ScenarioScriptNodeValue,
+
+    /// Describes a field object kind registered at runtime.
+    ///
+    /// References a kind registered with [`ParsedDefinitions::register_custom_object`]; its size,
+    /// composite count, and const-ness come from there.
+    Custom(String),
 }
 
 impl FieldObject {
+    /// Get the type name used for this object in the JSON definitions (e.g. `float` or `Reflexive`).
+    ///
+    /// For named objects, this is the name of the object.
+    pub fn type_name(&self) -> &str {
+        match self {
+            Self::NamedObject(n) | Self::Custom(n) => n.as_str(),
+            Self::Reflexive(_) => "Reflexive",
+            Self::TagReference { .. } => "TagReference",
+            Self::TagGroup => "TagGroup",
+            Self::Data => "Data",
+            Self::FileData => "FileData",
+            Self::BSPVertexData => "BSPVertexData",
+            Self::UTF16String => "UTF16String",
+            Self::F32 => "float",
+            Self::U8 => "uint8",
+            Self::U16 => "uint16",
+            Self::U32 => "uint32",
+            Self::I8 => "int8",
+            Self::I16 => "int16",
+            Self::I32 => "int32",
+            Self::Angle => "Angle",
+            Self::Rectangle => "Rectangle",
+            Self::Vector2D => "Vector2D",
+            Self::Vector3D => "Vector3D",
+            Self::CompressedVector2D => "CompressedVector2D",
+            Self::CompressedVector3D => "CompressedVector3D",
+            Self::CompressedFloat => "CompressedFloat",
+            Self::Euler2D => "Euler2D",
+            Self::Euler3D => "Euler3D",
+            Self::Plane2D => "Plane2D",
+            Self::Plane3D => "Plane3D",
+            Self::Rectangle3D => "Rectangle3D",
+            Self::Quaternion => "Quaternion",
+            Self::Matrix2x3 => "Matrix2x3",
+            Self::Matrix3x3 => "Matrix3x3",
+            Self::Matrix4x3 => "Matrix4x3",
+            Self::ColorRGB => "ColorRGB",
+            Self::ColorARGB => "ColorARGB",
+            Self::Pixel32 => "Pixel32",
+            Self::String32 => "String32",
+            Self::Address => "Address",
+            Self::Index => "Index",
+            Self::ReflexiveIndex { .. } => "ReflexiveIndex",
+            Self::Vector2DInt => "Vector2DInt",
+            Self::TagID => "TagID",
+            Self::ID => "ID",
+            Self::ScenarioScriptNodeValue => "ScenarioScriptNodeValue"
+        }
+    }
+
     const fn primitive_size(&self) -> usize {
         match self {
             Self::Reflexive(_) => 0xC,
@@ -1209,7 +1902,7 @@ impl FieldObject {
             Self::String32 => 32,
             Self::Rectangle3D => 24,
 
-            Self::NamedObject(_) => unreachable!()
+            Self::NamedObject(_) | Self::Custom(_) => unreachable!()
         }
     }
 
@@ -1217,7 +1910,7 @@ impl FieldObject {
         match self {
             Self::Reflexive(_) => 1,
             Self::TagReference { .. } => 1,
-            Self::NamedObject(_) => 1,
+            Self::NamedObject(_) | Self::Custom(_) => 1,
             Self::Data | Self::FileData | Self::BSPVertexData | Self::UTF16String => 1,
             Self::TagID | Self::ID => 1,
             Self::TagGroup => 1,
@@ -1247,9 +1940,10 @@ impl FieldObject {
         }
     }
 
-    const fn primitive_value_type(&self) -> Option<StaticValue> {
+    pub(crate) const fn primitive_value_type(&self) -> Option<StaticValue> {
         match self {
             Self::NamedObject(_)
+            | Self::Custom(_)
             | Self::Data
             | Self::FileData
             | Self::BSPVertexData
@@ -1300,7 +1994,7 @@ impl FieldObject {
 
     const fn is_const(&self) -> Option<bool> {
         Some(match self {
-            Self::NamedObject(_) => return None,
+            Self::NamedObject(_) | Self::Custom(_) => return None,
             Self::Reflexive(_) => false,
             Self::TagReference { .. } => false,
             Self::TagGroup => true,
@@ -1350,10 +2044,175 @@ impl SizeableObject for FieldObject {
     fn size(&self, parsed_tag_data: &ParsedDefinitions) -> usize {
         match self {
             Self::NamedObject(p) => parsed_tag_data.objects.get(p).unwrap().size(parsed_tag_data),
+            Self::Custom(c) => parsed_tag_data.custom_objects[c].size,
             _ => self.primitive_size()
         }
     }
 }
 
 mod parse;
-pub(crate) use parse::*;
+pub use parse::*;
+
+mod observer;
+pub use observer::*;
+
+mod engine;
+pub use engine::*;
+
+mod extension;
+
+mod value;
+
+mod primitive;
+pub use primitive::*;
+
+mod view;
+pub use view::*;
+
+mod budget;
+pub use budget::*;
+
+mod tag_space;
+pub use tag_space::*;
+
+mod export;
+pub use export::*;
+
+mod comparison;
+pub use comparison::*;
+
+mod rewrite;
+pub use rewrite::*;
+
+mod widget;
+pub use widget::*;
+
+mod provenance;
+pub use provenance::*;
+
+#[cfg(feature = "std")]
+mod watch;
+#[cfg(feature = "std")]
+pub use watch::*;
+
+mod query;
+pub use query::*;
+
+mod coverage;
+pub use coverage::*;
+
+mod alignment;
+pub use alignment::*;
+
+mod casing;
+pub use casing::*;
+
+mod error;
+pub use error::*;
+
+mod load;
+
+mod merge;
+pub use merge::*;
+
+mod fourcc;
+pub use fourcc::*;
+
+mod memory_pool;
+pub use memory_pool::*;
+
+mod dump;
+pub use dump::*;
+
+mod visitor;
+pub use visitor::*;
+
+mod offset;
+pub use offset::*;
+
+mod c_header;
+pub use c_header::*;
+
+mod custom_object;
+pub use custom_object::*;
+
+#[cfg(feature = "serde")]
+mod serialize;
+
+mod source_json;
+
+mod assembly;
+pub use assembly::*;
+
+mod component;
+pub use component::*;
+
+mod plugin_xml;
+
+mod color;
+pub use color::*;
+
+mod diff;
+pub use diff::*;
+
+mod fingerprint;
+
+mod tag_path;
+pub use tag_path::*;
+
+#[cfg(feature = "binary")]
+mod binary;
+#[cfg(feature = "binary")]
+pub use binary::*;
+
+mod utf16;
+pub use utf16::*;
+
+mod rust_tables;
+pub use rust_tables::*;
+
+mod script_nodes;
+pub use script_nodes::*;
+
+mod anchor;
+pub use anchor::*;
+
+mod group_subset;
+
+mod global_enum;
+pub use global_enum::*;
+
+mod drift;
+pub use drift::*;
+
+mod determinism;
+pub use determinism::*;
+
+mod codegen_bundle;
+pub use codegen_bundle::*;
+
+mod validation;
+pub use validation::*;
+
+mod cycle;
+pub use cycle::*;
+
+mod pack;
+pub use pack::*;
+
+mod engine_modifier;
+pub use engine_modifier::*;
+
+mod referencers;
+
+mod footprint;
+pub use footprint::*;
+
+mod topological;
+
+mod tag_references;
+pub use tag_references::*;
+
+mod refinalize;
+
+mod hierarchy;
@@ -0,0 +1,114 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::*;
+
+/// A field whose offset is not a multiple of its type's natural alignment.
+///
+/// These usually indicate a mistake in the definitions, such as a missing or wrongly sized padding
+/// field. Created by [`ParsedDefinitions::alignment_warnings`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AlignmentWarning {
+    /// Name of the struct containing the field.
+    pub struct_name: String,
+
+    /// Name of the field.
+    pub field: String,
+
+    /// Offset of the field relative to the start of the struct.
+    pub offset: usize,
+
+    /// Natural alignment of the field's type in bytes.
+    pub alignment: usize
+}
+
+impl core::fmt::Display for AlignmentWarning {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.write_fmt(format_args!(
+            "{struct_name}::{field} is at offset 0x{offset:X}, which is not aligned to {alignment} bytes",
+            struct_name=self.struct_name,
+            field=self.field,
+            offset=self.offset,
+            alignment=self.alignment
+        ))
+    }
+}
+
+impl ParsedDefinitions {
+    /// Find every field whose offset is not naturally aligned for its type (e.g. a 32-bit integer
+    /// at an odd offset).
+    ///
+    /// Offsets are checked relative to the start of each struct. Structs marked as
+    /// [`Struct::packed`] are skipped.
+    pub fn alignment_warnings(&self) -> Vec<AlignmentWarning> {
+        let mut warnings = Vec::new();
+        for o in self.objects.values() {
+            let NamedObject::Struct(s) = o else {
+                continue
+            };
+            if s.packed {
+                continue
+            }
+            for f in &s.fields {
+                let StructFieldType::Object(object) = &f.field_type else {
+                    continue
+                };
+                let alignment = self.natural_alignment(object);
+                if f.relative_offset % alignment != 0 {
+                    warnings.push(AlignmentWarning {
+                        struct_name: s.name.to_owned(),
+                        field: f.name.to_owned(),
+                        offset: f.relative_offset,
+                        alignment
+                    });
+                }
+            }
+        }
+        warnings
+    }
+
+    /// Get the natural alignment of an object in bytes.
+    ///
+    /// Primitives are aligned to the size of each component (so a vector of floats is aligned to
+    /// 4 bytes), and structs are aligned to their most aligned field.
+    pub fn natural_alignment(&self, object: &FieldObject) -> usize {
+        match object {
+            FieldObject::NamedObject(n) => match self.objects.get(n) {
+                Some(NamedObject::Struct(s)) => s.fields
+                    .iter()
+                    .filter_map(|f| match &f.field_type {
+                        StructFieldType::Object(o) => Some(self.natural_alignment(o)),
+                        _ => None
+                    })
+                    .max()
+                    .unwrap_or(1),
+                Some(o) => o.size(self).max(1),
+                None => 1
+            },
+            FieldObject::Reflexive(_)
+            | FieldObject::TagReference { .. }
+            | FieldObject::Data
+            | FieldObject::FileData
+            | FieldObject::BSPVertexData
+            | FieldObject::UTF16String => 4,
+            FieldObject::Custom(c) => {
+                let custom = &self.custom_objects[c];
+                (custom.size / custom.composite_count).clamp(1, 4)
+            },
+            o => (o.primitive_size() / o.composite_count()).clamp(1, 4)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn builtin_definitions_are_aligned() {
+        let warnings = load_all_definitions().alignment_warnings();
+        assert!(warnings.is_empty(), "unexpected alignment warnings: {warnings:?}");
+    }
+}
@@ -0,0 +1,262 @@
+use alloc::vec::Vec;
+
+use super::*;
+
+/// Describes how the values of an enum are interpreted beyond their names.
+///
+/// Set with `interpretation` in the JSON.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum EnumInterpretation {
+    /// Each option is a [`ScreenAnchor`], except `from parent`, which uses the anchor of the
+    /// parent element.
+    ScreenAnchor,
+
+    /// Each option is a [`CanvasSize`].
+    CanvasSize
+}
+
+impl EnumInterpretation {
+    /// Get the interpretation from its name in the JSON definitions (e.g. `screen anchor`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "screen anchor" => Some(Self::ScreenAnchor),
+            "canvas size" => Some(Self::CanvasSize),
+            _ => None
+        }
+    }
+
+    /// Get the name of the interpretation as used in the JSON definitions.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::ScreenAnchor => "screen anchor",
+            Self::CanvasSize => "canvas size"
+        }
+    }
+
+    /// Check that every option of the enum can be interpreted.
+    pub(crate) fn validate(self, e: &Enum) -> Result<(), DefinitionError> {
+        for option in &e.options {
+            let valid = match self {
+                Self::ScreenAnchor => option.name == "from parent" || ScreenAnchor::from_name(&option.name).is_some(),
+                Self::CanvasSize => CanvasSize::from_name(&option.name).is_some()
+            };
+            if !valid {
+                return Err(DefinitionError::Invalid {
+                    object: e.name.clone(),
+                    message: alloc::format!("{}::{} is not a valid {}", e.name, option.name, self.name())
+                })
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A point on the screen that HUD and UI elements are positioned relative to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ScreenAnchor {
+    /// The top left corner.
+    TopLeft,
+
+    /// The top right corner.
+    TopRight,
+
+    /// The bottom left corner.
+    BottomLeft,
+
+    /// The bottom right corner.
+    BottomRight,
+
+    /// The center of the screen.
+    Center,
+
+    /// The center of the top edge.
+    TopCenter,
+
+    /// The center of the bottom edge.
+    BottomCenter,
+
+    /// The center of the left edge.
+    LeftCenter,
+
+    /// The center of the right edge.
+    RightCenter
+}
+
+impl ScreenAnchor {
+    /// Get the anchor from its option name in the JSON definitions (e.g. `top left`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "top left" => Some(Self::TopLeft),
+            "top right" => Some(Self::TopRight),
+            "bottom left" => Some(Self::BottomLeft),
+            "bottom right" => Some(Self::BottomRight),
+            "center" => Some(Self::Center),
+            "top center" => Some(Self::TopCenter),
+            "bottom center" => Some(Self::BottomCenter),
+            "left center" => Some(Self::LeftCenter),
+            "right center" => Some(Self::RightCenter),
+            _ => None
+        }
+    }
+
+    /// Get the position of the anchor as a fraction of the width and height of the screen, where
+    /// `(0.0, 0.0)` is the top left corner.
+    pub const fn position(self) -> (f32, f32) {
+        match self {
+            Self::TopLeft => (0.0, 0.0),
+            Self::TopRight => (1.0, 0.0),
+            Self::BottomLeft => (0.0, 1.0),
+            Self::BottomRight => (1.0, 1.0),
+            Self::Center => (0.5, 0.5),
+            Self::TopCenter => (0.5, 0.0),
+            Self::BottomCenter => (0.5, 1.0),
+            Self::LeftCenter => (0.0, 0.5),
+            Self::RightCenter => (1.0, 0.5)
+        }
+    }
+
+    /// Get the direction an offset from the anchor is applied in on each axis.
+    ///
+    /// Offsets point away from the edges they are anchored to, so a positive offset from a right
+    /// or bottom anchor moves left or up, respectively.
+    pub const fn offset_direction(self) -> (f32, f32) {
+        let (x, y) = self.position();
+        (if x == 1.0 { -1.0 } else { 1.0 }, if y == 1.0 { -1.0 } else { 1.0 })
+    }
+
+    /// Get the position on the screen, in pixels, of an offset from the anchor.
+    ///
+    /// `offset` is in pixels of the `canvas` the element was authored for, and `screen` is the
+    /// resolution being rendered at.
+    pub fn resolve(self, offset: (f32, f32), canvas: CanvasSize, screen: (f32, f32), scaling: HUDScaling) -> (f32, f32) {
+        let (px, py) = self.position();
+        let (dx, dy) = self.offset_direction();
+        let (cw, ch) = (canvas.width as f32, canvas.height as f32);
+        match scaling {
+            HUDScaling::Stretch => {
+                let (sx, sy) = (screen.0 / cw, screen.1 / ch);
+                ((px * cw + dx * offset.0) * sx, (py * ch + dy * offset.1) * sy)
+            },
+            HUDScaling::Anchored => {
+                let scale = screen.1 / ch;
+                (px * screen.0 + dx * offset.0 * scale, py * screen.1 + dy * offset.1 * scale)
+            }
+        }
+    }
+}
+
+/// Resolution a HUD or UI element was authored for.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CanvasSize {
+    /// Width in pixels.
+    pub width: u32,
+
+    /// Height in pixels.
+    pub height: u32
+}
+
+impl CanvasSize {
+    /// Get the canvas size from its option name in the JSON definitions (e.g. `480p`).
+    ///
+    /// Canvases are always 4:3.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let height: u32 = name.strip_suffix('p')?.parse().ok()?;
+        (height > 0 && height.is_multiple_of(3)).then_some(Self { width: height / 3 * 4, height })
+    }
+}
+
+/// Describes how an engine fits HUD and UI elements to resolutions other than their canvas.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum HUDScaling {
+    /// The canvas is stretched to fill the screen, so elements are distorted on widescreen
+    /// resolutions.
+    Stretch,
+
+    /// Elements keep their aspect ratio and stay at the same distance from their anchor, scaled
+    /// by the height of the screen.
+    Anchored
+}
+
+impl HUDScaling {
+    /// Get the scaling from its name in the JSON definitions (e.g. `stretch`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "stretch" => Some(Self::Stretch),
+            "anchored" => Some(Self::Anchored),
+            _ => None
+        }
+    }
+
+    /// Get the name of the scaling as used in the JSON definitions.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Stretch => "stretch",
+            Self::Anchored => "anchored"
+        }
+    }
+}
+
+impl Enum {
+    /// Get the screen anchor of a value, if this enum is interpreted as
+    /// [`EnumInterpretation::ScreenAnchor`].
+    ///
+    /// Returns `None` for unknown values and `from parent`.
+    pub fn screen_anchor(&self, value: u32) -> Option<ScreenAnchor> {
+        if self.interpretation != Some(EnumInterpretation::ScreenAnchor) {
+            return None
+        }
+        ScreenAnchor::from_name(&self.options.iter().find(|o| o.value == value)?.name)
+    }
+
+    /// Get the canvas size of a value, if this enum is interpreted as
+    /// [`EnumInterpretation::CanvasSize`].
+    pub fn canvas_size(&self, value: u32) -> Option<CanvasSize> {
+        if self.interpretation != Some(EnumInterpretation::CanvasSize) {
+            return None
+        }
+        CanvasSize::from_name(&self.options.iter().find(|o| o.value == value)?.name)
+    }
+}
+
+impl ParsedDefinitions {
+    /// Get every enum with the given interpretation.
+    pub fn enums_with_interpretation(&self, interpretation: EnumInterpretation) -> Vec<&Enum> {
+        self.objects.values().filter_map(|o| match o {
+            NamedObject::Enum(e) if e.interpretation == Some(interpretation) => Some(e),
+            _ => None
+        }).collect()
+    }
+}
+
+#[cfg(all(test, feature = "engines-pc", feature = "engines-mcc"))]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn screen_anchors_resolve() {
+        let definitions = load_all_definitions();
+        let anchors = definitions.enums_with_interpretation(EnumInterpretation::ScreenAnchor);
+        assert!(anchors.iter().any(|e| e.name == "HUDInterfaceAnchor") && anchors.iter().any(|e| e.name == "HUDInterfaceChildAnchor"));
+
+        let NamedObject::Enum(child) = &definitions.objects["HUDInterfaceChildAnchor"] else { unreachable!() };
+        assert_eq!(child.screen_anchor(0), None);
+        assert_eq!(child.screen_anchor(4), Some(ScreenAnchor::BottomRight));
+        let NamedObject::Enum(canvas) = &definitions.objects["HUDInterfaceCanvasSize"] else { unreachable!() };
+        let canvas = canvas.canvas_size(0).unwrap();
+        assert_eq!((canvas.width, canvas.height), (640, 480));
+
+        assert_eq!(definitions.engines["pc-custom"].hud_scaling, HUDScaling::Stretch);
+        assert_eq!(definitions.engines["mcc-cea"].hud_scaling, HUDScaling::Anchored);
+        let anchor = ScreenAnchor::BottomRight;
+        assert_eq!(anchor.resolve((10.0, 20.0), canvas, (1280.0, 960.0), HUDScaling::Stretch), (1260.0, 920.0));
+        assert_eq!(anchor.resolve((10.0, 20.0), canvas, (1920.0, 960.0), HUDScaling::Anchored), (1900.0, 920.0));
+    }
+}
@@ -0,0 +1,224 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use super::*;
+
+/// Generate an [Assembly](https://github.com/XboxChaos/Assembly) plugin for a tag group.
+///
+/// `game` is written as the plugin's `game` attribute (e.g. `Halo1`). Fields are written with the
+/// offsets of the group's base struct, with reflexives containing their element's fields.
+///
+/// Assembly has no inline structs, so fields of inline structs (including the group's parent
+/// group) are flattened into the struct containing them, prefixed with the inline struct field's
+/// name unless it is inherited. Arrays are written one element at a time, padding is omitted,
+/// and editor sections become comments. Fields that take no space (e.g. empty structs and
+/// zero-width bitfields) are omitted. Fields that are hidden in the editor are not visible.
+pub fn generate_assembly_plugin(definitions: &ParsedDefinitions, group: &TagGroup, game: &str) -> String {
+    let base = group.definition(definitions).view(definitions);
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    let _ = writeln!(output, "<plugin game=\"{}\" baseSize=\"0x{:X}\">", escape(game), base.size());
+    let _ = writeln!(output, "\t<!-- Generated from the {} group definition -->", escape(&group.name));
+
+    let mut writer = PluginWriter { output, structs: Vec::new() };
+    writer.write_struct(base, 0, "", 1);
+    writer.output.push_str("</plugin>\n");
+    writer.output
+}
+
+struct PluginWriter<'a> {
+    output: String,
+    structs: Vec<&'a str>
+}
+
+impl<'a> PluginWriter<'a> {
+    fn write_struct(&mut self, s: StructView<'a>, base_offset: usize, prefix: &str, depth: usize) {
+        let name = s.get().name.as_str();
+        if self.structs.contains(&name) {
+            return
+        }
+        self.structs.push(name);
+
+        for f in s.fields() {
+            let offset = base_offset + f.relative_offset;
+            let visible = !f.flags.hidden_in_editor;
+            let count = f.count.field_count();
+            let element_size = f.field_type.size(s.definitions());
+
+            let object = match &f.field_type {
+                StructFieldType::Object(o) => o,
+                StructFieldType::Padding(_) => continue,
+                StructFieldType::EditorSection { heading, body } => {
+                    self.indent(depth);
+                    let _ = writeln!(self.output, "<comment title=\"{}\">{}</comment>", escape(heading), escape(body.as_deref().unwrap_or_default()));
+                    continue
+                }
+            };
+
+            // Nothing is stored for empty structs, zero-width bitfields, and empty arrays
+            if element_size * count == 0 {
+                continue
+            }
+
+            // Assembly has range types for some bounds, so use them where possible.
+            if f.count == FieldCount::Bounds {
+                let range = match object {
+                    FieldObject::F32 => Some("rangef"),
+                    FieldObject::Angle => Some("rangedegree"),
+                    FieldObject::I16 => Some("rangeint16"),
+                    _ => None
+                };
+                if let Some(range) = range {
+                    self.write_element(depth, range, &format!("{prefix}{}", f.name), offset, visible, "");
+                    continue
+                }
+            }
+
+            for i in 0..count {
+                let element_name = match f.count {
+                    FieldCount::One => format!("{prefix}{}", f.name),
+                    FieldCount::Bounds => format!("{prefix}{} {}", f.name, if i == 0 { "lower" } else { "upper" }),
+                    FieldCount::Array(_) => format!("{prefix}{} {i}", f.name)
+                };
+                let offset = offset + i * element_size;
+
+                match f.resolved_type() {
+                    ResolvedFieldType::Struct(inner) => {
+                        let is_parent = f.name == inner.get().name;
+                        let inner_prefix = if is_parent { String::from(prefix) } else { format!("{element_name} ") };
+                        self.write_struct(inner, offset, &inner_prefix, depth);
+                    },
+                    ResolvedFieldType::Enum(e) => {
+                        self.indent(depth);
+                        let _ = writeln!(self.output, "<enum16 name=\"{}\" offset=\"0x{offset:X}\" visible=\"{visible}\">", escape(&element_name));
+                        for o in &e.options {
+                            self.indent(depth + 1);
+                            let _ = writeln!(self.output, "<option name=\"{}\" value=\"0x{:X}\" />", escape(&o.name), o.value);
+                        }
+                        self.indent(depth);
+                        self.output.push_str("</enum16>\n");
+                    },
+                    ResolvedFieldType::Bitfield(b) => {
+                        self.indent(depth);
+                        let _ = writeln!(self.output, "<bitfield{} name=\"{}\" offset=\"0x{offset:X}\" visible=\"{visible}\">", b.width, escape(&element_name));
+                        for flag in &b.fields {
+                            self.indent(depth + 1);
+                            let _ = writeln!(self.output, "<bit name=\"{}\" index=\"{}\" />", escape(&flag.name), flag.value.trailing_zeros());
+                        }
+                        self.indent(depth);
+                        let _ = writeln!(self.output, "</bitfield{}>", b.width);
+                    },
+                    ResolvedFieldType::Reflexive(element) => {
+                        self.indent(depth);
+                        let _ = writeln!(self.output, "<reflexive name=\"{}\" offset=\"0x{offset:X}\" visible=\"{visible}\" entrySize=\"0x{:X}\">", escape(&element_name), element.size());
+                        self.write_struct(element, 0, "", depth + 1);
+                        self.indent(depth);
+                        self.output.push_str("</reflexive>\n");
+                    },
+                    ResolvedFieldType::Other(_) => self.write_primitive(depth, object, &element_name, offset, visible, element_size)
+                }
+            }
+        }
+
+        self.structs.pop();
+    }
+
+    fn write_primitive(&mut self, depth: usize, object: &FieldObject, name: &str, offset: usize, visible: bool, size: usize) {
+        let (element, extra) = match object {
+            FieldObject::TagReference { .. } => ("tagRef", " withGroup=\"true\""),
+            FieldObject::Data | FieldObject::FileData | FieldObject::BSPVertexData => ("dataRef", ""),
+            FieldObject::UTF16String => ("dataRef", " format=\"utf16\""),
+            FieldObject::String32 => ("ascii", " length=\"32\""),
+            FieldObject::F32 => ("float32", ""),
+            FieldObject::Angle => ("degree", ""),
+            FieldObject::U8 => ("uint8", ""),
+            FieldObject::I8 => ("int8", ""),
+            FieldObject::U16 | FieldObject::CompressedFloat => ("uint16", ""),
+            FieldObject::I16 | FieldObject::Index | FieldObject::ReflexiveIndex { .. } => ("int16", ""),
+            FieldObject::I32 => ("int32", ""),
+            FieldObject::U32
+            | FieldObject::TagID
+            | FieldObject::ID
+            | FieldObject::Address
+            | FieldObject::TagGroup
+            | FieldObject::CompressedVector2D
+            | FieldObject::CompressedVector3D
+            | FieldObject::ScenarioScriptNodeValue => ("uint32", ""),
+            FieldObject::Vector2D => ("point2", ""),
+            FieldObject::Vector3D => ("point3", ""),
+            FieldObject::Vector2DInt => ("point16", ""),
+            FieldObject::Euler2D => ("degree2", ""),
+            FieldObject::Euler3D => ("degree3", ""),
+            FieldObject::Plane2D => ("plane2", ""),
+            FieldObject::Plane3D => ("plane3", ""),
+            FieldObject::Quaternion => ("quaternion", ""),
+            FieldObject::Rectangle => ("rect16", ""),
+            FieldObject::ColorRGB => ("colorf", " format=\"rgb\""),
+            FieldObject::ColorARGB => ("colorf", " format=\"argb\""),
+            FieldObject::Pixel32 => ("colorb", " format=\"argb\""),
+
+            // No equivalent, so write the individual floats.
+            FieldObject::Rectangle3D | FieldObject::Matrix2x3 | FieldObject::Matrix3x3 | FieldObject::Matrix4x3 => {
+                let float_size = size_of::<f32>();
+                for (i, component) in object.components().iter().enumerate() {
+                    self.write_element(depth, "float32", &format!("{name} {}", component.name), offset + i * float_size, visible, "");
+                }
+                return
+            },
+
+            FieldObject::Custom(_) => {
+                self.write_element(depth, "raw", name, offset, visible, &format!(" size=\"0x{size:X}\""));
+                return
+            },
+
+            FieldObject::NamedObject(_) | FieldObject::Reflexive(_) => unreachable!("unresolved object {name}")
+        };
+        self.write_element(depth, element, name, offset, visible, extra);
+    }
+
+    fn write_element(&mut self, depth: usize, element: &str, name: &str, offset: usize, visible: bool, extra: &str) {
+        self.indent(depth);
+        let _ = writeln!(self.output, "<{element} name=\"{}\" offset=\"0x{offset:X}\" visible=\"{visible}\"{extra} />", escape(name));
+    }
+
+    fn indent(&mut self, depth: usize) {
+        for _ in 0..depth {
+            self.output.push('\t');
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c)
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn assembly_plugins_describe_groups() {
+        let definitions = load_all_definitions();
+        let plugin = generate_assembly_plugin(definitions, &definitions.groups["string_list"], "Halo1");
+        assert!(plugin.starts_with("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<plugin game=\"Halo1\" baseSize=\"0xC\">\n"));
+        assert!(plugin.contains("\t<reflexive name=\"strings\" offset=\"0x0\" visible=\"true\" entrySize=\"0x14\">\n\t\t<dataRef name=\"string\" offset=\"0x0\" visible=\"true\" />\n\t</reflexive>\n"));
+        assert!(plugin.ends_with("</plugin>\n"));
+
+        // Inherited fields are flattened into the child group.
+        let biped = generate_assembly_plugin(definitions, &definitions.groups["biped"], "Halo1");
+        assert!(biped.contains("<tagRef name=\"model\" offset=\"0x28\" visible=\"true\" withGroup=\"true\" />"));
+        assert_eq!(biped.matches("<reflexive ").count(), biped.matches("</reflexive>").count());
+    }
+}
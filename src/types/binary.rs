@@ -0,0 +1,394 @@
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::*;
+
+include!("binary_header.rs");
+
+impl ParsedDefinitions {
+    /// Write the definitions in a compact binary format that can be loaded much faster than JSON.
+    ///
+    /// The definitions should be finalized first, as they are written exactly as they are and no
+    /// finalization is done by [`ParsedDefinitions::from_binary`].
+    ///
+    /// The data starts with [`BINARY_MAGIC`], [`BINARY_FORMAT_VERSION`] as a little endian `u32`,
+    /// and the [`DefinitionsProvenance::json_digest`] of the JSON it was made from as a little
+    /// endian `u64` (0 if it was not made from the built-in definitions). The rest of the format is
+    /// not stable and only meant to be read by the same version of this crate.
+    pub fn to_binary(&self) -> Vec<u8> {
+        self.to_binary_with_digest(0)
+    }
+
+    /// Load definitions written by [`ParsedDefinitions::to_binary`].
+    pub fn from_binary(data: &[u8]) -> Result<ParsedDefinitions, DefinitionError> {
+        let error = |message: String| DefinitionError::InvalidBinary { message };
+
+        let mut reader = Reader { data, offset: 0 };
+        let magic: [u8; 4] = reader.bytes(4).map_err(error)?.try_into().unwrap();
+        if magic != BINARY_MAGIC {
+            return Err(error("not a binary definitions file".to_owned()))
+        }
+        let version = u32::read(&mut reader).map_err(error)?;
+        if version != BINARY_FORMAT_VERSION {
+            return Err(error(format!("binary definitions are version {version}, but only version {BINARY_FORMAT_VERSION} is supported")))
+        }
+        u64::read(&mut reader).map_err(error)?;
+
+        let mut definitions = ParsedDefinitions {
+            objects: Binary::read(&mut reader).map_err(error)?,
+            groups: Binary::read(&mut reader).map_err(error)?,
+            engines: Binary::read(&mut reader).map_err(error)?,
+            limit_profiles: Binary::read(&mut reader).map_err(error)?,
+            group_fourccs: BTreeMap::new(),
+            unknown_engine_policy: Binary::read(&mut reader).map_err(error)?,
+            custom_objects: Binary::read(&mut reader).map_err(error)?,
+            referencers: BTreeMap::new()
+        };
+        if reader.offset != data.len() {
+            return Err(error(format!("unexpected data at offset {}", reader.offset)))
+        }
+
+        definitions.group_fourccs = definitions.groups.values().map(|g| (g.fourcc_binary, g.name.to_owned())).collect();
+        definitions.index_referencers();
+        Ok(definitions)
+    }
+
+    fn to_binary_with_digest(&self, json_digest: u64) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&BINARY_MAGIC);
+        BINARY_FORMAT_VERSION.write(&mut data);
+        json_digest.write(&mut data);
+        self.objects.write(&mut data);
+        self.groups.write(&mut data);
+        self.engines.write(&mut data);
+        self.limit_profiles.write(&mut data);
+        self.unknown_engine_policy.write(&mut data);
+        self.custom_objects.write(&mut data);
+        data
+    }
+}
+
+/// Write the built-in definitions with [`ParsedDefinitions::to_binary`], including the digest of
+/// the JSON they were made from.
+///
+/// Calling this does not change how the built-in definitions are loaded. The result must be saved
+/// to a file, and the `RINGHOPPER_DEFINITIONS_PRECOMPILED` environment variable set to its path
+/// when building this crate (with the `binary` feature enabled). Only then is it embedded and used
+/// by [`load_all_definitions`](crate::load_all_definitions) instead of parsing JSON. Otherwise, or
+/// if the file was made from different JSON, JSON is parsed as usual.
+pub fn precompile_builtin_definitions() -> Vec<u8> {
+    crate::load_all_definitions().to_binary_with_digest(builtin_provenance().json_digest)
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, length: usize) -> Result<&'a [u8], String> {
+        let bytes = self.offset.checked_add(length)
+            .and_then(|end| self.data.get(self.offset..end))
+            .ok_or_else(|| format!("unexpected end of data at offset {}", self.offset))?;
+        self.offset += length;
+        Ok(bytes)
+    }
+}
+
+trait Binary: Sized {
+    fn write(&self, data: &mut Vec<u8>);
+    fn read(reader: &mut Reader) -> Result<Self, String>;
+}
+
+macro_rules! binary_integer {
+    ($($t:ty),+) => {
+        $(impl Binary for $t {
+            fn write(&self, data: &mut Vec<u8>) {
+                data.extend_from_slice(&self.to_le_bytes());
+            }
+            fn read(reader: &mut Reader) -> Result<Self, String> {
+                Ok(Self::from_le_bytes(reader.bytes(size_of::<Self>())?.try_into().unwrap()))
+            }
+        })+
+    };
+}
+
+binary_integer!(u8, u16, u32, u64, i64, f32, f64);
+
+impl Binary for usize {
+    fn write(&self, data: &mut Vec<u8>) {
+        (*self as u64).write(data)
+    }
+    fn read(reader: &mut Reader) -> Result<Self, String> {
+        let offset = reader.offset;
+        u64::read(reader)?.try_into().map_err(|_| format!("size at offset {offset} is too large"))
+    }
+}
+
+impl Binary for bool {
+    fn write(&self, data: &mut Vec<u8>) {
+        (*self as u8).write(data)
+    }
+    fn read(reader: &mut Reader) -> Result<Self, String> {
+        let offset = reader.offset;
+        match u8::read(reader)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            n => Err(format!("invalid bool {n} at offset {offset}"))
+        }
+    }
+}
+
+impl Binary for String {
+    fn write(&self, data: &mut Vec<u8>) {
+        self.len().write(data);
+        data.extend_from_slice(self.as_bytes());
+    }
+    fn read(reader: &mut Reader) -> Result<Self, String> {
+        let length = usize::read(reader)?;
+        let offset = reader.offset;
+        let bytes = reader.bytes(length)?;
+        core::str::from_utf8(bytes).map(|s| s.to_owned()).map_err(|_| format!("invalid UTF-8 at offset {offset}"))
+    }
+}
+
+impl<T: Binary> Binary for Option<T> {
+    fn write(&self, data: &mut Vec<u8>) {
+        self.is_some().write(data);
+        if let Some(value) = self {
+            value.write(data);
+        }
+    }
+    fn read(reader: &mut Reader) -> Result<Self, String> {
+        Ok(if bool::read(reader)? { Some(T::read(reader)?) } else { None })
+    }
+}
+
+impl<T: Binary> Binary for Vec<T> {
+    fn write(&self, data: &mut Vec<u8>) {
+        self.len().write(data);
+        self.iter().for_each(|v| v.write(data));
+    }
+    fn read(reader: &mut Reader) -> Result<Self, String> {
+        (0..usize::read(reader)?).map(|_| T::read(reader)).collect()
+    }
+}
+
+impl<T: Binary + Ord> Binary for BTreeSet<T> {
+    fn write(&self, data: &mut Vec<u8>) {
+        self.len().write(data);
+        self.iter().for_each(|v| v.write(data));
+    }
+    fn read(reader: &mut Reader) -> Result<Self, String> {
+        (0..usize::read(reader)?).map(|_| T::read(reader)).collect()
+    }
+}
+
+impl<K: Binary + Ord, V: Binary> Binary for BTreeMap<K, V> {
+    fn write(&self, data: &mut Vec<u8>) {
+        self.len().write(data);
+        for (k, v) in self {
+            k.write(data);
+            v.write(data);
+        }
+    }
+    fn read(reader: &mut Reader) -> Result<Self, String> {
+        (0..usize::read(reader)?).map(|_| Ok((K::read(reader)?, V::read(reader)?))).collect()
+    }
+}
+
+impl Binary for RangeInclusive<u8> {
+    fn write(&self, data: &mut Vec<u8>) {
+        self.start().write(data);
+        self.end().write(data);
+    }
+    fn read(reader: &mut Reader) -> Result<Self, String> {
+        Ok(u8::read(reader)?..=u8::read(reader)?)
+    }
+}
+
+/// Structs are written as each of their fields in order.
+macro_rules! binary_struct {
+    ($($name:ident { $($field:ident),+ })+) => {
+        $(impl Binary for $name {
+            fn write(&self, data: &mut Vec<u8>) {
+                $(self.$field.write(data);)+
+            }
+            fn read(reader: &mut Reader) -> Result<Self, String> {
+                Ok(Self { $($field: Binary::read(reader)?),+ })
+            }
+        })+
+    };
+}
+
+/// Enums without data are written as the index of the variant.
+macro_rules! binary_enum {
+    ($($name:ident { $($variant:ident),+ })+) => {
+        $(impl Binary for $name {
+            fn write(&self, data: &mut Vec<u8>) {
+                ([$(matches!(self, Self::$variant)),+].iter().position(|v| *v).unwrap() as u8).write(data)
+            }
+            fn read(reader: &mut Reader) -> Result<Self, String> {
+                let offset = reader.offset;
+                let index = u8::read(reader)?;
+                [$(Self::$variant),+].into_iter().nth(index as usize).ok_or_else(|| format!("invalid {} {index} at offset {offset}", stringify!($name)))
+            }
+        })+
+    };
+}
+
+/// Enums with data are written as a tag followed by the data of the variant.
+macro_rules! binary_tagged_enum {
+    ($($name:ident { $($tag:literal => $variant:ident $({ $($field:ident),+ })? $(($value:ident))?),+ })+) => {
+        $(impl Binary for $name {
+            fn write(&self, data: &mut Vec<u8>) {
+                match self {
+                    $(Self::$variant $({ $($field),+ })? $(($value))? => {
+                        ($tag as u8).write(data);
+                        $($($field.write(data);)+)?
+                        $($value.write(data);)?
+                    }),+
+                }
+            }
+            fn read(reader: &mut Reader) -> Result<Self, String> {
+                let offset = reader.offset;
+                match u8::read(reader)? {
+                    $($tag => Ok(Self::$variant $({ $($field: Binary::read(reader)?),+ })? $((binary_tagged_enum!(@read $value reader)))?),)+
+                    tag => Err(format!("invalid {} {tag} at offset {offset}", stringify!($name)))
+                }
+            }
+        })+
+    };
+    (@read $value:ident $reader:ident) => { Binary::read($reader)? };
+}
+
+binary_struct! {
+    LimitProfile { name, definition_file, description }
+    TagGroup {
+        name, definition_file, name_rust_enum, struct_name, supergroup, supported_engines, version, fourcc_binary, see_also,
+        extension, extension_aliases, scenario_types, documentation, short_code, sort_order
+    }
+    GroupDocumentation { overview, usage_notes }
+    Struct {
+        name, definition_file, fields, is_const, contains_tag_references, contains_data, contains_reflexives, max_depth, flags,
+        size, packed, defined_memory_pool
+    }
+    StructField {
+        name, name_rust_enum, name_rust_field, field_type, default_value, count, engine_counts, nullability, minimum, maximum,
+        limit, flags, relative_offset, padding_kind, widget, step, precision, memory_pool, byte_budget, normalized, color_space
+    }
+    Bitfield { name, definition_file, width, fields, exclusive_groups, implications, copy_of, flags }
+    Enum { name, definition_file, options, interpretation, global_role, copy_of, flags }
+    Field { name, name_rust_enum, name_rust_field, flags, value }
+    Flags {
+        cache_only, non_cached, uneditable_in_editor, hidden_in_editor, exclude, little_endian_in_tags, shifted_by_one,
+        supported_engines, comment, developer_note, description, introduced_in, removed_in, examples, see_also, research,
+        cache_rewrite, determinism
+    }
+    Research { confidence, observed_values, links, notes }
+    Engine {
+        name, definition_file, display_name, version, build, inherits, build_target, fallback, custom, cache_file_version,
+        cache_default, external_bsps, external_models, max_script_nodes, script_node_table, max_tag_space, compressed_models, data_alignment,
+        compressed_data_alignment, obfuscated_header_layout, bitmap_options, resource_maps, cache_parser, max_cache_file_size,
+        base_memory_address, required_tags, compression_type, grenades, minimum_weapons, tag_paths, hud_scaling, documentation
+    }
+    EngineDocumentation { description, release_date, distribution, quirks }
+    EngineGrenades { singleplayer, multiplayer, user_interface }
+    EngineSupportedResourceMaps { externally_indexed_tags }
+    EngineCacheFileSize { user_interface, singleplayer, multiplayer }
+    EngineRequiredTags { all, user_interface, singleplayer, multiplayer }
+    BaseMemoryAddress { address, inferred }
+    Build { string, aliases, enforced }
+    EngineBitmapOptions { swizzled, texture_dimension_must_modulo_block_size, cubemap_faces_stored_separately, alignment }
+    EngineTagPaths { max_length, character_set }
+    EngineScriptNodeTable { table_struct, node_struct, name, signature, first_salt }
+    CustomFieldObject { name, size, composite_count, is_const }
+}
+
+binary_enum! {
+    ScenarioType { Singleplayer, Multiplayer, UserInterface }
+    PaddingKind { Padding, Reserved }
+    ResearchConfidence { Speculative, Likely, Confirmed }
+    Nullability { NonNull, Nullable }
+    EngineCacheParser { Xbox, PC }
+    EngineCompressionType { Uncompressed, Deflate }
+    MemoryPool { TagData, VertexPool, SoundCache, TextureCache }
+    CacheRewrite { Pointer, Moved, Recomputed }
+    Determinism { Gameplay, Cosmetic }
+    ColorSpace { Linear, SRGB }
+    TagPathCharacterSet { ASCII }
+    UnknownEnginePolicy { Error, TreatAsUnsupported, TreatAsSupported }
+    EnumInterpretation { ScreenAnchor, CanvasSize }
+    HUDScaling { Stretch, Anchored }
+    GlobalEnumRole { MaterialType, DamageCategory }
+}
+
+binary_tagged_enum! {
+    NamedObject { 0 => Struct(s), 1 => Enum(e), 2 => Bitfield(b) }
+    SeeAlso { 0 => Group(group), 1 => Field { object, field } }
+    LimitType { 0 => Engine(engine), 1 => Default, 2 => Editor, 3 => Profile(profile) }
+    StructFieldType { 0 => Object(object), 1 => Padding(size), 2 => EditorSection { heading, body } }
+    FieldCount { 0 => One, 1 => Bounds, 2 => Array(count) }
+    StaticValue { 0 => Float(f), 1 => Uint(u), 2 => Int(i), 3 => String(s) }
+    SupportedEngines { 0 => AllEngines, 1 => SomeEngines(engines), 2 => AllExcept(engines) }
+    EditorWidget { 0 => Slider, 1 => ColorPicker, 2 => AngleDial, 3 => PathPicker { allowed_groups } }
+}
+
+impl Binary for FieldObject {
+    fn write(&self, data: &mut Vec<u8>) {
+        match self {
+            Self::NamedObject(name) => { 0u8.write(data); name.write(data); },
+            Self::Reflexive(name) => { 1u8.write(data); name.write(data); },
+            Self::TagReference { allowed_groups } => { 2u8.write(data); allowed_groups.write(data); },
+            Self::ReflexiveIndex { struct_name, reflexive_name_display, reflexive_name_rust } => {
+                3u8.write(data);
+                struct_name.write(data);
+                reflexive_name_display.write(data);
+                reflexive_name_rust.write(data);
+            },
+            Self::Custom(name) => { 4u8.write(data); name.write(data); },
+
+            // Everything else has no data, so it is written by the name used in the JSON.
+            _ => { 5u8.write(data); self.type_name().to_owned().write(data); }
+        }
+    }
+    fn read(reader: &mut Reader) -> Result<Self, String> {
+        let offset = reader.offset;
+        match u8::read(reader)? {
+            0 => Ok(Self::NamedObject(Binary::read(reader)?)),
+            1 => Ok(Self::Reflexive(Binary::read(reader)?)),
+            2 => Ok(Self::TagReference { allowed_groups: Binary::read(reader)? }),
+            3 => Ok(Self::ReflexiveIndex {
+                struct_name: Binary::read(reader)?,
+                reflexive_name_display: Binary::read(reader)?,
+                reflexive_name_rust: Binary::read(reader)?
+            }),
+            4 => Ok(Self::Custom(Binary::read(reader)?)),
+            5 => {
+                let name = String::read(reader)?;
+                simple_field_object(&name).ok_or_else(|| format!("unknown field type {name} at offset {offset}"))
+            },
+            tag => Err(format!("invalid FieldObject {tag} at offset {offset}"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn binary_definitions_round_trip() {
+        let definitions = load_all_definitions();
+        let binary = definitions.to_binary();
+        let loaded = ParsedDefinitions::from_binary(&binary).unwrap();
+        assert_eq!(loaded.fingerprint(), definitions.fingerprint());
+        assert_eq!(loaded.to_binary(), binary);
+        assert!(loaded.group_by_fourcc(definitions.groups["scenario"].fourcc_binary).is_some());
+
+        assert!(ParsedDefinitions::from_binary(&binary[..binary.len() - 1]).is_err());
+        assert!(ParsedDefinitions::from_binary(b"{}").is_err());
+    }
+}
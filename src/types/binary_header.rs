@@ -0,0 +1,10 @@
+// Shared with build.rs (through `include!`), which checks precompiled definitions against these.
+
+/// Version of the format written by [`ParsedDefinitions::to_binary`].
+///
+/// This is increased whenever the format changes, and data written with a different version is
+/// rejected.
+pub const BINARY_FORMAT_VERSION: u32 = 8;
+
+/// First four bytes of data written by [`ParsedDefinitions::to_binary`].
+pub const BINARY_MAGIC: [u8; 4] = *b"RHDB";
@@ -0,0 +1,315 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::borrow::ToOwned;
+use alloc::format;
+
+use super::*;
+
+/// Estimated tag space used by a single tag of a group on a given engine.
+///
+/// Created by [`ParsedDefinitions::estimate_budget`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BudgetEstimate {
+    /// Name of the tag group.
+    pub group: String,
+
+    /// Name of the engine.
+    pub engine: String,
+
+    /// Size of the group's base struct, in bytes, with every reflexive empty.
+    pub fixed_size: u64,
+
+    /// Size in bytes if every reflexive is filled to its default limit.
+    ///
+    /// Reflexives without a default limit are treated as empty.
+    pub typical_size: u64,
+
+    /// Size in bytes if every reflexive is filled to its limit for the engine, or `None` if any
+    /// reflexive that can be reached is unlimited.
+    pub worst_case_size: Option<u64>,
+
+    /// True if the group contains data fields in tag data (e.g. [`FieldObject::Data`]), whose sizes
+    /// are not included in any estimate.
+    pub has_unbounded_data: bool,
+
+    /// Maximum tag space of the engine ([`Engine::max_tag_space`]).
+    pub max_tag_space: u64
+}
+
+impl BudgetEstimate {
+    /// Returns true if the typical size alone exceeds the engine's tag space.
+    pub fn typical_exceeds_tag_space(&self) -> bool {
+        self.typical_size > self.max_tag_space
+    }
+
+    /// Returns true if the worst-case size exceeds (or may exceed) the engine's tag space.
+    pub fn worst_case_exceeds_tag_space(&self) -> bool {
+        self.worst_case_size.is_none_or(|s| s > self.max_tag_space)
+    }
+}
+
+impl ParsedDefinitions {
+    /// Estimate the tag space a single tag of a group uses when built for the given engine.
+    ///
+    /// Only fields supported by the engine are counted, and reflexive limits are looked up with
+    /// [`FieldView::limit_for_engine`]. Reflexives and data outside of tag data on the engine (see
+    /// [`FieldView::memory_pool`]), such as model vertices on engines with external models, do not
+    /// use tag space and are not counted. Returns `None` if the group does not exist.
+    pub fn estimate_budget(&self, group: &str, engine: &Engine) -> Option<BudgetEstimate> {
+        let group = self.get_group(group)?;
+        let base = self.struct_view(&group.struct_name)?;
+
+        let mut estimator = BudgetEstimator { engine, has_unbounded_data: false, stack: Vec::new() };
+        let (typical_size, worst_case_size) = estimator.estimate(base);
+
+        Some(BudgetEstimate {
+            group: group.name.to_owned(),
+            engine: engine.name.to_owned(),
+            fixed_size: base.size() as u64,
+            typical_size,
+            worst_case_size,
+            has_unbounded_data: estimator.has_unbounded_data,
+            max_tag_space: engine.max_tag_space
+        })
+    }
+}
+
+/// Maximum number of bytes a reflexive's elements can use on a given engine.
+///
+/// Created by [`ParsedDefinitions::reflexive_byte_ceilings`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ReflexiveByteCeiling {
+    /// Name of the struct containing the reflexive.
+    pub struct_name: String,
+
+    /// Name of the reflexive field.
+    pub field: String,
+
+    /// Maximum number of elements on the engine.
+    pub limit: usize,
+
+    /// Size of each element in bytes.
+    pub element_size: usize,
+
+    /// Where the elements live on the engine.
+    ///
+    /// Only ceilings in [`MemoryPool::TagData`] are limited by [`Engine::max_tag_space`].
+    pub memory_pool: MemoryPool,
+
+    /// Maximum size of every element combined in bytes.
+    pub bytes: u64,
+
+    /// Explicit byte budget of the reflexive ([`StructField::byte_budget`]), if any.
+    pub budget: Option<u64>
+}
+
+impl FieldView<'_> {
+    /// Get the maximum number of bytes this reflexive's elements can use on the given engine.
+    ///
+    /// Returns `None` if this is not a reflexive or it has no limit for the engine.
+    pub fn byte_ceiling(&self, engine: &str) -> Option<u64> {
+        let ResolvedFieldType::Reflexive(element) = self.resolved_type() else {
+            return None
+        };
+        Some(self.limit_for_engine(engine)? as u64 * element.size() as u64)
+    }
+}
+
+impl ParsedDefinitions {
+    /// Get the byte ceiling of every limited reflexive supported by the given engine.
+    ///
+    /// When finalizing, every ceiling in tag data must fit in [`Engine::max_tag_space`], and every
+    /// ceiling must fit in its [explicit budget](StructField::byte_budget), if any.
+    pub fn reflexive_byte_ceilings(&self, engine: &Engine) -> Vec<ReflexiveByteCeiling> {
+        let mut ceilings = Vec::new();
+        for o in self.objects.values() {
+            let NamedObject::Struct(s) = o else {
+                continue
+            };
+            for f in s.view(self).fields() {
+                if !f.flags.supported_engines.supports_engine(engine) {
+                    continue
+                }
+                let (Some(bytes), ResolvedFieldType::Reflexive(element)) = (f.byte_ceiling(&engine.name), f.resolved_type()) else {
+                    continue
+                };
+                ceilings.push(ReflexiveByteCeiling {
+                    struct_name: s.name.to_owned(),
+                    field: f.name.to_owned(),
+                    limit: f.limit_for_engine(&engine.name).expect("a byte ceiling requires a limit"),
+                    element_size: element.size(),
+                    memory_pool: f.memory_pool(engine),
+                    bytes,
+                    budget: f.byte_budget
+                });
+            }
+        }
+        ceilings
+    }
+
+    pub(crate) fn check_reflexive_byte_ceilings(&self) -> Result<(), DefinitionError> {
+        for engine in self.engines.values() {
+            for c in self.reflexive_byte_ceilings(engine) {
+                if c.memory_pool == MemoryPool::TagData && c.bytes > engine.max_tag_space {
+                    return Err(DefinitionError::Invalid {
+                        message: format!(
+                            "{struct_name}::{field}'s limit of {limit} (0x{bytes:X} bytes) exceeds the maximum tag space of {engine} (0x{max_tag_space:X} bytes)",
+                            struct_name=c.struct_name,
+                            field=c.field,
+                            limit=c.limit,
+                            bytes=c.bytes,
+                            engine=engine.name,
+                            max_tag_space=engine.max_tag_space
+                        ),
+                        object: c.struct_name
+                    })
+                }
+                if let Some(budget) = c.budget.filter(|b| c.bytes > *b) {
+                    return Err(DefinitionError::Invalid {
+                        message: format!(
+                            "{struct_name}::{field}'s limit of {limit} (0x{bytes:X} bytes) exceeds its byte budget of 0x{budget:X} bytes on {engine}",
+                            struct_name=c.struct_name,
+                            field=c.field,
+                            limit=c.limit,
+                            bytes=c.bytes,
+                            engine=engine.name
+                        ),
+                        object: c.struct_name
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+struct BudgetEstimator<'a> {
+    engine: &'a Engine,
+    has_unbounded_data: bool,
+    stack: Vec<&'a str>
+}
+
+impl<'a> BudgetEstimator<'a> {
+    /// Returns the (typical, worst case) size of one element of a struct.
+    fn estimate(&mut self, s: StructView<'a>) -> (u64, Option<u64>) {
+        let mut typical = s.size() as u64;
+        let mut worst_case = Some(typical);
+
+        // A struct that (indirectly) contains itself can grow without limit.
+        if self.stack.contains(&s.get().name.as_str()) {
+            return (typical, None)
+        }
+        self.stack.push(s.get().name.as_str());
+
+        for f in s.fields() {
+            if !f.flags.supported_engines.supports_engine(self.engine) {
+                continue
+            }
+
+            // Other pools do not use tag space.
+            let in_tag_data = f.memory_pool(self.engine) == MemoryPool::TagData;
+
+            let count = f.count_for_engine(&self.engine.name) as u64;
+            match f.resolved_type() {
+                ResolvedFieldType::Struct(inner) => {
+                    // The inline struct's own size is already part of this struct's size.
+                    let (t, w) = self.estimate(inner);
+                    let inline_size = inner.size() as u64;
+                    typical += (t - inline_size) * count;
+                    worst_case = worst_case.zip(w).map(|(a, b)| a.saturating_add((b - inline_size).saturating_mul(count)));
+                },
+                ResolvedFieldType::Reflexive(inner) if in_tag_data => {
+                    let (t, w) = self.estimate(inner);
+                    let default_limit = f.limit.as_ref()
+                        .and_then(|l| l.get(&LimitType::Default))
+                        .copied()
+                        .or_else(|| f.limit_for_engine(&self.engine.name))
+                        .unwrap_or(0) as u64;
+                    typical += t * default_limit * count;
+                    worst_case = match (worst_case, w, f.limit_for_engine(&self.engine.name)) {
+                        (Some(a), Some(b), Some(limit)) => Some(a.saturating_add(b.saturating_mul(limit as u64).saturating_mul(count))),
+                        _ => None
+                    };
+                },
+                ResolvedFieldType::Other(StructFieldType::Object(FieldObject::Data | FieldObject::FileData | FieldObject::BSPVertexData | FieldObject::UTF16String)) if in_tag_data => {
+                    self.has_unbounded_data = true;
+                },
+                _ => ()
+            }
+        }
+
+        self.stack.pop();
+        (typical, worst_case)
+    }
+}
+
+#[cfg(all(test, feature = "engines-pc"))]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn budget_bounds_are_ordered() {
+        let definitions = load_all_definitions();
+        let engine = &definitions.engines["pc-retail"];
+        for group in definitions.groups.keys() {
+            let Some(budget) = definitions.estimate_budget(group, engine) else {
+                continue
+            };
+            assert!(budget.fixed_size <= budget.typical_size);
+            if let Some(w) = budget.worst_case_size {
+                assert!(budget.fixed_size <= w);
+            }
+        }
+
+        let wind = definitions.estimate_budget("wind", engine).unwrap();
+        assert_eq!(wind.fixed_size, wind.typical_size);
+        assert_eq!(wind.worst_case_size, Some(wind.fixed_size));
+
+        // Model vertices only use tag space without external models.
+        assert!(engine.external_models);
+        let mut internal = engine.clone();
+        internal.external_models = false;
+        let external_model = definitions.estimate_budget("gbxmodel", engine).unwrap();
+        let internal_model = definitions.estimate_budget("gbxmodel", &internal).unwrap();
+        assert!(internal_model.typical_size > external_model.typical_size);
+    }
+
+    #[test]
+    fn reflexive_byte_ceilings_fit_in_tag_space() {
+        let definitions = load_all_definitions();
+        let engine = &definitions.engines["pc-retail"];
+        let ceilings = definitions.reflexive_byte_ceilings(engine);
+        assert!(!ceilings.is_empty());
+        for c in ceilings {
+            assert_eq!(c.bytes, (c.limit * c.element_size) as u64);
+            assert!(c.memory_pool != MemoryPool::TagData || c.bytes <= engine.max_tag_space);
+        }
+    }
+
+    #[test]
+    fn byte_budgets_are_checked() {
+        let load = |budget: u64| {
+            let mut definitions = ParsedDefinitions::default();
+            definitions.set_unknown_engine_policy(UnknownEnginePolicy::TreatAsUnsupported);
+            definitions.load_from_json(&get_all_definitions());
+            definitions.load_from_str("test.json", &alloc::format!(r#"[
+                {{ "name": "TestBudgetItem", "type": "struct", "size": 8, "fields": [{{ "name": "a", "type": "uint32" }}, {{ "name": "b", "type": "uint32" }}] }},
+                {{ "name": "TestBudget", "type": "struct", "size": 12, "fields": [
+                    {{ "name": "items", "type": "Reflexive", "struct": "TestBudgetItem", "limit": 16, "byte_budget": {budget} }}
+                ] }}
+            ]"#)).unwrap();
+            definitions
+        };
+
+        let mut definitions = load(128);
+        definitions.try_finalize().unwrap();
+        let engine = &definitions.engines["pc-retail"];
+        let ceiling = definitions.reflexive_byte_ceilings(engine).into_iter().find(|c| c.struct_name == "TestBudget").unwrap();
+        assert_eq!((ceiling.bytes, ceiling.budget), (128, Some(128)));
+
+        let error = load(127).try_finalize().unwrap_err();
+        assert!(matches!(&error, DefinitionError::Invalid { object, message } if object == "TestBudget" && message.contains("byte budget")), "{error}");
+    }
+}
@@ -0,0 +1,214 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use super::*;
+
+/// Options for [`generate_c_headers`].
+#[derive(Clone, Debug)]
+pub struct COptions {
+    /// Prefix for every generated type and constant name (e.g. `halo_`).
+    pub prefix: String,
+
+    /// Emit a static assertion for the size of every type.
+    pub static_asserts: bool,
+
+    /// Wrap the header in an include guard with this name, if any.
+    pub include_guard: Option<String>
+}
+
+impl Default for COptions {
+    fn default() -> Self {
+        Self {
+            prefix: String::new(),
+            static_asserts: true,
+            include_guard: Some("RINGHOPPER_DEFINITIONS_H".to_owned())
+        }
+    }
+}
+
+/// Generate a C header with a packed struct for every struct, and typedefs with constants for
+/// every enum and bitfield.
+///
+/// C has no zero-sized types, so empty structs and zero-width bitfields are not declared, and
+/// fields that take no space are written as comments.
+///
+/// Sizes match the definitions exactly. Pointers are 32-bit values (as they are in tag and cache
+/// files), so the header can be used on any platform. Enum options and bitfield flags are
+/// `#define`d as `<ENUM>_<OPTION>` and `<BITFIELD>_<FLAG>` masks, respectively.
+///
+/// The header requires C11 or C++11, and support for `#pragma pack`.
+pub fn generate_c_headers(definitions: &ParsedDefinitions, options: COptions) -> String {
+    let mut output = String::new();
+    let prefix = options.prefix.as_str();
+
+    if let Some(guard) = &options.include_guard {
+        let _ = writeln!(output, "#ifndef {guard}\n#define {guard}\n");
+    }
+    output.push_str("#include <stdint.h>\n\n");
+    if options.static_asserts {
+        output.push_str("#ifdef __cplusplus\n#define RINGHOPPER_STATIC_ASSERT static_assert\n#else\n#define RINGHOPPER_STATIC_ASSERT _Static_assert\n#endif\n\n");
+    }
+    output.push_str("#pragma pack(push, 1)\n\n");
+
+    let _ = writeln!(output, "typedef struct {prefix}Reflexive {{\n    uint32_t count;\n    uint32_t address;\n    uint32_t definitions;\n}} {prefix}Reflexive;\n");
+    let _ = writeln!(output, "typedef struct {prefix}TagReference {{\n    uint32_t group;\n    uint32_t path;\n    uint32_t path_length;\n    uint32_t tag_id;\n}} {prefix}TagReference;\n");
+    let _ = writeln!(output, "typedef struct {prefix}Data {{\n    uint32_t size;\n    uint32_t flags;\n    uint32_t file_offset;\n    uint32_t address;\n    uint32_t definitions;\n}} {prefix}Data;\n");
+    if options.static_asserts {
+        for (name, size) in [("Reflexive", 0xC), ("TagReference", 0x10), ("Data", 0x14)] {
+            write_static_assert(&mut output, prefix, name, size);
+        }
+        output.push('\n');
+    }
+
+    for object in definitions.objects.values() {
+        match object {
+            NamedObject::Enum(e) => {
+                let _ = writeln!(output, "typedef uint16_t {prefix}{};", e.name);
+                let constant_prefix = constant_name(prefix, &e.name);
+                for o in &e.options {
+                    let _ = writeln!(output, "#define {constant_prefix}_{} {}", format_as_screaming_snake_case(&o.name), o.value);
+                }
+                output.push('\n');
+            },
+            // Placeholder bitfields take no space, and C has no zero-sized types
+            NamedObject::Bitfield(b) if b.width == 0 => (),
+            NamedObject::Bitfield(b) => {
+                let _ = writeln!(output, "typedef uint{}_t {prefix}{};", b.width, b.name);
+                let constant_prefix = constant_name(prefix, &b.name);
+                for f in &b.fields {
+                    let _ = writeln!(output, "#define {constant_prefix}_{} 0x{:X}u", format_as_screaming_snake_case(&f.name), f.value);
+                }
+                output.push('\n');
+            },
+            NamedObject::Struct(_) => ()
+        }
+    }
+
+    // C needs complete types for members, so structs are written after every struct they contain.
+    for object in definitions.objects_topologically_sorted() {
+        if let NamedObject::Struct(s) = object {
+            write_struct(definitions, &options, s, &mut output);
+        }
+    }
+
+    output.push_str("#pragma pack(pop)\n");
+    if let Some(guard) = &options.include_guard {
+        let _ = writeln!(output, "\n#endif /* {guard} */");
+    }
+    output
+}
+
+fn constant_name(prefix: &str, object_name: &str) -> String {
+    let mut name = prefix.to_ascii_uppercase();
+    name.push_str(&format_pascal_case_for_rust_fields(object_name).to_ascii_uppercase());
+    name
+}
+
+fn write_static_assert(output: &mut String, prefix: &str, name: &str, size: usize) {
+    let _ = writeln!(output, "RINGHOPPER_STATIC_ASSERT(sizeof({prefix}{name}) == 0x{size:X}, \"{prefix}{name} must be 0x{size:X} bytes\");");
+}
+
+fn write_struct(definitions: &ParsedDefinitions, options: &COptions, s: &Struct, output: &mut String) {
+    let name = s.name.as_str();
+
+    // C has no zero-sized types, so empty structs (and fields of them) are left out
+    let prefix = options.prefix.as_str();
+    if s.size == 0 {
+        return
+    }
+    let _ = writeln!(output, "typedef struct {prefix}{name} {{");
+    let mut padding = 0;
+    for f in &s.fields {
+        if f.size(definitions) == 0 {
+            if let StructFieldType::Object(o) = &f.field_type {
+                let _ = writeln!(output, "    /* {} {} (zero-sized) */", c_type(prefix, o).0, Language::C.safe_identifier(&f.name_rust_field));
+            }
+            continue
+        }
+        let (c_type, member, mut dimensions) = match &f.field_type {
+            StructFieldType::Object(FieldObject::Custom(_)) => {
+                (String::from("uint8_t"), Language::C.safe_identifier(&f.name_rust_field), alloc::vec![f.field_type.size(definitions)])
+            },
+            StructFieldType::Object(o) => {
+                let (c_type, composite) = c_type(prefix, o);
+                let member = Language::C.safe_identifier(&f.name_rust_field);
+                (c_type, member, if composite > 1 { alloc::vec![composite] } else { Vec::new() })
+            },
+            StructFieldType::Padding(size) => {
+                padding += 1;
+                (String::from("uint8_t"), alloc::format!("_padding_{padding}"), alloc::vec![*size])
+            },
+            StructFieldType::EditorSection { .. } => continue
+        };
+        match f.count {
+            FieldCount::One => (),
+            FieldCount::Bounds => dimensions.insert(0, 2),
+            FieldCount::Array(n) => dimensions.insert(0, n)
+        }
+
+        let _ = write!(output, "    {c_type} {member}");
+        for d in dimensions {
+            let _ = write!(output, "[{d}]");
+        }
+        output.push_str(";\n");
+    }
+    let _ = writeln!(output, "}} {prefix}{name};");
+    if options.static_asserts {
+        write_static_assert(output, prefix, name, s.size);
+    }
+    output.push('\n');
+}
+
+/// Get the C type of a field object, along with how many of that type make up the object.
+fn c_type(prefix: &str, object: &FieldObject) -> (String, usize) {
+    let (c_type, count) = match object {
+        FieldObject::NamedObject(n) => return (alloc::format!("{prefix}{n}"), 1),
+        FieldObject::Reflexive(_) => return (alloc::format!("{prefix}Reflexive"), 1),
+        FieldObject::TagReference { .. } => return (alloc::format!("{prefix}TagReference"), 1),
+        FieldObject::Data | FieldObject::FileData | FieldObject::BSPVertexData | FieldObject::UTF16String => return (alloc::format!("{prefix}Data"), 1),
+        FieldObject::String32 => ("char", 32),
+        FieldObject::U8 => ("uint8_t", 1),
+        FieldObject::I8 => ("int8_t", 1),
+        FieldObject::U16 | FieldObject::Index | FieldObject::ReflexiveIndex { .. } | FieldObject::CompressedFloat => ("uint16_t", 1),
+        FieldObject::I16 => ("int16_t", 1),
+        FieldObject::Rectangle | FieldObject::Vector2DInt => ("int16_t", object.composite_count()),
+        FieldObject::I32 => ("int32_t", 1),
+        FieldObject::U32
+        | FieldObject::Pixel32
+        | FieldObject::Address
+        | FieldObject::ID
+        | FieldObject::TagID
+        | FieldObject::TagGroup
+        | FieldObject::CompressedVector2D
+        | FieldObject::CompressedVector3D
+        | FieldObject::ScenarioScriptNodeValue => ("uint32_t", 1),
+        o => ("float", o.primitive_size() / FieldObject::F32.primitive_size())
+    };
+    (String::from(c_type), count)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn c_headers_declare_every_struct() {
+        let definitions = load_all_definitions();
+        let header = generate_c_headers(definitions, COptions { prefix: "halo_".into(), ..Default::default() });
+        assert!(header.starts_with("#ifndef RINGHOPPER_DEFINITIONS_H\n"));
+        assert!(header.contains("typedef struct halo_StringList {\n    halo_Reflexive strings;\n} halo_StringList;\n"));
+        assert!(header.contains("RINGHOPPER_STATIC_ASSERT(sizeof(halo_Scenario) == 0x"));
+        assert!(header.contains("#define HALO_ACTOR_TYPE_ELITE 0\n"));
+
+        // Structs must be declared before they are used inline.
+        let inline_user = header.find("typedef struct halo_ActorVariant ").unwrap();
+        assert!(definitions.get_struct("ActorVariant").unwrap().fields.iter().all(|f| match &f.field_type {
+            StructFieldType::Object(FieldObject::NamedObject(n)) if definitions.get_struct(n).is_some() => {
+                header.find(&alloc::format!("typedef struct halo_{n} ")).unwrap() < inline_user
+            },
+            _ => true
+        }));
+    }
+}
@@ -0,0 +1,269 @@
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+
+/// Format a definition name as a Rust enum variant or type name (PascalCase).
+///
+/// This is the algorithm used for [`Field::name_rust_enum`](crate::Field::name_rust_enum),
+/// [`StructField::name_rust_enum`](crate::StructField::name_rust_enum), and
+/// [`TagGroup::name_rust_enum`](crate::TagGroup::name_rust_enum). Known acronyms such as `HUD` and
+/// `BSP` are fully capitalized, and names that collide with Rust keywords (i.e. `Self`) are
+/// prefixed with an underscore.
+///
+/// # Panics
+///
+/// Panics if `what` is empty or non-ASCII.
+pub fn format_for_rust_enums(what: &str) -> String {
+    // could change this to work in the future, but it'd make the code a little more complex
+    assert!(what.is_ascii(), "{what} is non-ascii; can't format rust enums");
+
+    let mut n = String::with_capacity(what.len() + 1);
+    let mut c = what.chars().peekable();
+
+    let first = c.peek().expect("enum field with empty name");
+    if !first.is_ascii_alphabetic() {
+        n += "_";
+    }
+
+    let mut needs_caps = true;
+    for c in c {
+        if c == '\'' {
+            continue
+        }
+        else if !c.is_alphanumeric() {
+            needs_caps = true;
+            continue;
+        }
+        else if needs_caps {
+            needs_caps = false;
+            n.extend(c.to_uppercase());
+        }
+        else {
+            n.push(c);
+        }
+    }
+
+    fn fixup_chars(find: &[&str], mut for_string: &mut str) {
+        loop {
+            let mut found: Option<(usize, &str)> = None;
+            for i in find {
+                found = for_string.find(i).map(|f| (f, *i));
+                if found.is_some() {
+                    break;
+                }
+            }
+
+            let Some((s, find)) = found else {
+                break
+            };
+
+            let start = s;
+            let end = s + find.len();
+            let (before, after) = for_string.split_at_mut(end);
+
+            before[start..end].make_ascii_uppercase();
+
+            if after.is_empty() {
+                break
+            }
+
+            // this is ASCII so this should be fine
+            after[0..1].make_ascii_uppercase();
+            for_string = after;
+        }
+    }
+
+    fixup_chars(&["Hud", "Gbx", "Ui", "Bsp", "Dxt", "Pcm", "Bc7", "Adpcm", "A1r5g5b5", "R5g6b5", "A4r4g4b4", "A8y8", "Ay8", "A8r8g8b8", "X8r8g8b8", "Ucs"], &mut n);
+
+    Language::Rust.safe_identifier(&n)
+}
+
+/// Format a definition name as a Rust field or variable name (snake_case).
+///
+/// This is the algorithm used for [`Field::name_rust_field`](crate::Field::name_rust_field) and
+/// [`StructField::name_rust_field`](crate::StructField::name_rust_field).
+/// Names that collide with Rust keywords are prefixed with an underscore.
+///
+/// # Panics
+///
+/// Panics if `what` is empty.
+pub fn format_for_rust_fields(what: &str) -> String {
+    let n = format_as_snake_case(&what.to_ascii_lowercase());
+
+    // we could use r# here, but that is not great to type...
+    if n == "begin" {
+        return format!("_{n}")
+    }
+    Language::Rust.safe_identifier(&n)
+}
+
+/// Format a PascalCase object name as a Rust field name (snake_case).
+///
+/// This is the algorithm used for the
+/// [`StructField::name_rust_field`](crate::StructField::name_rust_field) of a field holding an
+/// inherited struct, which is named after the struct itself.
+pub fn format_pascal_case_for_rust_fields(what: &str) -> String {
+    let mut n = String::with_capacity(what.len() * 2);
+    let mut last_char = 'A';
+    for c in what.chars() {
+        if c.is_ascii_uppercase() && !last_char.is_ascii_uppercase() {
+            n.push('_');
+        }
+        last_char = c;
+        n.push(c.to_ascii_lowercase());
+    }
+    n
+}
+
+/// Format a definition name as camelCase.
+///
+/// This is [`format_for_rust_enums`] with its leading word lowercased, so acronyms are handled the
+/// same way (e.g. `hud globals` becomes `hudGlobals`).
+///
+/// # Panics
+///
+/// Panics if `what` is empty or non-ASCII.
+pub fn format_as_camel_case(what: &str) -> String {
+    let mut n = format_for_rust_enums(what);
+    let bytes = n.as_bytes();
+    let leading_uppercase = bytes.iter().take_while(|b| b.is_ascii_uppercase()).count();
+
+    // Keep the last capital of an acronym if it starts the next word (e.g. "HUDGlobals").
+    let end = if leading_uppercase > 1 && bytes.get(leading_uppercase).is_some_and(|b| b.is_ascii_lowercase()) {
+        leading_uppercase - 1
+    }
+    else {
+        leading_uppercase.max(1).min(bytes.len())
+    };
+    n[..end].make_ascii_lowercase();
+    n
+}
+
+/// Format a definition name as SCREAMING_SNAKE_CASE (e.g. for constants).
+///
+/// This is [`format_for_rust_fields`] uppercased, without the keyword escaping.
+///
+/// # Panics
+///
+/// Panics if `what` is empty.
+pub fn format_as_screaming_snake_case(what: &str) -> String {
+    let mut n = format_as_snake_case(&what.to_ascii_lowercase());
+    n.make_ascii_uppercase();
+    n
+}
+
+/// A language that code can be generated for.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Language {
+    /// Rust (2021 edition, including reserved keywords).
+    Rust,
+
+    /// C (C23, including keywords from earlier standards).
+    C,
+
+    /// C#.
+    CSharp
+}
+
+impl Language {
+    /// Get every reserved keyword of the language, which cannot be used as identifiers.
+    ///
+    /// This only includes keywords that are always reserved (e.g. not C#'s contextual keywords).
+    pub const fn reserved_keywords(self) -> &'static [&'static str] {
+        match self {
+            Self::Rust => &[
+                "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn", "for",
+                "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static",
+                "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while", "abstract", "become", "box", "do",
+                "final", "gen", "macro", "override", "priv", "try", "typeof", "unsized", "virtual", "yield"
+            ],
+            Self::C => &[
+                "alignas", "alignof", "auto", "bool", "break", "case", "char", "const", "constexpr", "continue", "default", "do",
+                "double", "else", "enum", "extern", "false", "float", "for", "goto", "if", "inline", "int", "long", "nullptr",
+                "register", "restrict", "return", "short", "signed", "sizeof", "static", "static_assert", "struct", "switch",
+                "thread_local", "true", "typedef", "typeof", "typeof_unqual", "union", "unsigned", "void", "volatile", "while",
+                "_Alignas", "_Alignof", "_Atomic", "_BitInt", "_Bool", "_Complex", "_Decimal128", "_Decimal32", "_Decimal64",
+                "_Generic", "_Imaginary", "_Noreturn", "_Static_assert", "_Thread_local"
+            ],
+            Self::CSharp => &[
+                "abstract", "as", "base", "bool", "break", "byte", "case", "catch", "char", "checked", "class", "const", "continue",
+                "decimal", "default", "delegate", "do", "double", "else", "enum", "event", "explicit", "extern", "false", "finally",
+                "fixed", "float", "for", "foreach", "goto", "if", "implicit", "in", "int", "interface", "internal", "is", "lock",
+                "long", "namespace", "new", "null", "object", "operator", "out", "override", "params", "private", "protected",
+                "public", "readonly", "ref", "return", "sbyte", "sealed", "short", "sizeof", "stackalloc", "static", "string",
+                "struct", "switch", "this", "throw", "true", "try", "typeof", "uint", "ulong", "unchecked", "unsafe", "ushort",
+                "using", "virtual", "void", "volatile", "while"
+            ]
+        }
+    }
+
+    /// Returns true if the identifier is a reserved keyword of the language.
+    pub fn is_reserved(self, identifier: &str) -> bool {
+        self.reserved_keywords().contains(&identifier)
+    }
+
+    /// Escape the identifier if it is a reserved keyword of the language.
+    ///
+    /// Rust and C identifiers are prefixed with an underscore, and C# identifiers are prefixed with
+    /// `@`. Other identifiers are returned unchanged.
+    pub fn safe_identifier(self, identifier: &str) -> String {
+        if !self.is_reserved(identifier) {
+            return identifier.to_owned()
+        }
+        match self {
+            Self::Rust | Self::C => format!("_{identifier}"),
+            Self::CSharp => format!("@{identifier}")
+        }
+    }
+}
+
+fn format_as_snake_case(what_lowercase: &str) -> String {
+    let mut n = String::with_capacity(what_lowercase.len() + 1);
+    let mut c = what_lowercase.chars().peekable();
+
+    let first = c.peek().expect("struct field with empty name");
+    if !first.is_ascii_alphabetic() {
+        n += "_";
+    }
+
+    for c in c {
+        if c == '\'' {
+            continue
+        }
+        else if !c.is_alphanumeric() {
+            n.push('_');
+        }
+        else {
+            n.push(c);
+        }
+    }
+
+    n
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn casing_matches_builtin_names() {
+        let definitions = load_all_definitions();
+        for g in definitions.groups.values() {
+            assert_eq!(format_for_rust_enums(&g.name), g.name_rust_enum);
+        }
+
+        assert_eq!(format_for_rust_enums("hud globals"), "HUDGlobals");
+        assert_eq!(format_for_rust_fields("type"), "_type");
+        assert_eq!(format_pascal_case_for_rust_fields("ObjectFlags"), "object_flags");
+        assert_eq!(format_as_camel_case("hud globals"), "hudGlobals");
+        assert_eq!(format_as_camel_case("sound looping"), "soundLooping");
+        assert_eq!(format_as_screaming_snake_case("max tag space"), "MAX_TAG_SPACE");
+        assert_eq!(format_as_screaming_snake_case("type"), "TYPE");
+        assert_eq!(format_for_rust_fields("match"), "_match");
+        assert_eq!(format_for_rust_enums("self"), "_Self");
+        assert_eq!(Language::C.safe_identifier("register"), "_register");
+        assert_eq!(Language::CSharp.safe_identifier("object"), "@object");
+        assert_eq!(Language::CSharp.safe_identifier("scenery"), "scenery");
+    }
+}
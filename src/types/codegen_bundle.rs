@@ -0,0 +1,161 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use super::*;
+
+/// Everything needed to generate code for a set of groups, created by
+/// [`ParsedDefinitions::codegen_bundle`].
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct CodegenBundle<'a> {
+    /// Engine the bundle was filtered for, if any.
+    pub engine: Option<&'a Engine>,
+
+    /// Groups in the bundle, with supergroups before the groups that inherit them.
+    pub groups: Vec<&'a TagGroup>,
+
+    /// Structs used by the groups, with every struct before the structs that use it.
+    ///
+    /// Structs that reference each other through reflexives are ordered arbitrarily among
+    /// themselves.
+    pub structs: Vec<BundledStruct<'a>>,
+
+    /// Enums used by the structs, in alphabetical order.
+    pub enums: Vec<BundledEnum<'a>>,
+
+    /// Bitfields used by the structs, in alphabetical order.
+    pub bitfields: Vec<BundledBitfield<'a>>
+}
+
+/// A struct in a [`CodegenBundle`].
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct BundledStruct<'a> {
+    /// The struct.
+    pub definition: &'a Struct,
+
+    /// Fields of the struct supported by the bundle's engine, in order.
+    ///
+    /// Omitted fields still take up space, so use [`StructField::relative_offset`] for layout.
+    pub fields: Vec<&'a StructField>
+}
+
+/// An enum in a [`CodegenBundle`].
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct BundledEnum<'a> {
+    /// The enum.
+    pub definition: &'a Enum,
+
+    /// Options of the enum supported by the bundle's engine, in order.
+    pub options: Vec<&'a Field>
+}
+
+/// A bitfield in a [`CodegenBundle`].
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct BundledBitfield<'a> {
+    /// The bitfield.
+    pub definition: &'a Bitfield,
+
+    /// Fields of the bitfield supported by the bundle's engine, in order.
+    pub fields: Vec<&'a Field>
+}
+
+impl ParsedDefinitions {
+    /// Resolve everything needed to generate code for the given groups in one call.
+    ///
+    /// Supergroups of the groups are included. If `engine` is set, groups, fields, and options not
+    /// supported by it (or any engine it inherits) are left out, as is anything only they use.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a group or the engine does not exist.
+    pub fn codegen_bundle(&self, groups: &[&str], engine: Option<&str>) -> CodegenBundle<'_> {
+        let engine = engine.map(|e| self.engines.get(e).unwrap_or_else(|| panic!("no such engine {e}")));
+        let supported = |engines: &SupportedEngines| engine.is_none_or(|e| engines.supports_engine_or_ancestor(e, self));
+
+        let mut bundle = CodegenBundle { engine, groups: Vec::new(), structs: Vec::new(), enums: Vec::new(), bitfields: Vec::new() };
+        let mut seen_groups = BTreeSet::new();
+        for group in groups {
+            let mut chain = Vec::new();
+            let mut next = Some(*group);
+            while let Some(g) = next.filter(|g| seen_groups.insert(*g)) {
+                let group = self.groups.get(g).unwrap_or_else(|| panic!("no such group {g}"));
+                chain.push(group);
+                next = group.supergroup.as_deref();
+            }
+            bundle.groups.extend(chain.into_iter().rev().filter(|g| supported(&g.supported_engines)));
+        }
+
+        let mut visited = BTreeSet::new();
+        let mut used = BTreeSet::new();
+        for group in &bundle.groups {
+            self.bundle_struct(&group.struct_name, &supported, &mut visited, &mut used, &mut bundle.structs);
+        }
+
+        for name in used {
+            match self.objects.get(name) {
+                Some(NamedObject::Enum(e)) => bundle.enums.push(BundledEnum {
+                    definition: e,
+                    options: e.options.iter().filter(|o| supported(&o.flags.supported_engines)).collect()
+                }),
+                Some(NamedObject::Bitfield(b)) => bundle.bitfields.push(BundledBitfield {
+                    definition: b,
+                    fields: b.fields.iter().filter(|f| supported(&f.flags.supported_engines)).collect()
+                }),
+                _ => ()
+            }
+        }
+
+        bundle
+    }
+
+    /// Add a struct after every struct it uses, collecting the enums and bitfields it uses into `used`.
+    fn bundle_struct<'a>(&'a self, name: &'a str, supported: &dyn Fn(&SupportedEngines) -> bool, visited: &mut BTreeSet<&'a str>, used: &mut BTreeSet<&'a str>, structs: &mut Vec<BundledStruct<'a>>) {
+        let Some(NamedObject::Struct(s)) = self.objects.get(name) else {
+            return
+        };
+        if !visited.insert(name) {
+            return
+        }
+
+        let fields: Vec<&StructField> = s.fields.iter().filter(|f| supported(&f.flags.supported_engines)).collect();
+        for f in &fields {
+            if let StructFieldType::Object(FieldObject::NamedObject(n) | FieldObject::Reflexive(n)) = &f.field_type {
+                used.insert(n);
+                self.bundle_struct(n, supported, visited, used, structs);
+            }
+        }
+        structs.push(BundledStruct { definition: s, fields });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn bundles_are_in_dependency_order() {
+        let definitions = load_all_definitions();
+        let bundle = definitions.codegen_bundle(&["weapon", "bitmap"], None);
+        let groups: alloc::vec::Vec<&str> = bundle.groups.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(groups, ["object", "item", "weapon", "bitmap"]);
+
+        let position = |name: &str| bundle.structs.iter().position(|s| s.definition.name == name).unwrap_or_else(|| panic!("{name} is missing"));
+        for (index, s) in bundle.structs.iter().enumerate() {
+            for f in &s.fields {
+                if let StructFieldType::Object(FieldObject::NamedObject(n)) = &f.field_type {
+                    if definitions.get_struct(n).is_some() {
+                        assert!(position(n) < index, "{n} is after {}", s.definition.name);
+                    }
+                    else {
+                        assert!(bundle.enums.iter().any(|e| e.definition.name == *n) || bundle.bitfields.iter().any(|b| b.definition.name == *n), "{n} is missing");
+                    }
+                }
+            }
+        }
+        assert!(position("Object") < position("Weapon"));
+        assert!(bundle.structs.iter().all(|s| s.definition.name != "Scenario"));
+    }
+}
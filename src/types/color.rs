@@ -0,0 +1,94 @@
+use alloc::borrow::ToOwned;
+use serde_json::Value;
+
+use super::*;
+
+/// Describes how the values of a color field are encoded.
+///
+/// Color pickers and bitmap pipelines can use this to convert values consistently.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ColorSpace {
+    /// Values are proportional to light intensity.
+    Linear,
+
+    /// Values are gamma-encoded with the sRGB transfer function.
+    SRGB
+}
+
+impl ColorSpace {
+    /// Get the color space from its name in the JSON definitions (e.g. `srgb`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "linear" => Some(Self::Linear),
+            "srgb" => Some(Self::SRGB),
+            _ => None
+        }
+    }
+
+    /// Get the name of the color space as used in the JSON definitions.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Linear => "linear",
+            Self::SRGB => "srgb"
+        }
+    }
+
+    pub(crate) fn load_from_json(field_name: &str, value: &Value, object: &FieldObject) -> Result<Self, DefinitionError> {
+        let invalid = |expected| DefinitionError::InvalidValue { object: field_name.to_owned(), key: "color_space".to_owned(), expected };
+        if !matches!(object, FieldObject::ColorRGB | FieldObject::ColorARGB | FieldObject::Pixel32) {
+            return Err(invalid("only set on color fields"))
+        }
+        value.as_str().and_then(Self::from_name).ok_or_else(|| invalid("a known color space"))
+    }
+}
+
+impl StructField {
+    /// Get the expected range of each channel of a color field, or `None` if it is not a color.
+    ///
+    /// This is the field's minimum and maximum if set. Otherwise, it is 0 to 1 for float colors
+    /// and 0 to 255 for [`FieldObject::Pixel32`].
+    pub fn color_range(&self) -> Option<(f64, f64)> {
+        let full_range = match &self.field_type {
+            StructFieldType::Object(FieldObject::ColorRGB | FieldObject::ColorARGB) => (0.0, 1.0),
+            StructFieldType::Object(FieldObject::Pixel32) => (0.0, 255.0),
+            _ => return None
+        };
+        let component = |value: &Option<StaticValue>| match value {
+            Some(StaticValue::Float(f)) => Some(*f as f64),
+            Some(StaticValue::Uint(u)) => Some(*u as f64),
+            Some(StaticValue::Int(i)) => Some(*i as f64),
+            _ => None
+        };
+        Some((component(&self.minimum).unwrap_or(full_range.0), component(&self.maximum).unwrap_or(full_range.1)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn color_spaces_are_loaded() {
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_str("test.json", r#"[{
+            "name": "TestColors",
+            "type": "struct",
+            "fields": [
+                { "name": "tint", "type": "ColorRGB", "color_space": "srgb" },
+                { "name": "intensity", "type": "ColorARGB", "color_space": "linear", "maximum": 4.0 },
+                { "name": "packed", "type": "Pixel32" }
+            ],
+            "size": 32
+        }]"#).unwrap();
+        definitions.finalize_without_engines();
+
+        let colors = definitions.get_struct("TestColors").unwrap();
+        assert_eq!(colors.field("tint").unwrap().color_space, Some(ColorSpace::SRGB));
+        assert_eq!(colors.field("intensity").unwrap().color_range(), Some((0.0, 4.0)));
+        assert_eq!(colors.field("packed").unwrap().color_space, None);
+        assert_eq!(colors.field("packed").unwrap().color_range(), Some((0.0, 255.0)));
+        assert_eq!(ColorSpace::from_name(ColorSpace::Linear.name()), Some(ColorSpace::Linear));
+    }
+}
@@ -0,0 +1,201 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::borrow::ToOwned;
+
+use super::*;
+
+/// A property that differs between two engines.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct EnginePropertyDifference {
+    /// Name of the property, matching the name of the field in [`Engine`] (e.g. `max_tag_space` or
+    /// `max_cache_file_size.multiplayer`).
+    pub property: &'static str,
+
+    /// Value for the first engine.
+    pub a: String,
+
+    /// Value for the second engine.
+    pub b: String
+}
+
+/// A field, enum option, or bitfield flag that is only supported by one of two engines.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FieldSupportDifference {
+    /// Name of the struct, enum, or bitfield.
+    pub object: String,
+
+    /// Name of the field, option, or flag.
+    pub field: String,
+
+    /// True if only the first engine supports it; false if only the second engine does.
+    pub supported_by_a: bool
+}
+
+/// A struct field whose limit differs between two engines.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FieldLimitDifference {
+    /// Name of the struct.
+    pub object: String,
+
+    /// Name of the field.
+    pub field: String,
+
+    /// Limit for the first engine, if any.
+    pub a: Option<usize>,
+
+    /// Limit for the second engine, if any.
+    pub b: Option<usize>
+}
+
+/// Summary of the differences between two engines.
+///
+/// Created by [`ParsedDefinitions::compare_engines`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct EngineComparison {
+    /// Numeric limits of the engines that differ (e.g. tag space and cache file size).
+    pub limits: Vec<EnginePropertyDifference>,
+
+    /// Capabilities and formats of the engines that differ (e.g. compression and external data).
+    pub capabilities: Vec<EnginePropertyDifference>,
+
+    /// Tag groups only supported by the first engine.
+    pub groups_only_in_a: Vec<String>,
+
+    /// Tag groups only supported by the second engine.
+    pub groups_only_in_b: Vec<String>,
+
+    /// Fields, options, and flags only supported by one engine.
+    pub fields: Vec<FieldSupportDifference>,
+
+    /// Struct fields with different limits.
+    pub field_limits: Vec<FieldLimitDifference>
+}
+
+impl EngineComparison {
+    /// Returns true if no differences were found.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl ParsedDefinitions {
+    /// Compare two engines, listing everything that must be considered when retargeting a map from
+    /// `a` to `b`.
+    ///
+    /// Returns `None` if either engine does not exist.
+    pub fn compare_engines(&self, a: &str, b: &str) -> Option<EngineComparison> {
+        let a = self.get_engine(a)?;
+        let b = self.get_engine(b)?;
+        let mut comparison = EngineComparison::default();
+
+        let mut limit = |property: &'static str, get: &dyn Fn(&Engine) -> String| {
+            let (va, vb) = (get(a), get(b));
+            if va != vb {
+                comparison.limits.push(EnginePropertyDifference { property, a: va, b: vb });
+            }
+        };
+        limit("max_script_nodes", &|e| e.max_script_nodes.to_string());
+        limit("max_tag_space", &|e| e.max_tag_space.to_string());
+        limit("max_cache_file_size.user_interface", &|e| e.max_cache_file_size.user_interface.to_string());
+        limit("max_cache_file_size.singleplayer", &|e| e.max_cache_file_size.singleplayer.to_string());
+        limit("max_cache_file_size.multiplayer", &|e| e.max_cache_file_size.multiplayer.to_string());
+        limit("minimum_weapons", &|e| e.minimum_weapons.to_string());
+        limit("tag_paths.max_length", &|e| e.tag_paths.max_length.to_string());
+        limit("tag_paths.character_set", &|e| e.tag_paths.character_set.name().to_owned());
+        limit("grenades.user_interface", &|e| format!("{:?}", e.grenades.user_interface));
+        limit("grenades.singleplayer", &|e| format!("{:?}", e.grenades.singleplayer));
+        limit("grenades.multiplayer", &|e| format!("{:?}", e.grenades.multiplayer));
+
+        let mut capability = |property: &'static str, get: &dyn Fn(&Engine) -> String| {
+            let (va, vb) = (get(a), get(b));
+            if va != vb {
+                comparison.capabilities.push(EnginePropertyDifference { property, a: va, b: vb });
+            }
+        };
+        capability("cache_file_version", &|e| e.cache_file_version.to_string());
+        capability("hud_scaling", &|e| e.hud_scaling.name().to_owned());
+        capability("cache_parser", &|e| match e.cache_parser {
+            EngineCacheParser::Xbox => "xbox".to_owned(),
+            EngineCacheParser::PC => "pc".to_owned()
+        });
+        capability("compression_type", &|e| match e.compression_type {
+            EngineCompressionType::Uncompressed => "uncompressed".to_owned(),
+            EngineCompressionType::Deflate => "deflate".to_owned()
+        });
+        capability("resource_maps", &|e| match &e.resource_maps {
+            None => "none".to_owned(),
+            Some(r) if r.externally_indexed_tags => "externally indexed tags".to_owned(),
+            Some(_) => "supported".to_owned()
+        });
+        capability("external_bsps", &|e| e.external_bsps.to_string());
+        capability("external_models", &|e| e.external_models.to_string());
+        capability("compressed_models", &|e| e.compressed_models.to_string());
+        capability("obfuscated_header_layout", &|e| e.obfuscated_header_layout.to_string());
+        capability("data_alignment", &|e| e.data_alignment.to_string());
+        capability("compressed_data_alignment", &|e| e.compressed_data_alignment.to_string());
+        capability("base_memory_address", &|e| format!("0x{:08X}", e.base_memory_address.address));
+        capability("bitmap_options.swizzled", &|e| e.bitmap_options.swizzled.to_string());
+        capability("bitmap_options.texture_dimension_must_modulo_block_size", &|e| e.bitmap_options.texture_dimension_must_modulo_block_size.to_string());
+        capability("bitmap_options.cubemap_faces_stored_separately", &|e| e.bitmap_options.cubemap_faces_stored_separately.to_string());
+        capability("bitmap_options.alignment", &|e| e.bitmap_options.alignment.to_string());
+
+        for g in self.groups.values() {
+            match (g.supported_engines.supports_engine(a), g.supported_engines.supports_engine(b)) {
+                (true, false) => comparison.groups_only_in_a.push(g.name.to_owned()),
+                (false, true) => comparison.groups_only_in_b.push(g.name.to_owned()),
+                _ => ()
+            }
+        }
+
+        for object in self.objects.values() {
+            let mut support = |field: &str, flags: &Flags| {
+                let (sa, sb) = (flags.supported_engines.supports_engine(a), flags.supported_engines.supports_engine(b));
+                if sa != sb {
+                    comparison.fields.push(FieldSupportDifference { object: object.name().to_owned(), field: field.to_owned(), supported_by_a: sa });
+                }
+            };
+            match object {
+                NamedObject::Struct(s) => {
+                    for f in s.fields.iter().filter(|f| matches!(f.field_type, StructFieldType::Object(_))) {
+                        support(&f.name, &f.flags);
+                    }
+                    for f in s.view(self).fields() {
+                        let (la, lb) = (f.limit_for_engine(&a.name), f.limit_for_engine(&b.name));
+                        if la != lb {
+                            comparison.field_limits.push(FieldLimitDifference { object: s.name.to_owned(), field: f.name.to_owned(), a: la, b: lb });
+                        }
+                    }
+                },
+                NamedObject::Enum(e) => e.options.iter().for_each(|o| support(&o.name, &o.flags)),
+                NamedObject::Bitfield(b) => b.fields.iter().for_each(|f| support(&f.name, &f.flags))
+            }
+        }
+
+        Some(comparison)
+    }
+}
+
+#[cfg(all(test, feature = "engines-pc", feature = "engines-xbox"))]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn compare_engines_is_symmetric() {
+        let definitions = load_all_definitions();
+        assert!(definitions.compare_engines("pc-retail", "pc-retail").unwrap().is_empty());
+
+        let forward = definitions.compare_engines("pc-retail", "xbox-us").unwrap();
+        let backward = definitions.compare_engines("xbox-us", "pc-retail").unwrap();
+        assert!(!forward.is_empty());
+        assert_eq!(forward.groups_only_in_a, backward.groups_only_in_b);
+        assert_eq!(forward.fields.len(), backward.fields.len());
+        assert!(forward.groups_only_in_a.iter().any(|g| g == "gbxmodel"));
+
+        assert!(definitions.compare_engines("pc-retail", "not an engine").is_none());
+    }
+}
@@ -0,0 +1,86 @@
+use super::*;
+
+/// Describes one value of a composite [`FieldObject`] (e.g. the `x` of a [`FieldObject::Vector3D`]).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Component {
+    /// Name of the component.
+    ///
+    /// Components of nested values are joined with `.` (e.g. `rotation.forward.x`).
+    pub name: &'static str,
+
+    /// Type of the component.
+    pub primitive_type: PrimitiveType
+}
+
+macro_rules! components {
+    ($primitive_type:ident: $($name:literal),+) => {
+        &[$(Component { name: $name, primitive_type: PrimitiveType::$primitive_type }),+]
+    };
+}
+
+impl FieldObject {
+    /// Get the components of a composite object, in the order they are stored.
+    ///
+    /// Returns an empty slice for objects that are a single value, including packed values such as
+    /// [`FieldObject::CompressedVector3D`] and [`FieldObject::Pixel32`], as well as custom objects.
+    ///
+    /// ```
+    /// use ringhopper_definitions::{FieldObject, PrimitiveType};
+    ///
+    /// let components = FieldObject::Rectangle.components();
+    /// assert_eq!(components.iter().map(|c| c.name).collect::<Vec<_>>(), ["top", "left", "bottom", "right"]);
+    /// assert!(components.iter().all(|c| c.primitive_type == PrimitiveType::I16));
+    /// ```
+    pub const fn components(&self) -> &'static [Component] {
+        match self {
+            Self::Vector2D => components!(F32: "x", "y"),
+            Self::Vector3D => components!(F32: "x", "y", "z"),
+            Self::Vector2DInt => components!(I16: "x", "y"),
+            Self::Plane2D => components!(F32: "offset", "vector.x", "vector.y"),
+            Self::Plane3D => components!(F32: "offset", "vector.x", "vector.y", "vector.z"),
+            Self::Rectangle3D => components!(F32: "x.from", "x.to", "y.from", "y.to", "z.from", "z.to"),
+            Self::Euler2D => components!(Angle: "yaw", "pitch"),
+            Self::Euler3D => components!(Angle: "yaw", "pitch", "roll"),
+            Self::Rectangle => components!(I16: "top", "left", "bottom", "right"),
+            Self::Quaternion => components!(F32: "x", "y", "z", "w"),
+            Self::Matrix2x3 => components!(F32: "forward.x", "forward.y", "forward.z", "up.x", "up.y", "up.z"),
+            Self::Matrix3x3 => components!(F32:
+                "forward.x", "forward.y", "forward.z",
+                "left.x", "left.y", "left.z",
+                "up.x", "up.y", "up.z"
+            ),
+            Self::Matrix4x3 => components!(F32:
+                "scale",
+                "rotation.forward.x", "rotation.forward.y", "rotation.forward.z",
+                "rotation.left.x", "rotation.left.y", "rotation.left.z",
+                "rotation.up.x", "rotation.up.y", "rotation.up.z",
+                "translation.x", "translation.y", "translation.z"
+            ),
+            Self::ColorRGB => components!(F32: "r", "g", "b"),
+            Self::ColorARGB => components!(F32: "a", "r", "g", "b"),
+            _ => &[]
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn components_match_composite_count() {
+        let composites = [
+            FieldObject::Vector2D, FieldObject::Vector3D, FieldObject::Vector2DInt, FieldObject::Plane2D, FieldObject::Plane3D,
+            FieldObject::Rectangle3D, FieldObject::Euler2D, FieldObject::Euler3D, FieldObject::Rectangle, FieldObject::Quaternion,
+            FieldObject::Matrix2x3, FieldObject::Matrix3x3, FieldObject::Matrix4x3, FieldObject::ColorRGB, FieldObject::ColorARGB
+        ];
+        for object in composites {
+            let components = object.components();
+            assert_eq!(components.len(), object.composite_count(), "{} has the wrong number of components", object.type_name());
+
+            let size: usize = components.iter().map(|c| if c.primitive_type == PrimitiveType::I16 { 2 } else { 4 }).sum();
+            assert_eq!(size, object.primitive_size(), "{} components do not add up to its size", object.type_name());
+        }
+        assert!(FieldObject::CompressedVector3D.components().is_empty());
+    }
+}
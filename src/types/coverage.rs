@@ -0,0 +1,171 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::*;
+
+/// Describes why a field is not fully verified for an engine.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CoverageGap {
+    /// The field (or padding) has research that is not yet confirmed.
+    Unconfirmed(ResearchConfidence),
+
+    /// The field is a reflexive with no limit for the engine.
+    MissingLimit
+}
+
+/// A field that is supported by an engine but not fully verified.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct UnverifiedField {
+    /// Path of field names from the group's base struct to the field.
+    pub path: Vec<String>,
+
+    /// Name of the struct containing the field.
+    pub struct_name: String,
+
+    /// Name of the field.
+    pub field: String,
+
+    /// Every reason the field is not fully verified.
+    pub gaps: Vec<CoverageGap>
+}
+
+/// Coverage of a single tag group for an engine.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct GroupCoverage {
+    /// Name of the tag group.
+    pub group: String,
+
+    /// Number of fields (including padding) supported by the engine.
+    ///
+    /// Structs used more than once in the group are only counted once.
+    pub total_fields: usize,
+
+    /// Fields that are not fully verified.
+    pub unverified: Vec<UnverifiedField>
+}
+
+impl GroupCoverage {
+    /// Returns true if every field is verified.
+    pub fn is_complete(&self) -> bool {
+        self.unverified.is_empty()
+    }
+}
+
+/// Describes which supported groups and fields of an engine still need research.
+///
+/// Created by [`ParsedDefinitions::coverage_report`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct CoverageReport {
+    /// Name of the engine.
+    pub engine: String,
+
+    /// Coverage of every group supported by the engine.
+    pub groups: Vec<GroupCoverage>
+}
+
+impl CoverageReport {
+    /// Number of fields supported by the engine across every group.
+    pub fn total_fields(&self) -> usize {
+        self.groups.iter().map(|g| g.total_fields).sum()
+    }
+
+    /// Number of fields that are not fully verified across every group.
+    pub fn unverified_fields(&self) -> usize {
+        self.groups.iter().map(|g| g.unverified.len()).sum()
+    }
+
+    /// Fraction of fields that are fully verified, from 0.0 to 1.0.
+    pub fn coverage(&self) -> f64 {
+        let total = self.total_fields();
+        if total == 0 {
+            return 1.0
+        }
+        (total - self.unverified_fields()) as f64 / total as f64
+    }
+}
+
+impl ParsedDefinitions {
+    /// Report which fields of every group supported by `engine` lack confirmed research or limits.
+    ///
+    /// Returns `None` if the engine does not exist.
+    pub fn coverage_report(&self, engine: &str) -> Option<CoverageReport> {
+        let engine = self.engines.get(engine)?;
+        let groups = self.groups
+            .values()
+            .filter(|g| g.supported_engines.supports_engine(engine))
+            .map(|g| {
+                let mut coverage = GroupCoverage { group: g.name.to_owned(), total_fields: 0, unverified: Vec::new() };
+                self.check_struct_coverage(&g.struct_name, engine, &mut Vec::new(), &mut BTreeSet::new(), &mut coverage);
+                coverage
+            })
+            .collect();
+
+        Some(CoverageReport { engine: engine.name.to_owned(), groups })
+    }
+
+    fn check_struct_coverage<'a>(&'a self, struct_name: &'a str, engine: &Engine, path: &mut Vec<&'a str>, visited: &mut BTreeSet<&'a str>, coverage: &mut GroupCoverage) {
+        if !visited.insert(struct_name) {
+            return
+        }
+        let Some(view) = self.struct_view(struct_name) else {
+            return
+        };
+
+        for f in view.fields() {
+            let field = f.get();
+            if !field.flags.supported_engines.supports_engine(engine) {
+                continue
+            }
+
+            coverage.total_fields += 1;
+            path.push(field.name.as_str());
+
+            let mut gaps = Vec::new();
+            if let Some(research) = field.flags.research.as_ref().filter(|r| r.confidence != ResearchConfidence::Confirmed) {
+                gaps.push(CoverageGap::Unconfirmed(research.confidence));
+            }
+            if matches!(field.field_type, StructFieldType::Object(FieldObject::Reflexive(_))) && f.limit_for_engine(&engine.name).is_none() {
+                gaps.push(CoverageGap::MissingLimit);
+            }
+            if !gaps.is_empty() {
+                coverage.unverified.push(UnverifiedField {
+                    path: path.iter().map(|p| (*p).to_owned()).collect(),
+                    struct_name: struct_name.to_owned(),
+                    field: field.name.to_owned(),
+                    gaps
+                });
+            }
+
+            if let StructFieldType::Object(FieldObject::NamedObject(n) | FieldObject::Reflexive(n)) = &field.field_type {
+                self.check_struct_coverage(n, engine, path, visited, coverage);
+            }
+            path.pop();
+        }
+    }
+}
+
+#[cfg(all(test, feature = "engines-pc"))]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn coverage_reports_unconfirmed_research() {
+        let definitions = load_all_definitions();
+        let report = definitions.coverage_report("pc-retail").unwrap();
+        assert!(report.coverage() > 0.0 && report.coverage() <= 1.0);
+
+        let particle = report.groups.iter().find(|g| g.group == "particle").unwrap();
+        assert!(particle
+            .unverified
+            .iter()
+            .any(|f| f.gaps.contains(&CoverageGap::Unconfirmed(ResearchConfidence::Speculative))));
+
+        assert!(definitions.coverage_report("not an engine").is_none());
+    }
+}
@@ -0,0 +1,102 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+
+use super::*;
+
+/// Describes a field object kind registered at runtime, such as a type used by an experimental
+/// format.
+///
+/// Fields of this type are loaded as [`FieldObject::Custom`]. See
+/// [`ParsedDefinitions::register_custom_object`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CustomFieldObject {
+    /// Name of the type as used in the JSON definitions.
+    pub name: String,
+
+    /// Size of the type in bytes.
+    pub size: usize,
+
+    /// Number of values that make up the type (e.g. 3 for a vector of three floats).
+    pub composite_count: usize,
+
+    /// The type contains no pointers, so it is the same in tag and cache files (see
+    /// [`Struct::is_const`]).
+    pub is_const: bool
+}
+
+impl CustomFieldObject {
+    /// Describe a custom field object kind.
+    pub fn new(name: &str, size: usize, composite_count: usize, is_const: bool) -> Self {
+        Self { name: name.to_owned(), size, composite_count, is_const }
+    }
+}
+
+impl ParsedDefinitions {
+    /// Register a custom field object kind, so fields can use it as their type.
+    ///
+    /// This must be done before loading any definitions that use it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name is already used by a built-in type, another custom type, or a loaded
+    /// struct, enum, or bitfield, if the composite count is 0, or if the size is not a multiple of
+    /// the composite count.
+    pub fn register_custom_object(&mut self, object: CustomFieldObject) {
+        let name = &object.name;
+        assert!(!name.is_empty(), "custom object has no name");
+        let builtin = simple_field_object(name).is_some() || matches!(name.as_str(), "Reflexive" | "TagReference" | "ReflexiveIndex" | "pad" | "editor_section");
+        assert!(!builtin, "custom object {name} has the same name as a built-in type");
+        assert!(object.composite_count > 0, "custom object {name} has a composite count of 0");
+        assert!(object.size.is_multiple_of(object.composite_count), "custom object {name}'s size is not a multiple of its composite count");
+        assert!(!self.custom_objects.contains_key(name), "custom object {name} is already registered");
+        assert!(!self.objects.contains_key(name), "custom object {name} has the same name as an object");
+        self.custom_objects.insert(name.to_owned(), object);
+    }
+
+    /// Get a custom field object kind by name, or `None` if it is not registered.
+    pub fn custom_object(&self, name: &str) -> Option<&CustomFieldObject> {
+        self.custom_objects.get(name)
+    }
+
+    /// Turn fields referencing registered custom object kinds into [`FieldObject::Custom`].
+    pub(crate) fn resolve_custom_objects(&self, object: &mut NamedObject) {
+        let NamedObject::Struct(s) = object else {
+            return
+        };
+        for f in &mut s.fields {
+            if let StructFieldType::Object(FieldObject::NamedObject(n)) = &f.field_type {
+                if self.custom_objects.contains_key(n) {
+                    f.field_type = StructFieldType::Object(FieldObject::Custom(n.to_owned()));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn custom_objects_are_loaded() {
+        let mut definitions = ParsedDefinitions::default();
+        definitions.register_custom_object(CustomFieldObject::new("Half3", 6, 3, true));
+        definitions.load_from_str("test.json", r#"[{
+            "name": "TestCustom",
+            "type": "struct",
+            "fields": [{ "name": "normal", "type": "Half3" }, { "name": "value", "type": "uint16" }],
+            "size": 8
+        }]"#).unwrap();
+        definitions.finalize_without_engines();
+
+        let s = definitions.get_struct("TestCustom").unwrap();
+        assert!(matches!(&s.fields[0].field_type, StructFieldType::Object(FieldObject::Custom(n)) if n == "Half3"));
+        assert_eq!(s.fields[1].relative_offset, 6);
+        assert!(s.is_const);
+
+        let error = definitions.load_from_str("test.json", r#"[{ "name": "Half3", "type": "struct", "fields": [], "size": 0 }]"#).unwrap_err();
+        assert!(matches!(error, DefinitionError::Invalid { object, .. } if object == "Half3"));
+        assert!(definitions.get_struct("Half3").is_none());
+    }
+}
@@ -0,0 +1,108 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::*;
+
+/// Describes a struct that contains itself through inline struct fields, which would make it
+/// infinitely large.
+///
+/// Reflexives point to separate blocks, so structs may reference themselves through them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ReferenceCycle {
+    /// Each struct and the name of the field that leads to the next struct in the cycle.
+    ///
+    /// The last field leads back to the first struct.
+    pub path: Vec<(String, String)>
+}
+
+impl core::fmt::Display for ReferenceCycle {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let Some((first, _)) = self.path.first() else {
+            return Ok(())
+        };
+        fmt.write_fmt(format_args!("struct {first} contains itself: "))?;
+        for (object, field) in &self.path {
+            fmt.write_fmt(format_args!("{object}::{field} -> "))?;
+        }
+        fmt.write_str(first)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum Visit {
+    InProgress,
+    Done
+}
+
+impl ParsedDefinitions {
+    /// Find every struct that contains itself through inline struct fields.
+    ///
+    /// Each cycle is only reported once. [`ParsedDefinitions::finalize`] panics if this is not
+    /// empty.
+    pub fn inline_cycles(&self) -> Vec<ReferenceCycle> {
+        fn visit<'a>(definitions: &'a ParsedDefinitions, name: &'a str, visits: &mut BTreeMap<&'a str, Visit>, path: &mut Vec<(&'a str, &'a str)>, cycles: &mut Vec<ReferenceCycle>) {
+            let Some(NamedObject::Struct(s)) = definitions.objects.get(name) else {
+                return
+            };
+            match visits.get(name) {
+                Some(Visit::Done) => return,
+                Some(Visit::InProgress) => {
+                    let start = path.iter().position(|(object, _)| *object == name).expect("struct in progress should be in the path");
+                    cycles.push(ReferenceCycle {
+                        path: path[start..].iter().map(|(o, f)| ((*o).to_owned(), (*f).to_owned())).collect()
+                    });
+                    return
+                },
+                None => ()
+            }
+
+            visits.insert(name, Visit::InProgress);
+            for f in &s.fields {
+                if let StructFieldType::Object(FieldObject::NamedObject(n)) = &f.field_type {
+                    path.push((name, &f.name));
+                    visit(definitions, n, visits, path, cycles);
+                    path.pop();
+                }
+            }
+            visits.insert(name, Visit::Done);
+        }
+
+        let mut visits = BTreeMap::new();
+        let mut cycles = Vec::new();
+        for name in self.objects.keys() {
+            visit(self, name, &mut visits, &mut Vec::new(), &mut cycles);
+        }
+        cycles
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::borrow::ToOwned;
+
+    use crate::*;
+
+    #[test]
+    fn inline_cycles_are_reported() {
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_str("test.json", r#"[
+            { "name": "TestOuter", "type": "struct", "size": 4, "fields": [{ "name": "inner", "type": "TestInner" }] },
+            { "name": "TestInner", "type": "struct", "size": 4, "fields": [
+                { "name": "outer", "type": "TestOuter" },
+                { "name": "children", "type": "Reflexive", "struct": "TestInner" }
+            ] }
+        ]"#).unwrap();
+
+        let cycles = definitions.inline_cycles();
+        assert_eq!(cycles, [ReferenceCycle {
+            path: alloc::vec![("TestInner".to_owned(), "outer".to_owned()), ("TestOuter".to_owned(), "inner".to_owned())]
+        }]);
+        assert_eq!(alloc::format!("{}", cycles[0]), "struct TestInner contains itself: TestInner::outer -> TestOuter::inner -> TestInner");
+        assert_eq!(definitions.try_finalize(), Err(DefinitionError::ReferenceCycle(cycles[0].clone())));
+
+        assert!(load_all_definitions().inline_cycles().is_empty());
+    }
+}
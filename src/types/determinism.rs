@@ -0,0 +1,103 @@
+use alloc::vec::Vec;
+
+use super::*;
+
+/// Describes whether a field affects gameplay determinism.
+///
+/// Networked games stay in sync by simulating the same gameplay on every machine, so changes to
+/// [`Determinism::Gameplay`] fields break multiplayer sync between players with different tags,
+/// while changes to [`Determinism::Cosmetic`] fields do not.
+///
+/// Set with `determinism` in the JSON. Setting it on a struct sets it on every field of the
+/// struct that does not set it. Fields without it are unknown.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Determinism {
+    /// The field affects the simulation (e.g. damage, rate of fire, or physics).
+    Gameplay,
+
+    /// The field only affects presentation (e.g. sounds, HUD, or screen effects).
+    Cosmetic
+}
+
+impl Determinism {
+    /// Get the determinism from its name in the JSON definitions.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "gameplay" => Some(Self::Gameplay),
+            "cosmetic" => Some(Self::Cosmetic),
+            _ => None
+        }
+    }
+
+    /// Get the name of the determinism as used in the JSON definitions.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Gameplay => "gameplay",
+            Self::Cosmetic => "cosmetic"
+        }
+    }
+}
+
+impl ParsedDefinitions {
+    /// Get whether the field at `path` in a group affects determinism.
+    ///
+    /// `path` is the names of the fields leading from the group's base struct to the field, like in
+    /// [`TextField::path`]. Fields without a [`Flags::determinism`] take it from the nearest field
+    /// containing them (e.g. the reflexive they are in).
+    ///
+    /// Returns `None` if it is unknown or the path does not exist.
+    pub fn determinism_of(&self, group: &str, path: &[&str]) -> Option<Determinism> {
+        let mut struct_name = self.groups.get(group)?.struct_name.as_str();
+        let mut determinism = None;
+        for (i, name) in path.iter().enumerate() {
+            let field = self.get_struct(struct_name)?.fields.iter().find(|f| f.name == *name)?;
+            determinism = field.flags.determinism.or(determinism);
+            match &field.field_type {
+                StructFieldType::Object(FieldObject::NamedObject(n) | FieldObject::Reflexive(n)) => struct_name = n,
+                _ if i + 1 == path.len() => (),
+                _ => return None
+            }
+        }
+        determinism
+    }
+
+    /// List the paths of every field reachable from a group with the given determinism, including
+    /// fields that take it from a field containing them.
+    ///
+    /// See [`ParsedDefinitions::determinism_of`].
+    pub fn fields_with_determinism(&self, group: &str, determinism: Determinism) -> Vec<Vec<&str>> {
+        let Some(g) = self.groups.get(group) else {
+            return Vec::new()
+        };
+        let mut paths = Vec::new();
+        self.walk_struct_fields(&g.struct_name, &mut |path, _, _| {
+            if self.determinism_of(group, path) == Some(determinism) {
+                paths.push(path.to_vec());
+            }
+        });
+        paths
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn determinism_is_inherited() {
+        let definitions = load_all_definitions();
+        assert_eq!(definitions.determinism_of("weapon", &["triggers", "maximum rate of fire"]), Some(Determinism::Gameplay));
+        assert_eq!(definitions.determinism_of("weapon", &["hud interface"]), Some(Determinism::Cosmetic));
+        assert_eq!(definitions.determinism_of("weapon", &["label"]), None);
+        assert_eq!(definitions.determinism_of("weapon", &["label", "nonexistent"]), None);
+
+        let cosmetic = definitions.fields_with_determinism("damage_effect", Determinism::Cosmetic);
+        assert!(cosmetic.iter().any(|p| p[..] == ["screen flash", "color"]));
+        assert!(!cosmetic.iter().any(|p| p[0] == "damage"));
+
+        let gameplay = definitions.query().structs().with_determinism(Determinism::Gameplay).fields().count();
+        assert!(gameplay > 0);
+    }
+}
@@ -0,0 +1,291 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::*;
+
+/// Describes how a struct field, enum option, or bitfield flag changed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MemberChange {
+    /// The member was added.
+    Added(String),
+
+    /// The member was removed.
+    Removed(String),
+
+    /// The member was renamed.
+    ///
+    /// Fields are considered renamed if they have the same type, count, and offset. Options and
+    /// flags are considered renamed if they have the same value.
+    Renamed {
+        /// The old name.
+        from: String,
+
+        /// The new name.
+        to: String
+    },
+
+    /// The field's type, count, or offset changed.
+    Changed(String)
+}
+
+/// A change to a member of a struct, enum, or bitfield.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MemberDifference {
+    /// Name of the struct, enum, or bitfield.
+    pub object: String,
+
+    /// What changed.
+    pub change: MemberChange
+}
+
+/// A struct whose size changed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SizeDifference {
+    /// Name of the struct.
+    pub object: String,
+
+    /// The old size.
+    pub a: usize,
+
+    /// The new size.
+    pub b: usize
+}
+
+/// A limit of a struct field that changed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LimitDifference {
+    /// Name of the struct.
+    pub object: String,
+
+    /// Name of the field.
+    pub field: String,
+
+    /// Which limit changed.
+    pub limit_type: LimitType,
+
+    /// The old limit, if any.
+    pub a: Option<usize>,
+
+    /// The new limit, if any.
+    pub b: Option<usize>
+}
+
+/// Summary of the differences between two sets of definitions.
+///
+/// Created by [`ParsedDefinitions::diff`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DefinitionsDiff {
+    /// Engines only in the new definitions.
+    pub added_engines: Vec<String>,
+
+    /// Engines only in the old definitions.
+    pub removed_engines: Vec<String>,
+
+    /// Tag groups only in the new definitions.
+    pub added_groups: Vec<String>,
+
+    /// Tag groups only in the old definitions.
+    pub removed_groups: Vec<String>,
+
+    /// Structs, enums, and bitfields only in the new definitions.
+    ///
+    /// Objects that changed kind (e.g. from an enum to a bitfield) are both added and removed.
+    pub added_objects: Vec<String>,
+
+    /// Structs, enums, and bitfields only in the old definitions.
+    pub removed_objects: Vec<String>,
+
+    /// Changes to struct fields, excluding padding and editor sections.
+    pub fields: Vec<MemberDifference>,
+
+    /// Structs whose size changed.
+    pub sizes: Vec<SizeDifference>,
+
+    /// Changes to enum options and bitfield flags.
+    pub options: Vec<MemberDifference>,
+
+    /// Changes to field limits, including engine-specific and profile limits.
+    pub limits: Vec<LimitDifference>
+}
+
+impl DefinitionsDiff {
+    /// Returns true if no differences were found.
+    ///
+    /// Generated code only needs to be rebuilt if this is false.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl ParsedDefinitions {
+    /// Compare these definitions (the old ones) with `other` (the new ones).
+    ///
+    /// Both sets of definitions must be finalized so field offsets are known.
+    pub fn diff(&self, other: &ParsedDefinitions) -> DefinitionsDiff {
+        let mut diff = DefinitionsDiff::default();
+        added_and_removed(self.engines.keys(), other.engines.keys(), &mut diff.added_engines, &mut diff.removed_engines);
+        added_and_removed(self.groups.keys(), other.groups.keys(), &mut diff.added_groups, &mut diff.removed_groups);
+
+        let only_in = |a: &ParsedDefinitions, b: &ParsedDefinitions| -> Vec<String> {
+            a.objects.iter().filter(|(n, o)| b.objects.get(*n).is_none_or(|other| other.kind() != o.kind())).map(|(n, _)| n.to_owned()).collect()
+        };
+        diff.removed_objects = only_in(self, other);
+        diff.added_objects = only_in(other, self);
+
+        for (name, a) in &self.objects {
+            match (a, other.objects.get(name)) {
+                (NamedObject::Struct(a), Some(NamedObject::Struct(b))) => diff_structs(a, b, &mut diff),
+                (NamedObject::Enum(a), Some(NamedObject::Enum(b))) => diff_options(name, &a.options, &b.options, &mut diff.options),
+                (NamedObject::Bitfield(a), Some(NamedObject::Bitfield(b))) => diff_options(name, &a.fields, &b.fields, &mut diff.options),
+                _ => ()
+            }
+        }
+
+        diff
+    }
+}
+
+fn added_and_removed<'a>(a: impl Iterator<Item = &'a String>, b: impl Iterator<Item = &'a String>, added: &mut Vec<String>, removed: &mut Vec<String>) {
+    let a: BTreeSet<&String> = a.collect();
+    let b: BTreeSet<&String> = b.collect();
+    added.extend(b.difference(&a).map(|n| (*n).to_owned()));
+    removed.extend(a.difference(&b).map(|n| (*n).to_owned()));
+}
+
+fn diff_structs(a: &Struct, b: &Struct, diff: &mut DefinitionsDiff) {
+    let object = &a.name;
+    if a.size != b.size {
+        diff.sizes.push(SizeDifference { object: object.to_owned(), a: a.size, b: b.size });
+    }
+
+    fn values(s: &Struct) -> Vec<&StructField> {
+        s.fields.iter().filter(|f| matches!(f.field_type, StructFieldType::Object(_))).collect()
+    }
+    let (fields_a, fields_b) = (values(a), values(b));
+    let same_layout = |x: &StructField, y: &StructField| x.field_type == y.field_type && x.count == y.count && x.relative_offset == y.relative_offset;
+
+    let mut removed: Vec<&StructField> = Vec::new();
+    for fa in &fields_a {
+        match fields_b.iter().find(|fb| fb.name == fa.name) {
+            Some(fb) => {
+                if !same_layout(fa, fb) {
+                    diff.fields.push(MemberDifference { object: object.to_owned(), change: MemberChange::Changed(fa.name.to_owned()) });
+                }
+                diff_limits(object, fa, fb, &mut diff.limits);
+            },
+            None => removed.push(fa)
+        }
+    }
+
+    for fb in fields_b.iter().filter(|fb| !fields_a.iter().any(|fa| fa.name == fb.name)) {
+        let change = match removed.iter().position(|fa| same_layout(fa, fb)) {
+            Some(i) => {
+                let fa = removed.remove(i);
+                diff_limits(object, fa, fb, &mut diff.limits);
+                MemberChange::Renamed { from: fa.name.to_owned(), to: fb.name.to_owned() }
+            },
+            None => MemberChange::Added(fb.name.to_owned())
+        };
+        diff.fields.push(MemberDifference { object: object.to_owned(), change });
+    }
+    for fa in removed {
+        diff.fields.push(MemberDifference { object: object.to_owned(), change: MemberChange::Removed(fa.name.to_owned()) });
+    }
+}
+
+fn diff_limits(object: &str, a: &StructField, b: &StructField, limits: &mut Vec<LimitDifference>) {
+    let empty = BTreeMap::new();
+    let (la, lb) = (a.limit.as_ref().unwrap_or(&empty), b.limit.as_ref().unwrap_or(&empty));
+    let limit_types: BTreeSet<&LimitType> = la.keys().chain(lb.keys()).collect();
+    for limit_type in limit_types {
+        let (va, vb) = (la.get(limit_type).copied(), lb.get(limit_type).copied());
+        if va != vb {
+            limits.push(LimitDifference { object: object.to_owned(), field: b.name.to_owned(), limit_type: limit_type.to_owned(), a: va, b: vb });
+        }
+    }
+}
+
+fn diff_options(object: &str, a: &[Field], b: &[Field], options: &mut Vec<MemberDifference>) {
+    let mut push = |change| options.push(MemberDifference { object: object.to_owned(), change });
+    for oa in a.iter().filter(|oa| !b.iter().any(|ob| ob.name == oa.name)) {
+        match b.iter().find(|ob| ob.value == oa.value && !a.iter().any(|x| x.name == ob.name)) {
+            Some(ob) => push(MemberChange::Renamed { from: oa.name.to_owned(), to: ob.name.to_owned() }),
+            None => push(MemberChange::Removed(oa.name.to_owned()))
+        }
+    }
+    for ob in b.iter().filter(|ob| !a.iter().any(|oa| oa.name == ob.name)) {
+        if !a.iter().any(|oa| oa.value == ob.value && !b.iter().any(|x| x.name == oa.name)) {
+            push(MemberChange::Added(ob.name.to_owned()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::borrow::ToOwned;
+    use crate::*;
+
+    #[test]
+    fn diff_finds_changes() {
+        let load = |json: &str| {
+            let mut definitions = ParsedDefinitions::default();
+            definitions.load_from_str("test.json", json).unwrap();
+            definitions.finalize_without_engines();
+            definitions
+        };
+        let old = load(r#"[
+            { "name": "TestEnum", "type": "enum", "options": ["first", "second", "third"] },
+            { "name": "TestElement", "type": "struct", "fields": [{ "name": "value", "type": "uint32" }], "size": 4 },
+            {
+                "name": "TestStruct",
+                "type": "struct",
+                "fields": [
+                    { "name": "kind", "type": "TestEnum" },
+                    { "name": "old name", "type": "uint16" },
+                    { "name": "scale", "type": "float" },
+                    { "name": "elements", "type": "Reflexive", "struct": "TestElement", "limit": 8 }
+                ],
+                "size": 20
+            }
+        ]"#);
+        let new = load(r#"[
+            { "name": "TestEnum", "type": "enum", "options": ["first", "renamed", "third", "fourth"] },
+            { "name": "TestElement", "type": "struct", "fields": [{ "name": "value", "type": "uint32" }], "size": 4 },
+            {
+                "name": "TestStruct",
+                "type": "struct",
+                "fields": [
+                    { "name": "kind", "type": "TestEnum" },
+                    { "name": "new name", "type": "uint16" },
+                    { "name": "scale", "type": "int32" },
+                    { "name": "elements", "type": "Reflexive", "struct": "TestElement", "limit": 16 },
+                    { "name": "extra", "type": "uint32" }
+                ],
+                "size": 24
+            }
+        ]"#);
+
+        assert!(old.diff(&old).is_empty());
+        let diff = old.diff(&new);
+        let member = |object: &str, change| MemberDifference { object: object.to_owned(), change };
+        assert_eq!(diff.sizes, [SizeDifference { object: "TestStruct".to_owned(), a: 20, b: 24 }]);
+        assert_eq!(diff.fields, [
+            member("TestStruct", MemberChange::Changed("scale".to_owned())),
+            member("TestStruct", MemberChange::Renamed { from: "old name".to_owned(), to: "new name".to_owned() }),
+            member("TestStruct", MemberChange::Added("extra".to_owned()))
+        ]);
+        assert_eq!(diff.options, [
+            member("TestEnum", MemberChange::Renamed { from: "second".to_owned(), to: "renamed".to_owned() }),
+            member("TestEnum", MemberChange::Added("fourth".to_owned()))
+        ]);
+        assert!(diff.limits.iter().any(|l| l.field == "elements" && l.limit_type == LimitType::Default && (l.a, l.b) == (Some(8), Some(16))));
+        assert!(diff.added_objects.is_empty() && diff.removed_objects.is_empty());
+    }
+}
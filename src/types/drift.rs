@@ -0,0 +1,110 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::*;
+
+/// Describes how a copy of an enum or bitfield drifted from its original.
+///
+/// Copies are declared with `copy_of` in the JSON (see [`Enum::copy_of`] and
+/// [`Bitfield::copy_of`]), and must have the same options in the same order as the original.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct OptionDrift {
+    /// Name of the copy.
+    pub copy: String,
+
+    /// Name of the original.
+    pub original: String,
+
+    /// Index of the first option that differs.
+    pub index: usize,
+
+    /// Name of the original's option at [`OptionDrift::index`], if it has one.
+    pub expected: Option<String>,
+
+    /// Name of the copy's option at [`OptionDrift::index`], if it has one.
+    pub actual: Option<String>
+}
+
+impl core::fmt::Display for OptionDrift {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let Self { copy, original, index, .. } = self;
+        let option = |o: &Option<String>| o.as_deref().unwrap_or("nothing").to_owned();
+        fmt.write_fmt(format_args!(
+            "{copy} is a copy of {original}, but option {index} is {} instead of {}",
+            option(&self.actual),
+            option(&self.expected)
+        ))
+    }
+}
+
+impl NamedObject {
+    pub(crate) fn copy_of(&self) -> Option<&str> {
+        match self {
+            NamedObject::Enum(e) => e.copy_of.as_deref(),
+            NamedObject::Bitfield(b) => b.copy_of.as_deref(),
+            NamedObject::Struct(_) => None
+        }
+    }
+
+    fn options(&self) -> Option<&[Field]> {
+        match self {
+            NamedObject::Enum(e) => Some(&e.options),
+            NamedObject::Bitfield(b) => Some(&b.fields),
+            NamedObject::Struct(_) => None
+        }
+    }
+}
+
+impl ParsedDefinitions {
+    /// Find every copy of an enum or bitfield whose options drifted from its original.
+    ///
+    /// Copies of objects that are not loaded are ignored. [`ParsedDefinitions::finalize`] panics if
+    /// this is not empty.
+    pub fn option_drift(&self) -> Vec<OptionDrift> {
+        let mut drift = Vec::new();
+        for object in self.objects.values() {
+            let Some(original) = object.copy_of() else {
+                continue
+            };
+            let (Some(copy_options), Some(original_options)) = (object.options(), self.objects.get(original).and_then(NamedObject::options)) else {
+                continue
+            };
+            let name = |o: Option<&Field>| o.map(|o| o.name.to_owned());
+            let index = (0..copy_options.len().max(original_options.len()))
+                .find(|i| name(copy_options.get(*i)) != name(original_options.get(*i)));
+            if let Some(index) = index {
+                drift.push(OptionDrift {
+                    copy: object.name().to_owned(),
+                    original: original.to_owned(),
+                    index,
+                    expected: name(original_options.get(index)),
+                    actual: name(copy_options.get(index))
+                });
+            }
+        }
+        drift
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn drifted_copies_are_reported() {
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_str("test.json", r#"[
+            { "name": "TestOriginal", "type": "enum", "options": ["first", "second", "third"] },
+            { "name": "TestCopy", "type": "enum", "options": ["first", "third"], "copy_of": "TestOriginal" }
+        ]"#).unwrap();
+        let drift = definitions.option_drift();
+        assert_eq!(drift.len(), 1);
+        assert_eq!((drift[0].index, drift[0].expected.as_deref(), drift[0].actual.as_deref()), (1, Some("second"), Some("third")));
+        assert_eq!(alloc::format!("{}", drift[0]), "TestCopy is a copy of TestOriginal, but option 1 is third instead of second");
+        assert_eq!(definitions.try_finalize(), Err(DefinitionError::OptionDrift(drift[0].clone())));
+
+        assert!(load_all_definitions().option_drift().is_empty());
+    }
+}
@@ -0,0 +1,206 @@
+use alloc::string::String;
+use core::fmt::Write;
+
+use super::*;
+
+/// First line of every dump made by [`ParsedDefinitions::dump_canonical`].
+///
+/// The number is increased whenever the format changes.
+pub const CANONICAL_DUMP_HEADER: &str = "ringhopper-definitions canonical dump 1";
+
+impl ParsedDefinitions {
+    /// Dump the definitions as text in a stable format, for snapshot testing.
+    ///
+    /// The format does not change between patch releases. If it changes otherwise, the version in
+    /// [`CANONICAL_DUMP_HEADER`] is increased.
+    ///
+    /// The dump starts with [`CANONICAL_DUMP_HEADER`], followed by every engine, group, and object
+    /// sorted by name within each section. Each definition begins with an unindented line of its
+    /// kind and name, followed by its properties and members indented with two spaces, one per
+    /// line:
+    ///
+    /// ```text
+    /// engine <name>
+    ///   display_name "<display name>"
+    ///   inherits <engine>
+    ///   build "<build>" enforced=<bool> aliases=["<alias>", ...]
+    ///   cache_file_version <version>
+    ///   flags <build_target, cache_default, fallback, custom if set>
+    /// group <name>
+    ///   struct <struct>
+    ///   supergroup <group>
+    ///   fourcc "<fourcc>" 0x<fourcc>
+    ///   version <version>
+    ///   extension <extension>
+    ///   engines <engines>
+    /// struct <name> size=0x<size>
+    ///   0x<offset> <type> "<name>" count=<count> size=0x<size> [flags=<flags>] [engines=<engines>] [limits=<limits>]
+    ///   0x<offset> pad size=0x<size> [reserved]
+    /// enum <name>
+    ///   <value> "<option>" [engines=<engines>]
+    /// bitfield <name> width=<width>
+    ///   0x<mask> "<field>" [engines=<engines>]
+    /// ```
+    ///
+    /// Optional properties are omitted when unset. Strings are quoted, with `\`, `"`, and control
+    /// characters escaped as `\\`, `\"`, and `\u{XX}`. Editor sections, documentation, and other
+    /// presentation-only metadata are not included.
+    pub fn dump_canonical(&self) -> String {
+        let mut output = String::new();
+        output.push_str(CANONICAL_DUMP_HEADER);
+        output.push('\n');
+
+        for engine in self.engines.values() {
+            let _ = writeln!(output, "engine {}", engine.name);
+            let _ = writeln!(output, "  display_name {}", quote(&engine.display_name));
+            if let Some(inherits) = &engine.inherits {
+                let _ = writeln!(output, "  inherits {inherits}");
+            }
+            if let Some(build) = &engine.build {
+                let aliases = build.aliases.iter().map(|a| quote(a)).collect::<Vec<_>>().join(", ");
+                let _ = writeln!(output, "  build {} enforced={} aliases=[{aliases}]", quote(&build.string), build.enforced);
+            }
+            let _ = writeln!(output, "  cache_file_version {}", engine.cache_file_version);
+            let flags = [("build_target", engine.build_target), ("cache_default", engine.cache_default), ("fallback", engine.fallback), ("custom", engine.custom)]
+                .iter()
+                .filter(|f| f.1)
+                .map(|f| f.0)
+                .collect::<Vec<_>>()
+                .join(" ");
+            if !flags.is_empty() {
+                let _ = writeln!(output, "  flags {flags}");
+            }
+        }
+
+        for group in self.groups.values() {
+            let _ = writeln!(output, "group {}", group.name);
+            let _ = writeln!(output, "  struct {}", group.struct_name);
+            if let Some(supergroup) = &group.supergroup {
+                let _ = writeln!(output, "  supergroup {supergroup}");
+            }
+            let _ = writeln!(output, "  fourcc {} 0x{:08X}", quote(&group.fourcc_string()), group.fourcc_binary);
+            let _ = writeln!(output, "  version {}", group.version);
+            let _ = writeln!(output, "  extension {}", group.extension);
+            let _ = writeln!(output, "  engines {}", format_engines(&group.supported_engines));
+        }
+
+        for object in self.objects.values() {
+            match object {
+                NamedObject::Struct(s) => {
+                    let _ = writeln!(output, "struct {} size=0x{:X}", s.name, s.size);
+                    for f in &s.fields {
+                        let size = f.size(self);
+                        match &f.field_type {
+                            StructFieldType::Object(o) => {
+                                let count = match f.count {
+                                    FieldCount::One => String::from("1"),
+                                    FieldCount::Bounds => String::from("bounds"),
+                                    FieldCount::Array(n) => alloc::format!("{n}")
+                                };
+                                let _ = write!(output, "  0x{:04X} {} {} count={count} size=0x{size:X}", f.relative_offset, dump_type(o), quote(&f.name));
+                                let flags = FlagType::ALL.iter().filter(|t| f.flags.has_flag(**t)).map(|t| t.json_name()).collect::<Vec<_>>().join(",");
+                                if !flags.is_empty() {
+                                    let _ = write!(output, " flags={flags}");
+                                }
+                                write_engines(&mut output, &f.flags.supported_engines);
+                                if let Some(limits) = &f.limit {
+                                    let limits = limits
+                                        .iter()
+                                        .map(|(t, l)| match t {
+                                            LimitType::Default => alloc::format!("default:{l}"),
+                                            LimitType::Editor => alloc::format!("editor:{l}"),
+                                            LimitType::Engine(e) => alloc::format!("{e}:{l}"),
+                                            LimitType::Profile(p) => alloc::format!("profile/{p}:{l}")
+                                        })
+                                        .collect::<Vec<_>>()
+                                        .join(",");
+                                    let _ = write!(output, " limits={limits}");
+                                }
+                                output.push('\n');
+                            },
+                            StructFieldType::Padding(_) => {
+                                let reserved = if f.padding_kind == Some(PaddingKind::Reserved) { " reserved" } else { "" };
+                                let _ = writeln!(output, "  0x{:04X} pad size=0x{size:X}{reserved}", f.relative_offset);
+                            },
+                            StructFieldType::EditorSection { .. } => ()
+                        }
+                    }
+                },
+                NamedObject::Enum(e) => {
+                    let _ = writeln!(output, "enum {}", e.name);
+                    for o in &e.options {
+                        let _ = write!(output, "  {} {}", o.value, quote(&o.name));
+                        write_engines(&mut output, &o.flags.supported_engines);
+                        output.push('\n');
+                    }
+                },
+                NamedObject::Bitfield(b) => {
+                    let _ = writeln!(output, "bitfield {} width={}", b.name, b.width);
+                    for f in &b.fields {
+                        let _ = write!(output, "  0x{:08X} {}", f.value, quote(&f.name));
+                        write_engines(&mut output, &f.flags.supported_engines);
+                        output.push('\n');
+                    }
+                }
+            }
+        }
+
+        output
+    }
+}
+
+fn write_engines(output: &mut String, engines: &SupportedEngines) {
+    if !matches!(engines, SupportedEngines::AllEngines) {
+        let _ = write!(output, " engines={}", quote(&format_engines(engines)));
+    }
+}
+
+fn dump_type(object: &FieldObject) -> String {
+    match object {
+        FieldObject::Reflexive(s) => alloc::format!("Reflexive<{s}>"),
+        FieldObject::TagReference { allowed_groups } => alloc::format!("TagReference<{}>", allowed_groups.join(",")),
+        o => String::from(o.type_name())
+    }
+}
+
+fn quote(string: &str) -> String {
+    let mut quoted = String::with_capacity(string.len() + 2);
+    quoted.push('"');
+    for c in string.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            c if c.is_control() => {
+                let _ = write!(quoted, "\\u{{{:02X}}}", c as u32);
+            },
+            c => quoted.push(c)
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(all(test, feature = "engines-pc"))]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn canonical_dump_matches_snapshot() {
+        // If the format changed on purpose, increase the version in CANONICAL_DUMP_HEADER and
+        // regenerate the snapshot.
+        let mut definitions = ParsedDefinitions::load_builtin();
+        definitions.retain_groups(&["light_volume", "string_list", "wind"]);
+        definitions.engines.retain(|e, _| e == "pc" || e == "pc-retail");
+        assert_eq!(definitions.dump_canonical(), include_str!("dump_snapshot.txt"));
+    }
+
+    #[test]
+    fn canonical_dump_is_well_formed() {
+        let definitions = load_all_definitions();
+        let dump = definitions.dump_canonical();
+        assert!(dump.starts_with(CANONICAL_DUMP_HEADER));
+        assert!(dump.contains("\ngroup wind\n  struct Wind\n"));
+        assert!(dump.contains("\nstruct StringList size=0xC\n  0x0000 Reflexive<StringListString> \"strings\" count=1 size=0xC"));
+        assert!(dump.lines().skip(1).all(|l| !l.is_empty() && !l.ends_with(' ')));
+    }
+}
@@ -0,0 +1,522 @@
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+use super::*;
+
+/// Iterates through an engine and every engine it inherits, from most to least derived.
+///
+/// Created by [`ParsedDefinitions::engine_lineage`].
+pub struct EngineLineage<'a> {
+    definitions: &'a ParsedDefinitions,
+    next: Option<&'a Engine>,
+    visited: BTreeSet<&'a str>
+}
+
+impl<'a> Iterator for EngineLineage<'a> {
+    type Item = &'a Engine;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next.take()?;
+
+        // Guard against malformed inheritance loops.
+        if !self.visited.insert(current.name.as_str()) {
+            return None
+        }
+
+        self.next = current.inherits.as_ref().and_then(|i| self.definitions.engines.get(i));
+        Some(current)
+    }
+}
+
+/// A borrowed [`Engine`] along with the definitions it belongs to, with inheritance resolved.
+///
+/// Engine properties (e.g. limits, bitmap options, required tags, and cache parser settings) are
+/// already flattened when the engine is loaded, so they can be read directly through this view.
+/// Anything that depends on other definitions, such as field limits and supported groups, also
+/// takes the engine's ancestors into account.
+///
+/// Created by [`Engine::resolved`] and [`ParsedDefinitions::resolved_engine`].
+#[derive(Copy, Clone)]
+pub struct ResolvedEngine<'a> {
+    definitions: &'a ParsedDefinitions,
+    inner: &'a Engine
+}
+
+impl<'a> ResolvedEngine<'a> {
+    /// Get the underlying engine.
+    pub fn get(&self) -> &'a Engine {
+        self.inner
+    }
+
+    /// Iterate through this engine and all of its ancestors, starting with the engine itself.
+    pub fn lineage(&self) -> EngineLineage<'a> {
+        self.definitions.engine_lineage(&self.inner.name)
+    }
+
+    /// Returns true if this engine is `ancestor` or inherits it, directly or indirectly.
+    pub fn inherits_from(&self, ancestor: &str) -> bool {
+        self.lineage().any(|e| e.name == ancestor)
+    }
+
+    /// Get the maximum count of a field for this engine.
+    ///
+    /// See [`FieldView::limit_for_engine`]. Returns `None` if the field does not exist or has no
+    /// limit.
+    pub fn field_limit(&self, struct_name: &str, field: &str) -> Option<usize> {
+        self.definitions.struct_view(struct_name)?.field(field)?.limit_for_engine(&self.inner.name)
+    }
+
+    /// Get the number of elements of a field used by this engine.
+    ///
+    /// See [`FieldView::count_for_engine`]. Returns `None` if the field does not exist.
+    pub fn field_count(&self, struct_name: &str, field: &str) -> Option<usize> {
+        self.definitions.struct_view(struct_name)?.field(field).map(|f| f.count_for_engine(&self.inner.name))
+    }
+
+    /// Returns true if this engine, or any engine it inherits, supports the group.
+    pub fn supports_group(&self, group: &TagGroup) -> bool {
+        group.supported_engines.supports_engine_or_ancestor(self.inner, self.definitions)
+    }
+
+    /// Iterate through all groups supported by this engine.
+    pub fn supported_groups(&self) -> impl Iterator<Item = &'a TagGroup> + 'a {
+        let engine = *self;
+        self.definitions.groups.values().filter(move |g| engine.supports_group(g))
+    }
+}
+
+impl Deref for ResolvedEngine<'_> {
+    type Target = Engine;
+    fn deref(&self) -> &Self::Target {
+        self.inner
+    }
+}
+
+impl Engine {
+    /// Get a view of this engine with inheritance resolved against the given definitions.
+    pub fn resolved<'a>(&'a self, definitions: &'a ParsedDefinitions) -> ResolvedEngine<'a> {
+        ResolvedEngine { definitions, inner: self }
+    }
+}
+
+/// How finalizing handles references to engines that are not loaded.
+///
+/// This applies to [`SupportedEngines`] lists, `introduced_in`/`removed_in` markers, engine limits
+/// ([`LimitType::Engine`]), and engine-specific array counts. It is set with
+/// [`ParsedDefinitions::set_unknown_engine_policy`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum UnknownEnginePolicy {
+    /// Unknown engines are an error, so [`ParsedDefinitions::finalize`] panics and
+    /// [`ParsedDefinitions::try_finalize`] returns an error.
+    #[default]
+    Error,
+
+    /// Unknown engines are treated as not supporting anything.
+    ///
+    /// They are removed from supported engine lists, so something only supported by unknown
+    /// engines is not supported by any loaded engine. Something introduced in an unknown engine is
+    /// not supported by any loaded engine, either.
+    TreatAsUnsupported,
+
+    /// Unknown engines are treated as supporting everything.
+    ///
+    /// Unknown engines are removed from supported engine lists, but something only supported by
+    /// unknown engines is assumed to be supported by every loaded engine. `introduced_in` markers
+    /// referencing an unknown engine are ignored.
+    TreatAsSupported
+}
+
+impl ParsedDefinitions {
+    /// Set how references to engines that are not loaded are handled when finalizing.
+    ///
+    /// This is useful when only some engines are loaded on purpose. With either lenient policy,
+    /// unknown engines are dropped from `unsupported_engines` lists and `removed_in` markers, and
+    /// limits and array counts for unknown engines are discarded.
+    ///
+    /// This must be set before calling [`ParsedDefinitions::finalize`].
+    pub fn set_unknown_engine_policy(&mut self, policy: UnknownEnginePolicy) {
+        self.unknown_engine_policy = policy;
+    }
+
+    /// Get how references to engines that are not loaded are handled when finalizing.
+    pub fn unknown_engine_policy(&self) -> UnknownEnginePolicy {
+        self.unknown_engine_policy
+    }
+
+    /// Get an engine by name with inheritance resolved, or `None` if it does not exist.
+    pub fn resolved_engine(&self, name: &str) -> Option<ResolvedEngine<'_>> {
+        self.engines.get(name).map(|e| e.resolved(self))
+    }
+
+    /// Iterate through the given engine and all of its ancestors via [`Engine::inherits`], starting
+    /// with the engine itself.
+    ///
+    /// Returns an empty iterator if the engine does not exist.
+    pub fn engine_lineage(&self, engine: &str) -> EngineLineage<'_> {
+        EngineLineage {
+            definitions: self,
+            next: self.engines.get(engine),
+            visited: BTreeSet::new()
+        }
+    }
+
+    /// Returns true if `engine` is `ancestor` or inherits it, directly or indirectly.
+    pub fn engine_inherits(&self, engine: &str, ancestor: &str) -> bool {
+        self.engine_lineage(engine).any(|e| e.name == ancestor)
+    }
+
+    /// Find the engine a cache file was built for from its build string and cache file version.
+    ///
+    /// Build targets with the same cache file version are matched against [`Build::string`] first,
+    /// then [`Build::aliases`], which may use `*` as a wildcard. Trailing null bytes (e.g. from a
+    /// `String32`) are ignored.
+    ///
+    /// If nothing matches, the [`Engine::cache_default`] engine for the version is returned, then
+    /// any [`Engine::fallback`] engine for it. Engines with an [enforced](Build::enforced) build
+    /// are never returned as a fallback, since the game would reject the cache file.
+    pub fn engine_by_build(&self, build: &str, cache_file_version: u32) -> Option<&Engine> {
+        let build = build.trim_end_matches('\0');
+        let candidates = || self.engines.values().filter(move |e| e.cache_file_version == cache_file_version);
+        let targets = || candidates().filter(|e| e.build_target);
+
+        targets().find(|e| e.build.as_ref().is_some_and(|b| b.string == build))
+            .or_else(|| targets().find(|e| e.build.as_ref().is_some_and(|b| b.aliases.iter().any(|a| wildcard_matches(a, build)))))
+            .or_else(|| {
+                let not_enforced = |e: &&Engine| !e.build.as_ref().is_some_and(|b| b.enforced);
+                candidates().filter(not_enforced).find(|e| e.cache_default)
+                    .or_else(|| candidates().filter(not_enforced).find(|e| e.fallback))
+            })
+    }
+
+    /// Iterate through all engines from most to least specific.
+    ///
+    /// This is the order to try candidates in when identifying which engine a cache file is for.
+    /// Engines that inherit more engines come first, with ties broken by custom engines first,
+    /// then [build targets](Engine::build_target), then name. [Fallback](Engine::fallback) engines
+    /// always come last.
+    pub fn engines_by_specificity(&self) -> impl Iterator<Item = &Engine> {
+        let mut engines: Vec<(usize, &Engine)> = self.engines
+            .values()
+            .map(|e| (self.engine_lineage(&e.name).count(), e))
+            .collect();
+        engines.sort_by(|(a_depth, a), (b_depth, b)| {
+            a.fallback.cmp(&b.fallback)
+                .then_with(|| b_depth.cmp(a_depth))
+                .then_with(|| b.custom.cmp(&a.custom))
+                .then_with(|| b.build_target.cmp(&a.build_target))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        engines.into_iter().map(|(_, e)| e)
+    }
+
+    /// Get the names of all engines that inherit the given engine, directly or indirectly.
+    ///
+    /// The engine itself is not included.
+    pub fn engine_descendants(&self, engine: &str) -> BTreeSet<String> {
+        self.engines
+            .keys()
+            .filter(|e| e.as_str() != engine && self.engine_inherits(e, engine))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Match `text` against `pattern`, where `*` in `pattern` matches any number of characters.
+fn wildcard_matches(pattern: &str, text: &str) -> bool {
+    let Some((prefix, rest)) = pattern.split_once('*') else {
+        return pattern == text
+    };
+    let Some(mut text) = text.strip_prefix(prefix) else {
+        return false
+    };
+
+    let mut parts = rest.split('*').peekable();
+    while let Some(part) = parts.next() {
+        if parts.peek().is_none() {
+            return text.ends_with(part)
+        }
+        match text.find(part) {
+            Some(i) => text = &text[i + part.len()..],
+            None => return false
+        }
+    }
+    true
+}
+
+impl SupportedEngines {
+    /// Get the names of all loaded engines that are supported.
+    ///
+    /// For [`SupportedEngines::SomeEngines`] and [`SupportedEngines::AllExcept`], listed engines
+    /// also cover any engines that inherit them. Finalized definitions are already expanded, but
+    /// this is useful for lists that were constructed by hand or for engines that were added after
+    /// finalizing.
+    pub fn expanded(&self, definitions: &ParsedDefinitions) -> BTreeSet<String> {
+        match self {
+            Self::AllEngines => definitions.engines.keys().cloned().collect(),
+            Self::SomeEngines(engines) => Self::expand_list(engines, definitions),
+            Self::AllExcept(engines) => {
+                let excluded = Self::expand_list(engines, definitions);
+                definitions.engines.keys().filter(|e| !excluded.contains(*e)).cloned().collect()
+            }
+        }
+    }
+
+    fn expand_list(engines: &BTreeSet<String>, definitions: &ParsedDefinitions) -> BTreeSet<String> {
+        let mut expanded = BTreeSet::new();
+        for e in engines {
+            expanded.insert(e.to_owned());
+            expanded.append(&mut definitions.engine_descendants(e));
+        }
+        expanded
+    }
+
+    /// Returns true if the engine, or any engine it inherits, is supported.
+    ///
+    /// Unlike [`SupportedEngines::supports_engine`], this walks the engine's inheritance chain, so
+    /// derived engines that were not known when this list was expanded are still matched.
+    pub fn supports_engine_or_ancestor(&self, engine: &Engine, definitions: &ParsedDefinitions) -> bool {
+        let listed = |engines: &BTreeSet<String>| {
+            let mut current = Some(engine.name.as_str());
+            let mut visited = BTreeSet::new();
+            while let Some(c) = current {
+                if engines.contains(c) {
+                    return true
+                }
+                if !visited.insert(c) {
+                    break
+                }
+                current = if c == engine.name {
+                    engine.inherits.as_deref()
+                }
+                else {
+                    definitions.engines.get(c).and_then(|e| e.inherits.as_deref())
+                };
+            }
+            false
+        };
+
+        match self {
+            Self::AllEngines => true,
+            Self::SomeEngines(engines) => listed(engines),
+            Self::AllExcept(engines) => !listed(engines)
+        }
+    }
+}
+
+/// Reason a cache file built for one engine may not load on another.
+///
+/// Created by [`Engine::can_load_cache_built_for`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CompatibilityIssue {
+    /// The cache file version differs.
+    CacheFileVersion {
+        /// Version the loading engine expects.
+        expected: u32,
+
+        /// Version the cache file was built with.
+        actual: u32
+    },
+
+    /// The cache file is for a different platform (e.g. Xbox versus PC).
+    CacheParser,
+
+    /// The cache file's header is laid out differently.
+    HeaderLayout,
+
+    /// The loading engine enforces a build string that the cache file does not have.
+    BuildMismatch {
+        /// Build string the loading engine enforces.
+        expected: String,
+
+        /// Build string the cache file was built with, if any.
+        actual: Option<String>
+    },
+
+    /// The cache file uses resource maps, but the loading engine does not support them.
+    ResourceMapsUnsupported,
+
+    /// The cache file uses externally indexed tags, but the loading engine does not support them.
+    ExternallyIndexedTagsUnsupported,
+
+    /// The cache file is compressed differently.
+    CompressionType,
+
+    /// The cache file may be larger than the loading engine allows.
+    ///
+    /// This is not fatal, since a given cache file may still be small enough.
+    LargerCacheFileLimit,
+
+    /// The cache file's tag data may be larger than the loading engine allows.
+    ///
+    /// This is not fatal, since a given cache file may still be small enough.
+    LargerTagSpace
+}
+
+impl CompatibilityIssue {
+    /// Returns true if this issue always prevents the cache file from loading.
+    pub const fn is_fatal(&self) -> bool {
+        !matches!(self, Self::LargerCacheFileLimit | Self::LargerTagSpace)
+    }
+}
+
+impl core::fmt::Display for CompatibilityIssue {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::CacheFileVersion { expected, actual } => fmt.write_fmt(format_args!("cache file version {actual} does not match {expected}")),
+            Self::CacheParser => fmt.write_str("cache file is for a different platform"),
+            Self::HeaderLayout => fmt.write_str("cache file header layout differs"),
+            Self::BuildMismatch { expected, actual: Some(actual) } => fmt.write_fmt(format_args!("build {actual} does not match enforced build {expected}")),
+            Self::BuildMismatch { expected, actual: None } => fmt.write_fmt(format_args!("cache file has no build, but build {expected} is enforced")),
+            Self::ResourceMapsUnsupported => fmt.write_str("resource maps are not supported"),
+            Self::ExternallyIndexedTagsUnsupported => fmt.write_str("externally indexed tags are not supported"),
+            Self::CompressionType => fmt.write_str("compression type differs"),
+            Self::LargerCacheFileLimit => fmt.write_str("cache file may exceed the maximum cache file size"),
+            Self::LargerTagSpace => fmt.write_str("tag data may exceed the maximum tag space")
+        }
+    }
+}
+
+/// Whether a cache file built for one engine can be loaded by another, and why.
+///
+/// Created by [`Engine::can_load_cache_built_for`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Compatibility {
+    /// Every issue found, fatal or not.
+    pub issues: Vec<CompatibilityIssue>
+}
+
+impl Compatibility {
+    /// Returns true if there are no fatal issues.
+    pub fn is_compatible(&self) -> bool {
+        !self.issues.iter().any(|i| i.is_fatal())
+    }
+}
+
+impl Engine {
+    /// Determine whether this engine can load a cache file built for `target`.
+    ///
+    /// This combines the cache file version, platform, header layout, build enforcement, resource
+    /// maps, and compression into one rule, so every tool agrees on what is compatible.
+    pub fn can_load_cache_built_for(&self, target: &Engine) -> Compatibility {
+        let mut issues = Vec::new();
+        if self.name == target.name {
+            return Compatibility { issues }
+        }
+
+        if self.cache_file_version != target.cache_file_version {
+            issues.push(CompatibilityIssue::CacheFileVersion { expected: self.cache_file_version, actual: target.cache_file_version });
+        }
+        if self.cache_parser != target.cache_parser {
+            issues.push(CompatibilityIssue::CacheParser);
+        }
+        if self.obfuscated_header_layout != target.obfuscated_header_layout {
+            issues.push(CompatibilityIssue::HeaderLayout);
+        }
+
+        if let Some(build) = self.build.as_ref().filter(|b| b.enforced) {
+            let actual = target.build.as_ref().map(|b| b.string.as_str());
+            let accepted = actual.is_some_and(|a| a == build.string || build.aliases.iter().any(|alias| wildcard_matches(alias, a)));
+            if !accepted {
+                issues.push(CompatibilityIssue::BuildMismatch { expected: build.string.to_owned(), actual: actual.map(|a| a.to_owned()) });
+            }
+        }
+
+        match (&self.resource_maps, &target.resource_maps) {
+            (None, Some(_)) => issues.push(CompatibilityIssue::ResourceMapsUnsupported),
+            (Some(ours), Some(theirs)) if theirs.externally_indexed_tags && !ours.externally_indexed_tags => {
+                issues.push(CompatibilityIssue::ExternallyIndexedTagsUnsupported)
+            },
+            _ => ()
+        }
+
+        if self.compression_type != target.compression_type {
+            issues.push(CompatibilityIssue::CompressionType);
+        }
+
+        let (ours, theirs) = (&self.max_cache_file_size, &target.max_cache_file_size);
+        if theirs.user_interface > ours.user_interface || theirs.singleplayer > ours.singleplayer || theirs.multiplayer > ours.multiplayer {
+            issues.push(CompatibilityIssue::LargerCacheFileLimit);
+        }
+        if target.max_tag_space > self.max_tag_space {
+            issues.push(CompatibilityIssue::LargerTagSpace);
+        }
+
+        Compatibility { issues }
+    }
+}
+
+#[cfg(all(test, feature = "engines-pc", feature = "engines-xbox"))]
+mod test {
+    use alloc::vec::Vec;
+    use crate::*;
+
+    #[test]
+    fn cache_compatibility() {
+        let definitions = load_all_definitions();
+        let pc = &definitions.engines["pc-retail"];
+        let xbox = &definitions.engines["xbox-us"];
+        let custom_edition = &definitions.engines["pc-custom"];
+
+        assert!(pc.can_load_cache_built_for(pc).issues.is_empty());
+        assert!(!pc.can_load_cache_built_for(xbox).is_compatible());
+        assert!(!xbox.can_load_cache_built_for(pc).is_compatible());
+        assert!(custom_edition
+            .can_load_cache_built_for(pc)
+            .issues
+            .iter()
+            .any(|i| matches!(i, CompatibilityIssue::CacheFileVersion { .. })));
+    }
+
+    #[test]
+    fn resolved_engine_uses_ancestors() {
+        let definitions = load_all_definitions();
+        let engine = definitions.resolved_engine("pc-custom").unwrap();
+        assert!(engine.inherits_from("pc"));
+        assert_eq!(engine.lineage().count(), definitions.engine_lineage("pc-custom").count());
+        assert!(engine.cache_parser == EngineCacheParser::PC);
+
+        let strings = definitions.struct_view("StringList").unwrap().field("strings").unwrap();
+        assert_eq!(engine.field_limit("StringList", "strings"), strings.limit_for_engine("pc-custom"));
+        assert_eq!(engine.field_count("StringList", "strings"), Some(1));
+        assert_eq!(engine.field_limit("StringList", "not a field"), None);
+
+        assert!(engine.supported_groups().any(|g| g.name == "scenario"));
+        assert!(!definitions.resolved_engine("xbox-us").unwrap().inherits_from("pc"));
+    }
+
+    #[test]
+    fn engines_by_specificity_orders_derived_first() {
+        let definitions = load_all_definitions();
+        let order: Vec<&str> = definitions.engines_by_specificity().map(|e| e.name.as_str()).collect();
+        assert_eq!(order.len(), definitions.engines.len());
+
+        let position = |name| order.iter().position(|e| *e == name).unwrap();
+        assert!(position("pc-custom") < position("pc"));
+        assert!(position("xbox-us") < position("xbox"));
+
+        let first_fallback = order.iter().position(|e| definitions.engines[*e].fallback).unwrap();
+        assert!(order[first_fallback..].iter().all(|e| definitions.engines[*e].fallback));
+    }
+
+    #[test]
+    fn engine_by_build_string() {
+        let definitions = load_all_definitions();
+        let find = |build, version| definitions.engine_by_build(build, version).map(|e| e.name.as_str());
+        assert_eq!(find("01.00.10.0621", 7), Some("pc-retail"));
+        assert_eq!(find("01.00.10.0621\0\0\0", 609), Some("pc-custom"));
+        assert_eq!(find("01.00.00.0564", 7), Some("pc-retail"));
+        assert_eq!(find("01.10.12.2276", 5), Some("xbox-us"));
+        assert_eq!(find("not a build", 5), Some("xbox"));
+        assert_eq!(find("not a build", 0xFFFF), None);
+
+        assert!(super::wildcard_matches("01.00.*.06*", "01.00.10.0621"));
+        assert!(!super::wildcard_matches("01.00.*.06*", "01.00.10.0521"));
+        assert!(super::wildcard_matches("*", ""));
+    }
+}
@@ -0,0 +1,235 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde_json::{Map, Value};
+
+use super::*;
+
+/// Changes layered on a base engine to describe a modded engine (e.g. a server or client mod with
+/// raised limits).
+///
+/// Modifiers can be loaded from JSON objects with the type `engine_modifier`, such as in a
+/// [`DefinitionPack`]:
+///
+/// ```json
+/// {
+///     "name": "my-mod:pc-custom",
+///     "type": "engine_modifier",
+///     "base": "pc-custom",
+///     "display_name": "Halo Custom Edition (my mod)",
+///     "max_script_nodes": 32767,
+///     "field_limits": { "Scenario": { "source files": 32 } },
+///     "enabled_groups": ["my_group"]
+/// }
+/// ```
+///
+/// Applying a modifier adds a derived, [`Engine::custom`] engine that inherits the base engine, so
+/// everything not changed by the modifier (including support for groups and fields) comes from it.
+/// The derived engine has no build and is never a [build target](Engine::build_target), so
+/// [`ParsedDefinitions::engine_by_build`] keeps returning the stock engine for cache files.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct EngineModifier {
+    /// Name of the derived engine, namespaced as `namespace:name` so mods do not collide with each
+    /// other or with stock engines.
+    pub name: String,
+
+    /// Name of the engine the modifier is layered on.
+    pub base: String,
+
+    /// Displayed name of the derived engine, or the base engine's if `None`.
+    pub display_name: Option<String>,
+
+    /// Replaces [`Engine::max_script_nodes`], if set.
+    pub max_script_nodes: Option<u64>,
+
+    /// Replaces [`Engine::max_tag_space`], if set.
+    pub max_tag_space: Option<u64>,
+
+    /// Limits for fields on the derived engine, keyed by struct and then field name.
+    pub field_limits: BTreeMap<String, BTreeMap<String, usize>>,
+
+    /// Groups to support on the derived engine even if the base engine does not support them.
+    pub enabled_groups: Vec<String>
+}
+
+impl EngineModifier {
+    /// Create a modifier that changes nothing.
+    pub fn new(name: &str, base: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            base: base.to_owned(),
+            display_name: None,
+            max_script_nodes: None,
+            max_tag_space: None,
+            field_limits: BTreeMap::new(),
+            enabled_groups: Vec::new()
+        }
+    }
+
+    /// Parse a modifier from its JSON object.
+    pub fn from_json(object: &Map<String, Value>) -> Result<Self, DefinitionError> {
+        let name = object.get("name").and_then(|n| n.as_str()).unwrap_or("<noname>");
+        let invalid = |key: &str, expected: &'static str| DefinitionError::InvalidValue { object: name.to_owned(), key: key.to_owned(), expected };
+        if !is_namespaced(name) {
+            return Err(invalid("name", "a namespaced name (e.g. `namespace:engine`)"))
+        }
+
+        let base = object.get("base")
+            .ok_or_else(|| DefinitionError::MissingKey { object: name.to_owned(), key: "base".to_owned() })?
+            .as_str()
+            .ok_or_else(|| invalid("base", "a string"))?;
+        let string = |key: &str| object.get(key).map(|v| v.as_str().map(str::to_owned).ok_or_else(|| invalid(key, "a string"))).transpose();
+        let number = |key: &str| object.get(key).map(|v| v.as_u64().ok_or_else(|| invalid(key, "an unsigned integer"))).transpose();
+
+        let mut modifier = Self::new(name, base);
+        modifier.display_name = string("display_name")?;
+        modifier.max_script_nodes = number("max_script_nodes")?;
+        modifier.max_tag_space = number("max_tag_space")?;
+
+        if let Some(limits) = object.get("field_limits") {
+            let expected = "an object of structs to objects of field limits";
+            for (struct_name, fields) in limits.as_object().ok_or_else(|| invalid("field_limits", expected))? {
+                let fields = fields.as_object().ok_or_else(|| invalid("field_limits", expected))?;
+                let mut limits = BTreeMap::new();
+                for (field, limit) in fields {
+                    let limit = limit.as_u64().and_then(|l| usize::try_from(l).ok()).ok_or_else(|| invalid("field_limits", expected))?;
+                    limits.insert(field.to_owned(), limit);
+                }
+                modifier.field_limits.insert(struct_name.to_owned(), limits);
+            }
+        }
+
+        if let Some(groups) = object.get("enabled_groups") {
+            modifier.enabled_groups = groups
+                .as_array()
+                .and_then(|g| g.iter().map(|g| g.as_str().map(str::to_owned)).collect())
+                .ok_or_else(|| invalid("enabled_groups", "an array of strings"))?;
+        }
+
+        Ok(modifier)
+    }
+}
+
+/// Returns true if the name is in the form `namespace:name`.
+fn is_namespaced(name: &str) -> bool {
+    name.split_once(':').is_some_and(|(namespace, name)| !namespace.is_empty() && !name.is_empty())
+}
+
+impl ParsedDefinitions {
+    /// Add the engine derived from a modifier.
+    ///
+    /// The base engine, structs, fields, and groups referenced by the modifier must already be
+    /// loaded. Nothing is changed if an error is returned. The definitions must be finalized
+    /// afterwards.
+    pub fn apply_engine_modifier(&mut self, modifier: &EngineModifier) -> Result<(), DefinitionError> {
+        let missing = |field: &str, reference: &str| DefinitionError::MissingReference {
+            object: modifier.name.to_owned(),
+            field: field.to_owned(),
+            reference: reference.to_owned()
+        };
+
+        if !is_namespaced(&modifier.name) {
+            return Err(DefinitionError::InvalidValue { object: modifier.name.to_owned(), key: "name".to_owned(), expected: "a namespaced name (e.g. `namespace:engine`)" })
+        }
+        if self.engines.contains_key(&modifier.name) {
+            return Err(DefinitionError::Duplicate { name: modifier.name.to_owned() })
+        }
+        let base = self.engines.get(&modifier.base).ok_or_else(|| missing("base", &modifier.base))?;
+        for (struct_name, fields) in &modifier.field_limits {
+            let Some(NamedObject::Struct(s)) = self.objects.get(struct_name) else {
+                return Err(missing("field_limits", struct_name))
+            };
+            if let Some(f) = fields.keys().find(|f| !s.fields.iter().any(|sf| sf.name == **f)) {
+                return Err(missing("field_limits", &alloc::format!("{struct_name}::{f}")))
+            }
+        }
+        if let Some(g) = modifier.enabled_groups.iter().find(|g| !self.groups.contains_key(*g)) {
+            return Err(missing("enabled_groups", g))
+        }
+
+        let mut engine = base.clone();
+        engine.name = modifier.name.to_owned();
+        engine.inherits = Some(modifier.base.to_owned());
+        engine.custom = true;
+        engine.cache_default = false;
+        engine.fallback = false;
+        engine.build_target = false;
+        engine.build = None;
+        engine.documentation = EngineDocumentation::default();
+        if let Some(d) = &modifier.display_name {
+            engine.display_name = d.to_owned();
+        }
+        if let Some(n) = modifier.max_script_nodes {
+            engine.max_script_nodes = n;
+        }
+        if let Some(n) = modifier.max_tag_space {
+            engine.max_tag_space = n;
+        }
+        self.engines.insert(modifier.name.to_owned(), engine);
+
+        for (struct_name, fields) in &modifier.field_limits {
+            let Some(NamedObject::Struct(s)) = self.objects.get_mut(struct_name) else {
+                unreachable!()
+            };
+            for f in s.fields.iter_mut() {
+                if let Some(limit) = fields.get(&f.name) {
+                    f.limit.get_or_insert_with(BTreeMap::new).insert(LimitType::Engine(modifier.name.to_owned()), *limit);
+                }
+            }
+        }
+
+        for g in &modifier.enabled_groups {
+            match &mut self.groups.get_mut(g).expect("groups were already checked").supported_engines {
+                SupportedEngines::AllEngines => (),
+                SupportedEngines::SomeEngines(e) => { e.insert(modifier.name.to_owned()); },
+                SupportedEngines::AllExcept(e) => { e.remove(&modifier.name); }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "engines-pc", feature = "engines-xbox", feature = "engines-mac", feature = "engines-mcc"))]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn modifiers_derive_engines() {
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_json(&get_all_definitions());
+        definitions.load_from_str("mod.json", r#"[{
+            "name": "test:pc-custom",
+            "type": "engine_modifier",
+            "base": "pc-custom",
+            "max_script_nodes": 32767,
+            "field_limits": { "Scenario": { "source files": 64 } }
+        }]"#).unwrap();
+        definitions.try_finalize().unwrap();
+
+        let engine = definitions.resolved_engine("test:pc-custom").unwrap();
+        assert!(engine.custom && engine.inherits_from("pc-custom"));
+        assert_eq!(engine.max_script_nodes, 32767);
+        assert_eq!(engine.max_tag_space, definitions.engines["pc-custom"].max_tag_space);
+        assert_eq!(engine.field_limit("Scenario", "source files"), Some(64));
+        assert_eq!(engine.field_limit("Scenario", "scripts"), definitions.resolved_engine("pc-custom").unwrap().field_limit("Scenario", "scripts"));
+        assert!(engine.supports_group(&definitions.groups["weapon"]));
+        assert!(!engine.build_target && engine.build.is_none());
+        assert_eq!(definitions.engine_by_build("01.00.10.0621", 609).map(|e| e.name.as_str()), Some("pc-custom"));
+        assert_eq!(definitions.engine_by_build("01.00.10.0621", 7).map(|e| e.name.as_str()), Some("pc-retail"));
+
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_json(&get_all_definitions());
+        assert!(matches!(
+            definitions.load_from_str("mod.json", r#"[{ "name": "unnamespaced", "type": "engine_modifier", "base": "pc-custom" }]"#),
+            Err(DefinitionError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            definitions.load_from_str("mod.json", r#"[{ "name": "test:nothing", "type": "engine_modifier", "base": "nothing" }]"#),
+            Err(DefinitionError::MissingReference { .. })
+        ));
+    }
+}
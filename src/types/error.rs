@@ -0,0 +1,217 @@
+use alloc::string::String;
+
+use super::*;
+
+/// Describes why definitions could not be loaded or finalized.
+///
+/// Returned by [`ParsedDefinitions::try_load_from_json`] and [`ParsedDefinitions::try_finalize`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DefinitionError {
+    /// A required key is missing.
+    MissingKey {
+        /// Name of the object (or `<noname>` if it has none).
+        object: String,
+
+        /// Name of the key.
+        key: String
+    },
+
+    /// A key has a value of the wrong type or an invalid value.
+    InvalidValue {
+        /// Name of the object.
+        object: String,
+
+        /// Name of the key.
+        key: String,
+
+        /// What was expected (e.g. "a string").
+        expected: &'static str
+    },
+
+    /// An object or field has a type that does not exist.
+    UnknownType {
+        /// Name of the object.
+        object: String,
+
+        /// The unknown type.
+        object_type: String
+    },
+
+    /// More than one definition of the same kind has the same name.
+    Duplicate {
+        /// The duplicated name.
+        name: String
+    },
+
+    /// A definition references an object, group, or engine that does not exist.
+    MissingReference {
+        /// Name of the object containing the reference.
+        object: String,
+
+        /// Name of the field or key containing the reference.
+        field: String,
+
+        /// The name that could not be found.
+        reference: String
+    },
+
+    /// A struct's fields do not add up to its size.
+    BadSize {
+        /// Name of the struct.
+        object: String,
+
+        /// Size of the struct, as defined.
+        expected: usize,
+
+        /// Combined size of the struct's fields.
+        actual: usize
+    },
+
+    /// A definition file is not valid JSON or is not an array of objects.
+    InvalidJson {
+        /// Path of the file.
+        file: String,
+
+        /// Description of the problem.
+        message: String
+    },
+
+    /// A plugin could not be imported.
+    ///
+    /// Returned by [`ParsedDefinitions::load_plugin_xml`].
+    InvalidPlugin {
+        /// Path of the file.
+        file: String,
+
+        /// Description of the problem.
+        message: String
+    },
+
+    /// Binary definitions could not be loaded.
+    ///
+    /// Returned by `ParsedDefinitions::from_binary` (with the `binary` feature).
+    InvalidBinary {
+        /// Description of the problem.
+        message: String
+    },
+
+    /// Two fields or options of the same object have different names that map to the same Rust
+    /// name (e.g. `hit-points` and `hit points`).
+    NameCollision {
+        /// Name of the struct, bitfield, or enum.
+        object: String,
+
+        /// Name of the first field or option.
+        first: String,
+
+        /// Name of the second field or option.
+        second: String,
+
+        /// The Rust name they both map to.
+        rust_name: String
+    },
+
+    /// A definition is invalid in a way not covered by the other variants (e.g. a struct's default
+    /// value has the wrong number of values).
+    Invalid {
+        /// Name of the object.
+        object: String,
+
+        /// Description of the problem.
+        message: String
+    },
+
+    /// A copy of an enum or bitfield does not match its original.
+    OptionDrift(OptionDrift),
+
+    /// A struct contains itself through inline struct fields.
+    ReferenceCycle(ReferenceCycle)
+}
+
+impl core::fmt::Display for DefinitionError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MissingKey { object, key } => fmt.write_fmt(format_args!("no such field `{object}::{key}`")),
+            Self::InvalidValue { object, key, expected } => fmt.write_fmt(format_args!("expected {object}::{key} to be {expected}")),
+            Self::UnknownType { object, object_type } => fmt.write_fmt(format_args!("{object} has unknown type {object_type}")),
+            Self::Duplicate { name } => fmt.write_fmt(format_args!("duplicate definition {name} detected")),
+            Self::MissingReference { object, field, reference } => fmt.write_fmt(format_args!("{object}::{field} references {reference}, which does not exist")),
+            Self::BadSize { object, expected, actual } => fmt.write_fmt(format_args!("Size for {object} is incorrect (expected {expected}, got {actual} instead)")),
+            Self::InvalidJson { message, .. } | Self::InvalidPlugin { message, .. } | Self::InvalidBinary { message } | Self::Invalid { message, .. } => fmt.write_str(message),
+            Self::NameCollision { object, first, second, rust_name } => {
+                fmt.write_fmt(format_args!("{object} has {first} and {second}, which both map to the Rust name {rust_name}"))
+            },
+            Self::OptionDrift(drift) => drift.fmt(fmt),
+            Self::ReferenceCycle(cycle) => cycle.fmt(fmt)
+        }
+    }
+}
+
+impl core::error::Error for DefinitionError {}
+
+#[cfg(test)]
+mod test {
+    use alloc::borrow::ToOwned;
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    use serde_json::{json, Map, Value};
+
+    use crate::*;
+
+    fn objects(value: Value) -> Vec<Map<String, Value>> {
+        value.as_array().unwrap().iter().map(|o| o.as_object().unwrap().to_owned()).collect()
+    }
+
+    #[test]
+    fn errors_instead_of_panicking() {
+        let mut definitions = ParsedDefinitions::default();
+        let unknown = objects(json!([{ "name": "Thing", "type": "widget", "__json_file": "thing.json" }]));
+        assert_eq!(
+            definitions.try_load_from_json(&unknown),
+            Err(DefinitionError::UnknownType { object: "Thing".to_owned(), object_type: "widget".to_owned() })
+        );
+
+        let bad_reference = objects(json!([{
+            "name": "Thing",
+            "type": "struct",
+            "size": 4,
+            "fields": [{ "name": "other", "type": "OtherThing" }],
+            "__json_file": "thing.json"
+        }]));
+        definitions.try_load_from_json(&bad_reference).unwrap();
+        assert!(matches!(definitions.try_finalize(), Err(DefinitionError::MissingReference { reference, .. }) if reference == "OtherThing"));
+
+        let mut definitions = ParsedDefinitions::default();
+        let bad_size = objects(json!([{
+            "name": "Thing",
+            "type": "struct",
+            "size": 8,
+            "fields": [{ "name": "value", "type": "uint32" }],
+            "__json_file": "thing.json"
+        }]));
+        definitions.try_load_from_json(&bad_size).unwrap();
+        assert_eq!(definitions.try_finalize(), Err(DefinitionError::BadSize { object: "Thing".to_owned(), expected: 8, actual: 4 }));
+    }
+
+    #[test]
+    fn malformed_values_are_errors() {
+        let field = |size: usize, field: &str| alloc::format!(r#"[{{ "name": "Thing", "type": "struct", "size": {size}, "fields": [{{ "name": "value", {field} }}] }}]"#);
+        let cases = [
+            (field(12, r#""type": "Reflexive", "struct": "Thing", "non_null": 1"#), "non_null"),
+            (field(4, r#""type": "float", "minimum": 2.0, "maximum": 1.0"#), "minimum"),
+            (field(4, r#""type": "float", "widget": "color""#), "widget"),
+            (field(4, r#""type": "float", "step": -1"#), "step"),
+            (r#"[{ "name": "Thing", "type": "struct", "size": 4, "fields": [{ "name": "valu\u00e9", "type": "float" }] }]"#.to_owned(), "non-ASCII"),
+            (r#"[{ "name": "Thing", "type": "struct", "size": 8, "fields": [{ "name": "hit points", "type": "float" }, { "name": "hit-points", "type": "float" }] }]"#.to_owned(), "hit_points"),
+            (r#"[{ "name": "Thing", "type": "enum", "options": ["first", 2] }]"#.to_owned(), "not a string or object")
+        ];
+        for (json, expected) in cases {
+            let mut definitions = ParsedDefinitions::default();
+            let result = definitions.load_from_str("thing.json", &json).and_then(|_| definitions.try_finalize());
+            let error = result.expect_err(&json).to_string();
+            assert!(error.contains(expected), "expected an error containing {expected} for {json}, got {error}");
+        }
+    }
+}
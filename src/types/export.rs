@@ -0,0 +1,199 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use super::*;
+
+/// Describes the format of a table created by an exporter.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum TableFormat {
+    /// Comma-separated values, quoted as needed (RFC 4180).
+    Csv,
+
+    /// Tab-separated values. Tabs and line breaks in values are replaced with spaces.
+    Tsv
+}
+
+impl TableFormat {
+    fn write_row<S: AsRef<str>>(self, output: &mut String, cells: &[S]) {
+        for (i, cell) in cells.iter().enumerate() {
+            if i > 0 {
+                output.push(match self { Self::Csv => ',', Self::Tsv => '\t' });
+            }
+            let cell = cell.as_ref();
+            match self {
+                Self::Csv if cell.contains([',', '"', '\n', '\r']) => {
+                    output.push('"');
+                    output.push_str(&cell.replace('"', "\"\""));
+                    output.push('"');
+                },
+                Self::Csv => output.push_str(cell),
+                Self::Tsv => output.extend(cell.chars().map(|c| if matches!(c, '\t' | '\n' | '\r') { ' ' } else { c }))
+            }
+        }
+        output.push_str("\r\n");
+    }
+}
+
+impl ParsedDefinitions {
+    /// Export every field reachable from every tag group as a table, one row per field.
+    ///
+    /// Columns are `group`, `path`, `offset`, `size`, `type`, `count`, `flags`, `engines`,
+    /// `limits`, `description`, and `research`. The path is the names of the fields leading to the
+    /// field, joined with `/`, and the offset is relative to the start of the block the field is in
+    /// (the group's base struct or a reflexive element).
+    ///
+    /// The research column holds the confidence, observed values, and notes of any [`Research`]
+    /// annotation, separated by `; `. Editor sections are omitted, as is padding without research,
+    /// and padding with research is listed as `(padding)` with the type `pad`.
+    pub fn export_field_table(&self, format: TableFormat) -> String {
+        let mut output = String::new();
+        format.write_row(&mut output, &["group", "path", "offset", "size", "type", "count", "flags", "engines", "limits", "description", "research"]);
+
+        for group in self.groups.values() {
+            let Some(base) = self.struct_view(&group.struct_name) else {
+                continue
+            };
+            write_fields(format, &mut output, &group.name, base, 0, &mut Vec::new(), &mut Vec::new());
+        }
+
+        output
+    }
+
+    /// Export every enum option and bitfield flag as a table, one row per option or flag.
+    ///
+    /// Columns are `kind` (`enum` or `bitfield`), `owner` (the enum or bitfield), `name`, `value`,
+    /// and `engines`. Enum values are the option's value, and bitfield values are the flag's mask,
+    /// both in decimal.
+    pub fn export_value_table(&self, format: TableFormat) -> String {
+        let mut output = String::new();
+        format.write_row(&mut output, &["kind", "owner", "name", "value", "engines"]);
+
+        for object in self.objects.values() {
+            let (kind, owner, fields) = match object {
+                NamedObject::Enum(e) => ("enum", &e.name, &e.options),
+                NamedObject::Bitfield(b) => ("bitfield", &b.name, &b.fields),
+                NamedObject::Struct(_) => continue
+            };
+            for f in fields {
+                format.write_row(&mut output, &[kind, owner, &f.name, &format!("{}", f.value), &format_engines(&f.flags.supported_engines)]);
+            }
+        }
+
+        output
+    }
+}
+
+pub(crate) fn format_engines(engines: &SupportedEngines) -> String {
+    match engines {
+        SupportedEngines::AllEngines => String::from("all"),
+        SupportedEngines::SomeEngines(e) => e.iter().map(|e| e.as_str()).collect::<Vec<_>>().join(" "),
+        SupportedEngines::AllExcept(e) => format!("all except {}", e.iter().map(|e| e.as_str()).collect::<Vec<_>>().join(" "))
+    }
+}
+
+fn write_fields<'a>(format: TableFormat, output: &mut String, group: &str, s: StructView<'a>, base_offset: usize, path: &mut Vec<&'a str>, structs: &mut Vec<&'a str>) {
+    if structs.contains(&s.get().name.as_str()) {
+        return
+    }
+    structs.push(s.get().name.as_str());
+
+    for f in s.fields() {
+        let type_name = match &f.get().field_type {
+            StructFieldType::Object(object) => object.type_name(),
+            StructFieldType::Padding(_) if f.flags.research.is_some() => "pad",
+            _ => continue
+        };
+
+        path.push(if f.padding_kind.is_some() { "(padding)" } else { f.get().name.as_str() });
+        let offset = base_offset + f.relative_offset;
+
+        let count = match f.count {
+            FieldCount::One => String::from("1"),
+            FieldCount::Bounds => String::from("bounds"),
+            FieldCount::Array(n) => format!("{n}")
+        };
+        let flags = FlagType::ALL.iter().filter(|t| f.flags.has_flag(**t)).map(|t| t.json_name()).collect::<Vec<_>>().join(" ");
+        let engines = format_engines(&f.flags.supported_engines);
+        let mut limits = String::new();
+        for (limit_type, limit) in f.limit.iter().flatten() {
+            if !limits.is_empty() {
+                limits.push(' ');
+            }
+            let _ = match limit_type {
+                LimitType::Default => write!(limits, "default={limit}"),
+                LimitType::Editor => write!(limits, "editor={limit}"),
+                LimitType::Engine(e) => write!(limits, "{e}={limit}"),
+                LimitType::Profile(p) => write!(limits, "profile:{p}={limit}")
+            };
+        }
+        let description = f.flags.description.as_deref().or(f.flags.comment.as_deref()).unwrap_or_default();
+        let mut research = String::new();
+        if let Some(r) = &f.flags.research {
+            research.push_str(r.confidence.name());
+            if !r.observed_values.is_empty() {
+                let _ = write!(research, "; observed {}", r.observed_values.join(", "));
+            }
+            if let Some(notes) = &r.notes {
+                let _ = write!(research, "; {notes}");
+            }
+        }
+
+        format.write_row(output, &[
+            group,
+            &path.join("/"),
+            &format!("{offset}"),
+            &format!("{}", f.size()),
+            type_name,
+            &count,
+            &flags,
+            &engines,
+            &limits,
+            description,
+            &research
+        ]);
+
+        match f.resolved_type() {
+            ResolvedFieldType::Struct(inner) => write_fields(format, output, group, inner, offset, path, structs),
+            ResolvedFieldType::Reflexive(inner) => write_fields(format, output, group, inner, 0, path, structs),
+            _ => ()
+        }
+
+        path.pop();
+    }
+
+    structs.pop();
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn field_table_quotes_values() {
+        let definitions = load_all_definitions();
+        let csv = definitions.export_field_table(TableFormat::Csv);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("group,path,offset,size,type,count,flags,engines,limits,description,research"));
+        assert!(csv.contains("\r\nwind,velocity,0,8,float,bounds,,all,,,\r\n"));
+        assert!(csv.contains(",pad,1,,all,,,\"speculative; observed 2.0, 1.0\"\r\n"), "padding research should be exported");
+
+        let tsv = definitions.export_field_table(TableFormat::Tsv);
+        assert!(csv.lines().count() > tsv.lines().count(), "multiline comments should be quoted in CSV and flattened in TSV");
+        assert!(tsv.lines().all(|l| l.split('\t').count() == 11));
+    }
+
+    #[test]
+    fn value_table_covers_every_option() {
+        let definitions = load_all_definitions();
+        let table = definitions.export_value_table(TableFormat::Tsv);
+        let expected: usize = definitions.objects.values().map(|o| match o {
+            NamedObject::Enum(e) => e.options.len(),
+            NamedObject::Bitfield(b) => b.fields.len(),
+            NamedObject::Struct(_) => 0
+        }).sum();
+        assert_eq!(table.lines().count(), expected + 1);
+    }
+}
@@ -0,0 +1,65 @@
+use super::*;
+
+impl TagGroup {
+    /// Iterate through every extension that resolves to this group, starting with
+    /// [`TagGroup::extension`] and followed by [`TagGroup::extension_aliases`].
+    pub fn extensions(&self) -> impl Iterator<Item = &str> {
+        core::iter::once(self.extension.as_str()).chain(self.extension_aliases.iter().map(|e| e.as_str()))
+    }
+}
+
+impl ParsedDefinitions {
+    /// Get the group a tag file extension belongs to, including aliases.
+    ///
+    /// The extension is given without the leading period and is matched case-insensitively.
+    pub fn group_for_extension(&self, extension: &str) -> Option<&TagGroup> {
+        // Extensions are usually the group name, so check that first.
+        if let Some(g) = self.groups.get(extension).filter(|g| g.extension == extension) {
+            return Some(g)
+        }
+        self.groups.values().find(|g| g.extensions().any(|e| e.eq_ignore_ascii_case(extension)))
+    }
+
+    /// Get the group a tag path belongs to from its extension.
+    ///
+    /// Both `/` and `\` are accepted as path separators. Returns `None` if the path has no
+    /// extension or the extension is unknown.
+    ///
+    /// ```
+    /// let definitions = ringhopper_definitions::load_all_definitions();
+    /// let group = definitions.group_for_path("levels\\test\\bloodgulch\\bloodgulch.scenario").unwrap();
+    /// assert_eq!(group.name, "scenario");
+    /// ```
+    pub fn group_for_path(&self, path: &str) -> Option<&TagGroup> {
+        let file_name = path.rsplit(['/', '\\']).next()?;
+        let (_, extension) = file_name.rsplit_once('.')?;
+        self.group_for_extension(extension)
+    }
+
+    /// Get the canonical extension for tag files of a group.
+    pub fn extension_for_group(&self, group: &str) -> Option<&str> {
+        self.groups.get(group).map(|g| g.extension.as_str())
+    }
+}
+
+#[cfg(all(test, feature = "groups-ui"))]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn required_tags_resolve_to_groups() {
+        let definitions = load_all_definitions();
+        for engine in definitions.engines.values() {
+            let r = &engine.required_tags;
+            for path in r.all.iter().chain(&r.user_interface).chain(&r.singleplayer).chain(&r.multiplayer) {
+                let group = definitions.group_for_path(path).unwrap_or_else(|| panic!("{path} (required by {}) has no group", engine.name));
+                assert!(path.ends_with(&alloc::format!(".{}", group.extension)));
+            }
+        }
+
+        assert_eq!(definitions.group_for_path("globals\\globals.globals").unwrap().name, "globals");
+        assert_eq!(definitions.group_for_path("UI\\SHELL\\MAIN_MENU\\MP_MAP_LIST.UNICODE_STRING_LIST").unwrap().name, "unicode_string_list");
+        assert_eq!(definitions.extension_for_group("scenario_structure_bsp"), Some("scenario_structure_bsp"));
+        assert!(definitions.group_for_path("sound\\sfx\\ui\\cursor.wav").is_none());
+    }
+}
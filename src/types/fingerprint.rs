@@ -0,0 +1,108 @@
+use alloc::collections::BTreeSet;
+use alloc::string::ToString;
+
+use serde_json::{Map, Value};
+
+use super::*;
+
+impl ParsedDefinitions {
+    /// Get a hash of every definition, for detecting when data baked from the definitions (e.g.
+    /// generated code or caches) is stale.
+    ///
+    /// The hash is computed from the finalized definitions, so it is the same on every platform
+    /// and run, and it only changes if a definition (or a registered [`CustomFieldObject`])
+    /// changes. Which file a definition is in does not affect the hash.
+    ///
+    /// The hash is not cryptographic, and it may change between versions of this crate.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv1a::new();
+        self.for_each_definition_json(|_, definition| hasher.write_json(definition));
+        for custom in self.custom_objects.values() {
+            hasher.write_custom_object(custom);
+        }
+        hasher.0
+    }
+
+    /// Get a hash of a struct and everything it uses, or `None` if it is not a struct.
+    ///
+    /// This includes every struct, enum, bitfield, and [`CustomFieldObject`] reachable through
+    /// its fields (including reflexives), so it changes whenever the struct's layout or any
+    /// metadata of its fields change. See [`ParsedDefinitions::fingerprint`].
+    pub fn struct_fingerprint(&self, name: &str) -> Option<u64> {
+        self.get_struct(name)?;
+
+        let mut objects = BTreeSet::new();
+        self.collect_reachable_objects(name, &mut objects);
+
+        let mut hasher = Fnv1a::new();
+        let mut custom_objects = BTreeSet::new();
+        for object in objects.iter().filter_map(|o| self.objects.get(o)) {
+            hasher.write_json(self.object_to_json(object));
+            if let NamedObject::Struct(s) = object {
+                custom_objects.extend(s.fields.iter().filter_map(|f| match &f.field_type {
+                    StructFieldType::Object(FieldObject::Custom(c)) => self.custom_objects.get(c),
+                    _ => None
+                }).map(|c| &c.name));
+            }
+        }
+        for custom in custom_objects {
+            hasher.write_custom_object(&self.custom_objects[custom]);
+        }
+        Some(hasher.0)
+    }
+}
+
+/// 64-bit FNV-1a, which is simple and does not depend on the platform.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    const fn new() -> Self {
+        Self(0xCBF29CE484222325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 ^= *b as u64;
+            self.0 = self.0.wrapping_mul(0x100000001B3);
+        }
+    }
+
+    fn write_json(&mut self, definition: Map<String, Value>) {
+        self.write(Value::Object(definition).to_string().as_bytes());
+        self.write(&[0]);
+    }
+
+    fn write_custom_object(&mut self, custom: &CustomFieldObject) {
+        self.write(custom.name.as_bytes());
+        self.write(&[0]);
+        for value in [custom.size, custom.composite_count, custom.is_const as usize] {
+            self.write(&(value as u64).to_le_bytes());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn fingerprints_change_with_definitions() {
+        let load = |size: usize| {
+            let mut definitions = ParsedDefinitions::default();
+            definitions.load_from_str("test.json", &alloc::format!(r#"[
+                {{ "name": "TestChild", "type": "struct", "fields": [{{ "type": "pad", "size": {size} }}], "size": {size} }},
+                {{ "name": "TestParent", "type": "struct", "fields": [{{ "name": "children", "type": "Reflexive", "struct": "TestChild" }}], "size": 12 }},
+                {{ "name": "TestOther", "type": "struct", "fields": [{{ "name": "value", "type": "uint32" }}], "size": 4 }}
+            ]"#)).unwrap();
+            definitions.finalize_without_engines();
+            definitions
+        };
+        let (a, b) = (load(4), load(8));
+        assert_eq!(a.fingerprint(), load(4).fingerprint());
+        assert_ne!(a.fingerprint(), b.fingerprint());
+        assert_ne!(a.struct_fingerprint("TestParent"), b.struct_fingerprint("TestParent"));
+        assert_eq!(a.struct_fingerprint("TestOther"), b.struct_fingerprint("TestOther"));
+        assert_eq!(a.struct_fingerprint("NotAStruct"), None);
+        assert!(load_all_definitions().struct_fingerprint("Scenario").is_some());
+    }
+}
@@ -0,0 +1,219 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+use super::*;
+
+/// Approximate heap usage of definitions, created by [`ParsedDefinitions::memory_footprint`].
+///
+/// Each entry is the number of bytes used by an object, group, or engine, including its own size
+/// and everything it owns (string bytes and vector capacities). Map and allocator overhead is not
+/// counted, so these are lower bounds.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MemoryFootprint<'a> {
+    /// Bytes used by each struct, enum, and bitfield.
+    pub objects: BTreeMap<&'a str, usize>,
+
+    /// Bytes used by each group.
+    pub groups: BTreeMap<&'a str, usize>,
+
+    /// Bytes used by each engine.
+    pub engines: BTreeMap<&'a str, usize>,
+
+    /// Bytes used by everything else (limit profiles, custom objects, and lookup indices).
+    pub other: usize
+}
+
+impl MemoryFootprint<'_> {
+    /// Get the total number of bytes used.
+    pub fn total(&self) -> usize {
+        self.objects.values().chain(self.groups.values()).chain(self.engines.values()).sum::<usize>() + self.other
+    }
+}
+
+impl ParsedDefinitions {
+    /// Get the approximate heap usage of each object, group, and engine.
+    pub fn memory_footprint(&self) -> MemoryFootprint<'_> {
+        fn entries<V: HeapSize>(map: &BTreeMap<String, V>) -> BTreeMap<&str, usize> {
+            map.iter().map(|(k, v)| (k.as_str(), size_of::<(String, V)>() + k.heap_size() + v.heap_size())).collect()
+        }
+
+        let Self { objects, groups, engines, limit_profiles, group_fourccs, unknown_engine_policy: _, custom_objects, referencers } = self;
+        MemoryFootprint {
+            objects: entries(objects),
+            groups: entries(groups),
+            engines: entries(engines),
+            other: size_of::<Self>() + limit_profiles.heap_size() + group_fourccs.heap_size() + custom_objects.heap_size() + referencers.heap_size()
+        }
+    }
+}
+
+/// Number of bytes owned by a value on the heap, not including the value itself.
+trait HeapSize {
+    fn heap_size(&self) -> usize;
+}
+
+impl HeapSize for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Option<T> {
+    fn heap_size(&self) -> usize {
+        self.as_ref().map_or(0, T::heap_size)
+    }
+}
+
+impl<T: HeapSize> HeapSize for BTreeSet<T> {
+    fn heap_size(&self) -> usize {
+        self.iter().map(|v| size_of::<T>() + v.heap_size()).sum()
+    }
+}
+
+impl<K: HeapSize, V: HeapSize> HeapSize for BTreeMap<K, V> {
+    fn heap_size(&self) -> usize {
+        self.iter().map(|(k, v)| size_of::<(K, V)>() + k.heap_size() + v.heap_size()).sum()
+    }
+}
+
+impl<A: HeapSize, B: HeapSize> HeapSize for (A, B) {
+    fn heap_size(&self) -> usize {
+        self.0.heap_size() + self.1.heap_size()
+    }
+}
+
+/// Types that never own anything on the heap.
+macro_rules! heap_size_none {
+    ($($t:ty),+) => {
+        $(impl HeapSize for $t {
+            fn heap_size(&self) -> usize {
+                0
+            }
+        })+
+    };
+}
+
+heap_size_none!(
+    u8, u16, u32, u64, i64, usize, f32, f64, bool, RangeInclusive<u8>, ScenarioType, PaddingKind, ResearchConfidence, Nullability,
+    EngineCacheParser, EngineCompressionType, MemoryPool, CacheRewrite, Determinism, ColorSpace, TagPathCharacterSet,
+    EnumInterpretation, HUDScaling, GlobalEnumRole, EngineTagPaths
+);
+
+/// Structs own everything their fields own. Every field must be listed.
+macro_rules! heap_size_struct {
+    ($($name:ident { $($field:ident),+ })+) => {
+        $(impl HeapSize for $name {
+            fn heap_size(&self) -> usize {
+                let Self { $($field),+ } = self;
+                0 $(+ $field.heap_size())+
+            }
+        })+
+    };
+}
+
+/// Enums own everything the data of their current variant owns.
+macro_rules! heap_size_enum {
+    ($($name:ident { $($variant:ident $({ $($field:ident),+ })? $(($value:ident))?),+ })+) => {
+        $(impl HeapSize for $name {
+            fn heap_size(&self) -> usize {
+                match self {
+                    $(Self::$variant $({ $($field),+ })? $(($value))? => 0 $($(+ $field.heap_size())+)? $(+ $value.heap_size())?),+
+                }
+            }
+        })+
+    };
+}
+
+heap_size_struct! {
+    LimitProfile { name, definition_file, description }
+    TagGroup {
+        name, definition_file, name_rust_enum, struct_name, supergroup, supported_engines, version, fourcc_binary, see_also,
+        extension, extension_aliases, scenario_types, documentation, short_code, sort_order
+    }
+    GroupDocumentation { overview, usage_notes }
+    Struct {
+        name, definition_file, fields, is_const, contains_tag_references, contains_data, contains_reflexives, max_depth, flags,
+        size, packed, defined_memory_pool
+    }
+    StructField {
+        name, name_rust_enum, name_rust_field, field_type, default_value, count, engine_counts, nullability, minimum, maximum,
+        limit, flags, relative_offset, padding_kind, widget, step, precision, memory_pool, byte_budget, normalized, color_space
+    }
+    Bitfield { name, definition_file, width, fields, exclusive_groups, implications, copy_of, flags }
+    Enum { name, definition_file, options, interpretation, global_role, copy_of, flags }
+    Field { name, name_rust_enum, name_rust_field, flags, value }
+    Flags {
+        cache_only, non_cached, uneditable_in_editor, hidden_in_editor, exclude, little_endian_in_tags, shifted_by_one,
+        supported_engines, comment, developer_note, description, introduced_in, removed_in, examples, see_also, research,
+        cache_rewrite, determinism
+    }
+    Research { confidence, observed_values, links, notes }
+    Engine {
+        name, definition_file, display_name, version, build, inherits, build_target, fallback, custom, cache_file_version,
+        cache_default, external_bsps, external_models, max_script_nodes, script_node_table, max_tag_space, compressed_models, data_alignment,
+        compressed_data_alignment, obfuscated_header_layout, bitmap_options, resource_maps, cache_parser, max_cache_file_size,
+        base_memory_address, required_tags, compression_type, grenades, minimum_weapons, tag_paths, hud_scaling, documentation
+    }
+    EngineDocumentation { description, release_date, distribution, quirks }
+    EngineGrenades { singleplayer, multiplayer, user_interface }
+    EngineSupportedResourceMaps { externally_indexed_tags }
+    EngineCacheFileSize { user_interface, singleplayer, multiplayer }
+    EngineRequiredTags { all, user_interface, singleplayer, multiplayer }
+    BaseMemoryAddress { address, inferred }
+    Build { string, aliases, enforced }
+    EngineBitmapOptions { swizzled, texture_dimension_must_modulo_block_size, cubemap_faces_stored_separately, alignment }
+    EngineScriptNodeTable { table_struct, node_struct, name, signature, first_salt }
+    CustomFieldObject { name, size, composite_count, is_const }
+}
+
+heap_size_enum! {
+    NamedObject { Struct(s), Enum(e), Bitfield(b) }
+    SeeAlso { Group(group), Field { object, field } }
+    LimitType { Engine(engine), Default, Editor, Profile(profile) }
+    StructFieldType { Object(object), Padding(size), EditorSection { heading, body } }
+    FieldCount { One, Bounds, Array(count) }
+    StaticValue { Float(f), Uint(u), Int(i), String(s) }
+    SupportedEngines { AllEngines, SomeEngines(engines), AllExcept(engines) }
+    EditorWidget { Slider, ColorPicker, AngleDial, PathPicker { allowed_groups } }
+}
+
+impl HeapSize for FieldObject {
+    fn heap_size(&self) -> usize {
+        match self {
+            Self::NamedObject(name) | Self::Reflexive(name) | Self::Custom(name) => name.heap_size(),
+            Self::TagReference { allowed_groups } => allowed_groups.heap_size(),
+            Self::ReflexiveIndex { struct_name, reflexive_name_display, reflexive_name_rust } => {
+                struct_name.heap_size() + reflexive_name_display.heap_size() + reflexive_name_rust.heap_size()
+            },
+            _ => 0
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn footprints_count_owned_data() {
+        let definitions = load_all_definitions();
+        let footprint = definitions.memory_footprint();
+        assert_eq!(footprint.objects.len(), definitions.objects.len());
+        assert_eq!(footprint.engines.len(), definitions.engines.len());
+        assert!(footprint.objects["Scenario"] > footprint.objects["Weapon"]);
+        assert!(footprint.groups["scenario"] > size_of::<TagGroup>());
+
+        let mut trimmed = ParsedDefinitions::load_builtin();
+        trimmed.retain_groups(&["weapon"]);
+        assert!(trimmed.memory_footprint().total() < footprint.total());
+    }
+}
@@ -0,0 +1,106 @@
+use alloc::string::String;
+
+use super::*;
+
+/// Convert a fourcc to its 4-character form (e.g. `0x77696E64` to `wind`).
+///
+/// Bytes that are not printable ASCII are written as `\xNN` escapes, backslashes are written as
+/// `\\`, and trailing spaces are preserved, so the result can always be converted back with
+/// [`fourcc_from_str`].
+pub fn fourcc_to_string(fourcc: u32) -> String {
+    let mut result = String::with_capacity(4);
+    for b in fourcc.to_be_bytes() {
+        if b == b'\\' {
+            result.push_str("\\\\");
+        }
+        else if b.is_ascii_graphic() || b == b' ' {
+            result.push(b as char);
+        }
+        else {
+            result.push_str(&alloc::format!("\\x{b:02X}"));
+        }
+    }
+    result
+}
+
+/// Convert a 4-character fourcc (e.g. `wind`) to its integer form.
+///
+/// This accepts the output of [`fourcc_to_string`]. Returns `None` if the string is not exactly
+/// four bytes once escapes are decoded, or if it has a backslash that is not part of a `\\` or
+/// `\xNN` escape (with exactly two hex digits).
+pub fn fourcc_from_str(fourcc: &str) -> Option<u32> {
+    let mut bytes = [0u8; 4];
+    let mut count = 0;
+    let mut input = fourcc.as_bytes();
+    while !input.is_empty() {
+        let byte = match input {
+            [b'\\', b'x', hi, lo, ..] if hi.is_ascii_hexdigit() && lo.is_ascii_hexdigit() => {
+                let hex = core::str::from_utf8(&[*hi, *lo]).ok().and_then(|h| u8::from_str_radix(h, 16).ok())?;
+                input = &input[4..];
+                hex
+            },
+            [b'\\', b'\\', ..] => {
+                input = &input[2..];
+                b'\\'
+            },
+            [b'\\', ..] => return None,
+            [b, ..] if b.is_ascii() => {
+                input = &input[1..];
+                *b
+            },
+            _ => return None
+        };
+        *bytes.get_mut(count)? = byte;
+        count += 1;
+    }
+    (count == 4).then(|| u32::from_be_bytes(bytes))
+}
+
+impl TagGroup {
+    /// Get the group's fourcc in its 4-character form.
+    ///
+    /// See [`fourcc_to_string`].
+    pub fn fourcc_string(&self) -> String {
+        fourcc_to_string(self.fourcc_binary)
+    }
+}
+
+impl ParsedDefinitions {
+    /// Look up a group by its fourcc (e.g. one read out of a tag file or cache file).
+    pub fn group_by_fourcc(&self, fourcc: u32) -> Option<&TagGroup> {
+        self.group_fourccs
+            .get(&fourcc)
+            .and_then(|name| self.groups.get(name))
+            .filter(|g| g.fourcc_binary == fourcc)
+            .or_else(|| self.groups.values().find(|g| g.fourcc_binary == fourcc))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn fourcc_round_trip_and_lookup() {
+        assert_eq!(fourcc_to_string(0x77696E64), "wind");
+        assert_eq!(fourcc_from_str("wind"), Some(0x77696E64));
+        assert_eq!(fourcc_to_string(0x6F626A00), "obj\\x00");
+        assert_eq!(fourcc_from_str("obj\\x00"), Some(0x6F626A00));
+        assert_eq!(fourcc_from_str("obj "), Some(0x6F626A20));
+        assert_eq!(fourcc_from_str("wind2"), None);
+        assert_eq!(fourcc_from_str("win"), None);
+        assert_eq!(fourcc_to_string(0x5C783431), "\\\\x41");
+        assert_eq!(fourcc_from_str("\\\\x41"), Some(0x5C783431));
+        assert_eq!(fourcc_from_str("ab\\x41"), None);
+        assert_eq!(fourcc_from_str("abc\\x+F"), None);
+        assert_eq!(fourcc_from_str("abc\\"), None);
+
+        let definitions = load_all_definitions();
+        for group in definitions.groups.values() {
+            assert_eq!(definitions.group_by_fourcc(group.fourcc_binary).unwrap().name, group.name);
+            assert_eq!(fourcc_from_str(&group.fourcc_string()), Some(group.fourcc_binary));
+        }
+        assert_eq!(definitions.group_by_fourcc(fourcc_from_str("actr").unwrap()).unwrap().name, "actor");
+        assert!(definitions.group_by_fourcc(0).is_none());
+    }
+}
@@ -0,0 +1,96 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+
+use super::*;
+
+/// Role of an enum that is shared vocabulary across tag groups and tools (e.g. material types).
+///
+/// Set with `global` in the JSON. Each role is used by at most one enum, and the options of these
+/// enums are never reordered or removed, so their indices can be relied on (e.g. stored in files
+/// or sent between tools). New options may only be appended.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum GlobalEnumRole {
+    /// Materials of surfaces and objects, used for effects and damage modifiers.
+    MaterialType,
+
+    /// Categories of damage, used for damage modifiers and resistances.
+    DamageCategory
+}
+
+impl GlobalEnumRole {
+    /// Get the role from its name in the JSON definitions (e.g. `material type`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "material type" => Some(Self::MaterialType),
+            "damage category" => Some(Self::DamageCategory),
+            _ => None
+        }
+    }
+
+    /// Get the name of the role as used in the JSON definitions.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::MaterialType => "material type",
+            Self::DamageCategory => "damage category"
+        }
+    }
+}
+
+impl ParsedDefinitions {
+    /// Get the enum with the given global role, if it is loaded.
+    pub fn global_enum(&self, role: GlobalEnumRole) -> Option<&Enum> {
+        self.global_enums().find(|e| e.global_role == Some(role))
+    }
+
+    /// Get every enum with a global role.
+    pub fn global_enums(&self) -> impl Iterator<Item = &Enum> {
+        self.objects.values().filter_map(|o| match o {
+            NamedObject::Enum(e) if e.global_role.is_some() => Some(e),
+            _ => None
+        })
+    }
+
+    pub(crate) fn check_unique_global_enums(&self) -> Result<(), DefinitionError> {
+        let mut roles: BTreeMap<GlobalEnumRole, &str> = BTreeMap::new();
+        for e in self.global_enums() {
+            let role = e.global_role.unwrap();
+            if let Some(other) = roles.insert(role, &e.name) {
+                return Err(DefinitionError::Invalid {
+                    object: e.name.clone(),
+                    message: format!("enums {other} and {} both have the global role {}", e.name, role.name())
+                })
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    // Indices of global enums are relied on, so only append to these lists.
+    const MATERIAL_TYPES: &[&str] = &[
+        "dirt", "sand", "stone", "snow", "wood", "metal hollow", "metal thin", "metal thick", "rubber", "glass", "force field",
+        "grunt", "hunter armor", "hunter skin", "elite", "jackal", "jackal energy shield", "engineer skin", "engineer force field",
+        "flood combat form", "flood carrier form", "cyborg armor", "cyborg energy shield", "human armor", "human skin", "sentinel",
+        "monitor", "plastic", "water", "leaves", "elite energy shield", "ice", "hunter shield"
+    ];
+    const DAMAGE_CATEGORIES: &[&str] = &[
+        "none", "falling", "bullet", "grenade", "high explosive", "sniper", "melee", "flame", "mounted weapon", "vehicle", "plasma",
+        "needle", "shotgun"
+    ];
+
+    #[test]
+    fn global_enums_keep_their_order() {
+        let definitions = load_all_definitions();
+        for (role, expected) in [(GlobalEnumRole::MaterialType, MATERIAL_TYPES), (GlobalEnumRole::DamageCategory, DAMAGE_CATEGORIES)] {
+            let e = definitions.global_enum(role).unwrap_or_else(|| panic!("no {} enum", role.name()));
+            let names: alloc::vec::Vec<&str> = e.options.iter().map(|o| o.name.as_str()).collect();
+            assert!(names.len() >= expected.len() && &names[..expected.len()] == expected, "{} was reordered", e.name);
+        }
+        assert_eq!(definitions.global_enums().count(), 2);
+    }
+}
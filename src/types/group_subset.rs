@@ -0,0 +1,186 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde_json::{Map, Value};
+
+use super::*;
+
+impl ParsedDefinitions {
+    /// Load and finalize only the given groups from parsed JSON objects, skipping every struct,
+    /// enum, and bitfield they do not depend on.
+    ///
+    /// Only the work done after parsing (loading and finalizing) is skipped for unused objects, so if
+    /// `objects` still has to be parsed from JSON, this is not much faster than
+    /// [`ParsedDefinitions::load_from_json`] and [`ParsedDefinitions::finalize`]. Supergroups of the
+    /// given groups are loaded too, as are all engines and limit profiles.
+    ///
+    /// [`ParsedDefinitions::groups`] will only contain the loaded groups, but tag references are
+    /// validated and expanded against every group, so they may allow groups that were not loaded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a group does not exist or the definitions are malformed.
+    pub fn load_groups_from_json(&mut self, objects: &[Map<String, Value>], groups: &[&str]) {
+        let name_of = |o: &Map<String, Value>| o.get("name").and_then(|n| n.as_str()).unwrap_or_default().to_owned();
+        let kind_of = |o: &Map<String, Value>| o.get("type").and_then(|t| t.as_str()).unwrap_or_default().to_owned();
+
+        let all_groups: BTreeMap<String, &Map<String, Value>> = objects
+            .iter()
+            .filter(|o| kind_of(o) == "group")
+            .map(|o| (name_of(o), o))
+            .collect();
+        let named_objects: BTreeMap<String, &Map<String, Value>> = objects
+            .iter()
+            .filter(|o| !matches!(kind_of(o).as_str(), "group" | "engine" | "limit_profile"))
+            .map(|o| (name_of(o), o))
+            .collect();
+
+        let mut loaded_groups: BTreeSet<String> = BTreeSet::new();
+        for group in groups {
+            let mut next = Some(*group);
+            while let Some(g) = next.filter(|g| loaded_groups.insert(String::from(*g))) {
+                let group = all_groups.get(g).unwrap_or_else(|| panic!("no such group {g}"));
+                next = group.get("supergroup").and_then(|s| s.as_str());
+            }
+        }
+
+        let mut dependencies = BTreeSet::new();
+        for group in &loaded_groups {
+            collect_json_dependencies(all_groups[group], &named_objects, &mut dependencies);
+        }
+
+        let subset: Vec<Map<String, Value>> = objects
+            .iter()
+            .filter(|o| match kind_of(o).as_str() {
+                "group" | "engine" | "limit_profile" => true,
+                _ => dependencies.contains(&name_of(o))
+            })
+            .cloned()
+            .collect();
+
+        self.load_from_json(&subset);
+        self.finalize_and_validate(true, true).unwrap_or_else(|e| panic!("{e}"));
+        self.resolve_parent_class_references();
+        self.find_const_structs();
+        self.find_struct_contents();
+
+        self.groups.retain(|g, _| loaded_groups.contains(g));
+        self.group_fourccs = self.groups.values().map(|g| (g.fourcc_binary, g.name.to_owned())).collect();
+    }
+
+    /// Drop every group except the given groups and their supergroups, along with every struct,
+    /// enum, and bitfield they do not depend on.
+    ///
+    /// This is useful for shrinking definitions to only what a tool needs. Objects referenced by
+    /// `copy_of` and `see_also` are kept so the definitions stay valid, and engines and limit
+    /// profiles are always kept. Like [`ParsedDefinitions::load_groups_from_json`], tag references
+    /// may still allow groups that were dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a group does not exist.
+    pub fn retain_groups(&mut self, groups: &[&str]) {
+        let mut kept_groups: BTreeSet<String> = BTreeSet::new();
+        for group in groups {
+            let mut next = Some(*group);
+            while let Some(g) = next.filter(|g| kept_groups.insert(String::from(*g))) {
+                let group = self.groups.get(g).unwrap_or_else(|| panic!("no such group {g}"));
+                next = group.supergroup.as_deref();
+            }
+        }
+
+        let mut kept_objects = BTreeSet::new();
+        let mut pending: Vec<String> = kept_groups.iter().map(|g| self.groups[g].struct_name.to_owned()).collect();
+        while let Some(next) = pending.pop() {
+            let mut reachable = BTreeSet::new();
+            self.collect_reachable_objects(&next, &mut reachable);
+            for name in reachable {
+                if !kept_objects.insert(name.to_owned()) {
+                    continue
+                }
+                let Some(object) = self.objects.get(&name) else {
+                    continue
+                };
+                pending.extend(object.copy_of().map(str::to_owned));
+                let flags: Vec<&Flags> = match object {
+                    NamedObject::Struct(s) => s.fields.iter().map(|f| &f.flags).chain([&s.flags]).collect(),
+                    NamedObject::Enum(e) => e.options.iter().map(|o| &o.flags).chain([&e.flags]).collect(),
+                    NamedObject::Bitfield(b) => b.fields.iter().map(|f| &f.flags).chain([&b.flags]).collect()
+                };
+                for see_also in flags.iter().flat_map(|f| &f.see_also) {
+                    if let SeeAlso::Field { object, .. } = see_also {
+                        pending.push(object.to_owned());
+                    }
+                }
+            }
+        }
+
+        self.groups.retain(|g, _| kept_groups.contains(g));
+        self.objects.retain(|o, _| kept_objects.contains(o));
+        self.group_fourccs = self.groups.values().map(|g| (g.fourcc_binary, g.name.to_owned())).collect();
+        self.index_referencers();
+    }
+}
+
+/// Recursively collect the names of all objects referenced in a JSON object (by type, reflexive,
+/// index, inheritance, copy_of, or see_also).
+fn collect_json_dependencies(object: &Map<String, Value>, objects: &BTreeMap<String, &Map<String, Value>>, into: &mut BTreeSet<String>) {
+    let add = |name: &str, into: &mut BTreeSet<String>| {
+        if let Some(o) = objects.get(name).filter(|_| into.insert(name.to_owned())) {
+            collect_json_dependencies(o, objects, into);
+        }
+    };
+
+    for (key, value) in object {
+        match (key.as_str(), value) {
+            ("type" | "struct" | "inherits" | "copy_of", Value::String(name)) => add(name, into),
+            ("see_also", Value::Array(see_also)) => {
+                for (name, _) in see_also.iter().filter_map(|s| s.as_str()?.split_once('.')) {
+                    add(name, into);
+                }
+            },
+            (_, Value::Object(o)) => collect_json_dependencies(o, objects, into),
+            (_, Value::Array(a)) => {
+                for o in a.iter().filter_map(|o| o.as_object()) {
+                    collect_json_dependencies(o, objects, into);
+                }
+            },
+            _ => ()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn loading_some_groups_loads_their_dependencies() {
+        let all = load_all_definitions();
+        let definitions = load_definitions_for_groups(&["weapon", "bitmap"]);
+        assert_eq!(definitions.groups.keys().collect::<alloc::vec::Vec<_>>(), ["bitmap", "item", "object", "weapon"]);
+        assert!(definitions.get_struct("Scenario").is_none());
+        assert!(definitions.group_by_fourcc(all.groups["scenario"].fourcc_binary).is_none());
+
+        for name in ["Weapon", "WeaponTrigger", "Object", "Bitmap"] {
+            let NamedObject::Struct(s) = &definitions.objects[name] else { unreachable!() };
+            let NamedObject::Struct(expected) = &all.objects[name] else { unreachable!() };
+            for (f, e) in s.fields.iter().zip(&expected.fields) {
+                assert_eq!(f.relative_offset, e.relative_offset, "{name}::{} differs", f.name);
+                assert!(f.field_type == e.field_type, "{name}::{} differs", f.name);
+            }
+        }
+    }
+
+    #[test]
+    fn retaining_groups_drops_unreachable_objects() {
+        let mut definitions = ParsedDefinitions::load_builtin();
+        definitions.retain_groups(&["weapon"]);
+        assert_eq!(definitions.groups.keys().collect::<alloc::vec::Vec<_>>(), ["item", "object", "weapon"]);
+        assert!(definitions.objects.len() < load_all_definitions().objects.len());
+        assert!(definitions.get_struct("WeaponTrigger").is_some() && definitions.get_struct("Scenario").is_none());
+        assert!(definitions.group_by_fourcc(load_all_definitions().groups["bitmap"].fourcc_binary).is_none());
+        assert!(definitions.validate().problems.iter().all(|p| matches!(p.kind, ValidationProblemKind::MissingReference { .. }) && p.field.is_some()));
+    }
+}
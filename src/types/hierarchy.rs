@@ -0,0 +1,53 @@
+use super::*;
+
+impl ParsedDefinitions {
+    /// Iterate through every group that inherits the given group, directly or through other
+    /// supergroups, in name order.
+    ///
+    /// A group is not considered a subgroup of itself.
+    pub fn subgroups_of<'a>(&'a self, group: &'a str) -> impl Iterator<Item = &'a TagGroup> + 'a {
+        self.groups.values().filter(move |g| g.supergroups(self).any(|s| s.name == group))
+    }
+}
+
+impl TagGroup {
+    /// Iterate through the group's supergroup, its supergroup's supergroup, and so on.
+    ///
+    /// Supergroups that do not exist in `definitions` end the chain.
+    pub fn supergroups<'a>(&self, definitions: &'a ParsedDefinitions) -> impl Iterator<Item = &'a TagGroup> + 'a {
+        let first = self.supergroup.as_ref().and_then(|s| definitions.groups.get(s));
+        core::iter::successors(first, |g| g.supergroup.as_ref().and_then(|s| definitions.groups.get(s)))
+            .take(definitions.groups.len())
+    }
+
+    /// Returns true if this group inherits `other`, directly or through other supergroups.
+    ///
+    /// A group is not considered a subgroup of itself.
+    pub fn is_subgroup_of(&self, other: &TagGroup, definitions: &ParsedDefinitions) -> bool {
+        self.supergroups(definitions).any(|g| g.name == other.name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use crate::*;
+
+    #[test]
+    fn supergroups_are_inherited() {
+        let definitions = load_all_definitions();
+        let vehicle = &definitions.groups["vehicle"];
+        let chain: Vec<&str> = vehicle.supergroups(definitions).map(|g| g.name.as_str()).collect();
+        assert_eq!(chain, ["unit", "object"]);
+
+        assert!(vehicle.is_subgroup_of(&definitions.groups["object"], definitions));
+        assert!(!vehicle.is_subgroup_of(vehicle, definitions));
+        assert!(!definitions.groups["object"].is_subgroup_of(vehicle, definitions));
+
+        let subgroups: Vec<&str> = definitions.subgroups_of("object").map(|g| g.name.as_str()).collect();
+        assert!(subgroups.contains(&"vehicle") && subgroups.contains(&"weapon") && subgroups.contains(&"unit"));
+        assert!(!subgroups.contains(&"object") && !subgroups.contains(&"bitmap"));
+        assert_eq!(definitions.subgroups_of("bitmap").count(), 0);
+    }
+}
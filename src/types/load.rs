@@ -0,0 +1,70 @@
+use super::*;
+
+#[cfg(feature = "std")]
+use std::io;
+
+impl ParsedDefinitions {
+    /// Load the definitions in a JSON definition file.
+    ///
+    /// `file` is the path of the file, relative to the root of the definitions (e.g.
+    /// `tag/my_group.json`), and it is used for [`Struct::definition_file`] and friends. The file
+    /// uses the same format as the built-in definitions.
+    ///
+    /// This can be called more than once (e.g. once for the built-in definitions, then once per
+    /// additional file) before calling [`ParsedDefinitions::try_finalize`]. However, engines can
+    /// only inherit engines loaded by the same call.
+    pub fn load_from_str(&mut self, file: &str, json: &str) -> Result<(), DefinitionError> {
+        self.load_from_slice(file, json.as_bytes())
+    }
+
+    /// Load the definitions in a JSON definition file from raw bytes.
+    ///
+    /// See [`ParsedDefinitions::load_from_str`].
+    pub fn load_from_slice(&mut self, file: &str, json: &[u8]) -> Result<(), DefinitionError> {
+        self.try_load_from_json(&try_parse_definition_file(file, json)?)
+    }
+
+    /// Load the definitions in a JSON definition file from a reader.
+    ///
+    /// See [`ParsedDefinitions::load_from_str`].
+    #[cfg(feature = "std")]
+    pub fn load_from_reader<R: io::Read>(&mut self, file: &str, mut reader: R) -> io::Result<()> {
+        let mut json = alloc::vec::Vec::new();
+        reader.read_to_end(&mut json)?;
+        self.load_from_slice(file, &json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Load every `.json` file in a directory (recursively), such as a copy of this crate's `json`
+    /// directory or a directory of additional definitions.
+    ///
+    /// Paths are relative to `directory`. All files are loaded in one call, so engines in the
+    /// directory can inherit each other.
+    #[cfg(feature = "std")]
+    pub fn load_from_directory<P: AsRef<std::path::Path>>(&mut self, directory: P) -> io::Result<()> {
+        let mut objects = alloc::vec::Vec::new();
+        for (file, data) in read_definition_directory(directory.as_ref())? {
+            objects.extend(try_parse_definition_file(&file, &data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?);
+        }
+        self.try_load_from_json(&objects).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+#[cfg(all(test, feature = "engines-pc", feature = "engines-xbox", feature = "engines-mac", feature = "engines-mcc"))]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn load_extra_definitions_on_top_of_builtin() {
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_json(&get_all_definitions());
+        definitions.load_from_str("tag/test.json", r#"[
+            { "name": "TestExtension", "type": "struct", "fields": [{ "name": "value", "type": "float" }], "size": 4 },
+            { "name": "test_extension", "type": "group", "struct": "TestExtension", "version": 1, "fourcc_binary": 1952805748 }
+        ]"#).unwrap();
+        definitions.try_finalize().unwrap();
+        assert_eq!(definitions.groups["test_extension"].definition_file, "tag/test.json");
+
+        let mut definitions = ParsedDefinitions::default();
+        assert!(matches!(definitions.load_from_str("tag/bad.json", "{"), Err(DefinitionError::InvalidJson { .. })));
+    }
+}
@@ -0,0 +1,129 @@
+use alloc::borrow::ToOwned;
+use serde_json::Value;
+
+use super::*;
+
+/// Describes where data lives at runtime.
+///
+/// Memory budget analyzers can use this to attribute bytes to the right pool.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum MemoryPool {
+    /// Tag data, which is limited by [`Engine::max_tag_space`].
+    #[default]
+    TagData,
+
+    /// Vertex and index buffers (e.g. model data on engines with [`Engine::external_models`]).
+    VertexPool,
+
+    /// Sound sample data, which is streamed into the sound cache.
+    SoundCache,
+
+    /// Bitmap pixel data, which is streamed into the texture cache.
+    TextureCache
+}
+
+impl MemoryPool {
+    /// Get the memory pool from its name in the JSON definitions (e.g. `vertex pool`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "tag data" => Some(Self::TagData),
+            "vertex pool" => Some(Self::VertexPool),
+            "sound cache" => Some(Self::SoundCache),
+            "texture cache" => Some(Self::TextureCache),
+            _ => None
+        }
+    }
+
+    /// Get the name of the memory pool as used in the JSON definitions.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::TagData => "tag data",
+            Self::VertexPool => "vertex pool",
+            Self::SoundCache => "sound cache",
+            Self::TextureCache => "texture cache"
+        }
+    }
+
+    /// Get the pool that data defined to be in this pool is in on the given engine.
+    ///
+    /// The vertex pool only exists on engines with [`Engine::external_models`], so model data is in
+    /// tag data on other engines.
+    pub fn on_engine(self, engine: &Engine) -> Self {
+        match self {
+            Self::VertexPool if !engine.external_models => Self::TagData,
+            pool => pool
+        }
+    }
+
+    pub(crate) fn load_from_json(object_name: &str, value: &Value) -> Result<Self, DefinitionError> {
+        value.as_str().and_then(Self::from_name).ok_or_else(|| DefinitionError::InvalidValue {
+            object: object_name.to_owned(),
+            key: "memory_pool".to_owned(),
+            expected: "a known memory pool"
+        })
+    }
+}
+
+impl Struct {
+    /// Get where instances of the struct live at runtime on the given engine when read through a
+    /// reflexive.
+    ///
+    /// This is [`Struct::defined_memory_pool`] as it applies to the engine (see
+    /// [`MemoryPool::on_engine`]).
+    pub fn memory_pool(&self, engine: &Engine) -> MemoryPool {
+        self.defined_memory_pool.on_engine(engine)
+    }
+}
+
+impl FieldView<'_> {
+    /// Get where the field's data lives at runtime on the given engine.
+    ///
+    /// This is [`StructField::memory_pool`] if set. Otherwise, reflexives use their struct's
+    /// [`Struct::memory_pool`], and anything else uses the pool of the struct containing the field.
+    pub fn memory_pool(&self, engine: &Engine) -> MemoryPool {
+        let pool = match (self.memory_pool, self.resolved_type()) {
+            (Some(pool), _) => pool,
+            (None, ResolvedFieldType::Reflexive(s)) => s.get().defined_memory_pool,
+            (None, _) => self.parent().defined_memory_pool
+        };
+        pool.on_engine(engine)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    #[cfg(feature = "engines-pc")]
+    fn memory_pools_are_attributed() {
+        let definitions = load_all_definitions();
+        let external = definitions.engines.values().find(|e| e.external_models).unwrap();
+        let internal = definitions.engines.values().find(|e| !e.external_models).unwrap();
+
+        let vertex = definitions.get_struct("ModelVertexUncompressed").unwrap();
+        assert_eq!(vertex.defined_memory_pool, MemoryPool::VertexPool);
+        assert_eq!(vertex.memory_pool(external), MemoryPool::VertexPool);
+        assert_eq!(vertex.memory_pool(internal), MemoryPool::TagData);
+
+        let vertices = definitions.struct_view("ModelGeometryPart").unwrap().field("uncompressed vertices").unwrap();
+        assert_eq!(vertices.memory_pool(external), MemoryPool::VertexPool);
+        assert_eq!(vertices.memory_pool(internal), MemoryPool::TagData);
+
+        let samples = definitions.struct_view("SoundPermutation").unwrap().field("samples").unwrap();
+        assert_eq!(samples.memory_pool(internal), MemoryPool::SoundCache);
+
+        let permutations = definitions.struct_view("SoundPitchRange").unwrap().field("permutations").unwrap();
+        assert_eq!(permutations.memory_pool(external), MemoryPool::TagData);
+    }
+
+    #[test]
+    fn memory_pool_names_round_trip() {
+
+        for pool in [MemoryPool::TagData, MemoryPool::VertexPool, MemoryPool::SoundCache, MemoryPool::TextureCache] {
+            assert_eq!(MemoryPool::from_name(pool.name()), Some(pool));
+        }
+    }
+}
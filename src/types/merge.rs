@@ -0,0 +1,265 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::*;
+
+/// How [`ParsedDefinitions::merge`] resolves conflicts between the definitions and an overlay.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MergePolicy {
+    /// Keep the existing definition or limit.
+    KeepExisting,
+
+    /// Replace the existing definition or limit with the overlay's.
+    PreferOverlay,
+
+    /// Do not merge anything if there are any conflicts.
+    Reject
+}
+
+/// Describes a conflict found by [`ParsedDefinitions::merge`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MergeConflict {
+    /// A tag group is defined by both.
+    Group(String),
+
+    /// An engine is defined by both.
+    Engine(String),
+
+    /// A limit profile is defined by both.
+    LimitProfile(String),
+
+    /// An object is defined by both, and the overlay's definition does not extend the existing one.
+    Object(String),
+
+    /// A field has a different limit in the overlay.
+    Limit {
+        /// Name of the struct.
+        object: String,
+
+        /// Name of the field.
+        field: String,
+
+        /// The limit that differs.
+        limit: LimitType
+    }
+}
+
+/// Describes what [`ParsedDefinitions::merge`] changed.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct MergeReport {
+    /// Names of definitions that were added by the overlay.
+    pub added: Vec<String>,
+
+    /// Names of objects that were extended by the overlay (e.g. new fields or enum options).
+    pub extended: Vec<String>,
+
+    /// Every conflict, resolved according to the [`MergePolicy`].
+    pub conflicts: Vec<MergeConflict>
+}
+
+impl ParsedDefinitions {
+    /// Merge an overlay, such as definitions for a community engine, into these definitions.
+    ///
+    /// New definitions are added. Existing objects can be extended without conflict:
+    ///
+    /// - Structs can be redefined with the same size as long as every existing field is kept, in
+    ///   the same order and with the same type (e.g. to name fields that were padding). Limits of
+    ///   fields are merged, so an overlay can also override limits for its engines.
+    /// - Enums can have options appended.
+    /// - Bitfields can have fields added to unused bits.
+    ///
+    /// Anything else is a [`MergeConflict`] and resolved according to `policy`. If `policy` is
+    /// [`MergePolicy::Reject`] and there are any conflicts, nothing is merged and the conflicts are
+    /// returned.
+    ///
+    /// Neither needs to be finalized beforehand, but the result must be finalized afterwards.
+    pub fn merge(&mut self, overlay: ParsedDefinitions, policy: MergePolicy) -> Result<MergeReport, Vec<MergeConflict>> {
+        let mut report = MergeReport::default();
+        let keep_overlay = policy == MergePolicy::PreferOverlay;
+
+        fn merge_map<V>(existing: &BTreeMap<String, V>, overlay: BTreeMap<String, V>, keep_overlay: bool, report: &mut MergeReport, conflict: fn(String) -> MergeConflict) -> BTreeMap<String, V> {
+            let mut merged = BTreeMap::new();
+            for (name, value) in overlay {
+                if existing.contains_key(&name) {
+                    report.conflicts.push(conflict(name.to_owned()));
+                    if !keep_overlay {
+                        continue
+                    }
+                }
+                else {
+                    report.added.push(name.to_owned());
+                }
+                merged.insert(name, value);
+            }
+            merged
+        }
+
+        let groups = merge_map(&self.groups, overlay.groups, keep_overlay, &mut report, MergeConflict::Group);
+        let engines = merge_map(&self.engines, overlay.engines, keep_overlay, &mut report, MergeConflict::Engine);
+        let limit_profiles = merge_map(&self.limit_profiles, overlay.limit_profiles, keep_overlay, &mut report, MergeConflict::LimitProfile);
+
+        let mut objects = BTreeMap::new();
+        for (name, object) in overlay.objects {
+            let Some(existing) = self.objects.get(&name) else {
+                report.added.push(name.to_owned());
+                objects.insert(name, object);
+                continue
+            };
+
+            match merge_object(existing, &object, keep_overlay, &mut report.conflicts) {
+                Some(merged) => {
+                    report.extended.push(name.to_owned());
+                    objects.insert(name, merged);
+                },
+                None => {
+                    report.conflicts.push(MergeConflict::Object(name.to_owned()));
+                    if keep_overlay {
+                        objects.insert(name, object);
+                    }
+                }
+            }
+        }
+
+        if policy == MergePolicy::Reject && !report.conflicts.is_empty() {
+            return Err(report.conflicts)
+        }
+
+        self.group_fourccs.extend(groups.values().map(|g| (g.fourcc_binary, g.name.to_owned())));
+        self.groups.extend(groups);
+        self.engines.extend(engines);
+        self.limit_profiles.extend(limit_profiles);
+        self.objects.extend(objects);
+        for (name, custom) in overlay.custom_objects {
+            self.custom_objects.entry(name).or_insert(custom);
+        }
+        Ok(report)
+    }
+}
+
+/// Merge an overlay object into an existing one, or return `None` if the overlay does not extend it.
+fn merge_object(existing: &NamedObject, overlay: &NamedObject, keep_overlay: bool, conflicts: &mut Vec<MergeConflict>) -> Option<NamedObject> {
+    match (existing, overlay) {
+        (NamedObject::Struct(e), NamedObject::Struct(o)) => {
+            if e.size != o.size {
+                return None
+            }
+
+            // Every existing field must be kept, in order.
+            let mut overlay_fields = o.fields.iter().filter(|f| matches!(f.field_type, StructFieldType::Object(_)));
+            for f in e.fields.iter().filter(|f| matches!(f.field_type, StructFieldType::Object(_))) {
+                let StructFieldType::Object(existing_type) = &f.field_type else { unreachable!() };
+                let found = overlay_fields.any(|of| of.name == f.name && matches!(&of.field_type, StructFieldType::Object(t) if t.type_name() == existing_type.type_name()));
+                if !found {
+                    return None
+                }
+            }
+
+            let mut merged = o.to_owned();
+            for f in &mut merged.fields {
+                let Some(existing_limits) = e.field(&f.name).and_then(|ef| ef.limit.as_ref()) else {
+                    continue
+                };
+                let mut limits = existing_limits.to_owned();
+                for (limit, value) in f.limit.iter().flatten() {
+                    match limits.get(limit) {
+                        // The editor limit is the highest limit, so it is raised rather than replaced.
+                        Some(v) if *limit == LimitType::Editor => {
+                            limits.insert(LimitType::Editor, (*v).max(*value));
+                        },
+                        Some(v) if v == value => (),
+                        Some(_) => {
+                            conflicts.push(MergeConflict::Limit { object: e.name.to_owned(), field: f.name.to_owned(), limit: limit.to_owned() });
+                            if keep_overlay {
+                                limits.insert(limit.to_owned(), *value);
+                            }
+                        },
+                        None => {
+                            limits.insert(limit.to_owned(), *value);
+                        }
+                    }
+                }
+                f.limit = Some(limits);
+            }
+            Some(NamedObject::Struct(merged))
+        },
+        (NamedObject::Enum(e), NamedObject::Enum(o)) => {
+            if e.options.iter().zip(o.options.iter()).any(|(a, b)| a.name != b.name) {
+                return None
+            }
+            let mut merged = e.to_owned();
+            merged.options.extend(o.options.iter().skip(e.options.len()).cloned());
+            Some(NamedObject::Enum(merged))
+        },
+        (NamedObject::Bitfield(e), NamedObject::Bitfield(o)) => {
+            if e.width != o.width {
+                return None
+            }
+            let mut merged = e.to_owned();
+            for f in &o.fields {
+                match e.fields.iter().find(|ef| ef.value == f.value) {
+                    Some(ef) if ef.name == f.name => (),
+                    Some(_) => return None,
+                    None => merged.fields.push(f.to_owned())
+                }
+            }
+            Some(NamedObject::Bitfield(merged))
+        },
+        _ => None
+    }
+}
+
+#[cfg(all(test, feature = "engines-pc", feature = "engines-xbox", feature = "engines-mac", feature = "engines-mcc"))]
+mod test {
+    use alloc::borrow::ToOwned;
+
+    use crate::*;
+
+    fn overlay(json: &str) -> ParsedDefinitions {
+        let mut overlay = ParsedDefinitions::default();
+        overlay.load_from_str("overlay.json", json).unwrap();
+        overlay
+    }
+
+    #[test]
+    fn merge_extends_and_detects_conflicts() {
+        let limit_overlay = r#"[
+            {
+                "name": "StringList",
+                "fields": [{ "name": "strings", "type": "Reflexive", "struct": "StringListString", "limit": { "default": 800, "pc-custom": 4096 } }],
+                "type": "struct",
+                "size": 12
+            }
+        ]"#;
+
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_json(&get_all_definitions());
+        let report = definitions.merge(overlay(limit_overlay), MergePolicy::Reject).unwrap();
+        assert!(report.conflicts.is_empty());
+        assert_eq!(report.extended, ["StringList"]);
+        definitions.finalize();
+        let strings = definitions.struct_view("StringList").unwrap().field("strings").unwrap();
+        assert_eq!(strings.limit_for_engine("pc-retail"), Some(800));
+        assert_eq!(strings.limit_for_engine("pc-custom"), Some(4096));
+
+        let conflicting_overlay = r#"[
+            {
+                "name": "StringList",
+                "fields": [{ "name": "strings", "type": "Reflexive", "struct": "StringListString", "limit": 1600 }],
+                "type": "struct",
+                "size": 12
+            },
+            { "name": "ActorType", "options": ["jackal"], "type": "enum" }
+        ]"#;
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_json(&get_all_definitions());
+        let conflicts = definitions.merge(overlay(conflicting_overlay), MergePolicy::Reject).unwrap_err();
+        assert!(conflicts.contains(&MergeConflict::Object("ActorType".to_owned())));
+        assert!(conflicts.contains(&MergeConflict::Limit { object: "StringList".to_owned(), field: "strings".to_owned(), limit: LimitType::Default }));
+        assert_eq!(definitions.get_enum("ActorType").unwrap().options[0].name, "elite");
+    }
+}
@@ -0,0 +1,53 @@
+use super::*;
+
+/// Describes what kind of definition was parsed.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum DefinitionKind {
+    /// A struct, enum, or bitfield, stored in [`ParsedDefinitions::objects`].
+    Object(ObjectKind),
+
+    /// A tag group, stored in [`ParsedDefinitions::groups`].
+    Group,
+
+    /// An engine, stored in [`ParsedDefinitions::engines`].
+    Engine
+}
+
+/// Describes a single definition that was just parsed.
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct ParseEvent<'a> {
+    /// Name of the definition.
+    pub name: &'a str,
+
+    /// Kind of definition.
+    pub kind: DefinitionKind,
+
+    /// The json file the definition is defined in.
+    pub definition_file: &'a str
+}
+
+/// Receives callbacks as definitions are parsed.
+///
+/// This can be used for showing progress or building side indices without traversing the
+/// definitions again afterwards.
+pub trait ParseObserver {
+    /// Called once before parsing with the total number of definitions that will be parsed.
+    fn begin(&mut self, total: usize) {
+        let _ = total;
+    }
+
+    /// Called after each object, group, and engine is parsed.
+    fn parsed(&mut self, event: &ParseEvent);
+}
+
+impl ParseObserver for () {
+    fn parsed(&mut self, _: &ParseEvent) {}
+}
+
+impl<F: FnMut(&ParseEvent)> ParseObserver for F {
+    fn parsed(&mut self, event: &ParseEvent) {
+        self(event)
+    }
+}
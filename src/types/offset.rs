@@ -0,0 +1,169 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::*;
+
+impl Struct {
+    /// Find the field that contains the byte at `offset` from the start of the struct.
+    ///
+    /// Returns the field along with the offset of the byte from the start of the field, or `None`
+    /// if `offset` is not inside the struct. Padding fields are returned like any other field, and
+    /// editor sections are never returned.
+    ///
+    /// The struct must be finalized so its fields have their offsets set.
+    pub fn field_at_offset(&self, offset: usize) -> Option<(&StructField, usize)> {
+        if offset >= self.size {
+            return None
+        }
+        self.fields
+            .iter()
+            .rev()
+            .filter(|f| !matches!(f.field_type, StructFieldType::EditorSection { .. }))
+            .find(|f| f.relative_offset <= offset)
+            .map(|f| (f, offset - f.relative_offset))
+    }
+
+    /// Find the innermost field that contains the byte at `offset`, descending into inline structs.
+    ///
+    /// Returns the path of fields from this struct to the innermost field, along with the offset
+    /// of the byte from the start of the innermost field. For arrays of structs, the element the
+    /// byte is in is descended into. Reflexives are not descended into, since their elements are
+    /// not stored inline.
+    pub fn deep_field_at_offset<'a>(&'a self, definitions: &'a ParsedDefinitions, offset: usize) -> Option<(Vec<&'a StructField>, usize)> {
+        let mut path = Vec::new();
+        let mut current = self;
+        let mut offset = offset;
+        loop {
+            let (field, field_offset) = current.field_at_offset(offset)?;
+            path.push(field);
+            offset = field_offset;
+
+            let StructFieldType::Object(FieldObject::NamedObject(n)) = &field.field_type else {
+                break
+            };
+            let Some(inner) = definitions.get_struct(n) else {
+                break
+            };
+            offset %= inner.size.max(1);
+            current = inner;
+        }
+        Some((path, offset))
+    }
+}
+
+/// Which part of a field [`StructField::byte_range`] covers.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FieldLayout<'a> {
+    /// Every element of the field, as laid out in the struct.
+    All,
+
+    /// Only the elements used by the given engine (see [`FieldView::count_for_engine`]).
+    Engine(&'a str),
+
+    /// A single element of an array or bounds (e.g. 1 for the upper bound).
+    Element(usize)
+}
+
+impl StructField {
+    /// Get the range of bytes the field occupies, relative to the start of its struct.
+    ///
+    /// Padding covers its whole size, and editor sections are always empty. For
+    /// [`FieldLayout::Element`], an element past the end of the field results in an empty range at
+    /// the end of the field.
+    ///
+    /// The field's struct must be finalized so its offset is set.
+    pub fn byte_range(&self, definitions: &ParsedDefinitions, layout: FieldLayout) -> Range<usize> {
+        let count = self.count.field_count();
+        let element_size = self.field_type.size(definitions);
+        let (first, elements) = match layout {
+            FieldLayout::All => (0, count),
+            FieldLayout::Engine(engine) => {
+                let engine_count = definitions
+                    .engine_lineage(engine)
+                    .find_map(|e| self.engine_counts.get(&e.name))
+                    .copied()
+                    .unwrap_or(count);
+                (0, engine_count.min(count))
+            },
+            FieldLayout::Element(index) => (index.min(count), usize::from(index < count))
+        };
+        let start = self.relative_offset + first * element_size;
+        start..start + elements * element_size
+    }
+
+    /// Get the bytes of the field from the bytes of its struct.
+    ///
+    /// Returns `None` if `data` is too small. See [`StructField::byte_range`].
+    pub fn slice<'b>(&self, definitions: &ParsedDefinitions, data: &'b [u8], layout: FieldLayout) -> Option<&'b [u8]> {
+        data.get(self.byte_range(definitions, layout))
+    }
+}
+
+impl FieldView<'_> {
+    /// Get the range of bytes the field occupies, relative to the start of its struct.
+    ///
+    /// See [`StructField::byte_range`].
+    pub fn byte_range(&self, layout: FieldLayout) -> Range<usize> {
+        self.get().byte_range(self.parent().definitions(), layout)
+    }
+
+    /// Get the bytes of the field from the bytes of its struct.
+    ///
+    /// See [`StructField::slice`].
+    pub fn slice<'b>(&self, data: &'b [u8], layout: FieldLayout) -> Option<&'b [u8]> {
+        self.get().slice(self.parent().definitions(), data, layout)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+    use crate::*;
+
+    #[test]
+    fn offsets_resolve_to_fields() {
+        let builtin = load_all_definitions();
+        let scenario = builtin.get_struct("Scenario").unwrap();
+        for f in scenario.fields.iter().filter(|f| f.size(builtin) > 0) {
+            let (found, inner) = scenario.field_at_offset(f.relative_offset).unwrap();
+            assert_eq!((found.relative_offset, inner), (f.relative_offset, 0));
+        }
+        assert!(scenario.field_at_offset(scenario.size).is_none());
+
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_str("test.json", r#"[
+            { "name": "TestPoint", "type": "struct", "fields": [{ "name": "x", "type": "float" }, { "name": "y", "type": "float" }], "size": 8 },
+            {
+                "name": "TestPath",
+                "type": "struct",
+                "fields": [
+                    { "name": "flags", "type": "uint32" },
+                    { "heading": "Points", "type": "editor_section" },
+                    { "name": "points", "type": "TestPoint", "count": 2 },
+                    { "type": "pad", "size": 4 }
+                ],
+                "size": 24
+            }
+        ]"#).unwrap();
+        definitions.finalize_without_engines();
+
+        let test_path = definitions.get_struct("TestPath").unwrap();
+        let (path, inner) = test_path.deep_field_at_offset(&definitions, 0x10).unwrap();
+        let names: Vec<&str> = path.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!((names, inner), (Vec::from(["points", "y"]), 0));
+
+        let (padding, inner) = test_path.field_at_offset(0x15).unwrap();
+        assert_eq!((padding.padding_kind, inner), (Some(PaddingKind::Padding), 1));
+
+        let points = definitions.struct_view("TestPath").unwrap().field("points").unwrap();
+        assert_eq!(points.byte_range(FieldLayout::All), 4..20);
+        assert_eq!(points.byte_range(FieldLayout::Element(1)), 12..20);
+        assert_eq!(points.byte_range(FieldLayout::Element(2)), 20..20);
+        assert_eq!(points.byte_range(FieldLayout::Engine("any engine")), 4..20);
+
+        let data: Vec<u8> = (0..24).collect();
+        assert_eq!(points.slice(&data, FieldLayout::Element(0)), Some(&data[4..12]));
+        assert_eq!(points.slice(&data[..16], FieldLayout::All), None);
+    }
+}
@@ -0,0 +1,374 @@
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::format;
+
+use serde_json::Value;
+
+use super::*;
+
+/// Name that packs use to depend on the built-in definitions.
+///
+/// Its version is the version of this crate (see [`DefinitionsProvenance::crate_version`]).
+pub const BUILTIN_PACK_NAME: &str = "ringhopper-definitions";
+
+/// Version of a definition pack, in the form `major.minor.patch`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PackVersion {
+    /// Major version.
+    pub major: u32,
+
+    /// Minor version.
+    pub minor: u32,
+
+    /// Patch version.
+    pub patch: u32
+}
+
+impl PackVersion {
+    /// Parse a version (e.g. `1.2.3`). Missing minor and patch versions are 0.
+    pub fn parse(version: &str) -> Option<Self> {
+        let mut parts = version.split('.');
+        let mut next = |required: bool| match parts.next() {
+            Some(p) => p.parse().ok(),
+            None if required => None,
+            None => Some(0)
+        };
+        let version = Self { major: next(true)?, minor: next(false)?, patch: next(false)? };
+        parts.next().is_none().then_some(version)
+    }
+
+    /// Get the version of the built-in definitions.
+    pub fn builtin() -> Self {
+        Self::parse(env!("CARGO_PKG_VERSION")).expect("crate version should be a valid pack version")
+    }
+}
+
+impl core::fmt::Display for PackVersion {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.write_fmt(format_args!("{}.{}.{}", self.major, self.minor, self.patch))
+    }
+}
+
+/// Describes a definition pack and the packs it is layered on.
+///
+/// Manifests are JSON objects, usually stored as `pack.json` in the root of the pack:
+///
+/// ```json
+/// {
+///     "name": "my-pack",
+///     "version": "1.0.0",
+///     "dependencies": { "ringhopper-definitions": "0.2.0", "other-pack": "1.1.0" }
+/// }
+/// ```
+///
+/// Each dependency maps the name of a pack to its minimum version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PackManifest {
+    /// Name of the pack.
+    pub name: String,
+
+    /// Version of the pack.
+    pub version: PackVersion,
+
+    /// Packs that must be loaded first, and their minimum versions.
+    ///
+    /// [`BUILTIN_PACK_NAME`] refers to the built-in definitions.
+    pub dependencies: BTreeMap<String, PackVersion>
+}
+
+impl PackManifest {
+    /// Parse a manifest from JSON.
+    pub fn from_json(json: &str) -> Result<Self, PackError> {
+        let invalid = |message: String| PackError::InvalidManifest { message };
+        let value: Value = serde_json::from_str(json).map_err(|e| invalid(format!("failed to parse pack manifest: {e}")))?;
+        let object = value.as_object().ok_or_else(|| invalid("pack manifest must be an object".to_owned()))?;
+
+        let name = object.get("name")
+            .and_then(|n| n.as_str())
+            .filter(|n| !n.is_empty())
+            .ok_or_else(|| invalid("pack manifest must have a non-empty name".to_owned()))?;
+        let version = |key: &str, value: Option<&Value>| value
+            .and_then(|v| v.as_str())
+            .and_then(PackVersion::parse)
+            .ok_or_else(|| invalid(format!("{name}::{key} must be a version string")));
+
+        let mut dependencies = BTreeMap::new();
+        if let Some(d) = object.get("dependencies") {
+            let d = d.as_object().ok_or_else(|| invalid(format!("{name}::dependencies must be an object")))?;
+            for (dependency, v) in d {
+                dependencies.insert(dependency.to_owned(), version(dependency, Some(v))?);
+            }
+        }
+
+        Ok(Self { name: name.to_owned(), version: version("version", object.get("version"))?, dependencies })
+    }
+}
+
+/// A definition pack: its manifest and its definition files.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct DefinitionPack {
+    /// The pack's manifest.
+    pub manifest: PackManifest,
+
+    /// Contents of each definition file, keyed by its path relative to the root of the pack.
+    pub files: BTreeMap<String, Vec<u8>>
+}
+
+impl DefinitionPack {
+    /// Create a pack from its manifest and definition files.
+    pub fn new(manifest: PackManifest, files: BTreeMap<String, Vec<u8>>) -> Self {
+        Self { manifest, files }
+    }
+
+    /// Read a pack from a directory with a `pack.json` manifest in its root.
+    ///
+    /// Every other `.json` file in the directory (recursively) is a definition file.
+    #[cfg(feature = "std")]
+    pub fn read_directory<P: AsRef<std::path::Path>>(directory: P) -> std::io::Result<Self> {
+        let invalid = |e: PackError| std::io::Error::new(std::io::ErrorKind::InvalidData, e);
+        let mut files = read_definition_directory(directory.as_ref())?;
+        let manifest = files.remove("pack.json").ok_or_else(|| invalid(PackError::InvalidManifest { message: "pack.json not found".to_owned() }))?;
+        let manifest = core::str::from_utf8(&manifest).map_err(|_| invalid(PackError::InvalidManifest { message: "pack.json is not UTF-8".to_owned() }))?;
+        Ok(Self::new(PackManifest::from_json(manifest).map_err(invalid)?, files))
+    }
+}
+
+/// Describes why definition packs could not be resolved or loaded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PackError {
+    /// A manifest is malformed.
+    InvalidManifest {
+        /// Description of the problem.
+        message: String
+    },
+
+    /// More than one pack has the same name, or a pack uses [`BUILTIN_PACK_NAME`].
+    DuplicatePack {
+        /// Name of the pack.
+        name: String
+    },
+
+    /// A pack depends on a pack that was not given.
+    MissingDependency {
+        /// Name of the pack.
+        pack: String,
+
+        /// Name of the missing dependency.
+        dependency: String
+    },
+
+    /// A pack depends on a newer version of a pack than the one given.
+    DependencyTooOld {
+        /// Name of the pack.
+        pack: String,
+
+        /// Name of the dependency.
+        dependency: String,
+
+        /// Minimum version required by the pack.
+        required: PackVersion,
+
+        /// Version of the dependency that was given.
+        actual: PackVersion
+    },
+
+    /// Packs depend on each other.
+    DependencyCycle {
+        /// Names of the packs in the cycle, where each depends on the next and the last depends
+        /// on the first.
+        packs: Vec<String>
+    },
+
+    /// A pack's definitions could not be loaded.
+    Definition {
+        /// Name of the pack.
+        pack: String,
+
+        /// Why it could not be loaded.
+        error: Box<DefinitionError>
+    }
+}
+
+impl core::fmt::Display for PackError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidManifest { message } => fmt.write_str(message),
+            Self::DuplicatePack { name } => fmt.write_fmt(format_args!("more than one pack is named {name}")),
+            Self::MissingDependency { pack, dependency } => fmt.write_fmt(format_args!("pack {pack} depends on {dependency}, which was not found")),
+            Self::DependencyTooOld { pack, dependency, required, actual } => {
+                fmt.write_fmt(format_args!("pack {pack} requires {dependency} {required} or newer, but {actual} was found"))
+            },
+            Self::DependencyCycle { packs } => fmt.write_fmt(format_args!("packs depend on each other: {} -> {}", packs.join(" -> "), packs[0])),
+            Self::Definition { pack, error } => fmt.write_fmt(format_args!("failed to load pack {pack}: {error}"))
+        }
+    }
+}
+
+impl core::error::Error for PackError {}
+
+#[derive(Copy, Clone, PartialEq)]
+enum Visit {
+    InProgress,
+    Done
+}
+
+/// Check the dependencies of every pack, returning the indices of the packs in the order they
+/// should be loaded (dependencies first).
+///
+/// Packs that do not depend on each other are kept in the order they were given. The built-in
+/// definitions are always available as [`BUILTIN_PACK_NAME`] with [`PackVersion::builtin`].
+pub fn resolve_pack_order(manifests: &[PackManifest]) -> Result<Vec<usize>, PackError> {
+    let mut indices: BTreeMap<&str, usize> = BTreeMap::new();
+    for (i, m) in manifests.iter().enumerate() {
+        if m.name == BUILTIN_PACK_NAME || indices.insert(&m.name, i).is_some() {
+            return Err(PackError::DuplicatePack { name: m.name.to_owned() })
+        }
+    }
+
+    for m in manifests {
+        for (dependency, required) in &m.dependencies {
+            let actual = if dependency == BUILTIN_PACK_NAME {
+                PackVersion::builtin()
+            }
+            else {
+                let index = indices.get(dependency.as_str()).ok_or_else(|| PackError::MissingDependency {
+                    pack: m.name.to_owned(),
+                    dependency: dependency.to_owned()
+                })?;
+                manifests[*index].version
+            };
+            if actual < *required {
+                return Err(PackError::DependencyTooOld { pack: m.name.to_owned(), dependency: dependency.to_owned(), required: *required, actual })
+            }
+        }
+    }
+
+    fn visit(manifests: &[PackManifest], indices: &BTreeMap<&str, usize>, index: usize, visits: &mut [Option<Visit>], path: &mut Vec<usize>, order: &mut Vec<usize>) -> Result<(), PackError> {
+        match visits[index] {
+            Some(Visit::Done) => return Ok(()),
+            Some(Visit::InProgress) => {
+                let start = path.iter().position(|p| *p == index).expect("pack in progress should be in the path");
+                return Err(PackError::DependencyCycle { packs: path[start..].iter().map(|p| manifests[*p].name.to_owned()).collect() })
+            },
+            None => ()
+        }
+
+        visits[index] = Some(Visit::InProgress);
+        path.push(index);
+        for dependency in manifests[index].dependencies.keys().filter_map(|d| indices.get(d.as_str())) {
+            visit(manifests, indices, *dependency, visits, path, order)?;
+        }
+        path.pop();
+        visits[index] = Some(Visit::Done);
+        order.push(index);
+        Ok(())
+    }
+
+    let mut visits = alloc::vec![None; manifests.len()];
+    let mut order = Vec::with_capacity(manifests.len());
+    for i in 0..manifests.len() {
+        visit(manifests, &indices, i, &mut visits, &mut Vec::new(), &mut order)?;
+    }
+    Ok(order)
+}
+
+impl ParsedDefinitions {
+    /// Load definition packs in dependency order.
+    ///
+    /// The dependencies of every pack are checked with [`resolve_pack_order`] before anything is
+    /// loaded. Each pack is loaded in one call (see [`ParsedDefinitions::load_from_slice`]), so
+    /// engines can inherit engines in the same pack.
+    ///
+    /// This does not load the built-in definitions, so load them first if any pack depends on
+    /// [`BUILTIN_PACK_NAME`]; otherwise, [`PackError::MissingDependency`] is returned. The result
+    /// must be finalized afterwards.
+    ///
+    /// Packs are loaded into a copy of the definitions, which only replaces them once every pack
+    /// has loaded, so nothing is changed if an error is returned.
+    pub fn load_packs(&mut self, packs: &[DefinitionPack]) -> Result<(), PackError> {
+        let manifests: Vec<PackManifest> = packs.iter().map(|p| p.manifest.clone()).collect();
+        let order = resolve_pack_order(&manifests)?;
+
+        if let Some(pack) = manifests.iter().find(|m| m.dependencies.contains_key(BUILTIN_PACK_NAME)) {
+            let builtin = crate::load_all_definitions();
+            let loaded = builtin.groups.keys().all(|g| self.groups.contains_key(g))
+                && builtin.engines.keys().all(|e| self.engines.contains_key(e));
+            if !loaded {
+                return Err(PackError::MissingDependency { pack: pack.name.to_owned(), dependency: BUILTIN_PACK_NAME.to_owned() })
+            }
+        }
+
+        let mut scratch = self.clone();
+        for index in order {
+            let pack = &packs[index];
+            let error = |error| PackError::Definition { pack: pack.manifest.name.to_owned(), error: Box::new(error) };
+            let mut objects = Vec::new();
+            for (file, data) in &pack.files {
+                objects.extend(try_parse_definition_file(file, data).map_err(error)?);
+            }
+            scratch.try_load_from_json(&objects).map_err(error)?;
+        }
+        *self = scratch;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::borrow::ToOwned;
+    use alloc::collections::BTreeMap;
+    use alloc::string::ToString;
+
+    use crate::*;
+
+    fn manifest(name: &str, version: &str, dependencies: &[(&str, &str)]) -> PackManifest {
+        let dependencies: BTreeMap<_, _> = dependencies.iter().map(|(n, v)| (*n, *v)).collect();
+        PackManifest::from_json(&serde_json::json!({ "name": name, "version": version, "dependencies": dependencies }).to_string()).unwrap()
+    }
+
+    #[test]
+    fn packs_are_loaded_after_their_dependencies() {
+        let base = manifest("base", "1.2", &[(BUILTIN_PACK_NAME, "0.1.0")]);
+        let addon = manifest("addon", "1.0.0", &[("base", "1.1.0")]);
+        assert_eq!(base.version, PackVersion { major: 1, minor: 2, patch: 0 });
+        assert_eq!(resolve_pack_order(&[addon.clone(), base.clone()]), Ok(alloc::vec![1, 0]));
+
+        let too_new = manifest("addon", "1.0.0", &[("base", "2.0.0")]);
+        assert!(matches!(resolve_pack_order(&[too_new, base.clone()]), Err(PackError::DependencyTooOld { .. })));
+        assert!(matches!(resolve_pack_order(core::slice::from_ref(&addon)), Err(PackError::MissingDependency { dependency, .. }) if dependency == "base"));
+        assert!(matches!(resolve_pack_order(&[manifest("base", "1.0.0", &[(BUILTIN_PACK_NAME, "999.0.0")])]), Err(PackError::DependencyTooOld { .. })));
+
+        let cycle = [manifest("a", "1.0.0", &[("b", "1.0.0")]), manifest("b", "1.0.0", &[("a", "1.0.0")])];
+        assert_eq!(resolve_pack_order(&cycle), Err(PackError::DependencyCycle { packs: alloc::vec!["a".to_owned(), "b".to_owned()] }));
+
+        let files = |json: &str| [("test.json".to_owned(), json.as_bytes().to_vec())].into_iter().collect();
+        let packs = [
+            DefinitionPack::new(addon, files(r#"[{ "name": "TestAddon", "type": "struct", "size": 4, "fields": [{ "name": "base", "type": "TestBase" }] }]"#)),
+            DefinitionPack::new(base, files(r#"[{ "name": "TestBase", "type": "struct", "size": 4, "fields": [{ "name": "value", "type": "float" }] }]"#))
+        ];
+        let mut definitions = ParsedDefinitions::default();
+        assert!(matches!(definitions.load_packs(&packs), Err(PackError::MissingDependency { dependency, .. }) if dependency == BUILTIN_PACK_NAME));
+
+        let mut definitions = ParsedDefinitions::load_builtin();
+        definitions.load_packs(&packs).unwrap();
+        definitions.try_finalize().unwrap();
+        assert!(definitions.objects.contains_key("TestAddon") && definitions.objects.contains_key("TestBase"));
+
+        // A pack failing to load leaves the packs loaded before it out, too.
+        let broken = manifest("broken", "1.0.0", &[("base", "1.0.0")]);
+        let packs = [
+            packs[1].clone(),
+            DefinitionPack::new(broken, files(r#"[{ "name": "TestBroken", "type": "widget" }]"#))
+        ];
+        let mut definitions = ParsedDefinitions::load_builtin();
+        let objects = definitions.objects.len();
+        assert!(matches!(definitions.load_packs(&packs), Err(PackError::Definition { pack, .. }) if pack == "broken"));
+        assert_eq!(definitions.objects.len(), objects);
+    }
+}
@@ -9,37 +9,41 @@ use alloc::string::ToString;
 use super::*;
 use serde_json::*;
 
+type Result<T, E = DefinitionError> = core::result::Result<T, E>;
+
 macro_rules! oget_name {
     ($obj:expr) => {
-        $obj.get("name").map(|c| c.as_str().unwrap()).unwrap_or("<noname>")
+        $obj.get("name").and_then(|c| c.as_str()).unwrap_or("<noname>")
+    };
+}
+
+macro_rules! wrong_type {
+    ($obj:expr, $field:expr, $expected:expr) => {
+        DefinitionError::InvalidValue { object: oget_name!($obj).to_owned(), key: $field.to_owned(), expected: $expected }
     };
 }
 
 macro_rules! oget {
     ($obj:expr, $field:expr) => {
-        $obj.get($field).unwrap_or_else(|| panic!("no such field `{name}::{field}`", field=$field, name=oget_name!($obj)))
+        $obj.get($field).ok_or_else(|| DefinitionError::MissingKey { object: oget_name!($obj).to_owned(), key: $field.to_owned() })?
     };
 }
 
 macro_rules! oget_str {
     ($obj:expr, $field:expr) => {
-        oget!($obj, $field).as_str().unwrap_or_else(|| panic!("expected {name}::{field} to be a string", field=$field, name=oget_name!($obj)))
+        oget!($obj, $field).as_str().ok_or_else(|| wrong_type!($obj, $field, "a string"))?
     };
 }
 
 macro_rules! oget_bool {
     ($obj:expr, $field:expr) => {
-        oget!($obj, $field).as_bool().unwrap_or_else(|| panic!("expected {name}::{field} to be a boolean", field=$field, name=oget_name!($obj)))
+        oget!($obj, $field).as_bool().ok_or_else(|| wrong_type!($obj, $field, "a boolean"))?
     };
 }
 
 macro_rules! oget_number {
     ($obj:expr, $field:expr, $accessor:tt) => {
-        oget!($obj, $field)
-            .as_number()
-            .unwrap_or_else(|| panic!("expected {name}::{field} to be a number", field=$field, name=oget_name!($obj)))
-            .$accessor()
-            .unwrap_or_else(|| panic!("expected {name}::{field} to be a certain type of number", field=$field, name=oget_name!($obj)))
+        oget!($obj, $field).as_number().and_then(|n| n.$accessor()).ok_or_else(|| wrong_type!($obj, $field, "a number in range"))?
     };
 }
 
@@ -49,8 +53,27 @@ macro_rules! oget_size {
     };
 }
 
+/// Make a [`DefinitionError::Invalid`] for `object`, with a formatted message.
+macro_rules! invalid {
+    ($object:expr, $($arg:tt)*) => {
+        DefinitionError::Invalid { object: ($object).to_owned(), message: format!($($arg)*) }
+    };
+}
+
+/// Return a [`DefinitionError::Invalid`] if the condition is false.
+macro_rules! ensure {
+    ($condition:expr, $object:expr, $($arg:tt)*) => {
+        if !$condition {
+            return Err(invalid!($object, $($arg)*))
+        }
+    };
+}
+
+/// Widths a bitfield can have, where 0 is a placeholder that takes no space.
+pub(crate) const BITFIELD_WIDTHS: [u8; 4] = [0, 8, 16, 32];
+
 /// Recursively resolve parent groups (e.g. object -> [unit, device, etc.] -> [biped, vehicle, device_machine, etc.])
-fn get_all_child_groups(parent: &String, groups: &BTreeMap<String, TagGroup>) -> Vec<String> {
+pub(crate) fn get_all_child_groups(parent: &String, groups: &BTreeMap<String, TagGroup>) -> Vec<String> {
     if parent == "*" {
         return groups.keys().map(|f| f.to_owned()).collect()
     }
@@ -71,293 +94,180 @@ fn get_all_child_groups(parent: &String, groups: &BTreeMap<String, TagGroup>) ->
 }
 
 impl ParsedDefinitions {
-    pub(crate) fn load_from_json(&mut self, objects: &Vec<Map<String, Value>>) {
+    /// Load definitions from parsed JSON objects.
+    ///
+    /// Each object must have a `__json_file` key naming the file it came from. Once everything is
+    /// loaded, call [`ParsedDefinitions::finalize`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the definitions are malformed. See [`ParsedDefinitions::try_load_from_json`].
+    pub fn load_from_json(&mut self, objects: &[Map<String, Value>]) {
+        self.load_from_json_with_observer(objects, &mut ())
+    }
+
+    /// Load definitions from parsed JSON objects, returning an error instead of panicking if they
+    /// are malformed.
+    ///
+    /// Nothing is loaded if an error is returned.
+    ///
+    /// See [`ParsedDefinitions::load_from_json`].
+    pub fn try_load_from_json(&mut self, objects: &[Map<String, Value>]) -> Result<(), DefinitionError> {
+        self.try_load_from_json_with_observer(objects, &mut ())
+    }
+
+    /// Load definitions from parsed JSON objects, calling `observer` as each definition is parsed.
+    ///
+    /// See [`ParsedDefinitions::load_from_json`].
+    pub fn load_from_json_with_observer(&mut self, objects: &[Map<String, Value>], observer: &mut dyn ParseObserver) {
+        self.try_load_from_json_with_observer(objects, observer).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Load definitions from parsed JSON objects, calling `observer` as each definition is parsed
+    /// and returning an error instead of panicking if they are malformed.
+    ///
+    /// Nothing is loaded if an error is returned, though `observer` may have already been called for
+    /// some definitions.
+    ///
+    /// See [`ParsedDefinitions::load_from_json`].
+    pub fn try_load_from_json_with_observer(&mut self, objects: &[Map<String, Value>], observer: &mut dyn ParseObserver) -> Result<(), DefinitionError> {
+        let mut groups = BTreeMap::<String, TagGroup>::new();
+        let mut limit_profiles = BTreeMap::<String, LimitProfile>::new();
+        let mut named_objects = BTreeMap::<String, NamedObject>::new();
         let mut all_engines = BTreeMap::<String, Map<String, Value>>::new();
+        let mut engine_modifiers = Vec::new();
+
+        observer.begin(objects.len());
 
         for object in objects {
             let object_type = oget_str!(object, "type");
             let object_name = oget_str!(object, "name").to_owned();
-            assert!(!object_name.is_empty());
+            if object_name.is_empty() {
+                return Err(wrong_type!(object, "name", "a non-empty string"))
+            }
+            let duplicate = || DefinitionError::Duplicate { name: object_name.to_owned() };
 
             match object_type {
                 "group" => {
-                    assert!(!self.groups.contains_key(&object_name), "duplicate group {object_name} detected");
-                    let parent_maybe = object.get("supergroup").map(|g| g.as_str().unwrap().to_owned());
-                    self.groups.insert(object_name.clone(), TagGroup {
-                        struct_name: oget_str!(object, "struct").to_owned(),
+                    if self.groups.contains_key(&object_name) || groups.contains_key(&object_name) {
+                        return Err(duplicate())
+                    }
+                    let group = load_group(&object_name, object)?;
+                    observer.parsed(&ParseEvent { name: &object_name, kind: DefinitionKind::Group, definition_file: &group.definition_file });
+                    groups.insert(object_name, group);
+                },
+                "limit_profile" => {
+                    if self.limit_profiles.contains_key(&object_name) || limit_profiles.contains_key(&object_name) {
+                        return Err(duplicate())
+                    }
+                    limit_profiles.insert(object_name.clone(), LimitProfile {
+                        name: object_name.clone(),
                         definition_file: oget_str!(object, "__json_file").to_owned(),
-                        supergroup: parent_maybe,
-                        supported_engines: SupportedEngines::load_from_json(object),
-                        version: oget_number!(object, "version", as_u64).try_into().unwrap_or_else(|e| panic!("{object_name}::version can't convert to u16: {e}")),
-                        fourcc_binary: oget_number!(object, "fourcc_binary", as_u64).try_into().unwrap_or_else(|e| panic!("{object_name}::fourcc_binary can't convert to u32: {e}")),
-                        name_rust_enum: format_for_rust_enums(&object_name),
-                        name: object_name,
+                        description: get_optional(object, "description", "a string", |d| d.as_str().map(ToOwned::to_owned))?
                     });
                 },
                 "engine" => {
-                    assert!(!all_engines.contains_key(&object_name), "duplicate engine {object_name} detected");
+                    if self.engines.contains_key(&object_name) || all_engines.contains_key(&object_name) {
+                        return Err(duplicate())
+                    }
                     all_engines.insert(object_name, object.clone());
                 },
-                _ => {
-                    assert!(!self.objects.contains_key(&object_name), "duplicate object {object_name} detected");
-                    self.objects.insert(object_name, NamedObject::load_from_json(object));
-                }
+                "engine_modifier" => {
+                    engine_modifiers.push((EngineModifier::from_json(object)?, oget_str!(object, "__json_file")));
+                },
+                "struct" | "enum" | "bitfield" => {
+                    if self.objects.contains_key(&object_name) || named_objects.contains_key(&object_name) {
+                        return Err(duplicate())
+                    }
+                    if self.custom_objects.contains_key(&object_name) {
+                        return Err(invalid!(object_name, "object {object_name} has the same name as a custom object"))
+                    }
+                    let mut parsed = NamedObject::load_from_json(object)?;
+                    self.resolve_custom_objects(&mut parsed);
+                    observer.parsed(&ParseEvent { name: &object_name, kind: DefinitionKind::Object(parsed.kind()), definition_file: parsed.definition_file() });
+                    named_objects.insert(object_name, parsed);
+                },
+                object_type => return Err(DefinitionError::UnknownType { object: object_name.to_owned(), object_type: object_type.to_owned() })
             }
         }
 
+        let mut engines = BTreeMap::new();
         for (engine_name, engine) in &all_engines {
-            // Values are ("engine::value", value)
-            fn get_chain(what: &str, engine_name: &str, all_engines: &BTreeMap<String, Map<String, Value>>) -> Vec<(String, Value)> {
-                let mut v: Vec<(String, Value)> = Vec::new();
-                let engine = all_engines.get(engine_name).unwrap_or_else(|| panic!("can't find engine {engine_name}"));
-                if let Some(n) = engine.get(what) {
-                    v.push((format!("{engine_name}::{what}"), n.to_owned()))
-                }
-                if let Some(i) = engine.get("inherits") {
-                    v.append(
-                        &mut get_chain(what, i.as_str().unwrap_or_else(|| panic!("inherits of {engine_name} is non-string")), all_engines)
-                    );
-                }
-                v
-            }
-
-            let get_chain = |what: &str, required: bool| -> Vec<(String, Value)> {
-                let result = get_chain(what, engine_name, &all_engines);
-                if required && result.is_empty() {
-                    panic!("{what} is not present in {engine_name} or its ancestors")
-                }
-                result
-            };
-
-            let hex_to_u64 = |hex: &Value| -> Option<u64> {
-                let str = hex.as_str()?;
-                if !str.starts_with("0x") {
-                    return None
-                }
-                u64::from_str_radix(&str[2..], 16).ok()
-            };
-
-            let parse_hex_u64 = |what: Vec<(String, Value)>| -> Vec<(String, u64)> {
-                what.iter()
-                    .map(|(f, v)| {
-                        let val = hex_to_u64(v).unwrap_or_else(|| panic!("{f} could not be parsed as hex"));
-                        (f.to_owned(), val)
-                    })
-                    .collect()
-            };
-
-            let first_value = |what: &str, required: bool| get_chain(what, required).first().map(|(_, v)| v.to_owned());
-            let first_object = |what: &str, required: bool| first_value(what, required).map(|v| v.as_object().unwrap_or_else(|| panic!("{what} is not an object")).to_owned());
-            let first_string = |what: &str, required: bool| first_value(what, required).map(|v| v.as_str().unwrap_or_else(|| panic!("{what} is nonstring")).to_owned());
-            let first_u64 = |what: &str, required: bool| first_value(what, required).map(|v| v.as_u64().unwrap_or_else(|| panic!("{what} is non-u64")).to_owned());
-            let first_bool = |what: &str, required: bool| first_value(what, required).map(|v| v.as_bool().unwrap_or_else(|| panic!("{what} is non-bool")).to_owned());
-
-            let base_memory_address = {
-                let bma_search = get_chain("base_memory_address", true);
-                let (bma_path, bma_obj) = bma_search.first().unwrap();
-
-                let bma_address_obj: &Value;
-                let bma_inferred_obj: &Value;
-
-                match bma_obj {
-                    Value::Object(o) => {
-                        bma_address_obj = o.get("value").unwrap_or_else(|| panic!("{bma_path} has no address"));
-                        bma_inferred_obj = o.get("inferred").unwrap_or(&Value::Bool(false));
-                    },
-                    Value::String(_) => {
-                        bma_address_obj = bma_obj;
-                        bma_inferred_obj = &Value::Bool(false);
-                    },
-                    _ => panic!("{bma_path} is not object or string")
-                }
-
-                BaseMemoryAddress {
-                    address: hex_to_u64(bma_address_obj).unwrap_or_else(|| panic!("{bma_path}'s address is nonhex")),
-                    inferred: bma_inferred_obj.as_bool().unwrap_or_else(|| panic!("{bma_path} strict is non-bool"))
-                }
-            };
-
-            let max_cache_file_size = {
-                let cfz_search = get_chain("max_cache_file_size", true);
-                let (cfz_path, cfz_obj) = cfz_search.first().unwrap();
-
-                let multiplayer: &Value;
-                let singleplayer: &Value;
-                let user_interface: &Value;
-
-                match cfz_obj {
-                    Value::Object(o) => {
-                        multiplayer = o.get("multiplayer").unwrap_or_else(|| panic!("{cfz_path} has no multiplayer"));
-                        singleplayer = o.get("singleplayer").unwrap_or_else(|| panic!("{cfz_path} has no singleplayer"));
-                        user_interface = o.get("user_interface").unwrap_or_else(|| panic!("{cfz_path} has no user_interface"));
-                    },
-                    Value::String(_) => {
-                        multiplayer = cfz_obj;
-                        singleplayer = cfz_obj;
-                        user_interface = cfz_obj;
-                    },
-                    _ => panic!("{cfz_path} is not object or string")
-                }
-
-                EngineCacheFileSize {
-                    multiplayer: hex_to_u64(multiplayer).unwrap_or_else(|| panic!("{cfz_path} multiplayer is not hex")),
-                    singleplayer: hex_to_u64(singleplayer).unwrap_or_else(|| panic!("{cfz_path} singleplayer is not hex")),
-                    user_interface: hex_to_u64(user_interface).unwrap_or_else(|| panic!("{cfz_path} user_interface is not hex")),
-                }
-            };
-
-            let required_tags = {
-                let ert = get_chain("required_tags", true);
-                let ert = ert.iter().map(|(k, v)| (k.to_owned(), v.as_object().unwrap_or_else(|| panic!("{k} is non-object"))));
-
-                let mut required_tags = EngineRequiredTags::default();
-
-                for obj in ert {
-                    let handler = |tags: &mut Vec<String>, what: &str| {
-                        let list = match obj.1.get(what) { Some(n) => n, None => return };
-                        let list = list.as_array().unwrap_or_else(|| panic!("{engine_name}::{what} is not an array"));
-                        tags.reserve(list.len());
-                        for i in list {
-                            tags.push(i.as_str().unwrap_or_else(|| panic!("{engine_name}::{what} contains non-strings")).to_owned());
-                        }
-                    };
-
-                    handler(&mut required_tags.all, "all");
-                    handler(&mut required_tags.user_interface, "user_interface");
-                    handler(&mut required_tags.singleplayer, "singleplayer");
-                    handler(&mut required_tags.multiplayer, "multiplayer");
-                }
-
-                required_tags.all.dedup();
-                required_tags.multiplayer.dedup();
-                required_tags.singleplayer.dedup();
-                required_tags.user_interface.dedup();
-
-                required_tags
-            };
+            let engine = load_engine(engine_name, engine, &all_engines)?;
+            observer.parsed(&ParseEvent { name: engine_name, kind: DefinitionKind::Engine, definition_file: &engine.definition_file });
+            engines.insert(engine_name.to_owned(), engine);
+        }
 
-            let build = match first_object("build", false) {
-                Some(n) => {
-                    let aliases = match n.get("aliases") {
-                        Some(Value::Array(n)) => n.iter().map(|n| n.as_str().unwrap().to_string()).collect(),
-                        None => vec![],
-                        _ => unreachable!("fallback is a non-array")
-                    };
-
-                    Some(Build {
-                        string: {
-                            let build = oget_str!(&n, "version").to_string();
-                            assert!(build.len() < 32, "build version exceeds 31 characters");
-                            build
-                        },
-                        aliases,
-                        enforced: oget_bool!(&n, "enforced")
-                    })
-                },
-                None => None
-            };
+        // Modifiers can refer to anything loaded here, so add everything first and take it back out
+        // if a modifier fails. Everything added has a new name, so removing it restores the rest.
+        let previous_engines = self.engines.clone();
+        let group_names: Vec<String> = groups.keys().cloned().collect();
+        let object_names: Vec<String> = named_objects.keys().cloned().collect();
+        let limit_profile_names: Vec<String> = limit_profiles.keys().cloned().collect();
+        self.engines.extend(engines);
+        self.groups.extend(groups);
+        self.objects.extend(named_objects);
+        self.limit_profiles.extend(limit_profiles);
+
+        // Modifiers are layered on engines that are already loaded, so apply them last
+        for (modifier, definition_file) in &engine_modifiers {
+            if let Err(e) = self.apply_engine_modifier(modifier) {
+                self.engines = previous_engines;
+                group_names.iter().for_each(|g| { self.groups.remove(g); });
+                object_names.iter().for_each(|o| { self.objects.remove(o); });
+                limit_profile_names.iter().for_each(|p| { self.limit_profiles.remove(p); });
+                return Err(e)
+            }
+            if let Some(engine) = self.engines.get_mut(&modifier.name) {
+                engine.definition_file = (*definition_file).to_owned();
+            }
+            observer.parsed(&ParseEvent { name: &modifier.name, kind: DefinitionKind::Engine, definition_file });
+        }
 
-            self.engines.insert(engine_name.to_owned(), Engine {
-                base_memory_address,
-                build,
-                definition_file: oget_str!(engine, "__json_file").to_owned(),
-                cache_default: engine.get("cache_default").unwrap_or(&Value::Bool(false)).as_bool().unwrap(),
-                build_target: first_bool("build_target", true).unwrap(),
-                fallback: engine.get("fallback").unwrap_or(&Value::Bool(false)).as_bool().unwrap(),
-                cache_file_version: first_u64("cache_file_version", true).unwrap().try_into().unwrap_or_else(|_| panic!("where's the cache file version???")),
-                display_name: first_string("display_name", true).unwrap(),
-                inherits: get_chain("inherits", false).first().map(|v| v.1.as_str().unwrap().to_owned()),
-                max_cache_file_size,
-                custom: first_bool("custom", false).unwrap_or(false),
-                max_script_nodes: first_u64("max_script_nodes", true).unwrap(),
-                max_tag_space: parse_hex_u64(get_chain("max_tag_space", true)).first().unwrap().1,
-                resource_maps: get_chain("resource_maps", false).first().map(|(_, v)| EngineSupportedResourceMaps {
-                    externally_indexed_tags: v.get("externally_indexed_tags").expect("externally_indexed_tags not set").as_bool().unwrap()
-                }),
-                external_models: first_bool("external_models", false).unwrap_or(false),
-                external_bsps: first_bool("external_bsps", false).unwrap_or(false),
-                minimum_weapons: first_u64("minimum_weapons", true).unwrap(),
-                cache_parser: match first_string("cache_parser", true).unwrap().as_str() {
-                    "pc" => EngineCacheParser::PC,
-                    "xbox" => EngineCacheParser::Xbox,
-                    parser => panic!("unknown cache_parser {parser}", parser=parser)
-                },
-                compression_type: match first_string("compression_type", true).unwrap().as_str() {
-                    "none" => EngineCompressionType::Uncompressed,
-                    "deflate" => EngineCompressionType::Deflate,
-                    compression_type => panic!("unknown compression_type {compression_type}", compression_type=compression_type)
-                },
-                grenades: {
-                    let as_u8 = |value: &Value| -> u8 {
-                        value.as_u64().expect("grenades is not a decimal").try_into().expect("grenades is not 0-255")
-                    };
-
-                    let value = first_value("grenades", true).expect("no grenades?");
-                    let limits = match value {
-                        Value::Number(_) => {
-                            let q: u8 = as_u8(&value);
-                            EngineGrenades {
-                                user_interface: q..=q,
-                                singleplayer: q..=q,
-                                multiplayer: q..=q
-                            }
-                        },
-                        Value::Object(o) => {
-                            // singleplayer, multiplayer, then user_interface
-                            let parse_limits = |value: &Value| -> (u8,u8,u8) {
-                                match value {
-                                    Value::Number(_) => {
-                                        let q: u8 = as_u8(value);
-                                        (q,q,q)
-                                    },
-                                    Value::Object(o) => {
-                                        let singleplayer = o.get("singleplayer").expect("no singleplayer grenades");
-                                        let multiplayer = o.get("multiplayer").expect("no multiplayer grenades");
-                                        let user_interface = o.get("user_interface").expect("no user_interface grenades");
-                                        (as_u8(singleplayer), as_u8(multiplayer), as_u8(user_interface))
-                                    },
-                                    _ => panic!("grenades min/max not an object or number")
-                                }
-                            };
+        for name in &group_names {
+            self.group_fourccs.insert(self.groups[name].fourcc_binary, name.to_owned());
+        }
 
-                            let minimum = parse_limits(o.get("minimum").expect("no minimum grenades"));
-                            let maximum = parse_limits(o.get("maximum").expect("no maximum grenades"));
+        Ok(())
+    }
 
-                            EngineGrenades {
-                                multiplayer: minimum.0..=maximum.0,
-                                singleplayer: minimum.1..=maximum.1,
-                                user_interface: minimum.2..=maximum.2,
-                            }
-                        },
-                        _ => panic!("grenades not an object or number")
-                    };
+    /// Validate the loaded definitions and compute everything that depends on all of them being
+    /// loaded (offsets, expanded tag references, [`Struct::is_const`], etc.).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the definitions are invalid. See [`ParsedDefinitions::try_finalize`].
+    pub fn finalize(&mut self) {
+        self.try_finalize().unwrap_or_else(|e| panic!("{e}"))
+    }
 
-                    assert!(limits.multiplayer.start() <= limits.multiplayer.end(), "multiplayer has bad grenade limits (min > max)");
-                    assert!(limits.singleplayer.start() <= limits.singleplayer.end(), "user_interface has bad grenade limits (min > max)");
-                    assert!(limits.user_interface.start() <= limits.user_interface.end(), "user_interface has bad grenade limits (min > max)");
+    /// Finalize the definitions, returning an error instead of panicking if they are invalid.
+    ///
+    /// Nothing is changed if an error is returned.
+    ///
+    /// See [`ParsedDefinitions::finalize`].
+    pub fn try_finalize(&mut self) -> Result<(), DefinitionError> {
+        self.finalize_and_validate(true, false)?;
+        self.resolve_parent_class_references();
+        self.find_const_structs();
+        self.find_struct_contents();
+        Ok(())
+    }
 
-                    limits
-                },
-                compressed_models: first_bool("compressed_models", true).unwrap(),
-                bitmap_options: get_chain("bitmap_options", true)[0].1.as_object().map(|o| EngineBitmapOptions {
-                    swizzled: o.get("swizzled").unwrap().as_bool().unwrap(),
-                    texture_dimension_must_modulo_block_size: o.get("texture_dimension_must_modulo_block_size").unwrap().as_bool().unwrap(),
-                    cubemap_faces_stored_separately: o.get("cubemap_faces_stored_separately").unwrap().as_bool().unwrap(),
-                    alignment: o.get("alignment").unwrap().as_u64().unwrap(),
-                }).unwrap(),
-                data_alignment: first_u64("data_alignment", true).unwrap(),
-                compressed_data_alignment: first_u64("compressed_data_alignment", true).unwrap(),
-                name: engine_name.to_owned(),
-                required_tags,
-                version: first_string("version", false),
-                obfuscated_header_layout: first_bool("obfuscated_header_layout", false).unwrap_or(false)
-            });
-        }
+    /// Finalize definitions that were deliberately loaded without any engines.
+    ///
+    /// Engine references (supported engines and limits) are kept as written rather than being
+    /// validated or expanded through engine inheritance.
+    pub(crate) fn finalize_without_engines(&mut self) {
+        self.finalize_and_validate(false, false).unwrap_or_else(|e| panic!("{e}"));
+        self.resolve_parent_class_references();
+        self.find_const_structs();
+        self.find_struct_contents();
     }
 
     // Fix all tag references to have child groups
     pub(crate) fn resolve_parent_class_references(&mut self) {
-        for (_, named_object) in &mut self.objects {
+        for named_object in self.objects.values_mut() {
             if let NamedObject::Struct(s) = named_object {
                 for f in &mut s.fields {
                     if let StructFieldType::Object(FieldObject::TagReference { allowed_groups } ) = &mut f.field_type {
@@ -386,199 +296,350 @@ impl ParsedDefinitions {
         }
     }
 
-    pub(crate) fn finalize_and_assert_valid(&mut self) {
-        let engine_inheritance = {
-            // Engine -> All engines that inherit that engine
-            let mut engines: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
-            for i in self.engines.keys() {
-                engines.insert(i.to_owned(), BTreeSet::new());
-            }
+    // If partial_groups is set, groups may refer to structs that were deliberately not loaded
+    // (see load_groups_from_json)
+    //
+    // Nothing is changed if an error is returned.
+    pub(crate) fn finalize_and_validate(&mut self, check_engines: bool, partial_groups: bool) -> Result<(), DefinitionError> {
+        // Missing references, duplicates, sizes, copies, and cycles are checked in one place, so
+        // validating and finalizing always agree on these
+        if let Some(problem) = self.validation_problems(partial_groups).into_iter().next() {
+            return Err(problem.into())
+        }
 
-            for (to_inherit, inheritors) in engines.iter_mut() {
-                loop {
-                    let mut again = false;
+        // Engine -> All engines that inherit that engine
+        let engine_inheritance: BTreeMap<String, BTreeSet<String>> = self.engines
+            .keys()
+            .map(|e| (e.to_owned(), self.engine_descendants(e)))
+            .collect();
 
-                    for (name, engine) in self.engines.iter() {
-                        if inheritors.contains(name) || name == to_inherit {
-                            continue
-                        }
+        let missing = |object: &str, field: &str, reference: &str| DefinitionError::MissingReference {
+            object: object.to_owned(),
+            field: field.to_owned(),
+            reference: reference.to_owned()
+        };
 
-                        if let Some(q) = engine.inherits.as_ref() {
-                            if q == to_inherit || !inheritors.contains(q) {
-                                inheritors.insert(name.to_owned());
-                                again = true;
-                            }
-                        }
+        let unknown_engine_policy = self.unknown_engine_policy;
+        let validate_supported_engines = |supported_engines: &mut SupportedEngines, object_name: &str, field_name: &str| -> Result<()> {
+            if !check_engines {
+                return Ok(())
+            }
+
+            let (v, allow_list) = match &*supported_engines {
+                SupportedEngines::AllEngines => return Ok(()),
+                SupportedEngines::SomeEngines(v) => (v, true),
+                SupportedEngines::AllExcept(v) => (v, false)
+            };
+
+            let mut actual_engines = BTreeSet::new();
+            let mut only_unknown_engines = true;
+            for engine in v.iter() {
+                let Some(inheritors) = engine_inheritance.get(engine) else {
+                    if unknown_engine_policy == UnknownEnginePolicy::Error {
+                        return Err(missing(object_name, field_name, engine))
                     }
+                    continue
+                };
+                only_unknown_engines = false;
+                actual_engines.insert(engine.to_string());
+                actual_engines.extend(inheritors.iter().cloned());
+            }
 
-                    if !again {
-                        break
-                    };
-                }
+            // A list of only unknown engines would otherwise support nothing
+            if allow_list && only_unknown_engines && unknown_engine_policy == UnknownEnginePolicy::TreatAsSupported {
+                *supported_engines = SupportedEngines::AllEngines;
+                return Ok(())
             }
 
-            engines
-        };
+            for engine in actual_engines.iter() {
+                let engine = &self.engines[engine];
+                ensure!(
+                    !engine.build.as_ref().is_some_and(|b| b.enforced && engine.cache_default),
+                    engine.name,
+                    "engine {} is marked as enforced but also cache_default",
+                    engine.name
+                );
+            }
 
-        let validate_supported_engines = |supported_engines: &mut SupportedEngines, object_name: &str, field_name: &str| {
-            if let SupportedEngines::SomeEngines(v) = supported_engines {
-                let mut actual_engines = BTreeSet::new();
-                for engine in v.iter() {
-                    actual_engines.insert(engine.to_string());
-                    actual_engines.extend(engine_inheritance
-                        .get(engine)
-                        .expect("failed to get an engine; this is a bug")
-                        .iter()
-                        .cloned()
-                    )
-                }
+            if let SupportedEngines::SomeEngines(v) | SupportedEngines::AllExcept(v) = supported_engines {
                 *v = actual_engines;
+            }
+            Ok(())
+        };
 
-                for engine in v.iter() {
-                    if !self.engines.contains_key(engine) {
-                        panic!("{object_name}::{field_name}'s limits references an engine {engine} which does not exist");
-                    }
-                    let engine = &self.engines[engine];
-                    if engine.build.as_ref().is_some_and(|b| b.enforced && engine.cache_default) {
-                        panic!("engine {object_name} is marked as enforced but also cache_default");
+        // Narrow supported engines with introduced_in/removed_in (this is done after expanding them)
+        let all_engines: BTreeSet<String> = self.engines.keys().cloned().collect();
+        let apply_engine_markers = |flags: &mut Flags, object_name: &str, field_name: &str| -> Result<()> {
+            if !check_engines || (flags.introduced_in.is_none() && flags.removed_in.is_none()) {
+                return Ok(())
+            }
+
+            let marker_engines = |engine: &String| -> Result<Option<BTreeSet<String>>> {
+                let Some(inheritors) = engine_inheritance.get(engine) else {
+                    if unknown_engine_policy == UnknownEnginePolicy::Error {
+                        return Err(missing(object_name, field_name, engine))
                     }
+                    return Ok(None)
+                };
+                let mut engines = inheritors.clone();
+                engines.insert(engine.to_owned());
+                Ok(Some(engines))
+            };
+
+            // Supported engines were already expanded through inheritance by this point
+            let mut supported = match &flags.supported_engines {
+                SupportedEngines::AllEngines => all_engines.clone(),
+                SupportedEngines::SomeEngines(v) => v.clone(),
+                SupportedEngines::AllExcept(v) => all_engines.difference(v).cloned().collect()
+            };
+            if let Some(introduced) = &flags.introduced_in {
+                match marker_engines(introduced)? {
+                    Some(introduced) => supported.retain(|e| introduced.contains(e)),
+                    None if unknown_engine_policy == UnknownEnginePolicy::TreatAsUnsupported => supported.clear(),
+                    None => ()
                 }
             }
+            if let Some(removed) = flags.removed_in.as_ref().map(marker_engines).transpose()?.flatten() {
+                supported.retain(|e| !removed.contains(e));
+            }
+            flags.supported_engines = SupportedEngines::SomeEngines(supported);
+            Ok(())
         };
 
         let available_groups: BTreeSet<String> = self.groups.keys().cloned().collect();
-        for (group_name, group) in &mut self.groups {
+        let validate_see_also = |see_also: &[SeeAlso], object_name: &str, field_name: &str| -> Result<()> {
+            for s in see_also {
+                match s {
+                    SeeAlso::Group(g) => {
+                        if !available_groups.contains(g) {
+                            return Err(missing(object_name, field_name, g))
+                        }
+                    },
+                    SeeAlso::Field { object, field } => {
+                        if !self.objects.get(object).is_some_and(|o| o.has_field(field)) {
+                            return Err(missing(object_name, field_name, &format!("{object}::{field}")))
+                        }
+                    }
+                }
+            }
+            Ok(())
+        };
+
+        let mut groups = self.groups.clone();
+        for (group_name, group) in &mut groups {
             let group_name_in_struct = &group.name;
-            assert_eq!(group_name_in_struct, group_name, "group name `{group_name_in_struct}` not consistent with name `{group_name}` in map");
+            ensure!(group_name_in_struct == group_name, group_name, "group name `{group_name_in_struct}` not consistent with name `{group_name}` in map");
 
-            let struct_name = &group.struct_name;
-            self.objects.get(struct_name).unwrap_or_else(|| panic!("group {group_name} refers to struct {struct_name} which does not exist"));
+            validate_supported_engines(&mut group.supported_engines, group_name, "supported_engines")?;
+            validate_see_also(&group.see_also, group_name, "see_also")?;
+        }
 
-            if let Some(s) = &group.supergroup {
-                available_groups.get(s).unwrap_or_else(|| panic!("group {group_name}'s supergroup refers to group {s} which does not exist"));
+        let mut extensions: BTreeMap<String, &str> = BTreeMap::new();
+        for (group_name, group) in &groups {
+            for e in group.extensions() {
+                ensure!(!e.is_empty() && !e.starts_with('.'), group_name, "group {group_name} has an invalid extension `{e}`");
+                if let Some(other) = extensions.insert(e.to_ascii_lowercase(), group_name) {
+                    return Err(invalid!(group_name, "groups {other} and {group_name} both use the extension {e}"))
+                }
             }
+        }
 
-            validate_supported_engines(&mut group.supported_engines, &group_name, "(self)");
+        let mut group_fourccs = BTreeMap::new();
+        for (group_name, group) in &groups {
+            if let Some(other) = group_fourccs.insert(group.fourcc_binary, group_name.to_owned()) {
+                return Err(invalid!(group_name, "groups {other} and {group_name} both use the fourcc {}", fourcc_to_string(group.fourcc_binary)))
+            }
+        }
+
+        let mut short_codes: BTreeMap<String, &str> = BTreeMap::new();
+        for (group_name, group) in &groups {
+            let Some(c) = group.short_code.as_ref() else {
+                continue
+            };
+            ensure!(!c.is_empty(), group_name, "group {group_name} has an empty short code");
+            if let Some(other) = short_codes.insert(c.to_ascii_lowercase(), group_name) {
+                return Err(invalid!(group_name, "groups {other} and {group_name} both use the short code {c}"))
+            }
+        }
+
+        if let Some(name) = self.custom_objects.keys().find(|n| self.objects.contains_key(*n)) {
+            return Err(invalid!(name, "object {name} has the same name as a custom object"))
         }
 
         let mut objects_to_verify = self.objects.clone();
         for (object_name, object) in &mut objects_to_verify {
             let name_in_object = object.name();
-            assert_eq!(name_in_object, object_name, "object name `{name_in_object}` not consistent with name `{object_name}` in map");
+            ensure!(name_in_object == object_name, object_name, "object name `{name_in_object}` not consistent with name `{object_name}` in map");
+            ensure!(object.copy_of() != Some(object_name.as_str()), object_name, "{object_name}::copy_of refers to itself");
 
-            let validate_flags = |flags: &mut Flags, field_name: &str| validate_supported_engines(&mut flags.supported_engines, &object_name, field_name);
+            let validate_flags = |flags: &mut Flags, field_name: &str| -> Result<()> {
+                validate_supported_engines(&mut flags.supported_engines, object_name, field_name)?;
+                apply_engine_markers(flags, object_name, field_name)?;
+                validate_see_also(&flags.see_also, object_name, field_name)
+            };
 
             match object {
                 NamedObject::Bitfield(b) => {
-                    validate_flags(&mut b.flags, "(self)");
+                    validate_flags(&mut b.flags, "(self)")?;
 
                     for f in &mut b.fields {
-                        validate_flags(&mut f.flags, &f.name);
+                        validate_flags(&mut f.flags, &f.name)?;
+                    }
+
+                    check_unique_rust_names(object_name, b.fields.iter().map(|f| (f.name.as_str(), f.name_rust_field.as_str(), f.name_rust_enum.as_str())))?;
+
+                    if !BITFIELD_WIDTHS.contains(&b.width) {
+                        return Err(DefinitionError::InvalidValue { object: object_name.to_owned(), key: "width".to_owned(), expected: "0, 8, 16, or 32" })
                     }
+                    ensure!(b.fields.len() <= b.width as usize, object_name, "bitfield {object_name} has too many fields; {} / {}", b.fields.len(), b.width);
 
-                    for i in 0..b.fields.len() {
-                        for j in i+1..b.fields.len() {
-                            let field_name = &b.fields[i].name;
-                            assert_ne!(field_name, &b.fields[j].name, "bitfield {object_name} has duplicate fields {field_name}");
+                    for group in &b.exclusive_groups {
+                        ensure!(group.len() >= 2, object_name, "bitfield {object_name} has an exclusive group with fewer than two fields");
+                        if let Some(f) = group.iter().find(|f| b.field_value(f).is_none()) {
+                            return Err(missing(object_name, "exclusive", f))
                         }
                     }
 
-                    assert!(b.fields.len() <= b.width as usize, "bitfield {object_name} has too many fields; {} / {}", b.fields.len(), b.width);
+                    for (field, implied) in &b.implications {
+                        let mask = b.field_value(field).ok_or_else(|| missing(object_name, "implies", field))?;
+                        if let Some(i) = implied.iter().find(|i| b.field_value(i).is_none()) {
+                            return Err(missing(object_name, field, i))
+                        }
+                        let normalized = b.normalize(mask);
+                        ensure!(
+                            b.violated_exclusive_groups(normalized).count() == 0,
+                            object_name,
+                            "bitfield {object_name}::{field} implies fields that are mutually exclusive"
+                        );
+                    }
                 },
                 NamedObject::Enum(e) => {
-                    validate_flags(&mut e.flags, "(self)");
+                    validate_flags(&mut e.flags, "(self)")?;
 
                     for f in &mut e.options {
-                        validate_flags(&mut f.flags, &f.name);
+                        validate_flags(&mut f.flags, &f.name)?;
                     }
 
-                    for i in 0..e.options.len() {
-                        for j in i+1..e.options.len() {
-                            let option_name = &e.options[i].name;
-                            assert_ne!(option_name, &e.options[j].name, "enum {object_name} has duplicate options {option_name}");
-                        }
-                    }
+                    check_unique_rust_names(object_name, e.options.iter().map(|f| (f.name.as_str(), f.name_rust_field.as_str(), f.name_rust_enum.as_str())))?;
 
-                    assert!(e.options.len() <= u16::MAX as usize, "enum {object_name} has too many options, {} / {}", e.options.len(), u16::MAX);
+                    ensure!(e.options.len() <= u16::MAX as usize, object_name, "enum {object_name} has too many options, {} / {}", e.options.len(), u16::MAX);
                 },
                 NamedObject::Struct(s) => {
-                    validate_flags(&mut s.flags, "(self)");
+                    validate_flags(&mut s.flags, "(self)")?;
 
-                    for i in 0..s.fields.len() {
-                        if matches!(s.fields[i].field_type, StructFieldType::Padding(_) | StructFieldType::EditorSection { .. } ) {
-                            continue
-                        }
-                        for j in i+1..s.fields.len() {
-                            if matches!(s.fields[j].field_type, StructFieldType::Padding(_) | StructFieldType::EditorSection { .. }) {
-                                continue
-                            }
-                            let field_name = &s.fields[i].name;
-                            assert_ne!(field_name, &s.fields[j].name, "struct {object_name} has duplicate fields {field_name}");
-                        }
-                    }
+                    check_unique_rust_names(
+                        object_name,
+                        s.fields
+                            .iter()
+                            .filter(|f| matches!(f.field_type, StructFieldType::Object(_)))
+                            .map(|f| (f.name.as_str(), f.name_rust_field.as_str(), f.name_rust_enum.as_str()))
+                    )?;
 
                     for f in &mut s.fields {
-                        // Consistency with named objects and groups
+                        // Referenced objects and groups were already checked, but indices also point to a reflexive
                         let field_name = &f.name;
-                        match &f.field_type {
-                            StructFieldType::Object(FieldObject::NamedObject(o)) => {
-                                self.objects.get(o).unwrap_or_else(|| panic!("{object_name}::{field_name} type refers to object {o} which does not exist"));
-                            },
-                            StructFieldType::Object(FieldObject::TagReference { allowed_groups }) => {
-                                for g in allowed_groups {
-                                    if g == "*" {
-                                        continue
-                                    }
-                                    self.groups.get(g).unwrap_or_else(|| panic!("{object_name}::{field_name} reference refers to tag group {g} which does not exist"));
-                                }
-                            },
-                            StructFieldType::Object(FieldObject::Reflexive(r)) => {
-                                self.objects.get(r).unwrap_or_else(|| panic!("{object_name}::{field_name} reflexive refers to object {r} which does not exist"));
-                            },
-                            StructFieldType::Object(FieldObject::ReflexiveIndex { struct_name, reflexive_name_display, .. }) => {
-                                let NamedObject::Struct(object) = self.objects.get(struct_name).unwrap_or_else(|| panic!("{object_name}::{field_name} index refers to object {struct_name} which does not exist")) else {
-                                    panic!("{object_name}::{field_name} index refers to object {struct_name} which is not a struct")
-                                };
-
+                        if let StructFieldType::Object(FieldObject::ReflexiveIndex { struct_name, reflexive_name_display, .. }) = &f.field_type {
+                            if let Some(NamedObject::Struct(object)) = self.objects.get(struct_name) {
                                 let Some(field) = object.fields.iter().find(|i| &i.name == reflexive_name_display) else {
-                                    panic!("{object_name}::{field_name} index refers to field {struct_name}::{reflexive_name_display} which was not found")
+                                    return Err(missing(object_name, field_name, &format!("{struct_name}::{reflexive_name_display}")))
                                 };
 
-                                match field.field_type {
-                                    StructFieldType::Object(FieldObject::Reflexive(_)) => {},
-                                    _ => {
-                                        panic!("{object_name}::{field_name} index refers to field {struct_name}::{reflexive_name_display} which is not a reflexive")
-                                    }
-                                }
-                                
-                                // TODO: We should make sure that a struct of struct_name is also an ancestor of this object (with however many levels...) 
+                                ensure!(
+                                    matches!(field.field_type, StructFieldType::Object(FieldObject::Reflexive(_))),
+                                    object_name,
+                                    "{object_name}::{field_name} index refers to field {struct_name}::{reflexive_name_display} which is not a reflexive"
+                                );
+
+                                // TODO: We should make sure that a struct of struct_name is also an ancestor of this object (with however many levels...)
                             }
-                            _ => ()
                         }
 
                         // Limits point to engines
-                        if let Some(n) = &f.limit {
-                            for (k, _) in n {
-                                if let LimitType::Engine(e) = k {
-                                    if !self.engines.contains_key(e) {
-                                        panic!("{object_name}::{field_name}'s limits contains an engine {e} which does not exist");
-                                    }
+                        if let Some(n) = f.limit.as_mut().filter(|_| check_engines) {
+                            if let Some(e) = n.keys().find_map(|k| match k {
+                                LimitType::Engine(e) if !self.engines.contains_key(e) && unknown_engine_policy == UnknownEnginePolicy::Error => Some(e),
+                                _ => None
+                            }) {
+                                return Err(missing(object_name, field_name, e))
+                            }
+                            n.retain(|k, _| !matches!(k, LimitType::Engine(e) if !self.engines.contains_key(e)));
+                        }
+
+                        // Limits point to limit profiles
+                        for k in f.limit.iter().flat_map(|l| l.keys()) {
+                            if let LimitType::Profile(p) = k {
+                                if !self.limit_profiles.contains_key(p) {
+                                    return Err(missing(object_name, field_name, p))
                                 }
                             }
                         }
 
-                        validate_flags(&mut f.flags, &field_name);
-                    }
+                        // Engine-specific array lengths point to engines and fit in the array
+                        if !f.engine_counts.is_empty() {
+                            let FieldCount::Array(default_count) = f.count else {
+                                return Err(invalid!(object_name, "{object_name}::{field_name} has engine-specific counts but is not an array"))
+                            };
+                            if check_engines && unknown_engine_policy != UnknownEnginePolicy::Error {
+                                f.engine_counts.retain(|e, _| self.engines.contains_key(e));
+                            }
+                            for (e, c) in &f.engine_counts {
+                                if check_engines && !self.engines.contains_key(e) {
+                                    return Err(missing(object_name, field_name, e))
+                                }
+                                ensure!(*c <= default_count, object_name, "{object_name}::{field_name}'s count for {e} ({c}) exceeds the default count ({default_count})");
+                            }
+                        }
 
-                    s.set_offsets_and_verify_sizes(self);
+                        // Minimum and maximum match the field's type and are in order
+                        f.bounds().map_err(|e| match e {
+                            DefinitionError::InvalidValue { object, key, expected } => DefinitionError::InvalidValue { object: format!("{object_name}::{object}"), key, expected },
+                            e => e
+                        })?;
+
+                        validate_flags(&mut f.flags, field_name)?;
+                    }
                 }
             }
         }
-        self.objects = objects_to_verify;
+
+        for object in objects_to_verify.values_mut() {
+            if let NamedObject::Struct(s) = object {
+                s.set_offsets(self);
+            }
+        }
+        self.check_unique_global_enums()?;
+
+        let previous_objects = core::mem::replace(&mut self.objects, objects_to_verify);
+        let previous_groups = core::mem::replace(&mut self.groups, groups);
+        if check_engines {
+            if let Err(e) = self.check_reflexive_byte_ceilings() {
+                self.objects = previous_objects;
+                self.groups = previous_groups;
+                return Err(e)
+            }
+        }
+
+        self.group_fourccs = group_fourccs;
+        self.index_referencers();
+        Ok(())
     }
 
     pub(crate) fn find_const_structs(&mut self) {
+        self.find_const_structs_in(None)
+    }
+
+    // If only is set, structs not in it are assumed to be up-to-date
+    pub(crate) fn find_const_structs_in(&mut self, only: Option<&BTreeSet<String>>) {
         let mut checked: BTreeMap<String, bool> = BTreeMap::new();
+        if let Some(only) = only {
+            for object in self.objects.values() {
+                if let NamedObject::Struct(s) = object {
+                    if !only.contains(&s.name) {
+                        checked.insert(s.name.to_owned(), s.is_const);
+                    }
+                }
+            }
+        }
 
         fn do_check<'a>(checked: &mut BTreeMap<String, bool>, field_type: &'a str, definitions: &'a ParsedDefinitions) -> bool {
             if let Some(c) = checked.get(field_type) {
@@ -612,6 +673,7 @@ impl ParsedDefinitions {
                             StructFieldType::Object(fo) => {
                                 let is_const = match fo {
                                     FieldObject::NamedObject(n) => do_check(checked, n, definitions),
+                                    FieldObject::Custom(c) => definitions.custom_objects[c].is_const,
                                     _ => fo.is_const().expect("field object is_const returned None and was not NamedObject")
                                 };
 
@@ -629,7 +691,9 @@ impl ParsedDefinitions {
         }
 
         for c in self.objects.values() {
-            do_check(&mut checked, c.name(), self);
+            if only.is_none_or(|o| o.contains(c.name())) {
+                do_check(&mut checked, c.name(), self);
+            }
         }
 
         for (name, can_be_const) in checked {
@@ -639,11 +703,116 @@ impl ParsedDefinitions {
             s.is_const = can_be_const;
         }
     }
+
+    pub(crate) fn find_struct_contents(&mut self) {
+        self.find_struct_contents_in(None)
+    }
+
+    // If only is set, structs not in it are assumed to be up-to-date
+    pub(crate) fn find_struct_contents_in(&mut self, only: Option<&BTreeSet<String>>) {
+        #[derive(Copy, Clone, Default)]
+        struct Contents {
+            tag_references: bool,
+            data: bool,
+            reflexives: bool,
+            max_depth: usize
+        }
+
+        // Returns the contents and whether they depend on a struct that is still being checked
+        fn do_check<'a>(checked: &mut BTreeMap<&'a str, Contents>, in_progress: &mut Vec<&'a str>, struct_name: &'a str, definitions: &'a ParsedDefinitions) -> (Contents, bool) {
+            if let Some(c) = checked.get(struct_name) {
+                return (*c, false)
+            }
+
+            let Some(NamedObject::Struct(s)) = definitions.objects.get(struct_name) else {
+                return (Contents::default(), false)
+            };
+
+            // Recursive structs add nothing new beyond what is already being checked
+            if in_progress.contains(&struct_name) {
+                return (Contents::default(), true)
+            }
+            in_progress.push(struct_name);
+
+            let mut contents = Contents::default();
+            let mut incomplete = false;
+            for f in &s.fields {
+                if f.flags.exclude {
+                    continue
+                }
+
+                let StructFieldType::Object(fo) = &f.field_type else {
+                    continue
+                };
+
+                match fo {
+                    FieldObject::NamedObject(n) | FieldObject::Reflexive(n) => {
+                        let is_reflexive = matches!(fo, FieldObject::Reflexive(_));
+                        let (inner, inner_incomplete) = do_check(checked, in_progress, n, definitions);
+                        incomplete |= inner_incomplete;
+                        contents.tag_references |= inner.tag_references;
+                        contents.data |= inner.data;
+                        contents.reflexives |= inner.reflexives || is_reflexive;
+                        contents.max_depth = contents.max_depth.max(inner.max_depth + is_reflexive as usize);
+                    },
+                    FieldObject::TagReference { .. } => contents.tag_references = true,
+                    FieldObject::Data | FieldObject::FileData | FieldObject::BSPVertexData | FieldObject::UTF16String => contents.data = true,
+                    _ => ()
+                }
+            }
+
+            in_progress.pop();
+            if !incomplete || in_progress.is_empty() {
+                checked.insert(struct_name, contents);
+            }
+            (contents, incomplete)
+        }
+
+        let mut checked = BTreeMap::new();
+        if let Some(only) = only {
+            for object in self.objects.values() {
+                let NamedObject::Struct(s) = object else {
+                    continue
+                };
+                if !only.contains(&s.name) {
+                    let contents = Contents { tag_references: s.contains_tag_references, data: s.contains_data, reflexives: s.contains_reflexives, max_depth: s.max_depth };
+                    checked.insert(s.name.as_str(), contents);
+                }
+            }
+        }
+        for name in self.objects.keys().filter(|n| only.is_none_or(|o| o.contains(*n))) {
+            do_check(&mut checked, &mut Vec::new(), name, self);
+        }
+
+        let checked: Vec<(String, Contents)> = checked.into_iter().map(|(k, v)| (k.to_owned(), v)).collect();
+        for (name, contents) in checked {
+            let Some(NamedObject::Struct(s)) = self.objects.get_mut(&name) else {
+                continue
+            };
+            s.contains_tag_references = contents.tag_references;
+            s.contains_data = contents.data;
+            s.contains_reflexives = contents.reflexives;
+            s.max_depth = contents.max_depth;
+        }
+    }
+}
+
+/// Get the JSON objects for all built-in definitions.
+///
+/// These can be passed to [`ParsedDefinitions::load_from_json`].
+pub fn get_all_definitions() -> Vec<Map<String, Value>> {
+    let mut all = get_tag_definitions();
+    all.append(&mut get_engine_definitions());
+    all
 }
 
-pub(crate) fn get_all_definitions() -> Vec<Map<String, Value>> {
+/// Get the JSON objects for all built-in tag and map definitions (everything except engines).
+///
+/// Groups whose `groups-*` feature is disabled are not included.
+pub fn get_tag_definitions() -> Vec<Map<String, Value>> {
     let mut jsons: BTreeMap<&'static str, &'static [u8]> = BTreeMap::new();
 
+    jsons.insert("limit_profiles.json", include_bytes!("../../json/limit_profiles.json"));
     jsons.insert("tag/actor_variant.json", include_bytes!("../../json/tag/actor_variant.json"));
     jsons.insert("tag/actor.json", include_bytes!("../../json/tag/actor.json"));
     jsons.insert("tag/antenna.json", include_bytes!("../../json/tag/antenna.json"));
@@ -677,6 +846,7 @@ pub(crate) fn get_all_definitions() -> Vec<Map<String, Value>> {
     jsons.insert("tag/hud_interface_types.json", include_bytes!("../../json/tag/hud_interface_types.json"));
     jsons.insert("tag/hud_message_text.json", include_bytes!("../../json/tag/hud_message_text.json"));
     jsons.insert("tag/hud_number.json", include_bytes!("../../json/tag/hud_number.json"));
+    #[cfg(feature = "groups-preferences")]
     jsons.insert("tag/input_device_defaults.json", include_bytes!("../../json/tag/input_device_defaults.json"));
     jsons.insert("tag/item_collection.json", include_bytes!("../../json/tag/item_collection.json"));
     jsons.insert("tag/item.json", include_bytes!("../../json/tag/item.json"));
@@ -689,6 +859,7 @@ pub(crate) fn get_all_definitions() -> Vec<Map<String, Value>> {
     jsons.insert("tag/model_animations.json", include_bytes!("../../json/tag/model_animations.json"));
     jsons.insert("tag/model_collision_geometry.json", include_bytes!("../../json/tag/model_collision_geometry.json"));
     jsons.insert("tag/model.json", include_bytes!("../../json/tag/model.json"));
+    #[cfg(feature = "groups-ui")]
     jsons.insert("tag/multiplayer_scenario_description.json", include_bytes!("../../json/tag/multiplayer_scenario_description.json"));
     jsons.insert("tag/object.json", include_bytes!("../../json/tag/object.json"));
     jsons.insert("tag/particle_system.json", include_bytes!("../../json/tag/particle_system.json"));
@@ -696,6 +867,7 @@ pub(crate) fn get_all_definitions() -> Vec<Map<String, Value>> {
     jsons.insert("tag/physics.json", include_bytes!("../../json/tag/physics.json"));
     jsons.insert("tag/placeholder.json", include_bytes!("../../json/tag/placeholder.json"));
     jsons.insert("tag/point_physics.json", include_bytes!("../../json/tag/point_physics.json"));
+    #[cfg(feature = "groups-preferences")]
     jsons.insert("tag/preferences_network_game.json", include_bytes!("../../json/tag/preferences_network_game.json"));
     jsons.insert("tag/projectile.json", include_bytes!("../../json/tag/projectile.json"));
     jsons.insert("tag/scenario_structure_bsp.json", include_bytes!("../../json/tag/scenario_structure_bsp.json"));
@@ -720,12 +892,15 @@ pub(crate) fn get_all_definitions() -> Vec<Map<String, Value>> {
     jsons.insert("tag/string_list.json", include_bytes!("../../json/tag/string_list.json"));
     jsons.insert("tag/tag.json", include_bytes!("../../json/tag/tag.json"));
     jsons.insert("tag/tag_collection.json", include_bytes!("../../json/tag/tag_collection.json"));
+    #[cfg(feature = "groups-ui")]
     jsons.insert("tag/ui_widget_collection.json", include_bytes!("../../json/tag/ui_widget_collection.json"));
+    #[cfg(feature = "groups-ui")]
     jsons.insert("tag/ui_widget_definition.json", include_bytes!("../../json/tag/ui_widget_definition.json"));
     jsons.insert("tag/unicode_string_list.json", include_bytes!("../../json/tag/unicode_string_list.json"));
     jsons.insert("tag/unit_hud_interface.json", include_bytes!("../../json/tag/unit_hud_interface.json"));
     jsons.insert("tag/unit.json", include_bytes!("../../json/tag/unit.json"));
     jsons.insert("tag/vehicle.json", include_bytes!("../../json/tag/vehicle.json"));
+    #[cfg(feature = "groups-ui")]
     jsons.insert("tag/virtual_keyboard.json", include_bytes!("../../json/tag/virtual_keyboard.json"));
     jsons.insert("tag/weapon_hud_interface.json", include_bytes!("../../json/tag/weapon_hud_interface.json"));
     jsons.insert("tag/weapon.json", include_bytes!("../../json/tag/weapon.json"));
@@ -735,124 +910,569 @@ pub(crate) fn get_all_definitions() -> Vec<Map<String, Value>> {
     jsons.insert("map/cache.json", include_bytes!("../../json/map/cache.json"));
     jsons.insert("map/resource.json", include_bytes!("../../json/map/resource.json"));
 
+    parse_definition_files(jsons)
+}
+
+/// Whether every built-in engine is embedded (i.e. no `engines-*` feature is disabled).
+///
+/// If not, built-in definitions are finalized with [`UnknownEnginePolicy::TreatAsUnsupported`],
+/// since other definitions may still refer to the missing engines.
+pub const ALL_BUILTIN_ENGINES: bool = cfg!(all(
+    feature = "engines-pc",
+    feature = "engines-xbox",
+    feature = "engines-mac",
+    feature = "engines-mcc"
+));
+
+/// Get the JSON objects for all built-in engine definitions.
+///
+/// Engines whose `engines-*` feature is disabled are not included.
+pub fn get_engine_definitions() -> Vec<Map<String, Value>> {
+    #[cfg_attr(not(any(feature = "engines-pc", feature = "engines-xbox")), allow(unused_mut))]
+    let mut jsons: BTreeMap<&'static str, &'static [u8]> = BTreeMap::new();
+
+    #[cfg(feature = "engines-mac")]
     jsons.insert("engine/halo macintosh demo.json", include_bytes!("../../json/engine/halo macintosh demo.json"));
+    #[cfg(feature = "engines-mac")]
     jsons.insert("engine/halo macintosh retail.json", include_bytes!("../../json/engine/halo macintosh retail.json"));
+    #[cfg(feature = "engines-mcc")]
     jsons.insert("engine/halo mcc cea.json", include_bytes!("../../json/engine/halo mcc cea.json"));
+    #[cfg(feature = "engines-pc")]
     jsons.insert("engine/halo pc custom edition.json", include_bytes!("../../json/engine/halo pc custom edition.json"));
+    #[cfg(feature = "engines-pc")]
     jsons.insert("engine/halo pc demo.json", include_bytes!("../../json/engine/halo pc demo.json"));
+    #[cfg(feature = "engines-pc")]
     jsons.insert("engine/halo pc retail.json", include_bytes!("../../json/engine/halo pc retail.json"));
+    #[cfg(feature = "engines-pc")]
     jsons.insert("engine/halo pc.json", include_bytes!("../../json/engine/halo pc.json"));
+    #[cfg(feature = "engines-pc")]
     jsons.insert("engine/halo pc betas.json", include_bytes!("../../json/engine/halo pc betas.json"));
+    #[cfg(feature = "engines-xbox")]
     jsons.insert("engine/halo xbox ntsc demo.json", include_bytes!("../../json/engine/halo xbox ntsc demo.json"));
+    #[cfg(feature = "engines-xbox")]
     jsons.insert("engine/halo xbox ntsc jp.json", include_bytes!("../../json/engine/halo xbox ntsc jp.json"));
+    #[cfg(feature = "engines-xbox")]
     jsons.insert("engine/halo xbox ntsc tw.json", include_bytes!("../../json/engine/halo xbox ntsc tw.json"));
+    #[cfg(feature = "engines-xbox")]
     jsons.insert("engine/halo xbox ntsc us.json", include_bytes!("../../json/engine/halo xbox ntsc us.json"));
+    #[cfg(feature = "engines-xbox")]
     jsons.insert("engine/halo xbox pal.json", include_bytes!("../../json/engine/halo xbox pal.json"));
+    #[cfg(feature = "engines-xbox")]
     jsons.insert("engine/halo xbox.json", include_bytes!("../../json/engine/halo xbox.json"));
+    #[cfg(feature = "engines-xbox")]
     jsons.insert("engine/halo xbox betas.json", include_bytes!("../../json/engine/halo xbox betas.json"));
+    #[cfg(feature = "engines-xbox")]
     jsons.insert("engine/custom/halo xbox nhe.json", include_bytes!("../../json/engine/custom/halo xbox nhe.json"));
+    #[cfg(feature = "engines-xbox")]
     jsons.insert("engine/custom/halo xbox pro.json", include_bytes!("../../json/engine/custom/halo xbox pro.json"));
+    #[cfg(feature = "engines-pc")]
     jsons.insert("engine/custom/halo pc custom edition extended.json", include_bytes!("../../json/engine/custom/halo pc custom edition extended.json"));
 
+    parse_definition_files(jsons)
+}
+
+fn parse_definition_files(jsons: BTreeMap<&'static str, &'static [u8]>) -> Vec<Map<String, Value>> {
     jsons.into_iter()
-            .map(|(file,v)| (file, from_slice::<Value>(v).unwrap_or_else(|e| panic!("failed to parse {file}: {e}"))))
-            .map(|(file, v)| (file, v.as_array().map(|a| a.to_owned()).unwrap_or_else(|| panic!("failed to convert {file} to an array"))))
-            .map(|(file, v)| {
-                let mut all_entries = v.iter()
-                    .map(|o| o.as_object().unwrap_or_else(|| panic!("invalid objects in {file}")).to_owned())
-                    .collect::<Vec<Map<String, Value>>>();
-
-                for i in &mut all_entries {
-                    i.insert("__json_file".to_string(), Value::String(file.to_string()));
+            .flat_map(|(file, v)| parse_definition_file(file, v))
+            .collect()
+}
+
+/// Parse the contents of a single definition file into JSON objects, tagging each with `file`.
+pub(crate) fn parse_definition_file(file: &str, data: &[u8]) -> Vec<Map<String, Value>> {
+    try_parse_definition_file(file, data).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Parse the contents of a single definition file into JSON objects, tagging each with `file`.
+pub(crate) fn try_parse_definition_file(file: &str, data: &[u8]) -> core::result::Result<Vec<Map<String, Value>>, DefinitionError> {
+    let invalid = |message: String| DefinitionError::InvalidJson { file: file.to_owned(), message };
+    let v = from_slice::<Value>(data).map_err(|e| invalid(format!("failed to parse {file}: {e}")))?;
+    let v = v.as_array().ok_or_else(|| invalid(format!("failed to convert {file} to an array")))?;
+
+    let mut all_entries = v.iter()
+        .map(|o| o.as_object().map(|o| o.to_owned()).ok_or_else(|| invalid(format!("invalid objects in {file}"))))
+        .collect::<core::result::Result<Vec<Map<String, Value>>, DefinitionError>>()?;
+
+    for i in &mut all_entries {
+        i.insert("__json_file".to_string(), Value::String(file.to_string()));
+    }
+
+    Ok(all_entries)
+}
+
+/// Get an optional key, converting it with `get`, or return an error saying what was `expected` if
+/// it cannot be converted.
+fn get_optional<T>(object: &Map<String, Value>, key: &str, expected: &'static str, get: impl FnOnce(&Value) -> Option<T>) -> Result<Option<T>> {
+    object.get(key).map(|v| get(v).ok_or_else(|| wrong_type!(object, key, expected))).transpose()
+}
+
+/// Get an array of strings, or `None` if `value` is not one.
+fn string_array<C: FromIterator<String>>(value: &Value) -> Option<C> {
+    value.as_array()?.iter().map(|s| s.as_str().map(ToOwned::to_owned)).collect()
+}
+
+/// Get the Rust field and enum names of a field, option, or group.
+///
+/// These are only generated for non-empty, ASCII names.
+fn rust_names(object_name: &str, name: &str) -> Result<(String, String)> {
+    ensure!(!name.is_empty(), object_name, "{object_name} has a field or option with an empty name");
+    ensure!(name.is_ascii(), object_name, "{object_name} has a field, option, or group with a non-ASCII name `{name}`");
+    Ok((format_for_rust_fields(name), format_for_rust_enums(name)))
+}
+
+fn load_group(object_name: &str, object: &Map<String, Value>) -> Result<TagGroup> {
+    let fourcc_binary: u32 = oget_number!(object, "fourcc_binary", as_u64).try_into().map_err(|_| wrong_type!(object, "fourcc_binary", "a 32-bit unsigned integer"))?;
+    let version: u16 = oget_number!(object, "version", as_u64).try_into().map_err(|_| wrong_type!(object, "version", "a 16-bit unsigned integer"))?;
+    Ok(TagGroup {
+        struct_name: oget_str!(object, "struct").to_owned(),
+        definition_file: oget_str!(object, "__json_file").to_owned(),
+        supergroup: get_optional(object, "supergroup", "a string", |g| g.as_str().map(ToOwned::to_owned))?,
+        supported_engines: SupportedEngines::load_from_json(object)?,
+        version,
+        fourcc_binary,
+        name_rust_enum: rust_names(object_name, object_name)?.1,
+        name: object_name.to_owned(),
+        see_also: load_see_also(object)?,
+        extension: get_optional(object, "extension", "a string", |e| e.as_str().map(ToOwned::to_owned))?.unwrap_or_else(|| object_name.to_owned()),
+        extension_aliases: get_optional(object, "extension_aliases", "an array of strings", string_array)?.unwrap_or_default(),
+        scenario_types: get_optional(object, "scenario_types", "an array of scenario types", |t| {
+            t.as_array()?.iter().map(|t| ScenarioType::from_name(t.as_str()?)).collect()
+        })?,
+        short_code: get_optional(object, "short_code", "a string", |c| c.as_str().map(ToOwned::to_owned))?,
+        sort_order: get_optional(object, "sort_order", "an unsigned 32-bit integer", |o| o.as_u64().and_then(|o| u32::try_from(o).ok()))?,
+        documentation: get_optional(object, "documentation", "an object with an overview and optional usage notes", |d| {
+            let d = d.as_object()?;
+            Some(GroupDocumentation {
+                overview: d.get("overview")?.as_str()?.to_owned(),
+                usage_notes: match d.get("usage_notes") {
+                    Some(n) => string_array(n)?,
+                    None => Vec::new()
                 }
+            })
+        })?,
+    })
+}
+
+fn load_engine(engine_name: &str, engine: &Map<String, Value>, all_engines: &BTreeMap<String, Map<String, Value>>) -> Result<Engine> {
+    let missing = |what: &str| DefinitionError::MissingKey { object: engine_name.to_owned(), key: what.to_owned() };
+    let wrong = |what: &str, expected: &'static str| DefinitionError::InvalidValue { object: engine_name.to_owned(), key: what.to_owned(), expected };
+
+    // Values are ("engine::value", value), starting with this engine and going through the engines it inherits
+    let get_chain = |what: &str| -> Result<Vec<(String, Value)>> {
+        let mut chain = Vec::new();
+        let mut current = engine_name;
+        for _ in 0..=all_engines.len() {
+            let engine = all_engines.get(current).ok_or_else(|| DefinitionError::MissingReference {
+                object: engine_name.to_owned(),
+                field: "inherits".to_owned(),
+                reference: current.to_owned()
+            })?;
+            if let Some(n) = engine.get(what) {
+                chain.push((format!("{current}::{what}"), n.to_owned()))
+            }
+            match engine.get("inherits") {
+                Some(i) => current = i.as_str().ok_or_else(|| wrong("inherits", "a string"))?,
+                None => return Ok(chain)
+            }
+        }
+        Err(wrong("inherits", "a chain of engines without loops"))
+    };
+
+    let hex_to_u64 = |hex: &Value| -> Option<u64> {
+        let str = hex.as_str()?;
+        if !str.starts_with("0x") {
+            return None
+        }
+        u64::from_str_radix(&str[2..], 16).ok()
+    };
+
+    let first_value = |what: &str| -> Result<Option<Value>> { Ok(get_chain(what)?.into_iter().next().map(|(_, v)| v)) };
+    let required_value = |what: &str| first_value(what)?.ok_or_else(|| missing(what));
+    let first_object = |what: &str| -> Result<Option<Map<String, Value>>> { first_value(what)?.map(|v| v.as_object().cloned().ok_or_else(|| wrong(what, "an object"))).transpose() };
+    let first_string = |what: &str| -> Result<Option<String>> { first_value(what)?.map(|v| v.as_str().map(ToOwned::to_owned).ok_or_else(|| wrong(what, "a string"))).transpose() };
+    let first_u64 = |what: &str| -> Result<Option<u64>> { first_value(what)?.map(|v| v.as_u64().ok_or_else(|| wrong(what, "an unsigned integer"))).transpose() };
+    let first_bool = |what: &str| -> Result<Option<bool>> { first_value(what)?.map(|v| v.as_bool().ok_or_else(|| wrong(what, "a boolean"))).transpose() };
+    let local_bool = |what: &str| engine.get(what).map_or(Some(false), |v| v.as_bool()).ok_or_else(|| wrong(what, "a boolean"));
+
+    let base_memory_address = {
+        let bma_search = get_chain("base_memory_address")?;
+        let (bma_path, bma_obj) = bma_search.first().ok_or_else(|| missing("base_memory_address"))?;
+
+        let (bma_address_obj, bma_inferred_obj) = match bma_obj {
+            Value::Object(o) => (o.get("value").ok_or_else(|| invalid!(engine_name, "{bma_path} has no address"))?, o.get("inferred")),
+            Value::String(_) => (bma_obj, None),
+            _ => return Err(invalid!(engine_name, "{bma_path} is not object or string"))
+        };
+
+        BaseMemoryAddress {
+            address: hex_to_u64(bma_address_obj).ok_or_else(|| invalid!(engine_name, "{bma_path}'s address is nonhex"))?,
+            inferred: bma_inferred_obj.map_or(Some(false), |i| i.as_bool()).ok_or_else(|| invalid!(engine_name, "{bma_path} inferred is non-bool"))?
+        }
+    };
+
+    let max_cache_file_size = {
+        let cfz_search = get_chain("max_cache_file_size")?;
+        let (cfz_path, cfz_obj) = cfz_search.first().ok_or_else(|| missing("max_cache_file_size"))?;
+
+        let (multiplayer, singleplayer, user_interface) = match cfz_obj {
+            Value::Object(o) => {
+                let get = |what: &str| o.get(what).ok_or_else(|| invalid!(engine_name, "{cfz_path} has no {what}"));
+                (get("multiplayer")?, get("singleplayer")?, get("user_interface")?)
+            },
+            Value::String(_) => (cfz_obj, cfz_obj, cfz_obj),
+            _ => return Err(invalid!(engine_name, "{cfz_path} is not object or string"))
+        };
+        let hex = |value: &Value, what: &str| hex_to_u64(value).ok_or_else(|| invalid!(engine_name, "{cfz_path} {what} is not hex"));
+
+        EngineCacheFileSize {
+            multiplayer: hex(multiplayer, "multiplayer")?,
+            singleplayer: hex(singleplayer, "singleplayer")?,
+            user_interface: hex(user_interface, "user_interface")?,
+        }
+    };
+
+    let required_tags = {
+        let ert = get_chain("required_tags")?;
+        if ert.is_empty() {
+            return Err(missing("required_tags"))
+        }
+
+        let mut required_tags = EngineRequiredTags::default();
+
+        for (path, obj) in &ert {
+            let obj = obj.as_object().ok_or_else(|| invalid!(engine_name, "{path} is non-object"))?;
+            for (tags, what) in [
+                (&mut required_tags.all, "all"),
+                (&mut required_tags.user_interface, "user_interface"),
+                (&mut required_tags.singleplayer, "singleplayer"),
+                (&mut required_tags.multiplayer, "multiplayer")
+            ] {
+                let Some(list) = obj.get(what) else {
+                    continue
+                };
+                let list: Vec<String> = string_array(list).ok_or_else(|| invalid!(engine_name, "{path}::{what} is not an array of strings"))?;
+                tags.extend(list);
+            }
+        }
+
+        required_tags.all.dedup();
+        required_tags.multiplayer.dedup();
+        required_tags.singleplayer.dedup();
+        required_tags.user_interface.dedup();
+
+        required_tags
+    };
 
-                all_entries
+    let build = match first_object("build")? {
+        Some(n) => {
+            let aliases = match n.get("aliases") {
+                Some(a) => string_array(a).ok_or_else(|| wrong("build", "an object with an array of string aliases"))?,
+                None => vec![]
+            };
+            let string = oget_str!(&n, "version").to_string();
+            ensure!(string.len() < 32, engine_name, "{engine_name}'s build version exceeds 31 characters");
+
+            Some(Build {
+                string,
+                aliases,
+                enforced: oget_bool!(&n, "enforced")
             })
-            .flatten()
-            .collect()
+        },
+        None => None
+    };
+
+    let grenades = {
+        let wrong_grenades = || wrong("grenades", "a count from 0 to 255, or minimum and maximum counts");
+        let as_u8 = |value: &Value| value.as_u64().and_then(|v| u8::try_from(v).ok()).ok_or_else(wrong_grenades);
+
+        // singleplayer, multiplayer, then user_interface
+        let parse_limits = |value: &Value| -> Result<(u8, u8, u8)> {
+            match value {
+                Value::Number(_) => {
+                    let q = as_u8(value)?;
+                    Ok((q, q, q))
+                },
+                Value::Object(o) => {
+                    let get = |what: &str| o.get(what).ok_or_else(wrong_grenades).and_then(as_u8);
+                    Ok((get("singleplayer")?, get("multiplayer")?, get("user_interface")?))
+                },
+                _ => Err(wrong_grenades())
+            }
+        };
+
+        let value = required_value("grenades")?;
+        let (minimum, maximum) = match &value {
+            Value::Number(_) => {
+                let q = parse_limits(&value)?;
+                (q, q)
+            },
+            Value::Object(o) => (
+                parse_limits(o.get("minimum").ok_or_else(wrong_grenades)?)?,
+                parse_limits(o.get("maximum").ok_or_else(wrong_grenades)?)?
+            ),
+            _ => return Err(wrong_grenades())
+        };
+
+        ensure!(minimum.0 <= maximum.0, engine_name, "{engine_name} has bad singleplayer grenade limits (min > max)");
+        ensure!(minimum.1 <= maximum.1, engine_name, "{engine_name} has bad multiplayer grenade limits (min > max)");
+        ensure!(minimum.2 <= maximum.2, engine_name, "{engine_name} has bad user_interface grenade limits (min > max)");
+
+        EngineGrenades {
+            singleplayer: minimum.0..=maximum.0,
+            multiplayer: minimum.1..=maximum.1,
+            user_interface: minimum.2..=maximum.2,
+        }
+    };
+
+    let bitmap_options = {
+        let o = first_object("bitmap_options")?.ok_or_else(|| missing("bitmap_options"))?;
+        let wrong_options = || wrong("bitmap_options", "an object with swizzled, texture_dimension_must_modulo_block_size, cubemap_faces_stored_separately, and alignment");
+        let get_bool = |what: &str| o.get(what).and_then(|v| v.as_bool()).ok_or_else(wrong_options);
+        EngineBitmapOptions {
+            swizzled: get_bool("swizzled")?,
+            texture_dimension_must_modulo_block_size: get_bool("texture_dimension_must_modulo_block_size")?,
+            cubemap_faces_stored_separately: get_bool("cubemap_faces_stored_separately")?,
+            alignment: o.get("alignment").and_then(|v| v.as_u64()).ok_or_else(wrong_options)?,
+        }
+    };
+
+    let documentation = match engine.get("documentation") {
+        Some(d) => {
+            let d = d.as_object().ok_or_else(|| invalid!(engine_name, "{engine_name}::documentation is not an object"))?;
+            let get_string = |what: &str| -> Result<Option<String>> {
+                d.get(what)
+                    .map(|v| v.as_str().map(ToOwned::to_owned).ok_or_else(|| invalid!(engine_name, "{engine_name}::documentation::{what} is nonstring")))
+                    .transpose()
+            };
+            let release_date = get_string("release_date")?;
+            if let Some(r) = &release_date {
+                let valid = r.split('-').enumerate().all(|(i, p)| p.len() == if i == 0 { 4 } else { 2 } && p.bytes().all(|b| b.is_ascii_digit()))
+                    && r.split('-').count() <= 3;
+                ensure!(valid, engine_name, "{engine_name}::documentation::release_date {r} is not YYYY, YYYY-MM, or YYYY-MM-DD");
+            }
+            EngineDocumentation {
+                description: get_string("description")?,
+                release_date,
+                distribution: get_string("distribution")?,
+                quirks: match d.get("quirks") {
+                    Some(q) => string_array(q).ok_or_else(|| invalid!(engine_name, "{engine_name}::documentation::quirks is not an array of strings"))?,
+                    None => Vec::new()
+                }
+            }
+        },
+        None => EngineDocumentation::default()
+    };
+
+    let max_tag_space = {
+        let chain = get_chain("max_tag_space")?;
+        let (path, value) = chain.first().ok_or_else(|| missing("max_tag_space"))?;
+        hex_to_u64(value).ok_or_else(|| invalid!(engine_name, "{path} could not be parsed as hex"))?
+    };
+
+    Ok(Engine {
+        base_memory_address,
+        build,
+        definition_file: oget_str!(engine, "__json_file").to_owned(),
+        cache_default: local_bool("cache_default")?,
+        build_target: first_bool("build_target")?.ok_or_else(|| missing("build_target"))?,
+        fallback: local_bool("fallback")?,
+        cache_file_version: first_u64("cache_file_version")?
+            .ok_or_else(|| missing("cache_file_version"))?
+            .try_into()
+            .map_err(|_| wrong("cache_file_version", "a 32-bit unsigned integer"))?,
+        display_name: first_string("display_name")?.ok_or_else(|| missing("display_name"))?,
+        inherits: engine.get("inherits").map(|i| i.as_str().map(ToOwned::to_owned).ok_or_else(|| wrong("inherits", "a string"))).transpose()?,
+        max_cache_file_size,
+        custom: first_bool("custom")?.unwrap_or(false),
+        max_script_nodes: first_u64("max_script_nodes")?.ok_or_else(|| missing("max_script_nodes"))?,
+        script_node_table: EngineScriptNodeTable::load_from_json(engine_name, &first_object("script_node_table")?.ok_or_else(|| missing("script_node_table"))?)?,
+        max_tag_space,
+        resource_maps: first_value("resource_maps")?.map(|v| {
+            v.get("externally_indexed_tags")
+                .and_then(|e| e.as_bool())
+                .map(|externally_indexed_tags| EngineSupportedResourceMaps { externally_indexed_tags })
+                .ok_or_else(|| wrong("resource_maps", "an object with externally_indexed_tags"))
+        }).transpose()?,
+        external_models: first_bool("external_models")?.unwrap_or(false),
+        external_bsps: first_bool("external_bsps")?.unwrap_or(false),
+        minimum_weapons: first_u64("minimum_weapons")?.ok_or_else(|| missing("minimum_weapons"))?,
+        tag_paths: EngineTagPaths::load_from_json(engine_name, &first_object("tag_paths")?.ok_or_else(|| missing("tag_paths"))?)?,
+        hud_scaling: {
+            let scaling = first_string("hud_scaling")?.ok_or_else(|| missing("hud_scaling"))?;
+            HUDScaling::from_name(&scaling).ok_or_else(|| wrong("hud_scaling", "a known HUD scaling"))?
+        },
+        cache_parser: match first_string("cache_parser")?.ok_or_else(|| missing("cache_parser"))?.as_str() {
+            "pc" => EngineCacheParser::PC,
+            "xbox" => EngineCacheParser::Xbox,
+            _ => return Err(wrong("cache_parser", "pc or xbox"))
+        },
+        compression_type: match first_string("compression_type")?.ok_or_else(|| missing("compression_type"))?.as_str() {
+            "none" => EngineCompressionType::Uncompressed,
+            "deflate" => EngineCompressionType::Deflate,
+            _ => return Err(wrong("compression_type", "none or deflate"))
+        },
+        grenades,
+        compressed_models: first_bool("compressed_models")?.ok_or_else(|| missing("compressed_models"))?,
+        bitmap_options,
+        data_alignment: first_u64("data_alignment")?.ok_or_else(|| missing("data_alignment"))?,
+        compressed_data_alignment: first_u64("compressed_data_alignment")?.ok_or_else(|| missing("compressed_data_alignment"))?,
+        name: engine_name.to_owned(),
+        required_tags,
+        version: first_string("version")?,
+        obfuscated_header_layout: first_bool("obfuscated_header_layout")?.unwrap_or(false),
+        documentation
+    })
 }
 
-trait LoadFromSerdeJSON {
-    fn load_from_json(object: &Map<String, Value>) -> Self;
+trait LoadFromSerdeJSON: Sized {
+    fn load_from_json(object: &Map<String, Value>) -> Result<Self>;
 }
 
 impl LoadFromSerdeJSON for NamedObject {
-    fn load_from_json(object: &Map<String, Value>) -> Self {
+    fn load_from_json(object: &Map<String, Value>) -> Result<Self> {
         let object_type = oget_str!(object, "type");
         match object_type {
-            "struct" => Self::Struct(Struct::load_from_json(object)),
-            "enum" => Self::Enum(Enum::load_from_json(object)),
-            "bitfield" => Self::Bitfield(Bitfield::load_from_json(object)),
-            _ => unreachable!("invalid object type {object_type} for struct {}", object.get("name").unwrap())
+            "struct" => Ok(Self::Struct(Struct::load_from_json(object)?)),
+            "enum" => Ok(Self::Enum(Enum::load_from_json(object)?)),
+            "bitfield" => Ok(Self::Bitfield(Bitfield::load_from_json(object)?)),
+            _ => Err(DefinitionError::UnknownType { object: oget_name!(object).to_owned(), object_type: object_type.to_owned() })
         }
     }
 }
 
 impl LoadFromSerdeJSON for SupportedEngines {
-    fn load_from_json(object: &Map<String, Value>) -> Self {
-        let supported = match object.get("supported_engines") {
-            Some(n) => n,
-            None => return Self::default()
-        };
-
-        let supported_engines = supported.as_array()
-            .unwrap_or_else(|| panic!("{}::version is not an array", oget_name!(object)))
-            .iter()
-            .map(|f| f.as_str().unwrap_or_else(|| panic!("{}::version contains non-strings", oget_name!(object))).to_owned())
-            .collect::<BTreeSet<String>>();
-
-        Self::SomeEngines(supported_engines)
+    fn load_from_json(object: &Map<String, Value>) -> Result<Self> {
+        let engine_list = |key: &str| get_optional(object, key, "an array of strings", string_array::<BTreeSet<String>>);
+
+        match (engine_list("supported_engines")?, engine_list("unsupported_engines")?) {
+            (Some(_), Some(_)) => Err(invalid!(oget_name!(object), "{} has both supported_engines and unsupported_engines set", oget_name!(object))),
+            (Some(supported), None) => Ok(Self::SomeEngines(supported)),
+            (None, Some(unsupported)) => Ok(Self::AllExcept(unsupported)),
+            (None, None) => Ok(Self::default())
+        }
     }
 }
 
 impl LoadFromSerdeJSON for Flags {
-    fn load_from_json(object: &Map<String, Value>) -> Self {
-        let get_flag = |flag: &str| {
-            object.get(flag).map(|f| f.as_bool().unwrap_or_else(|| panic!("expected {flag} to be a boolean"))).unwrap_or_default()
-        };
-        let get_str = |flag: &str| {
-            object.get(flag).map(|f| f.as_str().unwrap_or_else(|| panic!("expected {flag} to be a string")).to_owned())
-        };
-        Flags {
-            non_cached: get_flag("non_cached"),
-            cache_only: get_flag("cache_only"),
-            uneditable_in_editor: get_flag("read_only"),
-            hidden_in_editor: get_flag("hidden"),
-            exclude: get_flag("exclude"),
-            little_endian_in_tags: get_flag("little_endian"),
-            supported_engines: SupportedEngines::load_from_json(object),
-            shifted_by_one: get_flag("shifted_by_one"),
-            comment: get_str("comment"),
-            developer_note: get_str("developer_note"),
-            description: get_str("description")
+    fn load_from_json(object: &Map<String, Value>) -> Result<Self> {
+        let get_flag = |flag: &str| get_optional(object, flag, "a boolean", Value::as_bool).map(Option::unwrap_or_default);
+        let get_str = |flag: &str| get_optional(object, flag, "a string", |s| s.as_str().map(ToOwned::to_owned));
+        Ok(Flags {
+            non_cached: get_flag("non_cached")?,
+            cache_only: get_flag("cache_only")?,
+            uneditable_in_editor: get_flag("read_only")?,
+            hidden_in_editor: get_flag("hidden")?,
+            exclude: get_flag("exclude")?,
+            little_endian_in_tags: get_flag("little_endian")?,
+            supported_engines: SupportedEngines::load_from_json(object)?,
+            shifted_by_one: get_flag("shifted_by_one")?,
+            comment: get_str("comment")?,
+            developer_note: get_str("developer_note")?,
+            description: get_str("description")?,
+            introduced_in: get_str("introduced_in")?,
+            removed_in: get_str("removed_in")?,
+            examples: get_optional(object, "examples", "an array of strings or numbers", |a| {
+                a.as_array()?.iter().map(|e| match e {
+                    Value::String(s) => Some(s.to_owned()),
+                    Value::Number(n) => Some(n.to_string()),
+                    _ => None
+                }).collect()
+            })?.unwrap_or_default(),
+            see_also: load_see_also(object)?,
+            research: object.get("research").map(|r| load_research(oget_name!(object), r)).transpose()?,
+            cache_rewrite: get_optional(object, "cache_rewrite", "a known cache rewrite", |r| CacheRewrite::from_name(r.as_str()?))?,
+            determinism: get_optional(object, "determinism", "a known determinism", |d| Determinism::from_name(d.as_str()?))?
+        })
+    }
+}
+
+fn load_research(object_name: &str, research: &Value) -> Result<Research> {
+    let wrong = |expected| DefinitionError::InvalidValue { object: object_name.to_owned(), key: "research".to_owned(), expected };
+    let research = research.as_object().ok_or_else(|| wrong("an object"))?;
+    let get_strings = |key: &str| -> Result<Vec<String>> {
+        research.get(key).map(|v| string_array(v).ok_or_else(|| wrong("an object with arrays of strings"))).transpose().map(Option::unwrap_or_default)
+    };
+    Ok(Research {
+        confidence: match research.get("confidence").map(|c| c.as_str()) {
+            None | Some(Some("speculative")) => ResearchConfidence::Speculative,
+            Some(Some("likely")) => ResearchConfidence::Likely,
+            Some(Some("confirmed")) => ResearchConfidence::Confirmed,
+            Some(_) => return Err(wrong("an object with a confidence of speculative, likely, or confirmed"))
+        },
+        observed_values: get_strings("observed_values")?,
+        links: get_strings("links")?,
+        notes: research.get("notes").map(|n| n.as_str().map(ToOwned::to_owned).ok_or_else(|| wrong("an object with string notes"))).transpose()?
+    })
+}
+
+/// Check that no two names map to the same generated Rust field or enum name.
+///
+/// Names are given as (name, name_rust_field, name_rust_enum).
+fn check_unique_rust_names<'a>(object_name: &str, names: impl Iterator<Item = (&'a str, &'a str, &'a str)>) -> Result<()> {
+    let mut fields: BTreeMap<&str, &str> = BTreeMap::new();
+    let mut enums: BTreeMap<&str, &str> = BTreeMap::new();
+    for (name, rust_field, rust_enum) in names {
+        for (names, rust_name) in [(&mut fields, rust_field), (&mut enums, rust_enum)] {
+            if let Some(other) = names.insert(rust_name, name) {
+                return Err(DefinitionError::NameCollision {
+                    object: object_name.to_owned(),
+                    first: other.to_owned(),
+                    second: name.to_owned(),
+                    rust_name: rust_name.to_owned()
+                })
+            }
         }
     }
+    Ok(())
+}
+
+fn load_see_also(object: &Map<String, Value>) -> Result<Vec<SeeAlso>> {
+    let see_also: Vec<String> = get_optional(object, "see_also", "an array of strings", string_array)?.unwrap_or_default();
+    Ok(see_also
+        .into_iter()
+        .map(|s| match s.split_once('.') {
+            Some((object, field)) => SeeAlso::Field { object: object.to_owned(), field: field.to_owned() },
+            None => SeeAlso::Group(s)
+        })
+        .collect())
 }
 
 impl LoadFromSerdeJSON for StructField {
-    fn load_from_json(object: &Map<String, Value>) -> Self {
-        let field_type = StructFieldType::load_from_json(object);
+    fn load_from_json(object: &Map<String, Value>) -> Result<Self> {
+        let field_type = StructFieldType::load_from_json(object)?;
         let object_type = match &field_type {
             StructFieldType::Object(o) => o,
-            StructFieldType::Padding(_) => return Self {
+            StructFieldType::Padding(_) => return Ok(Self {
                 name: String::new(),
                 name_rust_enum: String::new(),
                 name_rust_field: String::new(),
                 count: FieldCount::One,
+                engine_counts: BTreeMap::new(),
                 default_value: None,
                 field_type,
-                flags: Flags::default(),
+                flags: Flags::load_from_json(object)?,
                 maximum: None,
                 minimum: None,
                 limit: None,
                 relative_offset: isize::MAX as usize,
-                nullability: Nullability::NonNull
-            },
-            StructFieldType::EditorSection { heading, .. } => return Self {
+                nullability: Nullability::NonNull,
+                padding_kind: Some(match get_optional(object, "reserved", "a boolean", Value::as_bool)? {
+                    Some(true) => PaddingKind::Reserved,
+                    _ => PaddingKind::Padding
+                }),
+                widget: None,
+                step: None,
+                precision: None,
+                memory_pool: None,
+                byte_budget: None,
+                normalized: false,
+                color_space: None
+            }),
+            StructFieldType::EditorSection { heading, .. } => return Ok(Self {
                 name: heading.clone(),
                 name_rust_enum: String::new(),
                 name_rust_field: String::new(),
                 count: FieldCount::One,
+                engine_counts: BTreeMap::new(),
                 default_value: None,
                 field_type,
                 flags: Flags::default(),
@@ -860,171 +1480,211 @@ impl LoadFromSerdeJSON for StructField {
                 minimum: None,
                 limit: None,
                 relative_offset: isize::MAX as usize,
-                nullability: Nullability::NonNull
-            },
+                nullability: Nullability::NonNull,
+                padding_kind: None,
+                widget: None,
+                step: None,
+                precision: None,
+                memory_pool: None,
+                byte_budget: None,
+                normalized: false,
+                color_space: None
+            }),
         };
 
         let name = oget_str!(object, "name").to_owned();
-        assert!(!name.is_empty());
-        let count = FieldCount::load_from_json(object);
+        let (name_rust_field, name_rust_enum) = rust_names(&name, &name)?;
+        let count = FieldCount::load_from_json(object)?;
+        let wrong = |key: &str, expected| DefinitionError::InvalidValue { object: name.to_owned(), key: key.to_owned(), expected };
 
-        let parse_static_value = |v: &Value| -> StaticValue {
-            let primitive_value_type = object_type.primitive_value_type().unwrap_or_else(|| panic!("{} does not have a primitive value type", oget_str!(object, "type")));
+        let parse_static_value = |key: &str, v: &Value| -> Result<StaticValue> {
+            let primitive_value_type = object_type.primitive_value_type().ok_or_else(|| wrong(key, "only set on a field with a primitive value type"))?;
             match primitive_value_type {
-                StaticValue::Float(_) => StaticValue::Float(v.as_f64().map(|f| f as f32).unwrap_or_else(|| panic!("expected float for {name}, got {v:?}"))),
-                StaticValue::String(_) => StaticValue::String(v.as_str().unwrap_or_else(|| panic!("expected string for {name}, got {v:?}")).to_owned()),
-                StaticValue::Int(_) => StaticValue::Int(v.as_i64().unwrap_or_else(|| panic!("expected i64 for {name}, got {v:?}"))),
-                StaticValue::Uint(_) => StaticValue::Uint(v.as_u64().unwrap_or_else(|| panic!("expected u64 for {name}, got {v:?}"))),
-            }
+                StaticValue::Float(_) => v.as_f64().map(|f| StaticValue::Float(f as f32)),
+                StaticValue::String(_) => v.as_str().map(|s| StaticValue::String(s.to_owned())),
+                StaticValue::Int(_) => v.as_i64().map(StaticValue::Int),
+                StaticValue::Uint(_) => v.as_u64().map(StaticValue::Uint),
+            }.ok_or_else(|| wrong(key, "a value of the field's type"))
         };
 
-        let get_static_value = |field_name: &str| -> Option<StaticValue> {
-            let o = object.get(field_name)?;
-            Some(parse_static_value(o))
+        let get_static_value = |key: &str| object.get(key).map(|v| parse_static_value(key, v)).transpose();
+
+        let default_value = match object.get("default") {
+            Some(o) => {
+                let values = match o {
+                    Value::Array(a) => a.as_slice(),
+                    o => core::slice::from_ref(o)
+                };
+                let result = values.iter().map(|v| parse_static_value("default", v)).collect::<Result<Vec<StaticValue>>>()?;
+
+                let field_count = count.field_count();
+                let expected_default_count = field_count * object_type.composite_count();
+                ensure!(
+                    expected_default_count == result.len(),
+                    name,
+                    "count for {name}::default was {} when it should be {expected_default_count}",
+                    result.len()
+                );
+                Some(result)
+            },
+            None => None
         };
 
-        let get_static_values = |field_name: &str| -> Option<Vec<StaticValue>> {
-            let o = object.get(field_name)?;
+        let limit = match object.get("limit") {
+            None => None,
+            Some(Value::Number(n)) => {
+                let mut map = BTreeMap::new();
+                let limit = n.as_u64().ok_or_else(|| wrong("limit", "an unsigned integer"))? as usize;
+                map.insert(LimitType::Editor, limit);
+                map.insert(LimitType::Default, limit);
+                Some(map)
+            },
+            Some(Value::Object(o)) => {
+                let mut map = BTreeMap::new();
+
+                let mut editor_limit: Option<usize> = None;
+                let mut explicit_editor_limit: Option<usize> = None;
+                let mut default_limit: Option<usize> = None;
+
+                for (k, v) in o {
+                    // Profiles only raise the editor limit if an editor limit is given explicitly
+                    if k == "profiles" {
+                        let profiles = v.as_object().ok_or_else(|| wrong("limit", "an object whose profiles are an object"))?;
+                        for (profile, v) in profiles {
+                            let v = v.as_u64().ok_or_else(|| wrong("limit", "an object with unsigned integer profile limits"))? as usize;
+                            map.insert(LimitType::Profile(profile.to_owned()), v);
+                        }
+                        continue
+                    }
 
-            let parse_static_values = |v: &[Value]| -> Vec<StaticValue> {
-                v.iter().map(|v| parse_static_value(v)).collect()
-            };
+                    let v = v.as_u64().ok_or_else(|| wrong("limit", "an object with unsigned integer limits"))? as usize;
 
-            let result = match o {
-                Value::Array(a) => parse_static_values(a.as_slice()),
-                _ => parse_static_values(&[o.to_owned()])
-            };
+                    match k.as_str() {
+                        "default" => default_limit = Some(v),
+                        "editor" => {
+                            explicit_editor_limit = Some(v);
+                            continue
+                        },
+                        _ => {
+                            map.insert(LimitType::Engine(k.to_owned()), v);
+                        }
+                    }
 
-            let field_count = count.field_count();
-            let expected_default_count = field_count * object_type.composite_count();
-            assert_eq!(expected_default_count, result.len(), "count for {name}::{field_name} was {} when it should be {}", result.len(), expected_default_count);
-            Some(result)
-        };
+                    editor_limit = Some(editor_limit.unwrap_or_default().max(v))
+                }
 
-        let limit = object.get("limit").map(|l| {
-            match l {
-                Value::Number(n) => {
-                    let mut map = BTreeMap::new();
-                    let limit = n.as_u64().unwrap_or_else(|| panic!("{name}::limit is not u64")) as usize;
-                    map.insert(LimitType::Editor, limit);
-                    map.insert(LimitType::Default, limit);
-                    map
-                },
+                let editor_limit = editor_limit.ok_or_else(|| wrong("limit", "an object with a default limit"))?;
+                let default_limit = default_limit.ok_or_else(|| wrong("limit", "an object with a default limit"))?;
 
-                Value::Object(o) => {
-                    let mut map = BTreeMap::new();
+                // Profiles fall back to the default limit
+                if map.keys().any(|k| matches!(k, LimitType::Profile(_))) {
+                    map.insert(LimitType::Default, default_limit);
+                }
+                if let Some(explicit) = explicit_editor_limit {
+                    if explicit < editor_limit {
+                        return Err(wrong("limit", "an object whose editor limit is at least its default and engine limits"))
+                    }
+                }
+                map.insert(LimitType::Editor, explicit_editor_limit.unwrap_or(editor_limit));
+                Some(map)
+            },
+            Some(_) => return Err(wrong("limit", "a number or an object"))
+        };
 
-                    let mut editor_limit: Option<usize> = None;
-                    let mut default_limit: Option<usize> = None;
+        let widget = object.get("widget").map(|w| EditorWidget::load_from_json(&name, w, object_type)).transpose()?;
 
-                    for (k, v) in o {
-                        let v = v.as_number().unwrap_or_else(|| panic!("{name}::limit is are not all numbers"))
-                            .as_u64().unwrap_or_else(|| panic!("{name}::limit is not all u64's"))
-                            as usize;
+        let is_numeric = object_type.primitive_value_type().is_some_and(|v| !matches!(v, StaticValue::String(_)));
+        let step = object.get("step").map(|s| load_step(&name, s, is_numeric)).transpose()?;
+        let precision = object.get("precision").map(|p| {
+            if !is_numeric {
+                return Err(wrong("precision", "only set on numeric fields"))
+            }
+            p.as_u64().and_then(|p| u8::try_from(p).ok()).ok_or_else(|| wrong("precision", "a small unsigned integer"))
+        }).transpose()?;
 
-                        if k == "default" {
-                            default_limit = Some(v);
-                        }
-                        else {
-                            map.insert(LimitType::Engine(k.to_owned()), v);
-                        }
+        let memory_pool = object.get("memory_pool").map(|p| {
+            if !matches!(object_type, FieldObject::Data | FieldObject::FileData | FieldObject::BSPVertexData | FieldObject::Reflexive(_)) {
+                return Err(wrong("memory_pool", "only set on data or reflexive fields"))
+            }
+            MemoryPool::load_from_json(&name, p)
+        }).transpose()?;
 
-                        editor_limit = Some(editor_limit.unwrap_or_default().max(v))
-                    }
+        let byte_budget = object.get("byte_budget").map(|b| {
+            if !matches!(object_type, FieldObject::Reflexive(_)) {
+                return Err(wrong("byte_budget", "only set on reflexive fields"))
+            }
+            b.as_u64().ok_or_else(|| wrong("byte_budget", "an unsigned integer"))
+        }).transpose()?;
 
-                    default_limit.unwrap_or_else(|| panic!("No default limit set for {name}"));
+        let normalized = get_optional(object, "normalize", "a boolean", Value::as_bool)?.unwrap_or(false);
+        if normalized && !matches!(object_type, FieldObject::Vector2D | FieldObject::Vector3D | FieldObject::Quaternion) {
+            return Err(wrong("normalize", "only set on vector or quaternion fields"))
+        }
 
-                    let editor_limit = editor_limit.unwrap_or_else(|| panic!("Unable to establish an editor limit for {name} (no limits maybe?)"));
-                    map.insert(LimitType::Editor, editor_limit);
-                    map
-                }
+        let color_space = object.get("color_space").map(|c| ColorSpace::load_from_json(&name, c, object_type)).transpose()?;
 
-                _ => panic!("{name} is not a number or an object")
+        let engine_counts = match object.get("count") {
+            Some(Value::Object(o)) => o
+                .iter()
+                .filter(|(k, _)| k.as_str() != "default")
+                .map(|(k, v)| v.as_u64().map(|c| (k.to_owned(), c as usize)).ok_or_else(|| wrong("count", "an object of unsigned integers")))
+                .collect::<Result<_>>()?,
+            _ => BTreeMap::new()
+        };
+
+        let nullability = match get_optional(object, "non_null", "a boolean", Value::as_bool)? {
+            Some(true) => Nullability::NonNull,
+            Some(false) => Nullability::Nullable,
+            None => match object_type {
+                FieldObject::Index => Nullability::Nullable,
+                FieldObject::Reflexive(_) => Nullability::Nullable,
+                _ => Nullability::NonNull
             }
-        });
+        };
 
-        StructField {
-            minimum: get_static_value("minimum"),
-            maximum: get_static_value("maximum"),
+        Ok(StructField {
+            minimum: get_static_value("minimum")?,
+            maximum: get_static_value("maximum")?,
             limit,
-            flags: Flags::load_from_json(object),
-            default_value: get_static_values("default"),
+            flags: Flags::load_from_json(object)?,
+            default_value,
             count,
-            name_rust_field: format_for_rust_fields(&name),
-            name_rust_enum: format_for_rust_enums(&name),
+            engine_counts,
+            name_rust_field,
+            name_rust_enum,
             name,
             relative_offset: isize::MAX as usize,
-            nullability: {
-                if let Some(non_null) = object.get("non_null") {
-                    if non_null.as_bool().expect("non_null was not a bool") {
-                        Nullability::NonNull
-                    }
-                    else {
-                        Nullability::Nullable
-                    }
-                }
-                else {
-                    match object_type {
-                        FieldObject::Index => Nullability::Nullable,
-                        FieldObject::Reflexive(_) => Nullability::Nullable,
-                        _ => Nullability::NonNull
-                    }
-                }
-            },
+            padding_kind: None,
+            widget,
+            step,
+            precision,
+            memory_pool,
+            byte_budget,
+            normalized,
+            color_space,
+            nullability,
             field_type
-        }
+        })
+    }
+}
+
+fn load_step(field_name: &str, step: &Value, is_numeric: bool) -> Result<f64> {
+    let invalid = |expected| DefinitionError::InvalidValue { object: field_name.to_owned(), key: "step".to_owned(), expected };
+    if !is_numeric {
+        return Err(invalid("only set on numeric fields"))
     }
+    step.as_f64().filter(|s| *s > 0.0 && s.is_finite()).ok_or_else(|| invalid("a positive number"))
 }
 
 impl LoadFromSerdeJSON for FieldObject {
-    fn load_from_json(object: &Map<String, Value>) -> Self {
+    fn load_from_json(object: &Map<String, Value>) -> Result<Self> {
         let field_type = oget_str!(object, "type");
 
-        match field_type {
+        Ok(match field_type {
             "Reflexive" => Self::Reflexive(oget_str!(object, "struct").to_owned()),
             "TagReference" => Self::TagReference {
-                allowed_groups: oget!(object, "groups")
-                    .as_array()
-                    .unwrap_or_else(|| panic!("expected {name}::groups to be an array", name=oget_name!(object)))
-                    .iter()
-                    .map(|f| f.as_str().unwrap_or_else(|| panic!("expected {name}::groups to only have strings", name=oget_name!(object))))
-                    .map(|s| s.to_owned())
-                    .collect()
+                allowed_groups: string_array(oget!(object, "groups")).ok_or_else(|| wrong_type!(object, "groups", "an array of strings"))?
             },
-            "TagGroup" => Self::TagGroup,
-            "Data" => Self::Data,
-            "FileData" => Self::FileData,
-            "BSPVertexData" => Self::BSPVertexData,
-            "UTF16String" => Self::UTF16String,
-            "float" => Self::F32,
-            "uint8" => Self::U8,
-            "uint16" => Self::U16,
-            "uint32" => Self::U32,
-            "int8" => Self::I8,
-            "int16" => Self::I16,
-            "int32" => Self::I32,
-            "Angle" => Self::Angle,
-            "Rectangle" => Self::Rectangle,
-            "Vector2D" => Self::Vector2D,
-            "Vector3D" => Self::Vector3D,
-            "CompressedVector2D" => Self::CompressedVector2D,
-            "CompressedVector3D" => Self::CompressedVector3D,
-            "CompressedFloat" => Self::CompressedFloat,
-            "Euler2D" => Self::Euler2D,
-            "Euler3D" => Self::Euler3D,
-            "Plane2D" => Self::Plane2D,
-            "Plane3D" => Self::Plane3D,
-            "Rectangle3D" => Self::Rectangle3D,
-            "Quaternion" => Self::Quaternion,
-            "Matrix2x3" => Self::Matrix2x3,
-            "Matrix3x3" => Self::Matrix3x3,
-            "Matrix4x3" => Self::Matrix4x3,
-            "ColorRGB" => Self::ColorRGB,
-            "ColorARGB" => Self::ColorARGB,
-            "Pixel32" => Self::Pixel32,
-            "String32" => Self::String32,
-            "Address" => Self::Address,
-            "Index" => Self::Index,
             "ReflexiveIndex" => {
                 let display = oget_str!(object, "reflexive").to_owned();
                 Self::ReflexiveIndex {
@@ -1033,87 +1693,119 @@ impl LoadFromSerdeJSON for FieldObject {
                     reflexive_name_display: display
                 }
             },
-            "Vector2DInt" => Self::Vector2DInt,
-            "TagID" => Self::TagID,
-            "ID" => Self::ID,
-            "ScenarioScriptNodeValue" => Self::ScenarioScriptNodeValue,
-            n => Self::NamedObject(n.to_owned()),
-        }
+            n => simple_field_object(n).unwrap_or_else(|| Self::NamedObject(n.to_owned())),
+        })
+    }
+}
+
+/// Get a built-in field object that has no parameters by its type name (e.g. `float`).
+pub(crate) fn simple_field_object(name: &str) -> Option<FieldObject> {
+    match name {
+        "TagGroup" => Some(FieldObject::TagGroup),
+        "Data" => Some(FieldObject::Data),
+        "FileData" => Some(FieldObject::FileData),
+        "BSPVertexData" => Some(FieldObject::BSPVertexData),
+        "UTF16String" => Some(FieldObject::UTF16String),
+        "float" => Some(FieldObject::F32),
+        "uint8" => Some(FieldObject::U8),
+        "uint16" => Some(FieldObject::U16),
+        "uint32" => Some(FieldObject::U32),
+        "int8" => Some(FieldObject::I8),
+        "int16" => Some(FieldObject::I16),
+        "int32" => Some(FieldObject::I32),
+        "Angle" => Some(FieldObject::Angle),
+        "Rectangle" => Some(FieldObject::Rectangle),
+        "Vector2D" => Some(FieldObject::Vector2D),
+        "Vector3D" => Some(FieldObject::Vector3D),
+        "CompressedVector2D" => Some(FieldObject::CompressedVector2D),
+        "CompressedVector3D" => Some(FieldObject::CompressedVector3D),
+        "CompressedFloat" => Some(FieldObject::CompressedFloat),
+        "Euler2D" => Some(FieldObject::Euler2D),
+        "Euler3D" => Some(FieldObject::Euler3D),
+        "Plane2D" => Some(FieldObject::Plane2D),
+        "Plane3D" => Some(FieldObject::Plane3D),
+        "Rectangle3D" => Some(FieldObject::Rectangle3D),
+        "Quaternion" => Some(FieldObject::Quaternion),
+        "Matrix2x3" => Some(FieldObject::Matrix2x3),
+        "Matrix3x3" => Some(FieldObject::Matrix3x3),
+        "Matrix4x3" => Some(FieldObject::Matrix4x3),
+        "ColorRGB" => Some(FieldObject::ColorRGB),
+        "ColorARGB" => Some(FieldObject::ColorARGB),
+        "Pixel32" => Some(FieldObject::Pixel32),
+        "String32" => Some(FieldObject::String32),
+        "Address" => Some(FieldObject::Address),
+        "Index" => Some(FieldObject::Index),
+        "Vector2DInt" => Some(FieldObject::Vector2DInt),
+        "TagID" => Some(FieldObject::TagID),
+        "ID" => Some(FieldObject::ID),
+        "ScenarioScriptNodeValue" => Some(FieldObject::ScenarioScriptNodeValue),
+        _ => None
     }
 }
 
 impl LoadFromSerdeJSON for StructFieldType {
-    fn load_from_json(object: &Map<String, Value>) -> Self {
-        match oget_str!(object, "type") {
+    fn load_from_json(object: &Map<String, Value>) -> Result<Self> {
+        Ok(match oget_str!(object, "type") {
             "pad" => Self::Padding(oget_size!(object)),
             "editor_section" => Self::EditorSection {
                 heading: oget_str!(object, "heading").to_owned(),
-                body: object
-                    .get("body")
-                    .map(|d| d.as_str().expect("body must be a string").to_owned())
+                body: get_optional(object, "body", "a string", |d| d.as_str().map(ToOwned::to_owned))?
             },
-            _ => Self::Object(FieldObject::load_from_json(object))
-        }
+            _ => Self::Object(FieldObject::load_from_json(object)?)
+        })
     }
 }
 
 impl LoadFromSerdeJSON for FieldCount {
-    fn load_from_json(object: &Map<String, Value>) -> Self {
-        let is_bounds = object.get("bounds").is_some_and(|f| f.as_bool().unwrap());
-        let count = object.get("count").map(|c| c.as_u64().unwrap() as usize);
+    fn load_from_json(object: &Map<String, Value>) -> Result<Self> {
+        let is_bounds = get_optional(object, "bounds", "a boolean", Value::as_bool)?.unwrap_or(false);
+        let count = get_optional(object, "count", "an unsigned integer or an object with a default count", |c| match c {
+            Value::Object(o) => o.get("default")?.as_u64(),
+            c => c.as_u64()
+        })?;
 
-        if is_bounds && count.is_some() {
-            panic!("{}'s field count is ambiguous (both bounds and count set)", oget_name!(object));
-        }
+        ensure!(!(is_bounds && count.is_some()), oget_name!(object), "{}'s field count is ambiguous (both bounds and count set)", oget_name!(object));
 
-        if is_bounds {
+        Ok(if is_bounds {
             Self::Bounds
         }
         else if let Some(c) = count {
-            Self::Array(c)
+            Self::Array(c as usize)
         }
         else {
             Self::One
-        }
+        })
     }
 }
 
 impl LoadFromSerdeJSON for Struct {
-    fn load_from_json(object: &Map<String, Value>) -> Self {
+    fn load_from_json(object: &Map<String, Value>) -> Result<Self> {
         let name = oget_str!(object, "name").to_owned();
-        assert!(!name.is_empty());
 
-        let flags = Flags::load_from_json(object);
+        let flags = Flags::load_from_json(object)?;
+        let packed = get_optional(object, "packed", "a boolean", Value::as_bool)?.unwrap_or(false);
+        let defined_memory_pool = object.get("memory_pool").map(|p| MemoryPool::load_from_json(&name, p)).transpose()?.unwrap_or_default();
 
-        let mut fields = object.get("fields")
-                                                    .unwrap_or_else(|| panic!("object {name} is missing fields"))
-                                                    .as_array()
-                                                    .unwrap_or_else(|| panic!("object {name}'s fields is not an array"))
-                                                    .iter()
-                                                    .map(|f| f.as_object().unwrap_or_else(|| panic!("object {name}'s fields contains non-objects")))
-                                                    .map(|f| StructField::load_from_json(f))
-                                                    .collect::<VecDeque<StructField>>();
+        let mut fields = oget!(object, "fields")
+            .as_array()
+            .ok_or_else(|| wrong_type!(object, "fields", "an array of objects"))?
+            .iter()
+            .map(|f| f.as_object().ok_or_else(|| wrong_type!(object, "fields", "an array of objects")).and_then(StructField::load_from_json))
+            .collect::<Result<VecDeque<StructField>>>()?;
 
         for i in &mut fields {
             i.flags.combine_with(&flags);
         }
 
-        if let Some(parent) = object.get("inherits").map(|p| p.as_str().unwrap().to_owned()) {
-            let mut parent_snake_case = String::with_capacity(parent.len() * 2);
-            let mut last_char = 'A';
-            for c in parent.chars() {
-                if c.is_ascii_uppercase() && !last_char.is_ascii_uppercase() {
-                    parent_snake_case.push('_');
-                }
-                last_char = c;
-                parent_snake_case.push(c.to_ascii_lowercase());
-            }
+        if let Some(parent) = get_optional(object, "inherits", "a string", |p| p.as_str().map(ToOwned::to_owned))? {
+            let parent_snake_case = format_pascal_case_for_rust_fields(&parent);
 
             fields.push_front(StructField {
                 name_rust_enum: parent.clone(),
                 name_rust_field: parent_snake_case,
                 name: parent.clone(),
                 count: FieldCount::One,
+                engine_counts: BTreeMap::new(),
                 field_type: StructFieldType::Object(FieldObject::NamedObject(parent)),
                 default_value: None,
                 minimum: None,
@@ -1121,206 +1813,424 @@ impl LoadFromSerdeJSON for Struct {
                 limit: None,
                 flags: Flags::default(),
                 relative_offset: usize::MAX,
-                nullability: Nullability::NonNull
+                nullability: Nullability::NonNull,
+                padding_kind: None,
+                widget: None,
+                step: None,
+                precision: None,
+                memory_pool: None,
+                byte_budget: None,
+                normalized: false,
+                color_space: None
             })
         }
 
-        Self {
+        Ok(Self {
             flags,
             fields: Vec::from(fields),
             definition_file: oget_str!(object, "__json_file").to_owned(),
             name,
-            size: oget_number!(object, "size", as_u64) as usize,
-            is_const: false
-        }
+            size: oget_size!(object),
+            is_const: false,
+            contains_tag_references: false,
+            contains_data: false,
+            contains_reflexives: false,
+            max_depth: 0,
+            packed,
+            defined_memory_pool
+        })
     }
 }
 
 impl LoadFromSerdeJSON for Field {
-    fn load_from_json(object: &Map<String, Value>) -> Self {
+    fn load_from_json(object: &Map<String, Value>) -> Result<Self> {
         let name = oget_str!(object, "name").to_owned();
-        Self {
-            name_rust_enum: format_for_rust_enums(&name),
-            name_rust_field: format_for_rust_fields(&name),
+        let (name_rust_field, name_rust_enum) = rust_names(&name, &name)?;
+        Ok(Self {
+            name_rust_enum,
+            name_rust_field,
             name,
-            flags: Flags::load_from_json(object),
+            flags: Flags::load_from_json(object)?,
             value: 0
-        }
+        })
     }
 }
 
-fn process_field_array(fields: &Vec<Value>) -> Vec<Field> {
-    let mut current_index = 0;
-
+fn process_field_array(object_name: &str, fields: &[Value]) -> Result<Vec<Field>> {
     fields.iter()
-        .map(|f| {
+        .zip(0..)
+        .map(|(f, index)| {
             let mut field = match f {
-                Value::String(name) => Field {
-                    name_rust_field: format_for_rust_fields(name),
-                    name_rust_enum: format_for_rust_enums(name),
-                    name: name.to_owned(),
-                    flags: Flags::default(),
-                    value: 0
+                Value::String(name) => {
+                    let (name_rust_field, name_rust_enum) = rust_names(object_name, name)?;
+                    Field {
+                        name_rust_field,
+                        name_rust_enum,
+                        name: name.to_owned(),
+                        flags: Flags::default(),
+                        value: 0
+                    }
                 },
-                Value::Object(o) => Field::load_from_json(o),
-                _ => panic!("bitfield/enum entries must be a string or object")
+                Value::Object(o) => Field::load_from_json(o)?,
+                _ => return Err(invalid!(object_name, "{object_name} has an entry that is not a string or object"))
             };
 
-            field.value = current_index;
-            current_index += 1;
+            field.value = index;
 
-            field
+            Ok(field)
         })
         .collect()
 }
 
 impl LoadFromSerdeJSON for Bitfield {
-    fn load_from_json(object: &Map<String, Value>) -> Self {
+    fn load_from_json(object: &Map<String, Value>) -> Result<Self> {
         let name = oget_str!(object, "name").to_owned();
-        assert!(!name.is_empty());
 
-        let mut fields = process_field_array(oget!(object, "fields").as_array().unwrap_or_else(|| panic!("{name}::fields must be an array")));
+        let field_array = oget!(object, "fields").as_array().ok_or_else(|| wrong_type!(object, "fields", "an array"))?;
+        let mut fields = process_field_array(&name, field_array)?;
         for f in &mut fields {
-            if f.value >= 32 {
-                panic!("field {name}::{} is too high to be represented as a bitfield", f.name);
-            }
+            ensure!(f.value < 32, name, "field {name}::{} is too high to be represented as a bitfield", f.name);
             f.value = 1 << f.value;
         }
 
-        Self {
-            width: oget_number!(object, "width", as_u64) as u8,
+        let exclusive_groups = get_optional(object, "exclusive", "an array of arrays of strings", |e| {
+            e.as_array()?.iter().map(string_array).collect()
+        })?.unwrap_or_default();
+
+        let implications = get_optional(object, "implies", "an object of arrays of strings", |i| {
+            i.as_object()?.iter().map(|(field, implied)| Some((field.to_owned(), string_array(implied)?))).collect()
+        })?.unwrap_or_default();
+
+        Ok(Self {
+            width: u8::try_from(oget_number!(object, "width", as_u64)).map_err(|_| wrong_type!(object, "width", "a bit width"))?,
             definition_file: oget_str!(object, "__json_file").to_owned(),
-            flags: Flags::load_from_json(object),
+            flags: Flags::load_from_json(object)?,
             fields,
+            exclusive_groups,
+            implications,
+            copy_of: get_optional(object, "copy_of", "a string", |c| c.as_str().map(ToOwned::to_owned))?,
             name
-        }
+        })
     }
 }
 
 impl LoadFromSerdeJSON for Enum {
-    fn load_from_json(object: &Map<String, Value>) -> Self {
+    fn load_from_json(object: &Map<String, Value>) -> Result<Self> {
         let name = oget_str!(object, "name").to_owned();
-        assert!(!name.is_empty());
 
-        Self {
-            flags: Flags::load_from_json(object),
+        let option_array = oget!(object, "options").as_array().ok_or_else(|| wrong_type!(object, "options", "an array"))?;
+        let result = Self {
+            flags: Flags::load_from_json(object)?,
             definition_file: oget_str!(object, "__json_file").to_owned(),
-            options: process_field_array(oget!(object, "options").as_array().unwrap_or_else(|| panic!("{name}::options must be an array"))),
+            options: process_field_array(&name, option_array)?,
+            interpretation: get_optional(object, "interpretation", "a known interpretation", |i| EnumInterpretation::from_name(i.as_str()?))?,
+            global_role: get_optional(object, "global", "a known global enum role", |g| GlobalEnumRole::from_name(g.as_str()?))?,
+            copy_of: get_optional(object, "copy_of", "a string", |c| c.as_str().map(ToOwned::to_owned))?,
             name
+        };
+        if let Some(interpretation) = result.interpretation {
+            interpretation.validate(&result)?;
         }
+        Ok(result)
     }
 }
 
-fn format_for_rust_enums(what: &str) -> String {
-    // could change this to work in the future, but it'd make the code a little more complex
-    assert!(what.is_ascii(), "{what} is non-ascii; can't format rust enums");
-
-    let mut n = String::with_capacity(what.len() + 1);
-    let mut c = what.chars().peekable();
-
-    let first = c.peek().expect("enum field with empty name");
-    if !first.is_ascii_alphabetic() {
-        n += "_";
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_load_all_definitions() {
+        crate::load_all_definitions();
     }
-
-    let mut needs_caps = true;
-    for c in c {
-        if c == '\'' {
-            continue
-        }
-        else if !c.is_alphanumeric() {
-            needs_caps = true;
-            continue;
-        }
-        else if needs_caps {
-            needs_caps = false;
-            n.extend(c.to_uppercase());
-        }
-        else {
-            n.push(c);
-        }
+    #[test]
+    #[cfg(feature = "engines-pc")]
+    fn shader_transparent_chicago_extended_works_on_custom_edition() {
+        let definitions = crate::load_all_definitions();
+        let supported = definitions.groups["shader_transparent_chicago_extended"]
+            .supported_engines
+            .supports_engine(&definitions.engines["pc-custom"]);
+        assert!(supported, "Custom Edition must support shader_transparent_chicago_extended")
+    }
+    #[test]
+    #[cfg(all(feature = "engines-pc", feature = "engines-xbox"))]
+    fn supported_engines_expand_through_inheritance() {
+        let definitions = crate::load_all_definitions();
+        let crate::NamedObject::Bitfield(flags) = &definitions.objects["ScenarioFlags"] else {
+            panic!("ScenarioFlags should be a bitfield")
+        };
+        let demo_ui = flags.fields.iter().find(|f| f.name == "use demo ui").unwrap();
+        let expanded = demo_ui.flags.supported_engines.expanded(definitions);
+        assert!(expanded.contains("xbox-demo"));
+        assert!(!expanded.contains("pc-custom"));
+
+        let descendants = definitions.engine_descendants("pc-custom");
+        assert!(descendants.contains("pc-custom-ex") && descendants.contains("pc-custom-chimera"));
+        assert!(!descendants.contains("pc-retail"));
+    }
+    #[test]
+    fn bitfield_exclusivity_and_implications() {
+        use crate::*;
+
+        let json = br#"[{
+            "name": "TestFlags",
+            "type": "bitfield",
+            "width": 8,
+            "fields": ["a", "b", "c", "d"],
+            "exclusive": [["a", "d"]],
+            "implies": { "a": ["b"], "b": ["c"] }
+        }]"#;
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_json(&super::parse_definition_file("test.json", json));
+        definitions.finalize();
+
+        let NamedObject::Bitfield(b) = &definitions.objects["TestFlags"] else { unreachable!() };
+        assert_eq!(b.normalize(0b0001), 0b0111);
+        assert_eq!(b.normalize(0b1000), 0b1000);
+        assert_eq!(b.violated_exclusive_groups(0b1001).count(), 1);
+        assert_eq!(b.violated_exclusive_groups(0b0111).count(), 0);
+    }
+    #[test]
+    #[cfg(all(feature = "engines-pc", feature = "engines-xbox", feature = "engines-mac", feature = "engines-mcc"))]
+    fn engine_dependent_array_lengths() {
+        use crate::*;
+
+        let json = br#"[{
+            "name": "TestArrays",
+            "type": "struct",
+            "fields": [{ "name": "values", "type": "uint32", "count": { "default": 4, "xbox": 2 } }],
+            "size": 16
+        }]"#;
+        let mut objects = get_all_definitions();
+        objects.extend(super::parse_definition_file("test.json", json));
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_json(&objects);
+        definitions.finalize();
+
+        let field = definitions.struct_view("TestArrays").unwrap().field("values").unwrap();
+        assert_eq!(field.count, FieldCount::Array(4));
+        assert_eq!(field.count_for_engine("xbox-us"), 2);
+        assert_eq!(field.count_for_engine("pc-retail"), 4);
     }
 
-    fn fixup_chars(find: &[&str], mut for_string: &mut str) {
-        loop {
-            let mut found: Option<(usize, &str)> = None;
-            for i in find {
-                found = for_string.find(i).map(|f| (f, *i));
-                if found.is_some() {
-                    break;
-                }
+    #[test]
+    #[cfg(all(feature = "engines-pc", feature = "engines-xbox", feature = "engines-mac", feature = "engines-mcc"))]
+    fn limit_profiles_override_limits() {
+        use crate::*;
+
+        let json = r#"[
+            { "name": "test", "type": "limit_profile" },
+            {
+                "name": "TestLimits",
+                "type": "struct",
+                "fields": [
+                    { "name": "items", "type": "Reflexive", "struct": "TestLimits", "limit": { "default": 8, "profiles": { "test": 32 } } },
+                    { "name": "others", "type": "Reflexive", "struct": "TestLimits", "limit": { "default": 4, "pc-retail": 6 } }
+                ],
+                "size": 24
             }
+        ]"#;
+        let mut objects = get_all_definitions();
+        objects.extend(super::parse_definition_file("test.json", json.as_bytes()));
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_json(&objects);
+        definitions.finalize();
+
+        let field = definitions.struct_view("TestLimits").unwrap().field("items").unwrap();
+        assert_eq!(field.limit_for_profile("pc-retail", None), Some(8));
+        assert_eq!(field.limit_for_profile("pc-retail", Some("stock")), Some(8));
+        assert_eq!(field.limit_for_profile("pc-retail", Some("test")), Some(32));
+        assert_eq!(field.editor_limit(), Some(8));
+
+        // Limits without profiles are unchanged
+        let others = definitions.struct_view("TestLimits").unwrap().field("others").unwrap();
+        assert_eq!(others.limit.as_ref().unwrap().keys().collect::<alloc::vec::Vec<_>>(), [&LimitType::Engine(alloc::string::String::from("pc-retail")), &LimitType::Editor]);
+        assert_eq!(others.editor_limit(), Some(6));
+
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_json(&super::parse_definition_file("test.json", json.replace(r#""default": 8"#, r#""default": 8, "editor": 32"#).as_bytes()));
+        definitions.finalize_without_engines();
+        assert_eq!(definitions.struct_view("TestLimits").unwrap().field("items").unwrap().editor_limit(), Some(32));
+    }
 
-            let Some((s, find)) = found else {
-                break
-            };
-
-            let start = s;
-            let end = s + find.len();
-            let (before, after) = for_string.split_at_mut(end);
-
-            before[start..end].make_ascii_uppercase();
-
-            if after.is_empty() {
-                break
+    #[test]
+    fn colliding_rust_names_are_rejected() {
+        use alloc::borrow::ToOwned;
+        use crate::*;
+
+        let json = br#"[
+            {
+                "name": "TestCollision",
+                "type": "struct",
+                "fields": [{ "name": "hit-points", "type": "uint32" }, { "name": "hit points", "type": "uint32" }],
+                "size": 8
             }
-
-            // this is ASCII so this should be fine
-            after[0..1].make_ascii_uppercase();
-            for_string = after;
-        }
+        ]"#;
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_json(&super::parse_definition_file("test.json", json));
+        assert_eq!(definitions.try_finalize(), Err(DefinitionError::NameCollision {
+            object: "TestCollision".to_owned(),
+            first: "hit-points".to_owned(),
+            second: "hit points".to_owned(),
+            rust_name: "hit_points".to_owned()
+        }));
     }
 
-    fixup_chars(&["Hud", "Gbx", "Ui", "Bsp", "Dxt", "Pcm", "Bc7", "Adpcm", "A1r5g5b5", "R5g6b5", "A4r4g4b4", "A8y8", "Ay8", "A8r8g8b8", "X8r8g8b8", "Ucs"], &mut n);
-
-    n
-}
-
-fn format_for_rust_fields(what: &str) -> String {
-    let what_lowercase = what.to_ascii_lowercase();
-
-    match what_lowercase.as_str() {
-        // we could use r# here, but that is not great to type...
-        "type" | "struct" | "enum" | "break" | "continue" | "loop" | "begin" | "static" => return format!("_{what_lowercase}"),
-        _ => ()
-    };
-
-    let mut n = String::with_capacity(what_lowercase.len() + 1);
-    let mut c = what_lowercase.chars().peekable();
-
-    let first = c.peek().expect("struct field with empty name");
-    if !first.is_ascii_alphabetic() {
-        n += "_";
+    #[test]
+    fn observer_sees_every_definition() {
+        use crate::*;
+
+        let mut engines = 0;
+        let mut total = 0;
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_json_with_observer(&get_all_definitions(), &mut |event: &ParseEvent| {
+            total += 1;
+            if event.kind == DefinitionKind::Engine {
+                engines += 1;
+            }
+        });
+        assert_eq!(engines, definitions.engines.len());
+        assert_eq!(total, definitions.objects.len() + definitions.groups.len() + definitions.engines.len());
     }
 
-    for c in c {
-        if c == '\'' {
-            continue
-        }
-        else if !c.is_alphanumeric() {
-            n.push('_');
-        }
-        else {
-            n.push(c);
-        }
+    #[test]
+    fn struct_contents_are_precomputed() {
+        use crate::*;
+
+        let json = br#"[
+            { "name": "TestLeaf", "type": "struct", "fields": [{ "name": "data", "type": "Data" }], "size": 20 },
+            { "name": "TestInline", "type": "struct", "fields": [{ "name": "leaves", "type": "Reflexive", "struct": "TestLeaf", "limit": 1 }], "size": 12 },
+            {
+                "name": "TestRoot",
+                "type": "struct",
+                "fields": [
+                    { "name": "inline", "type": "TestInline" },
+                    { "name": "self", "type": "Reflexive", "struct": "TestRoot", "limit": 1 }
+                ],
+                "size": 24
+            },
+            { "name": "TestFlat", "type": "struct", "fields": [{ "name": "value", "type": "float" }], "size": 4 }
+        ]"#;
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_json(&super::parse_definition_file("test.json", json));
+        definitions.finalize_without_engines();
+
+        let root = definitions.get_struct("TestRoot").unwrap();
+        assert!(root.contains_data && root.contains_reflexives && !root.contains_tag_references);
+        assert_eq!(root.max_depth, 1);
+        assert_eq!(definitions.get_struct("TestInline").unwrap().max_depth, 1);
+
+        let flat = definitions.get_struct("TestFlat").unwrap();
+        assert!(flat.is_const && !flat.contains_data && !flat.contains_reflexives && flat.max_depth == 0);
+
+        let scenario = crate::load_all_definitions().get_struct("Scenario").unwrap();
+        assert!(scenario.contains_tag_references && scenario.max_depth > 1);
     }
 
-    n
-}
+    #[test]
+    #[cfg(all(feature = "engines-pc", feature = "engines-xbox"))]
+    fn unknown_engine_policies() {
+        use crate::*;
+
+        let json = br#"[{
+            "name": "TestUnknownEngines",
+            "type": "struct",
+            "fields": [
+                { "name": "only missing", "type": "uint32", "supported_engines": ["missing"] },
+                { "name": "partly missing", "type": "uint32", "supported_engines": ["xbox", "missing"] },
+                { "name": "introduced", "type": "uint32", "introduced_in": "missing" },
+                { "name": "values", "type": "uint32", "count": { "default": 2, "missing": 1 } },
+                { "name": "items", "type": "Reflexive", "struct": "TestUnknownEngines", "limit": { "default": 8, "missing": 4 } }
+            ],
+            "size": 32
+        }]"#;
+        let load = |policy| {
+            let mut objects = get_engine_definitions();
+            objects.extend(super::parse_definition_file("test.json", json));
+            let mut definitions = ParsedDefinitions::default();
+            definitions.set_unknown_engine_policy(policy);
+            definitions.load_from_json(&objects);
+            definitions
+        };
 
+        assert!(load(UnknownEnginePolicy::Error).try_finalize().is_err());
+
+        for (policy, supported) in [(UnknownEnginePolicy::TreatAsUnsupported, false), (UnknownEnginePolicy::TreatAsSupported, true)] {
+            let mut definitions = load(policy);
+            definitions.try_finalize().unwrap();
+            let view = definitions.struct_view("TestUnknownEngines").unwrap();
+            let pc = &definitions.engines["pc-retail"];
+            assert_eq!(view.field("only missing").unwrap().flags.supported_engines.supports_engine(pc), supported);
+            assert_eq!(view.field("introduced").unwrap().flags.supported_engines.supports_engine(pc), supported);
+            let partly_missing = &view.field("partly missing").unwrap().flags.supported_engines;
+            assert!(!partly_missing.supports_engine(pc) && partly_missing.supports_engine(&definitions.engines["xbox-us"]));
+            assert!(view.field("values").unwrap().engine_counts.is_empty());
+            assert!(view.field("items").unwrap().limit.as_ref().unwrap().keys().all(|k| !matches!(k, LimitType::Engine(_))));
+        }
+    }
 
-#[cfg(test)]
-mod test {
     #[test]
-    fn test_load_all_definitions() {
-        crate::load_all_definitions();
+    fn empty_objects_take_no_space() {
+        use crate::*;
+
+        let json = r#"[
+            { "name": "TestEmpty", "type": "struct", "size": 0, "fields": [] },
+            { "name": "TestNoOptions", "type": "enum", "options": [] },
+            { "name": "TestNoBits", "type": "bitfield", "width": 0, "fields": [] },
+            { "name": "TestHolder", "type": "struct", "size": 18, "fields": [
+                { "name": "empty", "type": "TestEmpty" },
+                { "name": "kind", "type": "TestNoOptions" },
+                { "name": "flags", "type": "TestNoBits" },
+                { "name": "empties", "type": "Reflexive", "struct": "TestEmpty" },
+                { "name": "value", "type": "float" }
+            ] },
+            { "name": "test_empty", "type": "group", "struct": "TestHolder", "version": 1, "fourcc_binary": 1413829460 }
+        ]"#;
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_str("test.json", json).unwrap();
+        definitions.try_finalize().unwrap();
+
+        let holder = definitions.struct_view("TestHolder").unwrap();
+        let offsets: alloc::vec::Vec<usize> = holder.fields().map(|f| f.relative_offset).collect();
+        assert_eq!(offsets, [0, 0, 2, 2, 14]);
+        assert_eq!(holder.field_at_offset(0).unwrap().0.name, "kind");
+        assert!(definitions.get_struct("TestEmpty").unwrap().is_const);
+        assert!(definitions.validate().is_valid());
+
+        let header = generate_c_headers(&definitions, COptions::default());
+        assert!(!header.contains("TestNoBits;") && !header.contains("typedef struct TestEmpty"));
+        assert!(header.contains("typedef struct TestHolder {\n    /* TestEmpty empty (zero-sized) */\n    TestNoOptions kind;\n    /* TestNoBits flags (zero-sized) */\n"));
+
+        let plugin = generate_assembly_plugin(&definitions, &definitions.groups["test_empty"], "Halo1");
+        assert!(!plugin.contains("bitfield") && plugin.contains("<enum16 name=\"kind\" offset=\"0x0\"") && plugin.contains("entrySize=\"0x0\""));
+
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_str("test.json", &json.replace("\"width\": 0", "\"width\": 12")).unwrap();
+        assert!(matches!(definitions.try_finalize(), Err(DefinitionError::InvalidValue { object, .. }) if object == "TestNoBits"));
     }
+
     #[test]
-    fn shader_transparent_chicago_extended_works_on_custom_edition() {
+    fn builtin_fields_have_examples() {
         let definitions = crate::load_all_definitions();
-        let supported = definitions.groups["shader_transparent_chicago_extended"]
-            .supported_engines
-            .supports_engine(&definitions.engines["pc-custom"]);
-        assert!(supported, "Custom Edition must support shader_transparent_chicago_extended")
+        let examples = |s: &str, f: &str| definitions.get_struct(s).unwrap().field(f).unwrap().flags.examples.clone();
+        assert_eq!(examples("Weapon", "zoom levels"), ["4"]);
+        assert_eq!(examples("Weapon", "zoom magnification range"), ["2 to 8"]);
+        assert_eq!(examples("SoundPitchRange", "natural pitch"), ["1.0", "2.0"]);
+        assert!(examples("Weapon", "autoaim angle").is_empty());
+    }
+
+    #[test]
+    fn builtin_see_also_links_are_mutual() {
+        use crate::*;
+
+        let definitions = load_all_definitions();
+        assert_eq!(definitions.groups["gbxmodel"].see_also, [SeeAlso::Group("model".into())]);
+        for group in definitions.groups.values() {
+            for s in &group.see_also {
+                let SeeAlso::Group(other) = s else { panic!("{} should only link to groups", group.name) };
+                assert!(definitions.groups[other].see_also.contains(&SeeAlso::Group(group.name.clone())), "{other} should link back to {}", group.name);
+            }
+        }
+
+        let zoom_levels = definitions.get_struct("Weapon").unwrap().field("zoom levels").unwrap();
+        assert_eq!(zoom_levels.flags.see_also, [SeeAlso::Field { object: "Weapon".into(), field: "zoom magnification range".into() }]);
     }
 }
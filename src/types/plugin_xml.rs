@@ -0,0 +1,578 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde_json::{Map, Value};
+
+use super::*;
+
+impl ParsedDefinitions {
+    /// Import an Assembly-style plugin as a struct named `name`, along with a struct for each
+    /// reflexive and an enum or bitfield for each 16-bit enum and bitfield.
+    ///
+    /// `file` is used as the definition file of everything that is loaded. Element names are
+    /// case-insensitive, and common aliases used by older plugins (e.g. `struct` for `reflexive`,
+    /// `tag` for `tagRef`, and `short` for `int16`) are accepted.
+    ///
+    /// Plugins describe values rather than layouts, so some information is approximated:
+    ///
+    /// - Gaps between fields, `undefined`, and `raw` elements become padding.
+    /// - Fields overlapping an earlier field are skipped.
+    /// - Tag references allow every group.
+    /// - Field names are lowercased, and duplicate names are numbered.
+    ///
+    /// Like [`ParsedDefinitions::try_load_from_json`], nothing is loaded if an error is returned.
+    /// The definitions still need to be finalized afterwards.
+    pub fn load_plugin_xml(&mut self, file: &str, name: &str, xml: &str) -> Result<(), DefinitionError> {
+        let invalid = |message: String| DefinitionError::InvalidPlugin { file: file.to_owned(), message };
+        let root = XmlReader { xml, position: 0, depth: 0 }.read_document().map_err(|e| invalid(format!("failed to parse {file}: {e}")))?;
+        if !root.name.eq_ignore_ascii_case("plugin") {
+            return Err(invalid(format!("{file} has a <{}> root element instead of <plugin>", root.name)))
+        }
+        let size = root.attribute(&["baseSize", "headersize"]).map(parse_number).transpose().map_err(invalid)?;
+
+        let mut importer = PluginImporter { file, definitions: self, objects: Vec::new(), names: BTreeSet::new() };
+        importer.names.insert(name.to_owned());
+        importer.import_struct(name.to_owned(), &root, size).map_err(invalid)?;
+        let objects = importer.objects;
+        self.try_load_from_json(&objects)
+    }
+}
+
+struct PluginImporter<'a> {
+    file: &'a str,
+    definitions: &'a ParsedDefinitions,
+    objects: Vec<Map<String, Value>>,
+    names: BTreeSet<String>
+}
+
+/// A field of an imported struct, before padding is added.
+struct ImportedField {
+    offset: Option<usize>,
+    size: usize,
+    json: Map<String, Value>
+}
+
+impl PluginImporter<'_> {
+    fn import_struct(&mut self, name: String, element: &XmlElement, size: Option<usize>) -> Result<(), String> {
+        let mut imported = Vec::new();
+        let mut field_names = BTreeSet::new();
+        for child in &element.children {
+            if let Some(field) = self.import_field(&name, child, &mut field_names)? {
+                imported.push(field);
+            }
+        }
+
+        // Comments have no offset, so they go before the field following them.
+        let mut next_offset = size.unwrap_or(usize::MAX);
+        for field in imported.iter_mut().rev() {
+            match field.offset {
+                Some(offset) => next_offset = offset,
+                None => field.offset = Some(next_offset)
+            }
+        }
+        imported.sort_by_key(|f| f.offset);
+
+        let mut fields = Vec::new();
+        let mut end = 0;
+        for field in imported {
+            let offset = field.offset.expect("offsets were filled in");
+            if field.size > 0 && offset < end {
+                continue
+            }
+            if offset > end && offset != usize::MAX {
+                fields.push(pad(offset - end));
+                end = offset;
+            }
+            end += field.size;
+            fields.push(Value::Object(field.json));
+        }
+        let size = size.unwrap_or(end);
+        if end > size {
+            return Err(format!("fields of {name} end at 0x{end:X}, past its size of 0x{size:X}"))
+        }
+        if size > end {
+            fields.push(pad(size - end));
+        }
+
+        let mut object = self.definition("struct", &name);
+        object.insert("fields".to_owned(), Value::Array(fields));
+        object.insert("size".to_owned(), Value::from(size));
+        self.objects.push(object);
+        Ok(())
+    }
+
+    fn import_field(&mut self, parent: &str, element: &XmlElement, field_names: &mut BTreeSet<String>) -> Result<Option<ImportedField>, String> {
+        let kind = element.name.to_ascii_lowercase();
+        if kind == "comment" {
+            let mut json = Map::new();
+            json.insert("type".to_owned(), Value::from("editor_section"));
+            json.insert("heading".to_owned(), Value::from(element.attribute(&["title", "name"]).unwrap_or("Comment")));
+            let body = element.text.trim();
+            if !body.is_empty() {
+                json.insert("body".to_owned(), Value::from(body));
+            }
+            return Ok(Some(ImportedField { offset: None, size: 0, json }))
+        }
+        if matches!(kind.as_str(), "revisions" | "revision") {
+            return Ok(None)
+        }
+
+        let offset = parse_number(element.attribute(&["offset"]).ok_or_else(|| format!("<{}> in {parent} has no offset", element.name))?)?;
+        let field_name = unique_name(field_names, element.attribute(&["name"]).unwrap_or_default());
+        let size_attribute = |names: &[&str]| -> Result<usize, String> {
+            parse_number(element.attribute(names).ok_or_else(|| format!("{parent}::{field_name} has no size"))?)
+        };
+
+        let mut json = Map::new();
+        json.insert("name".to_owned(), Value::from(field_name.as_str()));
+        let (field_type, size) = match kind.as_str() {
+            "undefined" | "unknown" => return Ok(Some(ImportedField { offset: Some(offset), size: 4, json: pad_object(4) })),
+            "raw" => {
+                let size = size_attribute(&["size", "length"])?;
+                return Ok(Some(ImportedField { offset: Some(offset), size, json: pad_object(size) }))
+            },
+            "reflexive" | "struct" | "block" => {
+                let struct_name = self.object_name(parent, &field_name);
+                let entry_size = size_attribute(&["entrySize", "size", "elementSize"])?;
+                self.import_struct(struct_name.clone(), element, Some(entry_size))?;
+                json.insert("struct".to_owned(), Value::from(struct_name));
+                ("Reflexive", FieldObject::Reflexive(String::new()).primitive_size())
+            },
+            "tagref" | "tag" => {
+                json.insert("groups".to_owned(), Value::from(["*"].as_slice()));
+                ("TagReference", FieldObject::TagReference { allowed_groups: Vec::new() }.primitive_size())
+            },
+            "dataref" | "data" => {
+                let utf16 = element.attribute(&["format"]).is_some_and(|f| f.eq_ignore_ascii_case("utf16") || f.eq_ignore_ascii_case("unicode"));
+                (if utf16 { "UTF16String" } else { "Data" }, FieldObject::Data.primitive_size())
+            },
+            "ascii" | "string" => match size_attribute(&["length", "size"])? {
+                32 => ("String32", 32),
+                length => {
+                    json.insert("count".to_owned(), Value::from(length));
+                    ("uint8", length)
+                }
+            },
+            "colorf" => {
+                let argb = element.attribute(&["format"]).is_some_and(|f| f.contains(['a', 'A']));
+                if argb { ("ColorARGB", FieldObject::ColorARGB.primitive_size()) } else { ("ColorRGB", FieldObject::ColorRGB.primitive_size()) }
+            },
+            "enum16" => {
+                let enum_name = self.import_options(parent, &field_name, element, "enum", "option", 0xFFFF)?;
+                json.insert("type".to_owned(), Value::from(enum_name));
+                return Ok(Some(ImportedField { offset: Some(offset), size: 2, json: flags(json, element) }))
+            },
+            "bitfield8" | "bitfield16" | "bitfield32" | "bitmask8" | "bitmask16" | "bitmask32" => {
+                let width: usize = kind.trim_start_matches(|c: char| c.is_ascii_alphabetic()).parse().expect("width was matched");
+                let bitfield_name = self.import_options(parent, &field_name, element, "bitfield", "bit", width - 1)?;
+                json.insert("type".to_owned(), Value::from(bitfield_name));
+                return Ok(Some(ImportedField { offset: Some(offset), size: width / 8, json: flags(json, element) }))
+            },
+            other => {
+                let Some((field_type, bounds)) = simple_plugin_type(other) else {
+                    return Err(format!("{parent}::{field_name} has unknown type <{}>", element.name))
+                };
+                let object = simple_field_object(field_type).expect("plugin types map to simple field objects");
+                let mut size = object.size(self.definitions);
+                if bounds {
+                    json.insert("bounds".to_owned(), Value::Bool(true));
+                    size *= 2;
+                }
+                (field_type, size)
+            }
+        };
+        json.insert("type".to_owned(), Value::from(field_type));
+        Ok(Some(ImportedField { offset: Some(offset), size, json: flags(json, element) }))
+    }
+
+    /// Import the options of an enum or the bits of a bitfield, returning the name of the object.
+    fn import_options(&mut self, parent: &str, field_name: &str, element: &XmlElement, object_type: &str, option_element: &str, max_value: usize) -> Result<String, String> {
+        let mut options: Vec<Option<&str>> = Vec::new();
+        for option in element.children.iter().filter(|c| c.name.eq_ignore_ascii_case(option_element)) {
+            let value = parse_number(option.attribute(&["value", "index"]).ok_or_else(|| format!("{parent}::{field_name} has a <{option_element}> without a value"))?)?;
+            if value > max_value {
+                return Err(format!("{parent}::{field_name} has a <{option_element}> with value {value}, which is too large"))
+            }
+            if options.len() <= value {
+                options.resize(value + 1, None);
+            }
+            options[value] = Some(option.attribute(&["name"]).unwrap_or_default());
+        }
+
+        let mut names = BTreeSet::new();
+        let options: Vec<Value> = options
+            .into_iter()
+            .enumerate()
+            .map(|(i, o)| Value::from(unique_name(&mut names, o.unwrap_or(&format!("unused {i}")))))
+            .collect();
+
+        let name = self.object_name(parent, field_name);
+        let mut object = self.definition(object_type, &name);
+        if object_type == "bitfield" {
+            object.insert("fields".to_owned(), Value::Array(options));
+            object.insert("width".to_owned(), Value::from(max_value + 1));
+        }
+        else {
+            object.insert("options".to_owned(), Value::Array(options));
+        }
+        self.objects.push(object);
+        Ok(name)
+    }
+
+    /// Get an unused object name for an object belonging to a field.
+    fn object_name(&mut self, parent: &str, field_name: &str) -> String {
+        let base = format!("{parent}{}", format_for_rust_enums(field_name));
+        let mut name = base.clone();
+        let mut i = 2;
+        while self.definitions.objects.contains_key(&name) || !self.names.insert(name.clone()) {
+            name = format!("{base}{i}");
+            i += 1;
+        }
+        name
+    }
+
+    fn definition(&self, object_type: &str, name: &str) -> Map<String, Value> {
+        let mut object = Map::new();
+        object.insert("name".to_owned(), Value::from(name));
+        object.insert("type".to_owned(), Value::from(object_type));
+        object.insert("__json_file".to_owned(), Value::from(self.file));
+        object
+    }
+}
+
+fn flags(mut json: Map<String, Value>, element: &XmlElement) -> Map<String, Value> {
+    if element.attribute(&["visible"]).is_some_and(|v| v.eq_ignore_ascii_case("false")) {
+        json.insert(FlagType::HiddenInEditor.json_name().to_owned(), Value::Bool(true));
+    }
+    json
+}
+
+fn pad_object(size: usize) -> Map<String, Value> {
+    let mut json = Map::new();
+    json.insert("type".to_owned(), Value::from("pad"));
+    json.insert("size".to_owned(), Value::from(size));
+    json
+}
+
+fn pad(size: usize) -> Value {
+    Value::Object(pad_object(size))
+}
+
+/// Lowercase a name and strip anything that can't be in a definition name, numbering it if it is
+/// already used.
+fn unique_name(names: &mut BTreeSet<String>, name: &str) -> String {
+    let cleaned: Vec<String> = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_ascii_lowercase())
+        .collect();
+    let base = if cleaned.is_empty() || !cleaned[0].starts_with(|c: char| c.is_ascii_alphabetic()) {
+        format!("unnamed {}", cleaned.join(" ")).trim_end().to_owned()
+    }
+    else {
+        cleaned.join(" ")
+    };
+
+    let mut name = base.clone();
+    let mut i = 2;
+    while !names.insert(format_for_rust_fields(&name)) {
+        name = format!("{base} {i}");
+        i += 1;
+    }
+    name
+}
+
+/// Get the definition type of a plugin element that maps to a simple field object, and whether it
+/// is a range (bounds).
+fn simple_plugin_type(element: &str) -> Option<(&'static str, bool)> {
+    let field_type = match element {
+        "uint8" | "byte" | "enum8" => "uint8",
+        "int8" | "sbyte" => "int8",
+        "uint16" | "ushort" => "uint16",
+        "int16" | "short" | "index" => "int16",
+        "uint32" | "uint" | "ulong" | "enum32" => "uint32",
+        "int32" | "int" | "long" => "int32",
+        "float32" | "float" | "real" => "float",
+        "degree" | "angle" => "Angle",
+        "point2" | "vector2" => "Vector2D",
+        "point3" | "vector3" => "Vector3D",
+        "point16" => "Vector2DInt",
+        "degree2" => "Euler2D",
+        "degree3" => "Euler3D",
+        "plane2" => "Plane2D",
+        "plane3" => "Plane3D",
+        "quaternion" | "vector4" => "Quaternion",
+        "rect16" => "Rectangle",
+        "colorb" | "color32" => "Pixel32",
+        "stringid" | "id" | "datum" => "ID",
+        "tagid" => "TagID",
+        "rangef" => return Some(("float", true)),
+        "rangeint16" => return Some(("int16", true)),
+        "rangedegree" => return Some(("Angle", true)),
+        _ => return None
+    };
+    Some((field_type, false))
+}
+
+fn parse_number(value: &str) -> Result<usize, String> {
+    let value = value.trim();
+    let parsed = match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(hex) => usize::from_str_radix(hex, 16),
+        None => value.parse()
+    };
+    parsed.map_err(|_| format!("{value} is not a valid number"))
+}
+
+/// An element of a parsed XML document.
+struct XmlElement {
+    name: String,
+    attributes: Vec<(String, String)>,
+    text: String,
+    children: Vec<XmlElement>
+}
+
+impl XmlElement {
+    /// Get the first of the given attributes the element has (case-insensitive).
+    fn attribute(&self, names: &[&str]) -> Option<&str> {
+        names.iter().find_map(|n| self.attributes.iter().find(|(k, _)| k.eq_ignore_ascii_case(n)).map(|(_, v)| v.as_str()))
+    }
+}
+
+/// A minimal XML reader, covering what plugins use (no DTDs or namespaces).
+struct XmlReader<'a> {
+    xml: &'a str,
+    position: usize,
+    depth: usize
+}
+
+/// Elements nested deeper than this are rejected rather than risking a stack overflow.
+///
+/// Plugins only nest a few levels (reflexives in reflexives), so this is far more than needed.
+const MAX_ELEMENT_DEPTH: usize = 256;
+
+impl<'a> XmlReader<'a> {
+    fn read_document(mut self) -> Result<XmlElement, String> {
+        self.skip_misc()?;
+        let root = self.read_element()?;
+        self.skip_misc()?;
+        if self.position != self.xml.len() {
+            return Err(format!("unexpected content after the root element at byte {}", self.position))
+        }
+        Ok(root)
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.xml[self.position..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skip everything until (and including) `end`.
+    fn skip_past(&mut self, end: &str) -> Result<(), String> {
+        let index = self.rest().find(end).ok_or_else(|| format!("expected {end} after byte {}", self.position))?;
+        self.position += index + end.len();
+        Ok(())
+    }
+
+    /// Skip whitespace, comments, processing instructions, and doctypes.
+    fn skip_misc(&mut self) -> Result<(), String> {
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("<?") {
+                self.skip_past("?>")?;
+            }
+            else if rest.starts_with("<!--") {
+                self.skip_past("-->")?;
+            }
+            else if rest.starts_with("<!") {
+                self.skip_past(">")?;
+            }
+            else {
+                return Ok(())
+            }
+        }
+    }
+
+    fn read_name(&mut self) -> Result<String, String> {
+        let rest = self.rest();
+        let length = rest.find(|c: char| c.is_whitespace() || matches!(c, '=' | '>' | '/')).unwrap_or(rest.len());
+        if length == 0 {
+            return Err(format!("expected a name at byte {}", self.position))
+        }
+        self.position += length;
+        Ok(rest[..length].to_owned())
+    }
+
+    fn expect(&mut self, what: &str) -> Result<(), String> {
+        if !self.rest().starts_with(what) {
+            return Err(format!("expected {what} at byte {}", self.position))
+        }
+        self.position += what.len();
+        Ok(())
+    }
+
+    fn read_element(&mut self) -> Result<XmlElement, String> {
+        self.expect("<")?;
+        let name = self.read_name()?;
+        let mut element = XmlElement { name, attributes: Vec::new(), text: String::new(), children: Vec::new() };
+
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("/>") {
+                self.position += 2;
+                return Ok(element)
+            }
+            if self.rest().starts_with('>') {
+                self.position += 1;
+                break
+            }
+            let key = self.read_name()?;
+            self.skip_whitespace();
+            self.expect("=")?;
+            self.skip_whitespace();
+            let quote = self.rest().chars().next().filter(|c| matches!(c, '"' | '\'')).ok_or_else(|| format!("expected a quoted value for {key} at byte {}", self.position))?;
+            self.position += 1;
+            let length = self.rest().find(quote).ok_or_else(|| format!("unterminated value for {key}"))?;
+            let value = decode_entities(&self.rest()[..length])?;
+            self.position += length + 1;
+            element.attributes.push((key, value));
+        }
+
+        loop {
+            let rest = self.rest();
+            if rest.starts_with("</") {
+                self.position += 2;
+                let end_name = self.read_name()?;
+                if end_name != element.name {
+                    return Err(format!("<{}> is closed by </{end_name}>", element.name))
+                }
+                self.skip_whitespace();
+                self.expect(">")?;
+                return Ok(element)
+            }
+            else if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+                let length = cdata.find("]]>").ok_or("unterminated CDATA section")?;
+                element.text.push_str(&cdata[..length]);
+                self.position += "<![CDATA[".len() + length + "]]>".len();
+            }
+            else if rest.starts_with("<!--") || rest.starts_with("<?") {
+                self.skip_misc()?;
+            }
+            else if rest.starts_with('<') {
+                if self.depth == MAX_ELEMENT_DEPTH {
+                    return Err(format!("elements are nested more than {MAX_ELEMENT_DEPTH} deep at byte {}", self.position))
+                }
+                self.depth += 1;
+                let child = self.read_element();
+                self.depth -= 1;
+                element.children.push(child?);
+            }
+            else if rest.is_empty() {
+                return Err(format!("<{}> is not closed", element.name))
+            }
+            else {
+                let length = rest.find('<').unwrap_or(rest.len());
+                element.text.push_str(&decode_entities(&rest[..length])?);
+                self.position += length;
+            }
+        }
+    }
+}
+
+fn decode_entities(text: &str) -> Result<String, String> {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest.find(';').ok_or_else(|| format!("unterminated entity in {text}"))?;
+        let entity = &rest[1..end];
+        let c = match entity {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => {
+                let code = match entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => entity.strip_prefix('#').and_then(|d| d.parse().ok())
+                };
+                code.and_then(char::from_u32).ok_or_else(|| format!("unknown entity &{entity};"))?
+            }
+        };
+        decoded.push(c);
+        rest = &rest[end + 1..];
+    }
+    decoded.push_str(rest);
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn plugins_are_imported() {
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_plugin_xml("test.xml", "TestPlugin", r#"<?xml version="1.0" encoding="utf-8"?>
+            <!-- An old plugin -->
+            <plugin game="Halo1" baseSize="0x30">
+                <comment title="Test &amp; stuff">Some fields</comment>
+                <enum16 name="Type" offset="0x0" visible="true">
+                    <option name="First" value="0x0" />
+                    <option name="Third" value="2" />
+                </enum16>
+                <bitfield16 name="Flags" offset="0x2" visible="false">
+                    <bit name="Enabled" index="1" />
+                </bitfield16>
+                <float32 name="Scale" offset="0x8" visible="true" />
+                <rangef name="Scale" offset="0xC" visible="true" />
+                <tagRef name="Model" offset="0x14" visible="true" withGroup="true" />
+                <reflexive name="Items" offset="0x24" visible="true" entrySize="0x4">
+                    <int16 name="Value" offset="0x2" visible="true" />
+                </reflexive>
+            </plugin>"#).unwrap();
+        definitions.finalize_without_engines();
+
+        let plugin = definitions.get_struct("TestPlugin").unwrap();
+        assert_eq!(plugin.size, 0x30);
+        assert!(matches!(&plugin.fields[0].field_type, StructFieldType::EditorSection { heading, .. } if heading == "Test & stuff"));
+        assert_eq!(plugin.field("scale 2").unwrap().relative_offset, 0xC);
+        assert_eq!(plugin.field("scale 2").unwrap().count, FieldCount::Bounds);
+        assert!(plugin.field("flags").unwrap().flags.hidden_in_editor);
+        assert_eq!(plugin.field_at_offset(0x4).unwrap().0.padding_kind, Some(PaddingKind::Padding));
+
+        let NamedObject::Enum(e) = &definitions.objects["TestPluginType"] else { panic!() };
+        assert_eq!(e.options.iter().map(|o| o.name.as_str()).collect::<alloc::vec::Vec<_>>(), ["first", "unused 1", "third"]);
+        let NamedObject::Bitfield(b) = &definitions.objects["TestPluginFlags"] else { panic!() };
+        assert_eq!((b.width, b.field_value("enabled")), (16, Some(2)));
+        assert_eq!(definitions.get_struct("TestPluginItems").unwrap().field("value").unwrap().relative_offset, 2);
+
+        let error = definitions.load_plugin_xml("bad.xml", "TestBad", "<plugin><float32 name=\"x\" offset=\"0\"></plugin>").unwrap_err();
+        assert!(matches!(error, DefinitionError::InvalidPlugin { .. }));
+
+        let nested = alloc::format!("<plugin>{}{}</plugin>", "<a>".repeat(100_000), "</a>".repeat(100_000));
+        let error = definitions.load_plugin_xml("nested.xml", "TestNested", &nested).unwrap_err();
+        assert!(matches!(error, DefinitionError::InvalidPlugin { message, .. } if message.contains("nested")));
+    }
+
+    #[test]
+    fn exported_plugins_round_trip() {
+        let builtin = load_all_definitions();
+        let group = &builtin.groups["sound"];
+        let plugin = generate_assembly_plugin(builtin, group, "Halo1");
+
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_plugin_xml("sound.xml", "Sound", &plugin).unwrap();
+        definitions.finalize_without_engines();
+        assert_eq!(definitions.get_struct("Sound").unwrap().size, group.definition(builtin).size);
+    }
+}
@@ -0,0 +1,289 @@
+use super::*;
+
+/// Kind of a [`FieldObject`] without its payload.
+///
+/// Each variant mirrors the [`FieldObject`] variant of the same name and has a stable integer
+/// value, so it can be passed across FFI boundaries or stored in serialized data.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+#[non_exhaustive]
+pub enum PrimitiveType {
+    /// See [`FieldObject::NamedObject`].
+    NamedObject = 0,
+
+    /// See [`FieldObject::Reflexive`].
+    Reflexive = 1,
+
+    /// See [`FieldObject::TagReference`].
+    TagReference = 2,
+
+    /// See [`FieldObject::TagGroup`].
+    TagGroup = 3,
+
+    /// See [`FieldObject::Data`].
+    Data = 4,
+
+    /// See [`FieldObject::BSPVertexData`].
+    BSPVertexData = 5,
+
+    /// See [`FieldObject::UTF16String`].
+    UTF16String = 6,
+
+    /// See [`FieldObject::FileData`].
+    FileData = 7,
+
+    /// See [`FieldObject::F32`].
+    F32 = 8,
+
+    /// See [`FieldObject::U8`].
+    U8 = 9,
+
+    /// See [`FieldObject::U16`].
+    U16 = 10,
+
+    /// See [`FieldObject::U32`].
+    U32 = 11,
+
+    /// See [`FieldObject::I8`].
+    I8 = 12,
+
+    /// See [`FieldObject::I16`].
+    I16 = 13,
+
+    /// See [`FieldObject::I32`].
+    I32 = 14,
+
+    /// See [`FieldObject::TagID`].
+    TagID = 15,
+
+    /// See [`FieldObject::ID`].
+    ID = 16,
+
+    /// See [`FieldObject::Index`].
+    Index = 17,
+
+    /// See [`FieldObject::ReflexiveIndex`].
+    ReflexiveIndex = 18,
+
+    /// See [`FieldObject::Angle`].
+    Angle = 19,
+
+    /// See [`FieldObject::Address`].
+    Address = 20,
+
+    /// See [`FieldObject::Vector2D`].
+    Vector2D = 21,
+
+    /// See [`FieldObject::Vector3D`].
+    Vector3D = 22,
+
+    /// See [`FieldObject::CompressedVector2D`].
+    CompressedVector2D = 23,
+
+    /// See [`FieldObject::CompressedVector3D`].
+    CompressedVector3D = 24,
+
+    /// See [`FieldObject::CompressedFloat`].
+    CompressedFloat = 25,
+
+    /// See [`FieldObject::Vector2DInt`].
+    Vector2DInt = 26,
+
+    /// See [`FieldObject::Plane2D`].
+    Plane2D = 27,
+
+    /// See [`FieldObject::Plane3D`].
+    Plane3D = 28,
+
+    /// See [`FieldObject::Rectangle3D`].
+    Rectangle3D = 29,
+
+    /// See [`FieldObject::Euler2D`].
+    Euler2D = 30,
+
+    /// See [`FieldObject::Euler3D`].
+    Euler3D = 31,
+
+    /// See [`FieldObject::Rectangle`].
+    Rectangle = 32,
+
+    /// See [`FieldObject::Quaternion`].
+    Quaternion = 33,
+
+    /// See [`FieldObject::Matrix2x3`].
+    Matrix2x3 = 34,
+
+    /// See [`FieldObject::Matrix3x3`].
+    Matrix3x3 = 35,
+
+    /// See [`FieldObject::Matrix4x3`].
+    Matrix4x3 = 36,
+
+    /// See [`FieldObject::ColorRGB`].
+    ColorRGB = 37,
+
+    /// See [`FieldObject::ColorARGB`].
+    ColorARGB = 38,
+
+    /// See [`FieldObject::Pixel32`].
+    Pixel32 = 39,
+
+    /// See [`FieldObject::String32`].
+    String32 = 40,
+
+    /// See [`FieldObject::ScenarioScriptNodeValue`].
+    ScenarioScriptNodeValue = 41,
+
+    /// See [`FieldObject::Custom`].
+    Custom = 42
+}
+
+impl PrimitiveType {
+    /// Every primitive type, in order of their integer values.
+    pub const ALL: [PrimitiveType; 43] = [
+        Self::NamedObject,
+        Self::Reflexive,
+        Self::TagReference,
+        Self::TagGroup,
+        Self::Data,
+        Self::BSPVertexData,
+        Self::UTF16String,
+        Self::FileData,
+        Self::F32,
+        Self::U8,
+        Self::U16,
+        Self::U32,
+        Self::I8,
+        Self::I16,
+        Self::I32,
+        Self::TagID,
+        Self::ID,
+        Self::Index,
+        Self::ReflexiveIndex,
+        Self::Angle,
+        Self::Address,
+        Self::Vector2D,
+        Self::Vector3D,
+        Self::CompressedVector2D,
+        Self::CompressedVector3D,
+        Self::CompressedFloat,
+        Self::Vector2DInt,
+        Self::Plane2D,
+        Self::Plane3D,
+        Self::Rectangle3D,
+        Self::Euler2D,
+        Self::Euler3D,
+        Self::Rectangle,
+        Self::Quaternion,
+        Self::Matrix2x3,
+        Self::Matrix3x3,
+        Self::Matrix4x3,
+        Self::ColorRGB,
+        Self::ColorARGB,
+        Self::Pixel32,
+        Self::String32,
+        Self::ScenarioScriptNodeValue,
+        Self::Custom,
+    ];
+
+    /// Get the primitive type for an integer value, or `None` if it is out of range.
+    pub const fn from_u8(value: u8) -> Option<PrimitiveType> {
+        if (value as usize) < Self::ALL.len() {
+            Some(Self::ALL[value as usize])
+        }
+        else {
+            None
+        }
+    }
+
+    /// Get the integer value of the primitive type.
+    pub const fn as_u8(self) -> u8 {
+        self as u8
+    }
+}
+
+impl TryFrom<u8> for PrimitiveType {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::from_u8(value).ok_or(value)
+    }
+}
+
+impl From<PrimitiveType> for u8 {
+    fn from(value: PrimitiveType) -> Self {
+        value.as_u8()
+    }
+}
+
+impl From<&FieldObject> for PrimitiveType {
+    fn from(value: &FieldObject) -> Self {
+        value.primitive_type()
+    }
+}
+
+impl FieldObject {
+    /// Get the kind of this object without its payload.
+    pub const fn primitive_type(&self) -> PrimitiveType {
+        match self {
+            Self::NamedObject(_) => PrimitiveType::NamedObject,
+            Self::Reflexive(_) => PrimitiveType::Reflexive,
+            Self::TagReference { .. } => PrimitiveType::TagReference,
+            Self::TagGroup => PrimitiveType::TagGroup,
+            Self::Data => PrimitiveType::Data,
+            Self::BSPVertexData => PrimitiveType::BSPVertexData,
+            Self::UTF16String => PrimitiveType::UTF16String,
+            Self::FileData => PrimitiveType::FileData,
+            Self::F32 => PrimitiveType::F32,
+            Self::U8 => PrimitiveType::U8,
+            Self::U16 => PrimitiveType::U16,
+            Self::U32 => PrimitiveType::U32,
+            Self::I8 => PrimitiveType::I8,
+            Self::I16 => PrimitiveType::I16,
+            Self::I32 => PrimitiveType::I32,
+            Self::TagID => PrimitiveType::TagID,
+            Self::ID => PrimitiveType::ID,
+            Self::Index => PrimitiveType::Index,
+            Self::ReflexiveIndex { .. } => PrimitiveType::ReflexiveIndex,
+            Self::Angle => PrimitiveType::Angle,
+            Self::Address => PrimitiveType::Address,
+            Self::Vector2D => PrimitiveType::Vector2D,
+            Self::Vector3D => PrimitiveType::Vector3D,
+            Self::CompressedVector2D => PrimitiveType::CompressedVector2D,
+            Self::CompressedVector3D => PrimitiveType::CompressedVector3D,
+            Self::CompressedFloat => PrimitiveType::CompressedFloat,
+            Self::Vector2DInt => PrimitiveType::Vector2DInt,
+            Self::Plane2D => PrimitiveType::Plane2D,
+            Self::Plane3D => PrimitiveType::Plane3D,
+            Self::Rectangle3D => PrimitiveType::Rectangle3D,
+            Self::Euler2D => PrimitiveType::Euler2D,
+            Self::Euler3D => PrimitiveType::Euler3D,
+            Self::Rectangle => PrimitiveType::Rectangle,
+            Self::Quaternion => PrimitiveType::Quaternion,
+            Self::Matrix2x3 => PrimitiveType::Matrix2x3,
+            Self::Matrix3x3 => PrimitiveType::Matrix3x3,
+            Self::Matrix4x3 => PrimitiveType::Matrix4x3,
+            Self::ColorRGB => PrimitiveType::ColorRGB,
+            Self::ColorARGB => PrimitiveType::ColorARGB,
+            Self::Pixel32 => PrimitiveType::Pixel32,
+            Self::String32 => PrimitiveType::String32,
+            Self::ScenarioScriptNodeValue => PrimitiveType::ScenarioScriptNodeValue,
+            Self::Custom(_) => PrimitiveType::Custom,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn primitive_types_round_trip() {
+        for (i, p) in PrimitiveType::ALL.into_iter().enumerate() {
+            assert_eq!(p.as_u8() as usize, i);
+            assert_eq!(PrimitiveType::try_from(p.as_u8()), Ok(p));
+        }
+        assert_eq!(PrimitiveType::from_u8(PrimitiveType::ALL.len() as u8), None);
+        assert_eq!(FieldObject::Vector3D.primitive_type(), PrimitiveType::Vector3D);
+    }
+}
@@ -0,0 +1,46 @@
+/// Describes where the built-in definitions came from.
+///
+/// This is populated when the crate is built, so it can be included in bug reports to identify
+/// exactly which revision of the definitions was used.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DefinitionsProvenance {
+    /// Version of this crate.
+    pub crate_version: &'static str,
+
+    /// Revision (e.g. git commit) the definitions were built from, if known.
+    ///
+    /// This can be overridden at build time with the `RINGHOPPER_DEFINITIONS_REVISION` environment
+    /// variable, which is useful when building from a source tarball.
+    pub revision: Option<&'static str>,
+
+    /// 64-bit FNV-1a digest of every embedded JSON file (and its path).
+    ///
+    /// Two builds with the same digest embed identical definitions, regardless of `revision`.
+    pub json_digest: u64,
+
+    /// Number of embedded JSON files.
+    pub file_count: usize
+}
+
+impl core::fmt::Display for DefinitionsProvenance {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.write_fmt(format_args!(
+            "ringhopper-definitions {version} ({revision}, {count} files, digest {digest:016x})",
+            version=self.crate_version,
+            revision=self.revision.unwrap_or("unknown revision"),
+            count=self.file_count,
+            digest=self.json_digest
+        ))
+    }
+}
+
+/// Get the provenance of the built-in definitions.
+pub fn builtin_provenance() -> DefinitionsProvenance {
+    DefinitionsProvenance {
+        crate_version: env!("CARGO_PKG_VERSION"),
+        revision: option_env!("RINGHOPPER_DEFINITIONS_REVISION"),
+        json_digest: u64::from_str_radix(env!("RINGHOPPER_DEFINITIONS_JSON_DIGEST"), 16).expect("bad digest from build script"),
+        file_count: env!("RINGHOPPER_DEFINITIONS_FILE_COUNT").parse().expect("bad file count from build script")
+    }
+}
@@ -0,0 +1,494 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::ops::{Bound, RangeBounds};
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::borrow::ToOwned;
+
+use super::*;
+
+/// Composable filter over all objects in a [`ParsedDefinitions`].
+///
+/// Filters are combined with AND, so each added filter narrows the results further. Nothing is
+/// evaluated until [`Query::iter`] or [`Query::fields`] is called.
+///
+/// ```
+/// use ringhopper_definitions::{load_all_definitions, FlagType};
+///
+/// let definitions = load_all_definitions();
+/// let cache_only_structs = definitions
+///     .query()
+///     .structs()
+///     .with_field_flag(FlagType::CacheOnly)
+///     .in_group("scenario")
+///     .iter()
+///     .count();
+/// assert!(cache_only_structs > 0);
+/// ```
+#[derive(Clone)]
+pub struct Query<'a> {
+    definitions: &'a ParsedDefinitions,
+    kinds: Option<BTreeSet<ObjectKind>>,
+    field_flags: Vec<FlagType>,
+    determinism: Option<Determinism>,
+    groups: Vec<String>
+}
+
+impl ParsedDefinitions {
+    /// Begin a query over all objects.
+    pub fn query(&self) -> Query<'_> {
+        Query {
+            definitions: self,
+            kinds: None,
+            field_flags: Vec::new(),
+            determinism: None,
+            groups: Vec::new()
+        }
+    }
+
+    /// Iterate through all struct fields marked as introduced in the given engine.
+    ///
+    /// This only matches [`Flags::introduced_in`] exactly; it does not include fields introduced in
+    /// an ancestor of the engine.
+    pub fn fields_introduced_in<'a>(&'a self, engine: &'a str) -> impl Iterator<Item = (&'a Struct, &'a StructField)> + 'a {
+        self.query().structs().fields().filter(move |(_, f)| f.flags.introduced_in.as_deref() == Some(engine))
+    }
+
+    /// Iterate through all struct fields marked as removed in the given engine.
+    ///
+    /// This only matches [`Flags::removed_in`] exactly; it does not include fields removed in an
+    /// ancestor of the engine.
+    pub fn fields_removed_in<'a>(&'a self, engine: &'a str) -> impl Iterator<Item = (&'a Struct, &'a StructField)> + 'a {
+        self.query().structs().fields().filter(move |(_, f)| f.flags.removed_in.as_deref() == Some(engine))
+    }
+
+    /// Get an object by name.
+    pub fn get_object(&self, name: &str) -> Option<&NamedObject> {
+        self.objects.get(name)
+    }
+
+    /// Get a struct by name, or `None` if it does not exist or is not a struct.
+    pub fn get_struct(&self, name: &str) -> Option<&Struct> {
+        match self.objects.get(name)? {
+            NamedObject::Struct(s) => Some(s),
+            _ => None
+        }
+    }
+
+    /// Get an enum by name, or `None` if it does not exist or is not an enum.
+    pub fn get_enum(&self, name: &str) -> Option<&Enum> {
+        match self.objects.get(name)? {
+            NamedObject::Enum(e) => Some(e),
+            _ => None
+        }
+    }
+
+    /// Get a bitfield by name, or `None` if it does not exist or is not a bitfield.
+    pub fn get_bitfield(&self, name: &str) -> Option<&Bitfield> {
+        match self.objects.get(name)? {
+            NamedObject::Bitfield(b) => Some(b),
+            _ => None
+        }
+    }
+
+    /// Get a tag group by name.
+    pub fn get_group(&self, name: &str) -> Option<&TagGroup> {
+        self.groups.get(name)
+    }
+
+    /// Get an engine by name.
+    pub fn get_engine(&self, name: &str) -> Option<&Engine> {
+        self.engines.get(name)
+    }
+
+    /// Get all groups in the order they should be listed.
+    ///
+    /// Groups with a [`TagGroup::sort_order`] come first, in ascending order, followed by every
+    /// other group sorted by name.
+    pub fn groups_in_sort_order(&self) -> Vec<&TagGroup> {
+        let mut groups: Vec<&TagGroup> = self.groups.values().collect();
+        groups.sort_by(|a, b| match (a.sort_order, b.sort_order) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => core::cmp::Ordering::Less,
+            (None, Some(_)) => core::cmp::Ordering::Greater,
+            (None, None) => core::cmp::Ordering::Equal
+        }.then_with(|| a.name.cmp(&b.name)));
+        groups
+    }
+
+    /// Iterate through all tag groups that are meaningful for the given scenario type.
+    pub fn groups_for_scenario_type(&self, scenario_type: ScenarioType) -> impl Iterator<Item = &TagGroup> {
+        self.groups.values().filter(move |g| g.applies_to_scenario_type(scenario_type))
+    }
+
+    /// Iterate through all tag groups that are not meaningful for the given scenario type.
+    ///
+    /// Build validators can use this to flag content that does not belong in a map (e.g.
+    /// user-interface-only tags in a multiplayer map).
+    pub fn groups_not_for_scenario_type(&self, scenario_type: ScenarioType) -> impl Iterator<Item = &TagGroup> {
+        self.groups.values().filter(move |g| !g.applies_to_scenario_type(scenario_type))
+    }
+
+    /// Iterate through all tag groups whose names start with `prefix`, in name order.
+    ///
+    /// This seeks directly to the prefix rather than scanning every group.
+    pub fn groups_with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a TagGroup> + 'a {
+        with_prefix(&self.groups, prefix)
+    }
+
+    /// Iterate through all objects whose names start with `prefix`, in name order.
+    ///
+    /// This seeks directly to the prefix rather than scanning every object.
+    pub fn objects_with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a NamedObject> + 'a {
+        with_prefix(&self.objects, prefix)
+    }
+
+    /// Iterate through all engines whose names start with `prefix`, in name order.
+    ///
+    /// This seeks directly to the prefix rather than scanning every engine.
+    pub fn engines_with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a Engine> + 'a {
+        with_prefix(&self.engines, prefix)
+    }
+
+    /// Iterate through all tag groups whose names are in `range`, in name order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end, or if both are excluded and equal.
+    pub fn groups_in_range<'a, R: RangeBounds<&'a str>>(&self, range: R) -> impl Iterator<Item = &TagGroup> {
+        in_range(&self.groups, range)
+    }
+
+    /// Iterate through all objects whose names are in `range`, in name order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end, or if both are excluded and equal.
+    pub fn objects_in_range<'a, R: RangeBounds<&'a str>>(&self, range: R) -> impl Iterator<Item = &NamedObject> {
+        in_range(&self.objects, range)
+    }
+
+    /// List every text-bearing field reachable from every tag group.
+    ///
+    /// Results are ordered by group name, then by field order. Fields in structs used by multiple
+    /// groups (or multiple times in a group) are listed once per path.
+    ///
+    /// ```
+    /// use ringhopper_definitions::{load_all_definitions, TextFieldKind};
+    ///
+    /// let definitions = load_all_definitions();
+    /// let tag_paths = definitions
+    ///     .text_fields()
+    ///     .into_iter()
+    ///     .filter(|f| f.group.name == "scenario" && f.kind == TextFieldKind::TagPath)
+    ///     .count();
+    /// assert!(tag_paths > 0);
+    /// ```
+    pub fn text_fields(&self) -> Vec<TextField<'_>> {
+        let mut fields = Vec::new();
+        for group in self.groups.values() {
+            self.walk_struct_fields(&group.struct_name, &mut |path, s, field| {
+                let kind = match &field.field_type {
+                    StructFieldType::Object(FieldObject::String32) => TextFieldKind::String32,
+                    StructFieldType::Object(FieldObject::UTF16String) => TextFieldKind::UTF16String,
+                    StructFieldType::Object(FieldObject::TagReference { .. }) => TextFieldKind::TagPath,
+                    _ => return
+                };
+                fields.push(TextField { group, path: path.to_vec(), parent: s, field, kind });
+            });
+        }
+        fields
+    }
+
+    /// Call `visit` for every field reachable from a struct, descending into inline structs and
+    /// reflexives.
+    ///
+    /// This is [`ParsedDefinitions::walk_struct`] with the struct looked up by name, and it does
+    /// nothing if the struct does not exist.
+    pub(crate) fn walk_struct_fields<'a>(&'a self, struct_name: &str, visit: &mut dyn FnMut(&[&'a str], &'a Struct, &'a StructField)) {
+        if let Some(s) = self.struct_view(struct_name) {
+            self.walk_struct(s, &mut |path: &[&'a str], _, field: FieldView<'a>| visit(path, field.parent().get(), field.get()));
+        }
+    }
+
+    /// Recursively collect the names of all objects used by the given object, including itself.
+    pub(crate) fn collect_reachable_objects(&self, object: &str, into: &mut BTreeSet<String>) {
+        if !into.insert(object.to_owned()) {
+            return
+        }
+
+        let Some(NamedObject::Struct(s)) = self.objects.get(object) else {
+            return
+        };
+
+        for f in &s.fields {
+            match &f.field_type {
+                StructFieldType::Object(FieldObject::NamedObject(n)) | StructFieldType::Object(FieldObject::Reflexive(n)) => {
+                    self.collect_reachable_objects(n, into);
+                },
+                _ => ()
+            }
+        }
+    }
+}
+
+fn with_prefix<'a, V>(map: &'a BTreeMap<String, V>, prefix: &'a str) -> impl Iterator<Item = &'a V> + 'a {
+    map.range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+        .take_while(move |(k, _)| k.starts_with(prefix))
+        .map(|(_, v)| v)
+}
+
+fn in_range<'a, 'b, V, R: RangeBounds<&'b str>>(map: &'a BTreeMap<String, V>, range: R) -> impl Iterator<Item = &'a V> {
+    let bounds: (Bound<&str>, Bound<&str>) = (range.start_bound().cloned(), range.end_bound().cloned());
+    map.range::<str, _>(bounds).map(|(_, v)| v)
+}
+
+/// Describes what kind of text a [`TextField`] holds.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum TextFieldKind {
+    /// [`FieldObject::String32`]
+    String32,
+
+    /// [`FieldObject::UTF16String`]
+    UTF16String,
+
+    /// The path of a [`FieldObject::TagReference`]
+    TagPath
+}
+
+/// A text-bearing field found by [`ParsedDefinitions::text_fields`].
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct TextField<'a> {
+    /// The tag group the field was reached from.
+    pub group: &'a TagGroup,
+
+    /// Names of the fields leading from the group's base struct to this field, including this
+    /// field's name.
+    pub path: Vec<&'a str>,
+
+    /// The struct the field is in.
+    pub parent: &'a Struct,
+
+    /// The field itself.
+    pub field: &'a StructField,
+
+    /// What kind of text the field holds.
+    pub kind: TextFieldKind
+}
+
+impl<'a> Query<'a> {
+    /// Include structs in the results.
+    ///
+    /// If no kind is specified, all kinds are included.
+    pub fn structs(self) -> Self {
+        self.of_kind(ObjectKind::Struct)
+    }
+
+    /// Include enums in the results.
+    ///
+    /// If no kind is specified, all kinds are included.
+    pub fn enums(self) -> Self {
+        self.of_kind(ObjectKind::Enum)
+    }
+
+    /// Include bitfields in the results.
+    ///
+    /// If no kind is specified, all kinds are included.
+    pub fn bitfields(self) -> Self {
+        self.of_kind(ObjectKind::Bitfield)
+    }
+
+    /// Include objects of the given kind in the results.
+    ///
+    /// If no kind is specified, all kinds are included.
+    pub fn of_kind(mut self, kind: ObjectKind) -> Self {
+        self.kinds.get_or_insert_with(BTreeSet::new).insert(kind);
+        self
+    }
+
+    /// Only match objects that have the given flag set on themselves or on at least one of their
+    /// struct fields, enum options, or bitfield bits.
+    ///
+    /// When used with [`Query::fields`], only fields with this flag set are returned.
+    pub fn with_field_flag(mut self, flag: FlagType) -> Self {
+        self.field_flags.push(flag);
+        self
+    }
+
+    /// Only match objects that have the given [`Flags::determinism`] on themselves or on at least one
+    /// of their struct fields, enum options, or bitfield bits.
+    ///
+    /// When used with [`Query::fields`], only fields with this determinism are returned. Unlike
+    /// [`ParsedDefinitions::determinism_of`], this does not consider fields containing the field.
+    pub fn with_determinism(mut self, determinism: Determinism) -> Self {
+        self.determinism = Some(determinism);
+        self
+    }
+
+    /// Only match objects that are used by the given tag group, directly or indirectly.
+    ///
+    /// Unknown groups match nothing.
+    pub fn in_group(mut self, group: &str) -> Self {
+        self.groups.push(group.to_owned());
+        self
+    }
+
+    fn field_matches(&self, flags: &Flags) -> bool {
+        self.field_flags.iter().all(|f| flags.has_flag(*f)) && self.determinism.is_none_or(|d| flags.determinism == Some(d))
+    }
+
+    fn object_matches(&self, object: &NamedObject) -> bool {
+        if let Some(kinds) = &self.kinds {
+            if !kinds.contains(&object.kind()) {
+                return false
+            }
+        }
+
+        if self.field_flags.is_empty() && self.determinism.is_none() {
+            return true
+        }
+
+        match object {
+            NamedObject::Struct(s) => self.field_matches(&s.flags) || s.fields.iter().any(|f| self.field_matches(&f.flags)),
+            NamedObject::Enum(e) => self.field_matches(&e.flags) || e.options.iter().any(|f| self.field_matches(&f.flags)),
+            NamedObject::Bitfield(b) => self.field_matches(&b.flags) || b.fields.iter().any(|f| self.field_matches(&f.flags))
+        }
+    }
+
+    fn allowed_objects(&self) -> Option<BTreeSet<String>> {
+        let mut allowed: Option<BTreeSet<String>> = None;
+        for group in &self.groups {
+            let reachable: BTreeSet<String> = self.definitions.groups
+                .get(group)
+                .map(|g| g.object_closure(self.definitions).iter().map(|o| o.name().to_owned()).collect())
+                .unwrap_or_default();
+            allowed = Some(match allowed {
+                Some(a) => a.intersection(&reachable).cloned().collect(),
+                None => reachable
+            });
+        }
+        allowed
+    }
+
+    /// Iterate through all matching objects in name order.
+    pub fn iter(&self) -> impl Iterator<Item = &'a NamedObject> + 'a {
+        let allowed = self.allowed_objects();
+        let query = self.clone();
+        self.definitions
+            .objects
+            .values()
+            .filter(move |o| allowed.as_ref().is_none_or(|a| a.contains(o.name())))
+            .filter(move |o| query.object_matches(o))
+    }
+
+    /// Iterate through all fields of matching structs that also match the field filters.
+    pub fn fields(&self) -> impl Iterator<Item = (&'a Struct, &'a StructField)> + 'a {
+        let query = self.clone();
+        self.iter()
+            .filter_map(|o| match o {
+                NamedObject::Struct(s) => Some(s),
+                _ => None
+            })
+            .flat_map(move |s| {
+                let query = query.clone();
+                s.fields
+                    .iter()
+                    .filter(move |f| matches!(f.field_type, StructFieldType::Object(_)) && query.field_matches(&f.flags))
+                    .map(move |f| (s, f))
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+    use crate::*;
+
+    #[test]
+    fn query_narrows_results() {
+        let definitions = load_all_definitions();
+        let all = definitions.query().iter().count();
+        assert_eq!(all, definitions.objects.len());
+
+        let scenario_structs = definitions.query().structs().in_group("scenario").iter().count();
+        assert!(scenario_structs > 0 && scenario_structs < all);
+
+        for (_, field) in definitions.query().with_field_flag(FlagType::CacheOnly).fields() {
+            assert!(field.flags.cache_only);
+        }
+
+        assert_eq!(definitions.query().in_group("not a real group").iter().count(), 0);
+    }
+
+    #[test]
+    fn field_flags_match_the_object_flags() {
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_str("test.json", r#"[
+            { "name": "TestCacheOnlyStruct", "type": "struct", "cache_only": true, "fields": [{ "name": "value", "type": "float" }], "size": 4 },
+            { "name": "TestCacheOnlyField", "type": "struct", "fields": [{ "name": "value", "type": "float", "cache_only": true }], "size": 4 },
+            { "name": "TestPlain", "type": "struct", "fields": [{ "name": "value", "type": "float" }], "size": 4 }
+        ]"#).unwrap();
+
+        // Loading copies the struct's flags onto its fields, so clear them to test the struct alone.
+        let Some(NamedObject::Struct(s)) = definitions.objects.get_mut("TestCacheOnlyStruct") else { unreachable!() };
+        s.fields[0].flags.cache_only = false;
+
+        let matched: Vec<&str> = definitions.query().with_field_flag(FlagType::CacheOnly).iter().map(|o| o.name()).collect();
+        assert_eq!(matched, ["TestCacheOnlyField", "TestCacheOnlyStruct"]);
+    }
+
+    #[test]
+    fn prefix_queries_match_filtering() {
+        let definitions = load_all_definitions();
+        let expected: Vec<&str> = definitions.groups.keys().map(|k| k.as_str()).filter(|k| k.starts_with("scenario")).collect();
+        let actual: Vec<&str> = definitions.groups_with_prefix("scenario").map(|g| g.name.as_str()).collect();
+        assert!(!actual.is_empty());
+        assert_eq!(expected, actual);
+
+        assert!(definitions.objects_with_prefix("Shader").all(|o| o.name().starts_with("Shader")));
+        assert_eq!(definitions.objects_with_prefix("").count(), definitions.objects.len());
+        assert_eq!(definitions.groups_in_range("a".."b").count(), definitions.groups_with_prefix("a").count());
+    }
+
+    #[test]
+    #[cfg(feature = "groups-ui")]
+    fn scenario_type_applicability() {
+        let definitions = load_all_definitions();
+        assert!(definitions.groups_not_for_scenario_type(ScenarioType::Multiplayer).any(|g| g.name == "virtual_keyboard"));
+        assert!(definitions.groups_for_scenario_type(ScenarioType::UserInterface).any(|g| g.name == "virtual_keyboard"));
+        assert!(definitions.groups_for_scenario_type(ScenarioType::Multiplayer).any(|g| g.name == "scenario"));
+    }
+
+    #[test]
+    #[cfg(all(feature = "engines-pc", feature = "engines-xbox", feature = "engines-mac", feature = "engines-mcc"))]
+    fn explicit_sort_order_comes_first() {
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_json(&get_all_definitions());
+        definitions.load_from_str("tag/test.json", r#"[
+            { "name": "TestSorted", "type": "struct", "fields": [{ "name": "value", "type": "float" }], "size": 4 },
+            { "name": "test_sorted", "type": "group", "struct": "TestSorted", "version": 1, "fourcc_binary": 1952805748, "short_code": "tst", "sort_order": 100 }
+        ]"#).unwrap();
+        definitions.finalize();
+        assert_eq!(definitions.groups["test_sorted"].sort_order, Some(100));
+        assert_eq!(definitions.groups["test_sorted"].short_code.as_deref(), Some("tst"));
+
+        let sorted = definitions.groups_in_sort_order();
+        let position = |name: &str| sorted.iter().position(|g| g.name == name).unwrap();
+        let test_sorted = position("test_sorted");
+        assert!(position("sound_scenery") < test_sorted);
+        assert!(sorted[test_sorted + 1..].iter().all(|g| g.sort_order.is_none()));
+        assert!(test_sorted < position("actor"));
+    }
+
+    #[test]
+    fn builtin_groups_have_short_codes_and_sort_order() {
+        let definitions = load_all_definitions();
+        assert!(definitions.groups.values().all(|g| g.short_code.as_deref() == Some(g.fourcc_string().trim_end())));
+        assert_eq!(definitions.groups["hud_message_text"].short_code.as_deref(), Some("hmt"));
+
+        let sorted: Vec<&str> = definitions.groups_in_sort_order().iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(sorted[..6], ["object", "unit", "biped", "vehicle", "item", "weapon"]);
+        assert_eq!(sorted[13..17], ["device_light_fixture", "placeholder", "sound_scenery", "actor"]);
+    }
+}
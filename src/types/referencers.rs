@@ -0,0 +1,91 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use super::*;
+
+impl ParsedDefinitions {
+    /// Iterate through every struct field that references an object, either inline, through a
+    /// reflexive, or as an index into a reflexive of it.
+    ///
+    /// This is useful for finding everything affected by changing an object. Fields are ordered by
+    /// struct name and then by their position in the struct.
+    ///
+    /// Every struct is scanned on each call, so this always reflects the current
+    /// [`ParsedDefinitions::objects`], even if they were changed directly without finalizing again.
+    pub fn referencers_of<'a>(&'a self, name: &'a str) -> impl Iterator<Item = (&'a Struct, &'a StructField)> + 'a {
+        self.objects.values().filter_map(|o| match o {
+            NamedObject::Struct(s) => Some(s),
+            _ => None
+        }).flat_map(move |s| references_in(s).filter(move |(n, _)| *n == name).map(move |(_, index)| (s, &s.fields[index])))
+    }
+
+    // refinalize_objects keeps this up to date, since it is told about every change.
+    pub(crate) fn index_referencers(&mut self) {
+        let mut referencers: BTreeMap<String, Vec<(String, usize)>> = BTreeMap::new();
+        for object in self.objects.values() {
+            let NamedObject::Struct(s) = object else {
+                continue
+            };
+            for (n, index) in references_in(s) {
+                referencers.entry(n.to_owned()).or_default().push((s.name.to_owned(), index));
+            }
+        }
+        self.referencers = referencers;
+    }
+
+    /// Update the index for structs that were added, changed, or removed.
+    pub(crate) fn reindex_referencers_of(&mut self, structs: &[&str]) {
+        for r in self.referencers.values_mut() {
+            r.retain(|(s, _)| !structs.contains(&s.as_str()));
+        }
+        self.referencers.retain(|_, r| !r.is_empty());
+
+        for name in structs {
+            let Some(NamedObject::Struct(s)) = self.objects.get(*name) else {
+                continue
+            };
+            for (n, index) in references_in(s) {
+                let r = self.referencers.entry(n.to_owned()).or_default();
+                r.push((s.name.to_owned(), index));
+                r.sort_unstable();
+            }
+        }
+    }
+}
+
+fn references_in(s: &Struct) -> impl Iterator<Item = (&str, usize)> {
+    s.fields.iter().enumerate().filter_map(|(index, f)| match &f.field_type {
+        StructFieldType::Object(FieldObject::NamedObject(n) | FieldObject::Reflexive(n) | FieldObject::ReflexiveIndex { struct_name: n, .. }) => Some((n.as_str(), index)),
+        _ => None
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn referencers_are_indexed() {
+        let definitions = load_all_definitions();
+        let referencers: alloc::vec::Vec<(&str, &str)> = definitions
+            .referencers_of("ModelNode")
+            .map(|(s, f)| (s.name.as_str(), f.name.as_str()))
+            .collect();
+        assert!(referencers.contains(&("Model", "nodes")), "{referencers:?}");
+
+        for (s, f) in definitions.referencers_of("Object") {
+            assert!(matches!(&f.field_type, StructFieldType::Object(FieldObject::NamedObject(n)) if n == "Object"), "{}::{}", s.name, f.name);
+        }
+        assert!(definitions.referencers_of("Object").count() > 0);
+        assert_eq!(definitions.referencers_of("Nonexistent").count(), 0);
+    }
+
+    #[test]
+    fn referencers_reflect_direct_changes() {
+        let mut definitions = ParsedDefinitions::load_builtin();
+        assert!(definitions.referencers_of("ModelNode").any(|(s, _)| s.name == "Model"));
+        definitions.objects.remove("Model");
+        assert!(definitions.referencers_of("ModelNode").all(|(s, _)| s.name != "Model"));
+    }
+}
@@ -0,0 +1,184 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::*;
+use super::parse::get_all_child_groups;
+
+impl ParsedDefinitions {
+    /// Bring finalized definitions up to date after adding, changing, or removing some structs,
+    /// enums, or bitfields.
+    ///
+    /// `changed` must name every object changed since the definitions were last finalized. Only
+    /// those objects and the structs that use them (directly or not) are checked and recomputed:
+    /// references, sizes, offsets, expanded tag references, [`Struct::is_const`], what structs
+    /// contain, and which structs use which objects. This is much faster than finalizing
+    /// everything again, such as for editors that change one struct at a time.
+    ///
+    /// Engine support, flags, and other checks spanning all definitions are not redone, so call
+    /// [`ParsedDefinitions::finalize`] after changing those or any groups or engines. Nothing is
+    /// changed if an error is returned.
+    pub fn refinalize_objects(&mut self, changed: &[&str]) -> Result<(), DefinitionError> {
+        let mut affected: BTreeSet<String> = BTreeSet::new();
+        let mut pending: Vec<&str> = changed.to_vec();
+        while let Some(name) = pending.pop() {
+            if affected.insert(name.to_owned()) {
+                pending.extend(self.referencers.get(name).into_iter().flatten().map(|(s, _)| s.as_str()));
+            }
+        }
+
+        for name in &affected {
+            let Some(NamedObject::Struct(s)) = self.objects.get(name) else {
+                continue
+            };
+            for f in &s.fields {
+                let StructFieldType::Object(o) = &f.field_type else {
+                    continue
+                };
+                let missing = |reference: &str| Err(DefinitionError::MissingReference {
+                    object: s.name.to_owned(),
+                    field: f.name.to_owned(),
+                    reference: reference.to_owned()
+                });
+                match o {
+                    FieldObject::NamedObject(n) if !self.objects.contains_key(n) => return missing(n),
+                    FieldObject::Reflexive(n) | FieldObject::ReflexiveIndex { struct_name: n, .. } if !matches!(self.objects.get(n), Some(NamedObject::Struct(_))) => {
+                        return missing(n)
+                    },
+                    FieldObject::Custom(c) if !self.custom_objects.contains_key(c) => return missing(c),
+                    FieldObject::TagReference { allowed_groups } => {
+                        if let Some(g) = allowed_groups.iter().find(|g| *g != "*" && !self.groups.contains_key(*g)) {
+                            return missing(g)
+                        }
+                    },
+                    _ => ()
+                }
+            }
+        }
+
+        // Any new inline cycle has to go through a changed struct.
+        for name in changed {
+            let mut path = Vec::new();
+            if self.find_inline_path(name, name, &mut BTreeSet::new(), &mut path) {
+                return Err(DefinitionError::ReferenceCycle(ReferenceCycle { path }))
+            }
+        }
+
+        for name in &affected {
+            let Some(NamedObject::Struct(s)) = self.objects.get(name) else {
+                continue
+            };
+            let actual = s.fields.iter().map(|f| f.size(self)).sum();
+            if actual != s.size {
+                return Err(DefinitionError::BadSize { object: s.name.to_owned(), expected: s.size, actual })
+            }
+        }
+
+        for name in &affected {
+            let Some(NamedObject::Struct(s)) = self.objects.get(name) else {
+                continue
+            };
+            let mut s = s.clone();
+            let mut offset = 0;
+            for f in &mut s.fields {
+                f.relative_offset = offset;
+                offset += f.size(self);
+
+                // Tag references in unchanged structs were already expanded.
+                let StructFieldType::Object(FieldObject::TagReference { allowed_groups }) = &mut f.field_type else {
+                    continue
+                };
+                if !changed.contains(&name.as_str()) {
+                    continue
+                }
+                let mut expanded: Vec<String> = Vec::with_capacity(allowed_groups.len());
+                for g in allowed_groups.iter() {
+                    for g in core::iter::once(g.to_owned()).chain(get_all_child_groups(g, &self.groups)) {
+                        if g != "*" && !expanded.contains(&g) {
+                            expanded.push(g);
+                        }
+                    }
+                }
+                *allowed_groups = expanded;
+            }
+            self.objects.insert(name.to_owned(), NamedObject::Struct(s));
+        }
+
+        self.find_const_structs_in(Some(&affected));
+        self.find_struct_contents_in(Some(&affected));
+        self.reindex_referencers_of(changed);
+        Ok(())
+    }
+
+    /// Find a path of inline structs from one struct to another, returning true if found.
+    fn find_inline_path<'a>(&'a self, from: &'a str, to: &str, visited: &mut BTreeSet<&'a str>, path: &mut Vec<(String, String)>) -> bool {
+        let Some(NamedObject::Struct(s)) = self.objects.get(from) else {
+            return false
+        };
+        if !visited.insert(from) {
+            return false
+        }
+        for f in &s.fields {
+            let StructFieldType::Object(FieldObject::NamedObject(n)) = &f.field_type else {
+                continue
+            };
+            path.push((s.name.to_owned(), f.name.to_owned()));
+            if n == to || self.find_inline_path(n, to, visited, path) {
+                return true
+            }
+            path.pop();
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::borrow::ToOwned;
+
+    use crate::*;
+
+    #[test]
+    fn changes_propagate_to_dependents() {
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_str("test.json", r#"[
+            { "name": "TestInner", "type": "struct", "size": 4, "fields": [{ "name": "value", "type": "float" }] },
+            { "name": "TestOuter", "type": "struct", "size": 8, "fields": [{ "name": "inner", "type": "TestInner" }, { "name": "other", "type": "float" }] },
+            { "name": "TestHolder", "type": "struct", "size": 12, "fields": [{ "name": "outers", "type": "Reflexive", "struct": "TestOuter" }] }
+        ]"#).unwrap();
+        definitions.try_finalize().unwrap();
+        assert!(!definitions.get_struct("TestHolder").unwrap().contains_tag_references);
+        assert!(definitions.get_struct("TestOuter").unwrap().is_const);
+
+        let edit = |definitions: &mut ParsedDefinitions, name: &str, edit: &dyn Fn(&mut Struct)| {
+            let Some(NamedObject::Struct(s)) = definitions.objects.get_mut(name) else {
+                unreachable!()
+            };
+            edit(s);
+        };
+        edit(&mut definitions, "TestInner", &|s| {
+            s.fields[0].field_type = StructFieldType::Object(FieldObject::TagReference { allowed_groups: alloc::vec!["*".to_owned()] });
+        });
+        assert!(matches!(definitions.refinalize_objects(&["TestInner"]), Err(DefinitionError::BadSize { object, .. }) if object == "TestInner"));
+
+        edit(&mut definitions, "TestInner", &|s| s.size = 16);
+        assert_eq!(
+            definitions.refinalize_objects(&["TestInner"]),
+            Err(DefinitionError::BadSize { object: "TestOuter".to_owned(), expected: 8, actual: 20 })
+        );
+
+        edit(&mut definitions, "TestOuter", &|s| s.size = 20);
+        definitions.refinalize_objects(&["TestInner", "TestOuter"]).unwrap();
+        assert_eq!(definitions.get_struct("TestOuter").unwrap().fields[1].relative_offset, 16);
+        assert!(definitions.get_struct("TestHolder").unwrap().contains_tag_references);
+        assert!(!definitions.get_struct("TestOuter").unwrap().is_const);
+        assert_eq!(definitions.referencers_of("TestInner").count(), 1);
+
+        edit(&mut definitions, "TestInner", &|s| s.fields[0].field_type = StructFieldType::Object(FieldObject::NamedObject("TestOuter".to_owned())));
+        assert!(matches!(definitions.refinalize_objects(&["TestInner"]), Err(DefinitionError::ReferenceCycle(_))));
+
+        definitions.objects.remove("TestInner");
+        assert!(matches!(definitions.refinalize_objects(&["TestInner"]), Err(DefinitionError::MissingReference { object, .. }) if object == "TestOuter"));
+    }
+}
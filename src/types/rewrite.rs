@@ -0,0 +1,179 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::*;
+
+/// Describes how the cache builder rewrites a region of a struct.
+///
+/// Extraction tools can copy every region that is not rewritten as-is, and must reconstruct
+/// rewritten regions.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum CacheRewrite {
+    /// The region holds pointers, IDs, or offsets that are fixed up for the cache file (e.g. the
+    /// pointer of a reflexive).
+    Pointer,
+
+    /// The region's contents are moved elsewhere in the cache file (e.g. to a resource map).
+    Moved,
+
+    /// The region is computed by the cache builder from other data.
+    Recomputed
+}
+
+impl CacheRewrite {
+    /// Get the cache rewrite from its name in the JSON definitions.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "pointer" => Some(Self::Pointer),
+            "moved" => Some(Self::Moved),
+            "recomputed" => Some(Self::Recomputed),
+            _ => None
+        }
+    }
+
+    /// Get the name of the cache rewrite as used in the JSON definitions.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Pointer => "pointer",
+            Self::Moved => "moved",
+            Self::Recomputed => "recomputed"
+        }
+    }
+}
+
+impl StructField {
+    /// Get how the cache builder rewrites this field, if at all.
+    ///
+    /// This is [`Flags::cache_rewrite`] if set. Otherwise, fields that hold pointers (reflexives,
+    /// tag references, data, and IDs) are [`CacheRewrite::Pointer`], and cache-only fields are
+    /// [`CacheRewrite::Recomputed`]. Fields of inline structs are not considered.
+    ///
+    /// This applies to the field as a whole; see [`StructView::rewritten_ranges`] for the bytes
+    /// that are actually rewritten.
+    pub fn cache_rewrite(&self) -> Option<CacheRewrite> {
+        if let Some(r) = self.flags.cache_rewrite {
+            return Some(r)
+        }
+        let StructFieldType::Object(object) = &self.field_type else {
+            return None
+        };
+        match object {
+            FieldObject::Reflexive(_)
+            | FieldObject::TagReference { .. }
+            | FieldObject::Data
+            | FieldObject::FileData
+            | FieldObject::BSPVertexData
+            | FieldObject::UTF16String
+            | FieldObject::TagID
+            | FieldObject::Address => Some(CacheRewrite::Pointer),
+            _ if self.flags.cache_only => Some(CacheRewrite::Recomputed),
+            _ => None
+        }
+    }
+}
+
+impl StructView<'_> {
+    /// Get the byte ranges rewritten by the cache builder, relative to the start of the struct and
+    /// in order, including those of inline structs.
+    ///
+    /// For [`CacheRewrite::Pointer`], only the pointer and tag ID of reflexives, tag references, and
+    /// data are included; their counts, sizes, and other members are copied. Adjacent ranges with
+    /// the same rewrite are merged.
+    pub fn rewritten_ranges(&self) -> Vec<(Range<usize>, CacheRewrite)> {
+        let mut ranges = Vec::new();
+        collect_rewritten_ranges(*self, 0, &mut ranges);
+        ranges
+    }
+
+    /// Get the byte ranges that are not rewritten by the cache builder and can be copied as-is,
+    /// relative to the start of the struct and in order.
+    pub fn copied_ranges(&self) -> Vec<Range<usize>> {
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        for (r, _) in self.rewritten_ranges() {
+            if r.start > start {
+                ranges.push(start..r.start);
+            }
+            start = r.end;
+        }
+        if start < self.size() {
+            ranges.push(start..self.size());
+        }
+        ranges
+    }
+}
+
+fn collect_rewritten_ranges(s: StructView<'_>, base: usize, ranges: &mut Vec<(Range<usize>, CacheRewrite)>) {
+    for f in s.fields() {
+        let offset = base + f.relative_offset;
+        if let Some(rewrite) = f.get().cache_rewrite() {
+            let count = f.get().count.field_count().max(1);
+            let element_size = f.size() / count;
+            for element in (0..count).map(|i| offset + i * element_size) {
+                for part in rewritten_parts(f.get(), rewrite, element_size) {
+                    push_range(ranges, element + part.start..element + part.end, rewrite);
+                }
+            }
+        }
+        else if let ResolvedFieldType::Struct(inner) = f.resolved_type() {
+            collect_rewritten_ranges(inner, offset, ranges);
+        }
+    }
+}
+
+/// Get the parts of each element of a rewritten field that are actually rewritten.
+fn rewritten_parts(field: &StructField, rewrite: CacheRewrite, element_size: usize) -> Vec<Range<usize>> {
+    let StructFieldType::Object(object) = &field.field_type else {
+        return alloc::vec![0..element_size]
+    };
+    match (rewrite, object) {
+        // count, pointer, definitions
+        (CacheRewrite::Pointer, FieldObject::Reflexive(_)) => alloc::vec![4..8],
+
+        // group, path pointer, path length, tag ID
+        (CacheRewrite::Pointer, FieldObject::TagReference { .. }) => alloc::vec![4..8, 12..16],
+
+        // size, flags, file offset, pointer, definitions
+        (CacheRewrite::Pointer, FieldObject::Data | FieldObject::FileData | FieldObject::BSPVertexData | FieldObject::UTF16String) => alloc::vec![12..16],
+
+        _ => alloc::vec![0..element_size]
+    }
+}
+
+fn push_range(ranges: &mut Vec<(Range<usize>, CacheRewrite)>, range: Range<usize>, rewrite: CacheRewrite) {
+    match ranges.last_mut() {
+        Some((last, last_rewrite)) if last.end == range.start && *last_rewrite == rewrite => last.end = range.end,
+        _ => ranges.push((range, rewrite))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn rewritten_and_copied_ranges_cover_struct() {
+        let definitions = load_all_definitions();
+        for s in definitions.query().structs().iter() {
+            let NamedObject::Struct(s) = s else { unreachable!() };
+            let view = s.view(definitions);
+            let rewritten: usize = view.rewritten_ranges().iter().map(|(r, _)| r.len()).sum();
+            let copied: usize = view.copied_ranges().iter().map(|r| r.len()).sum();
+            assert_eq!(rewritten + copied, s.size, "{} ranges do not cover the struct", s.name);
+        }
+    }
+
+    #[test]
+    fn only_pointers_are_rewritten() {
+        let definitions = load_all_definitions();
+
+        let string_list = definitions.struct_view("StringList").unwrap();
+        assert_eq!(string_list.rewritten_ranges(), [(4..8, CacheRewrite::Pointer)]);
+        assert_eq!(string_list.copied_ranges(), [0..4, 8..12]);
+
+        let string = definitions.struct_view("StringListString").unwrap();
+        assert_eq!(string.rewritten_ranges(), [(12..16, CacheRewrite::Pointer)]);
+    }
+}
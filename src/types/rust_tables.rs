@@ -0,0 +1,214 @@
+use alloc::string::String;
+use core::fmt::Write;
+
+use super::*;
+
+/// Types used by the tables written by [`generate_rust_tables`].
+const TABLE_TYPES: &str = r#"/// A struct field, including padding.
+#[derive(Copy, Clone, Debug)]
+pub struct StaticField {
+    pub name: &'static str,
+    /// Type name as used in the JSON definitions (e.g. `Reflexive` or `pad`).
+    pub field_type: &'static str,
+    /// Struct, enum, or bitfield the field uses, if any.
+    pub object: Option<&'static str>,
+    /// Groups a tag reference can reference.
+    pub allowed_groups: &'static [&'static str],
+    pub offset: usize,
+    /// Size of the whole field, including every element.
+    pub size: usize,
+    /// Number of elements (e.g. 2 for bounds).
+    pub count: usize,
+    /// Default limit, if any.
+    pub limit: Option<usize>
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct StaticStruct {
+    pub name: &'static str,
+    pub size: usize,
+    pub fields: &'static [StaticField]
+}
+
+/// An enum or bitfield, with the value (or mask) of each option.
+#[derive(Copy, Clone, Debug)]
+pub struct StaticEnum {
+    pub name: &'static str,
+    /// Width in bits.
+    pub width: u8,
+    pub options: &'static [(&'static str, u32)]
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct StaticGroup {
+    pub name: &'static str,
+    pub struct_name: &'static str,
+    pub supergroup: Option<&'static str>,
+    pub fourcc: u32,
+    pub version: u16
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct StaticEngine {
+    pub name: &'static str,
+    pub display_name: &'static str,
+    pub inherits: Option<&'static str>,
+    pub build: Option<&'static str>,
+    pub cache_file_version: u32,
+    pub max_script_nodes: u64,
+    pub max_tag_space: u64,
+    pub base_memory_address: u64,
+    pub data_alignment: u64,
+    pub compressed_data_alignment: u64,
+    pub max_tag_path_length: usize
+}
+
+fn find<T>(table: &'static [T], name: &str, get_name: fn(&T) -> &'static str) -> Option<&'static T> {
+    table.binary_search_by(|t| get_name(t).cmp(name)).ok().map(|i| &table[i])
+}
+
+pub fn find_struct(name: &str) -> Option<&'static StaticStruct> {
+    find(STRUCTS, name, |s| s.name)
+}
+
+pub fn find_enum(name: &str) -> Option<&'static StaticEnum> {
+    find(ENUMS, name, |e| e.name)
+}
+
+pub fn find_bitfield(name: &str) -> Option<&'static StaticEnum> {
+    find(BITFIELDS, name, |b| b.name)
+}
+
+pub fn find_group(name: &str) -> Option<&'static StaticGroup> {
+    find(GROUPS, name, |g| g.name)
+}
+
+pub fn find_engine(name: &str) -> Option<&'static StaticEngine> {
+    find(ENGINES, name, |e| e.name)
+}
+
+"#;
+
+/// Generate Rust source with the definitions as `static` tables, for targets that cannot afford
+/// to parse the definitions at runtime (e.g. `no_std` targets without an allocator).
+///
+/// The source has no dependencies (including this crate), so it is meant to be written by a build
+/// script and included in a module:
+///
+/// ```ignore
+/// mod definitions {
+///     include!(concat!(env!("OUT_DIR"), "/definitions.rs"));
+/// }
+/// ```
+///
+/// It contains the table types (`StaticStruct`, `StaticField`, `StaticEnum`, `StaticGroup`, and
+/// `StaticEngine`), the `STRUCTS`, `ENUMS`, `BITFIELDS`, `GROUPS`, and `ENGINES` tables sorted by
+/// name, and `find_*` functions for looking them up. Editor sections are omitted, and
+/// everything else is as finalized (e.g. offsets and sizes are computed).
+pub fn generate_rust_tables(definitions: &ParsedDefinitions) -> String {
+    let mut output = String::from("// Generated by ringhopper-definitions. Do not edit.\n\n");
+    output.push_str(TABLE_TYPES);
+
+    output.push_str("pub static STRUCTS: &[StaticStruct] = &[\n");
+    for s in definitions.objects.values().filter_map(|o| if let NamedObject::Struct(s) = o { Some(s) } else { None }) {
+        let _ = writeln!(output, "    StaticStruct {{ name: {:?}, size: {}, fields: &[", s.name, s.size);
+        for f in &s.fields {
+            let (field_type, object, allowed_groups) = match &f.field_type {
+                StructFieldType::Object(o @ (FieldObject::NamedObject(n) | FieldObject::Reflexive(n))) => (o.type_name(), Some(n.as_str()), &[][..]),
+                StructFieldType::Object(o @ FieldObject::TagReference { allowed_groups }) => (o.type_name(), None, allowed_groups.as_slice()),
+                StructFieldType::Object(o) => (o.type_name(), None, &[][..]),
+                StructFieldType::Padding(_) => ("pad", None, &[][..]),
+                StructFieldType::EditorSection { .. } => continue
+            };
+            let _ = writeln!(
+                output,
+                "        StaticField {{ name: {:?}, field_type: {field_type:?}, object: {}, allowed_groups: &{allowed_groups:?}, offset: {}, size: {}, count: {}, limit: {:?} }},",
+                f.name,
+                rust_option(object),
+                f.relative_offset,
+                f.size(definitions),
+                f.count.field_count(),
+                f.limit.as_ref().and_then(|l| l.get(&LimitType::Default))
+            );
+        }
+        output.push_str("    ] },\n");
+    }
+    output.push_str("];\n\n");
+
+    for (table, kind) in [("ENUMS", ObjectKind::Enum), ("BITFIELDS", ObjectKind::Bitfield)] {
+        let _ = writeln!(output, "pub static {table}: &[StaticEnum] = &[");
+        for object in definitions.objects.values().filter(|o| o.kind() == kind) {
+            let (width, options) = match object {
+                NamedObject::Enum(e) => (16, &e.options),
+                NamedObject::Bitfield(b) => (b.width, &b.fields),
+                NamedObject::Struct(_) => unreachable!()
+            };
+            let _ = write!(output, "    StaticEnum {{ name: {:?}, width: {width}, options: &[", object.name());
+            for (i, o) in options.iter().enumerate() {
+                let _ = write!(output, "{}({:?}, 0x{:X})", if i == 0 { "" } else { ", " }, o.name, o.value);
+            }
+            output.push_str("] },\n");
+        }
+        output.push_str("];\n\n");
+    }
+
+    output.push_str("pub static GROUPS: &[StaticGroup] = &[\n");
+    for g in definitions.groups.values() {
+        let _ = writeln!(
+            output,
+            "    StaticGroup {{ name: {:?}, struct_name: {:?}, supergroup: {}, fourcc: 0x{:08X}, version: {} }},",
+            g.name,
+            g.struct_name,
+            rust_option(g.supergroup.as_deref()),
+            g.fourcc_binary,
+            g.version
+        );
+    }
+    output.push_str("];\n\n");
+
+    output.push_str("pub static ENGINES: &[StaticEngine] = &[\n");
+    for e in definitions.engines.values() {
+        let _ = writeln!(
+            output,
+            "    StaticEngine {{ name: {:?}, display_name: {:?}, inherits: {}, build: {}, cache_file_version: {}, max_script_nodes: {}, max_tag_space: 0x{:X}, base_memory_address: 0x{:X}, data_alignment: {}, compressed_data_alignment: {}, max_tag_path_length: {} }},",
+            e.name,
+            e.display_name,
+            rust_option(e.inherits.as_deref()),
+            rust_option(e.build.as_ref().map(|b| b.string.as_str())),
+            e.cache_file_version,
+            e.max_script_nodes,
+            e.max_tag_space,
+            e.base_memory_address.address,
+            e.data_alignment,
+            e.compressed_data_alignment,
+            e.tag_paths.max_length
+        );
+    }
+    output.push_str("];\n");
+
+    output
+}
+
+fn rust_option(value: Option<&str>) -> String {
+    match value {
+        Some(v) => alloc::format!("Some({v:?})"),
+        None => String::from("None")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn rust_tables_contain_every_definition() {
+        let definitions = load_all_definitions();
+        let tables = generate_rust_tables(definitions);
+        let scenario = definitions.get_struct("Scenario").unwrap();
+        assert!(tables.contains(&alloc::format!("    StaticStruct {{ name: \"Scenario\", size: {}, fields: &[\n", scenario.size)));
+        assert!(tables.contains("StaticField { name: \"strings\", field_type: \"Reflexive\", object: Some(\"StringListString\"), allowed_groups: &[], offset: 0, size: 12, count: 1, limit: Some("));
+        assert!(tables.contains("StaticGroup { name: \"scenario\", struct_name: \"Scenario\", supergroup: None, fourcc: 0x73636E72, version: 2 }"));
+        assert_eq!(tables.matches("    StaticEngine {").count(), definitions.engines.len());
+        assert_eq!(tables.matches("    StaticGroup {").count(), definitions.groups.len());
+    }
+}
@@ -0,0 +1,179 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use serde_json::{Map, Value};
+
+use super::*;
+
+/// Describes how an engine lays out compiled scripts in a scenario tag.
+///
+/// The `script syntax data` of a scenario holds a header ([`EngineScriptNodeTable::table_struct`])
+/// followed by every node ([`EngineScriptNodeTable::node_struct`]). Nodes refer to strings (e.g.
+/// names of functions and globals) by their byte offset into the `script string data`, which
+/// holds null-terminated strings. See [`ParsedDefinitions::script_node_layout`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EngineScriptNodeTable {
+    /// Struct of the header before the nodes.
+    ///
+    /// References an object in [`ParsedDefinitions::objects`].
+    pub table_struct: String,
+
+    /// Struct of each node.
+    ///
+    /// References an object in [`ParsedDefinitions::objects`].
+    pub node_struct: String,
+
+    /// Name stored in the header.
+    pub name: String,
+
+    /// Signature stored in the header's `data` field (e.g. `d@t@`).
+    pub signature: u32,
+
+    /// Salt of the first node.
+    pub first_salt: u16
+}
+
+impl EngineScriptNodeTable {
+    pub(crate) fn load_from_json(engine_name: &str, object: &Map<String, Value>) -> Result<Self, DefinitionError> {
+        let invalid = |what: &str, expected| DefinitionError::InvalidValue { object: engine_name.to_owned(), key: alloc::format!("script_node_table::{what}"), expected };
+        let get_str = |what: &str| object
+            .get(what)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| invalid(what, "a string"));
+        Ok(Self {
+            table_struct: get_str("table")?.to_owned(),
+            node_struct: get_str("node")?.to_owned(),
+            name: get_str("name")?.to_owned(),
+            signature: fourcc_from_str(get_str("signature")?).ok_or_else(|| invalid("signature", "a fourcc"))?,
+            first_salt: get_str("first_salt")?
+                .strip_prefix("0x")
+                .and_then(|s| u16::from_str_radix(s, 16).ok())
+                .ok_or_else(|| invalid("first_salt", "a 16-bit hex value"))?
+        })
+    }
+
+    pub(crate) fn to_json(&self) -> Map<String, Value> {
+        let mut object = Map::new();
+        object.insert("table".to_owned(), Value::from(self.table_struct.as_str()));
+        object.insert("node".to_owned(), Value::from(self.node_struct.as_str()));
+        object.insert("name".to_owned(), Value::from(self.name.as_str()));
+        object.insert("signature".to_owned(), Value::from(fourcc_to_string(self.signature)));
+        object.insert("first_salt".to_owned(), Value::from(alloc::format!("0x{:X}", self.first_salt)));
+        object
+    }
+}
+
+/// The script node table of an engine, resolved against its definitions.
+///
+/// Returned by [`ParsedDefinitions::script_node_layout`].
+#[derive(Copy, Clone)]
+pub struct ScriptNodeLayout<'a> {
+    /// The engine's script node table.
+    pub table: &'a EngineScriptNodeTable,
+
+    /// Struct of the header before the nodes.
+    pub header: StructView<'a>,
+
+    /// Struct of each node.
+    pub node: StructView<'a>,
+
+    /// Maximum number of nodes (see [`Engine::max_script_nodes`]).
+    pub max_nodes: usize
+}
+
+impl ScriptNodeLayout<'_> {
+    /// Get the byte offset of a node in the script syntax data.
+    pub fn node_offset(&self, index: usize) -> usize {
+        self.header.size + index * self.node.size
+    }
+
+    /// Get the size of script syntax data with room for the given number of nodes.
+    ///
+    /// Compiled scenarios always have room for [`ScriptNodeLayout::max_nodes`] nodes.
+    pub fn syntax_data_size(&self, node_count: usize) -> usize {
+        self.node_offset(node_count)
+    }
+
+    /// Get the salt of a node.
+    ///
+    /// Salts increase by one for each node, starting at [`EngineScriptNodeTable::first_salt`].
+    /// Like all data tables, salts are always at least 0x8000, so they wrap from 0xFFFF to 0x8000.
+    pub fn node_salt(&self, index: usize) -> u16 {
+        let salt = self.table.first_salt as usize + index;
+        if salt <= 0xFFFF {
+            salt as u16
+        }
+        else {
+            (0x8000 + (salt - 0x10000) % 0x8000) as u16
+        }
+    }
+
+    /// Get the ID used to refer to a node (e.g. in `next node`), which is its salt in the upper 16
+    /// bits and its index in the lower 16 bits.
+    pub fn node_id(&self, index: usize) -> u32 {
+        ((self.node_salt(index) as u32) << 16) | (index as u32 & 0xFFFF)
+    }
+
+    /// Get the index of a node from its ID, or `None` if the ID is null, out of range, or its salt
+    /// does not match.
+    pub fn node_index(&self, id: u32) -> Option<usize> {
+        let index = (id & 0xFFFF) as usize;
+        (index < self.max_nodes && self.node_id(index) == id).then_some(index)
+    }
+}
+
+impl ParsedDefinitions {
+    /// Get the layout of compiled scripts for an engine.
+    ///
+    /// Returns `None` if the engine or its structs do not exist.
+    pub fn script_node_layout(&self, engine: &str) -> Option<ScriptNodeLayout<'_>> {
+        let engine = self.get_engine(engine)?;
+        let table = &engine.script_node_table;
+        Some(ScriptNodeLayout {
+            table,
+            header: self.struct_view(&table.table_struct)?,
+            node: self.struct_view(&table.node_struct)?,
+            max_nodes: engine.max_script_nodes as usize
+        })
+    }
+}
+
+/// Get the string at a byte offset in a scenario's script string data.
+///
+/// Returns `None` if the offset is out of bounds, or the string is not null-terminated or not
+/// valid UTF-8.
+pub fn script_string(string_data: &[u8], offset: usize) -> Option<&str> {
+    let data = string_data.get(offset..)?;
+    let length = data.iter().position(|b| *b == 0)?;
+    core::str::from_utf8(&data[..length]).ok()
+}
+
+#[cfg(all(test, feature = "engines-pc"))]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn script_node_layouts_match_the_scenario() {
+        let definitions = load_all_definitions();
+        let syntax_data_limit = definitions.struct_view("Scenario").unwrap().field("script syntax data").unwrap().limit_for_engine("pc-custom").unwrap();
+        for engine in definitions.engines.values() {
+            let layout = definitions.script_node_layout(&engine.name).unwrap();
+            assert_eq!(layout.node.size, 20);
+            assert!(layout.syntax_data_size(layout.max_nodes) <= syntax_data_limit, "{} has more script nodes than fit", engine.name);
+        }
+
+        let layout = definitions.script_node_layout("pc-custom").unwrap();
+        assert_eq!(layout.header.size, 56);
+        assert_eq!(layout.node_offset(2), 96);
+        assert_eq!(fourcc_to_string(layout.table.signature), "d@t@");
+        assert_eq!(layout.node_id(1), 0xE7420001);
+        assert_eq!(layout.node_index(0xE7420001), Some(1));
+        assert_eq!(layout.node_index(0xE7410001), None);
+        assert_eq!(layout.node_index(u32::MAX), None);
+        assert_eq!(layout.node_salt(0x10000 - 0xE741), 0x8000);
+
+        assert_eq!(script_string(b"sleep\0begin\0", 6), Some("begin"));
+        assert_eq!(script_string(b"sleep", 0), None);
+    }
+}
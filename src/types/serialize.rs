@@ -0,0 +1,36 @@
+use alloc::format;
+
+use serde::{Serialize, Serializer};
+
+use super::*;
+
+/// Limits are serialized as strings so they can be used as map keys (e.g. in JSON objects).
+///
+/// These are `default`, `editor`, the name of the engine, or `profile/<name>` for limit profiles.
+impl Serialize for LimitType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Default => serializer.serialize_str("default"),
+            Self::Editor => serializer.serialize_str("editor"),
+            Self::Engine(e) => serializer.serialize_str(e),
+            Self::Profile(p) => serializer.serialize_str(&format!("profile/{p}"))
+        }
+    }
+}
+
+#[cfg(all(test, feature = "engines-pc"))]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn definitions_serialize_to_json() {
+        let definitions = load_all_definitions();
+        let json: serde_json::Value = serde_json::from_str(&serde_json::to_string(definitions).unwrap()).unwrap();
+
+        let scenario = &json["objects"]["Scenario"]["Struct"];
+        assert_eq!(scenario["size"].as_u64(), Some(definitions.get_struct("Scenario").unwrap().size as u64));
+        assert!(scenario["fields"].as_array().unwrap().iter().all(|f| f["relative_offset"].is_u64()));
+        assert_eq!(json["groups"]["wind"]["struct_name"], "Wind");
+        assert_eq!(json["engines"]["pc-retail"]["cache_file_version"], 7);
+    }
+}
@@ -0,0 +1,532 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde_json::{Map, Number, Value};
+
+use super::*;
+
+impl ParsedDefinitions {
+    /// Convert the definitions back to the JSON definition format, so they can be edited and
+    /// written back out.
+    ///
+    /// Returns the contents of each definition file (an array of definitions), keyed by
+    /// [`Struct::definition_file`] and friends. Definitions are sorted by kind, then by name.
+    ///
+    /// Loading the result gives back the same definitions. However, some things computed when
+    /// finalizing are kept, so supported engines include inheriting engines and struct flags are
+    /// repeated on every field.
+    pub fn to_definition_json(&self) -> BTreeMap<String, Value> {
+        let mut files: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+        self.for_each_definition_json(|file, definition| {
+            files.entry(file.to_owned()).or_default().push(Value::Object(definition));
+        });
+        files.into_iter().map(|(file, definitions)| (file, Value::Array(definitions))).collect()
+    }
+
+    /// Call `f` with the definition file and JSON of every definition, sorted by kind, then by name.
+    pub(crate) fn for_each_definition_json(&self, mut f: impl FnMut(&str, Map<String, Value>)) {
+        for engine in self.engines.values() {
+            f(&engine.definition_file, self.engine_to_json(engine));
+        }
+        for group in self.groups.values() {
+            f(&group.definition_file, group_to_json(group));
+        }
+        for profile in self.limit_profiles.values() {
+            let mut object = definition("limit_profile", &profile.name);
+            insert_opt_str(&mut object, "description", &profile.description);
+            f(&profile.definition_file, object);
+        }
+        for object in self.objects.values() {
+            f(object.definition_file(), self.object_to_json(object));
+        }
+    }
+
+    /// Get the JSON of a struct, enum, or bitfield.
+    pub(crate) fn object_to_json(&self, object: &NamedObject) -> Map<String, Value> {
+        match object {
+            NamedObject::Struct(s) => struct_to_json(self, s),
+            NamedObject::Enum(e) => {
+                let mut object = definition("enum", &e.name);
+                object.insert("options".to_owned(), Value::Array(e.options.iter().map(option_to_json).collect()));
+                if let Some(interpretation) = e.interpretation {
+                    object.insert("interpretation".to_owned(), Value::from(interpretation.name()));
+                }
+                if let Some(role) = e.global_role {
+                    object.insert("global".to_owned(), Value::from(role.name()));
+                }
+                insert_opt_str(&mut object, "copy_of", &e.copy_of);
+                insert_flags(&mut object, &e.flags);
+                object
+            },
+            NamedObject::Bitfield(b) => {
+                let mut object = definition("bitfield", &b.name);
+                object.insert("width".to_owned(), Value::from(b.width));
+                object.insert("fields".to_owned(), Value::Array(b.fields.iter().map(option_to_json).collect()));
+                if !b.exclusive_groups.is_empty() {
+                    object.insert("exclusive".to_owned(), Value::Array(b.exclusive_groups.iter().map(|g| strings(g)).collect()));
+                }
+                if !b.implications.is_empty() {
+                    object.insert("implies".to_owned(), Value::Object(b.implications.iter().map(|(k, v)| (k.to_owned(), strings(v))).collect()));
+                }
+                insert_opt_str(&mut object, "copy_of", &b.copy_of);
+                insert_flags(&mut object, &b.flags);
+                object
+            }
+        }
+    }
+
+    fn engine_to_json(&self, engine: &Engine) -> Map<String, Value> {
+        let hex = |value: u64| Value::String(alloc::format!("0x{value:X}"));
+        let parent = engine.inherits.as_ref().and_then(|i| self.engines.get(i));
+
+        let mut object = definition("engine", &engine.name);
+        object.insert("display_name".to_owned(), Value::from(engine.display_name.as_str()));
+        insert_opt_str(&mut object, "inherits", &engine.inherits);
+        insert_opt_str(&mut object, "version", &engine.version);
+        if let Some(build) = &engine.build {
+            let mut b = Map::new();
+            b.insert("version".to_owned(), Value::from(build.string.as_str()));
+            b.insert("enforced".to_owned(), Value::Bool(build.enforced));
+            if !build.aliases.is_empty() {
+                b.insert("aliases".to_owned(), strings(&build.aliases));
+            }
+            object.insert("build".to_owned(), Value::Object(b));
+        }
+        for (key, value) in [
+            ("build_target", engine.build_target),
+            ("fallback", engine.fallback),
+            ("cache_default", engine.cache_default),
+            ("custom", engine.custom),
+            ("external_models", engine.external_models),
+            ("external_bsps", engine.external_bsps),
+            ("compressed_models", engine.compressed_models),
+            ("obfuscated_header_layout", engine.obfuscated_header_layout)
+        ] {
+            object.insert(key.to_owned(), Value::Bool(value));
+        }
+        object.insert("cache_file_version".to_owned(), Value::from(engine.cache_file_version));
+        object.insert("max_script_nodes".to_owned(), Value::from(engine.max_script_nodes));
+        object.insert("script_node_table".to_owned(), Value::Object(engine.script_node_table.to_json()));
+        object.insert("max_tag_space".to_owned(), hex(engine.max_tag_space));
+        object.insert("minimum_weapons".to_owned(), Value::from(engine.minimum_weapons));
+
+        let mut tag_paths = Map::new();
+        tag_paths.insert("max_length".to_owned(), Value::from(engine.tag_paths.max_length));
+        tag_paths.insert("character_set".to_owned(), Value::from(engine.tag_paths.character_set.name()));
+        object.insert("tag_paths".to_owned(), Value::Object(tag_paths));
+        object.insert("hud_scaling".to_owned(), Value::from(engine.hud_scaling.name()));
+        object.insert("data_alignment".to_owned(), Value::from(engine.data_alignment));
+        object.insert("compressed_data_alignment".to_owned(), Value::from(engine.compressed_data_alignment));
+        object.insert("cache_parser".to_owned(), Value::from(match engine.cache_parser {
+            EngineCacheParser::PC => "pc",
+            EngineCacheParser::Xbox => "xbox"
+        }));
+        object.insert("compression_type".to_owned(), Value::from(match engine.compression_type {
+            EngineCompressionType::Uncompressed => "none",
+            EngineCompressionType::Deflate => "deflate"
+        }));
+
+        let mut base_memory_address = Map::new();
+        base_memory_address.insert("value".to_owned(), hex(engine.base_memory_address.address));
+        base_memory_address.insert("inferred".to_owned(), Value::Bool(engine.base_memory_address.inferred));
+        object.insert("base_memory_address".to_owned(), Value::Object(base_memory_address));
+
+        let size = &engine.max_cache_file_size;
+        let mut max_cache_file_size = Map::new();
+        max_cache_file_size.insert("user_interface".to_owned(), hex(size.user_interface));
+        max_cache_file_size.insert("singleplayer".to_owned(), hex(size.singleplayer));
+        max_cache_file_size.insert("multiplayer".to_owned(), hex(size.multiplayer));
+        object.insert("max_cache_file_size".to_owned(), Value::Object(max_cache_file_size));
+
+        let grenades = &engine.grenades;
+        let grenade_limits = |user_interface: u8, singleplayer: u8, multiplayer: u8| {
+            let mut limits = Map::new();
+            limits.insert("user_interface".to_owned(), Value::from(user_interface));
+            limits.insert("singleplayer".to_owned(), Value::from(singleplayer));
+            limits.insert("multiplayer".to_owned(), Value::from(multiplayer));
+            Value::Object(limits)
+        };
+        let mut grenade_object = Map::new();
+        grenade_object.insert("minimum".to_owned(), grenade_limits(*grenades.user_interface.start(), *grenades.singleplayer.start(), *grenades.multiplayer.start()));
+        grenade_object.insert("maximum".to_owned(), grenade_limits(*grenades.user_interface.end(), *grenades.singleplayer.end(), *grenades.multiplayer.end()));
+        object.insert("grenades".to_owned(), Value::Object(grenade_object));
+
+        let options = &engine.bitmap_options;
+        let mut bitmap_options = Map::new();
+        bitmap_options.insert("swizzled".to_owned(), Value::Bool(options.swizzled));
+        bitmap_options.insert("texture_dimension_must_modulo_block_size".to_owned(), Value::Bool(options.texture_dimension_must_modulo_block_size));
+        bitmap_options.insert("cubemap_faces_stored_separately".to_owned(), Value::Bool(options.cubemap_faces_stored_separately));
+        bitmap_options.insert("alignment".to_owned(), Value::from(options.alignment));
+        object.insert("bitmap_options".to_owned(), Value::Object(bitmap_options));
+
+        if let Some(resource_maps) = &engine.resource_maps {
+            let mut r = Map::new();
+            r.insert("externally_indexed_tags".to_owned(), Value::Bool(resource_maps.externally_indexed_tags));
+            object.insert("resource_maps".to_owned(), Value::Object(r));
+        }
+
+        // Required tags are combined with those of inherited engines when loading, so only write
+        // the ones this engine adds.
+        let required = &engine.required_tags;
+        let inherited = parent.map(|p| &p.required_tags);
+        let mut required_tags = Map::new();
+        for (key, tags, inherited) in [
+            ("all", &required.all, inherited.map(|i| &i.all)),
+            ("user_interface", &required.user_interface, inherited.map(|i| &i.user_interface)),
+            ("singleplayer", &required.singleplayer, inherited.map(|i| &i.singleplayer)),
+            ("multiplayer", &required.multiplayer, inherited.map(|i| &i.multiplayer))
+        ] {
+            let own: Vec<String> = tags.iter().filter(|t| !inherited.is_some_and(|i| i.contains(t))).cloned().collect();
+            if !own.is_empty() {
+                required_tags.insert(key.to_owned(), strings(&own));
+            }
+        }
+        object.insert("required_tags".to_owned(), Value::Object(required_tags));
+
+        let documentation = &engine.documentation;
+        let mut d = Map::new();
+        insert_opt_str(&mut d, "description", &documentation.description);
+        insert_opt_str(&mut d, "release_date", &documentation.release_date);
+        insert_opt_str(&mut d, "distribution", &documentation.distribution);
+        if !documentation.quirks.is_empty() {
+            d.insert("quirks".to_owned(), strings(&documentation.quirks));
+        }
+        if !d.is_empty() {
+            object.insert("documentation".to_owned(), Value::Object(d));
+        }
+
+        object
+    }
+}
+
+fn definition(kind: &str, name: &str) -> Map<String, Value> {
+    let mut object = Map::new();
+    object.insert("name".to_owned(), Value::from(name));
+    object.insert("type".to_owned(), Value::from(kind));
+    object
+}
+
+fn strings(strings: &[String]) -> Value {
+    Value::Array(strings.iter().map(|s| Value::from(s.as_str())).collect())
+}
+
+fn insert_opt_str(object: &mut Map<String, Value>, key: &str, value: &Option<String>) {
+    if let Some(v) = value {
+        object.insert(key.to_owned(), Value::from(v.as_str()));
+    }
+}
+
+fn insert_see_also(object: &mut Map<String, Value>, see_also: &[SeeAlso]) {
+    if see_also.is_empty() {
+        return
+    }
+    let see_also = see_also.iter().map(|s| match s {
+        SeeAlso::Group(g) => Value::from(g.as_str()),
+        SeeAlso::Field { object, field } => Value::String(alloc::format!("{object}.{field}"))
+    });
+    object.insert("see_also".to_owned(), Value::Array(see_also.collect()));
+}
+
+fn insert_supported_engines(object: &mut Map<String, Value>, supported_engines: &SupportedEngines) {
+    let (key, engines) = match supported_engines {
+        SupportedEngines::AllEngines => return,
+        SupportedEngines::SomeEngines(e) => ("supported_engines", e),
+        SupportedEngines::AllExcept(e) => ("unsupported_engines", e)
+    };
+    object.insert(key.to_owned(), Value::Array(engines.iter().map(|e| Value::from(e.as_str())).collect()));
+}
+
+fn insert_flags(object: &mut Map<String, Value>, flags: &Flags) {
+    for flag in FlagType::ALL {
+        if flags.has_flag(flag) {
+            object.insert(flag.json_name().to_owned(), Value::Bool(true));
+        }
+    }
+    insert_supported_engines(object, &flags.supported_engines);
+    insert_opt_str(object, "comment", &flags.comment);
+    insert_opt_str(object, "developer_note", &flags.developer_note);
+    insert_opt_str(object, "description", &flags.description);
+    insert_opt_str(object, "introduced_in", &flags.introduced_in);
+    insert_opt_str(object, "removed_in", &flags.removed_in);
+    if !flags.examples.is_empty() {
+        object.insert("examples".to_owned(), strings(&flags.examples));
+    }
+    insert_see_also(object, &flags.see_also);
+    if let Some(research) = &flags.research {
+        let mut r = Map::new();
+        r.insert("confidence".to_owned(), Value::from(research.confidence.name()));
+        if !research.observed_values.is_empty() {
+            r.insert("observed_values".to_owned(), strings(&research.observed_values));
+        }
+        if !research.links.is_empty() {
+            r.insert("links".to_owned(), strings(&research.links));
+        }
+        insert_opt_str(&mut r, "notes", &research.notes);
+        object.insert("research".to_owned(), Value::Object(r));
+    }
+    if let Some(rewrite) = flags.cache_rewrite {
+        object.insert("cache_rewrite".to_owned(), Value::from(rewrite.name()));
+    }
+    if let Some(determinism) = flags.determinism {
+        object.insert("determinism".to_owned(), Value::from(determinism.name()));
+    }
+}
+
+fn group_to_json(group: &TagGroup) -> Map<String, Value> {
+    let mut object = definition("group", &group.name);
+    object.insert("struct".to_owned(), Value::from(group.struct_name.as_str()));
+    insert_opt_str(&mut object, "supergroup", &group.supergroup);
+    object.insert("version".to_owned(), Value::from(group.version));
+    object.insert("fourcc_binary".to_owned(), Value::from(group.fourcc_binary));
+    if group.extension != group.name {
+        object.insert("extension".to_owned(), Value::from(group.extension.as_str()));
+    }
+    if !group.extension_aliases.is_empty() {
+        object.insert("extension_aliases".to_owned(), strings(&group.extension_aliases));
+    }
+    if let Some(types) = &group.scenario_types {
+        object.insert("scenario_types".to_owned(), Value::Array(types.iter().map(|t| Value::from(t.name())).collect()));
+    }
+    insert_opt_str(&mut object, "short_code", &group.short_code);
+    if let Some(order) = group.sort_order {
+        object.insert("sort_order".to_owned(), Value::from(order));
+    }
+    if let Some(documentation) = &group.documentation {
+        let mut d = Map::new();
+        d.insert("overview".to_owned(), Value::from(documentation.overview.as_str()));
+        if !documentation.usage_notes.is_empty() {
+            d.insert("usage_notes".to_owned(), strings(&documentation.usage_notes));
+        }
+        object.insert("documentation".to_owned(), Value::Object(d));
+    }
+    insert_supported_engines(&mut object, &group.supported_engines);
+    insert_see_also(&mut object, &group.see_also);
+    object
+}
+
+fn option_to_json(option: &Field) -> Value {
+    let mut object = Map::new();
+    insert_flags(&mut object, &option.flags);
+    if object.is_empty() {
+        return Value::from(option.name.as_str())
+    }
+    object.insert("name".to_owned(), Value::from(option.name.as_str()));
+    Value::Object(object)
+}
+
+fn static_value_to_json(value: &StaticValue) -> Value {
+    match value {
+        StaticValue::Float(f) => Number::from_f64(*f as f64).map_or(Value::Null, Value::Number),
+        StaticValue::Uint(u) => Value::from(*u),
+        StaticValue::Int(i) => Value::from(*i),
+        StaticValue::String(s) => Value::from(s.as_str())
+    }
+}
+
+fn struct_to_json(definitions: &ParsedDefinitions, s: &Struct) -> Map<String, Value> {
+    let mut object = definition("struct", &s.name);
+    let mut fields = s.fields.as_slice();
+
+    // Inheriting a struct adds it as the first field when loading.
+    if let Some(StructField { name, field_type: StructFieldType::Object(FieldObject::NamedObject(parent)), name_rust_enum, .. }) = fields.first() {
+        if name == parent && name_rust_enum == parent {
+            object.insert("inherits".to_owned(), Value::from(parent.as_str()));
+            fields = &fields[1..];
+        }
+    }
+
+    object.insert("fields".to_owned(), Value::Array(fields.iter().map(|f| Value::Object(field_to_json(definitions, f))).collect()));
+    object.insert("size".to_owned(), Value::from(s.size));
+    if s.packed {
+        object.insert("packed".to_owned(), Value::Bool(true));
+    }
+    if s.defined_memory_pool != MemoryPool::default() {
+        object.insert("memory_pool".to_owned(), Value::from(s.defined_memory_pool.name()));
+    }
+    insert_flags(&mut object, &s.flags);
+    object
+}
+
+fn field_to_json(definitions: &ParsedDefinitions, field: &StructField) -> Map<String, Value> {
+    let mut object = Map::new();
+    let field_object = match &field.field_type {
+        StructFieldType::Padding(size) => {
+            object.insert("type".to_owned(), Value::from("pad"));
+            object.insert("size".to_owned(), Value::from(*size));
+            if field.padding_kind == Some(PaddingKind::Reserved) {
+                object.insert("reserved".to_owned(), Value::Bool(true));
+            }
+            insert_flags(&mut object, &field.flags);
+            return object
+        },
+        StructFieldType::EditorSection { heading, body } => {
+            object.insert("type".to_owned(), Value::from("editor_section"));
+            object.insert("heading".to_owned(), Value::from(heading.as_str()));
+            insert_opt_str(&mut object, "body", body);
+            return object
+        },
+        StructFieldType::Object(o) => o
+    };
+
+    object.insert("name".to_owned(), Value::from(field.name.as_str()));
+    object.insert("type".to_owned(), Value::from(field_object.type_name()));
+    match field_object {
+        FieldObject::Reflexive(s) => {
+            object.insert("struct".to_owned(), Value::from(s.as_str()));
+        },
+        FieldObject::TagReference { allowed_groups } => {
+            // Child groups are added when loading, so only write groups whose parents are not
+            // already allowed.
+            let is_child = |group: &String| {
+                let mut parent = definitions.groups.get(group).and_then(|g| g.supergroup.as_ref());
+                while let Some(p) = parent {
+                    if allowed_groups.contains(p) {
+                        return true
+                    }
+                    parent = definitions.groups.get(p).and_then(|g| g.supergroup.as_ref());
+                }
+                false
+            };
+            let groups: Vec<String> = if definitions.groups.keys().all(|g| allowed_groups.contains(g)) {
+                Vec::from(["*".to_owned()])
+            }
+            else {
+                allowed_groups.iter().filter(|g| !is_child(g)).cloned().collect()
+            };
+            object.insert("groups".to_owned(), strings(&groups));
+        },
+        FieldObject::ReflexiveIndex { struct_name, reflexive_name_display, .. } => {
+            object.insert("struct".to_owned(), Value::from(struct_name.as_str()));
+            object.insert("reflexive".to_owned(), Value::from(reflexive_name_display.as_str()));
+        },
+        _ => ()
+    }
+
+    match field.count {
+        FieldCount::One => (),
+        FieldCount::Bounds => {
+            object.insert("bounds".to_owned(), Value::Bool(true));
+        },
+        FieldCount::Array(n) if field.engine_counts.is_empty() => {
+            object.insert("count".to_owned(), Value::from(n));
+        },
+        FieldCount::Array(n) => {
+            let mut count = Map::new();
+            count.insert("default".to_owned(), Value::from(n));
+            count.extend(field.engine_counts.iter().map(|(e, c)| (e.to_owned(), Value::from(*c))));
+            object.insert("count".to_owned(), Value::Object(count));
+        }
+    }
+
+    if let Some(default) = &field.default_value {
+        object.insert("default".to_owned(), Value::Array(default.iter().map(static_value_to_json).collect()));
+    }
+    if let Some(minimum) = &field.minimum {
+        object.insert("minimum".to_owned(), static_value_to_json(minimum));
+    }
+    if let Some(maximum) = &field.maximum {
+        object.insert("maximum".to_owned(), static_value_to_json(maximum));
+    }
+    if let Some(limits) = &field.limit {
+        object.insert("limit".to_owned(), limit_to_json(limits));
+    }
+
+    let default_nullability = matches!(field_object, FieldObject::Index | FieldObject::Reflexive(_));
+    let nullable = matches!(field.nullability, Nullability::Nullable);
+    if nullable != default_nullability {
+        object.insert("non_null".to_owned(), Value::Bool(!nullable));
+    }
+
+    if let Some(widget) = &field.widget {
+        let widget = match widget {
+            EditorWidget::Slider => Value::from("slider"),
+            EditorWidget::ColorPicker => Value::from("color"),
+            EditorWidget::AngleDial => Value::from("angle"),
+            EditorWidget::PathPicker { .. } => Value::from("path")
+        };
+        object.insert("widget".to_owned(), widget);
+    }
+    if let Some(step) = field.step {
+        object.insert("step".to_owned(), Number::from_f64(step).map_or(Value::Null, Value::Number));
+    }
+    if let Some(precision) = field.precision {
+        object.insert("precision".to_owned(), Value::from(precision));
+    }
+    if let Some(pool) = field.memory_pool {
+        object.insert("memory_pool".to_owned(), Value::from(pool.name()));
+    }
+    if let Some(budget) = field.byte_budget {
+        object.insert("byte_budget".to_owned(), Value::from(budget));
+    }
+    if field.normalized {
+        object.insert("normalize".to_owned(), Value::Bool(true));
+    }
+    if let Some(color_space) = field.color_space {
+        object.insert("color_space".to_owned(), Value::from(color_space.name()));
+    }
+
+    insert_flags(&mut object, &field.flags);
+    object
+}
+
+fn limit_to_json(limits: &BTreeMap<LimitType, usize>) -> Value {
+    if let Some(default) = limits.get(&LimitType::Default) {
+        if limits.len() == 2 && limits.get(&LimitType::Editor) == Some(default) {
+            return Value::from(*default)
+        }
+    }
+
+    // The editor limit is the highest of the other limits, so it is recomputed when loading.
+    let mut object = Map::new();
+    let mut profiles = Map::new();
+    for (limit_type, limit) in limits {
+        match limit_type {
+            LimitType::Default => {
+                object.insert("default".to_owned(), Value::from(*limit));
+            },
+            LimitType::Engine(e) => {
+                object.insert(e.to_owned(), Value::from(*limit));
+            },
+            LimitType::Profile(p) => {
+                profiles.insert(p.to_owned(), Value::from(*limit));
+            },
+            LimitType::Editor => ()
+        }
+    }
+    if !profiles.is_empty() {
+        object.insert("profiles".to_owned(), Value::Object(profiles));
+    }
+
+    // Limits written as objects do not keep their default, but one is required when loading. The
+    // editor limit is at least every other limit, so using it leaves the editor limit unchanged.
+    if !object.contains_key("default") {
+        if let Some(editor) = limits.get(&LimitType::Editor) {
+            object.insert("default".to_owned(), Value::from(*editor));
+        }
+    }
+    Value::Object(object)
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+    use crate::*;
+
+    #[test]
+    fn definition_json_round_trips() {
+        let definitions = load_all_definitions();
+        let files = definitions.to_definition_json();
+        assert!(files.contains_key(&definitions.groups["wind"].definition_file));
+
+        let mut reloaded = ParsedDefinitions::default();
+        let mut objects = Vec::new();
+        for (file, json) in &files {
+            objects.extend(super::super::parse_definition_file(file, &serde_json::to_vec(json).unwrap()));
+        }
+        reloaded.load_from_json(&objects);
+        reloaded.finalize();
+        assert_eq!(reloaded.dump_canonical(), definitions.dump_canonical());
+        assert_eq!(reloaded.to_definition_json(), files);
+    }
+}
@@ -0,0 +1,141 @@
+use alloc::borrow::ToOwned;
+use alloc::format;
+use serde_json::{Map, Value};
+
+use super::*;
+
+/// Describes which tag paths an engine can load.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EngineTagPaths {
+    /// Maximum length of a tag path in bytes, excluding the extension and null terminator.
+    pub max_length: usize,
+
+    /// Characters allowed in tag paths.
+    pub character_set: TagPathCharacterSet
+}
+
+/// Describes which characters can be used in a tag path.
+///
+/// Paths are always separated with `\`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum TagPathCharacterSet {
+    /// Printable ASCII, excluding characters that are not allowed in Windows paths (`<>:"/|?*`).
+    ASCII
+}
+
+impl TagPathCharacterSet {
+    /// Get the character set from its name in the JSON definitions (e.g. `ascii`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "ascii" => Some(Self::ASCII),
+            _ => None
+        }
+    }
+
+    /// Get the name of the character set as used in the JSON definitions.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::ASCII => "ascii"
+        }
+    }
+
+    /// Return true if the character can be used in a tag path.
+    pub const fn allows(self, c: char) -> bool {
+        match self {
+            Self::ASCII => matches!(c, ' '..='~') && !matches!(c, '<' | '>' | ':' | '"' | '/' | '|' | '?' | '*')
+        }
+    }
+}
+
+/// Describes why a tag path cannot be used by an engine.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TagPathError {
+    /// The path is longer than [`EngineTagPaths::max_length`].
+    TooLong {
+        /// Length of the path in bytes.
+        length: usize,
+
+        /// Maximum length of the path in bytes.
+        max_length: usize
+    },
+
+    /// The path contains a character not in [`EngineTagPaths::character_set`].
+    InvalidCharacter(char)
+}
+
+impl core::fmt::Display for TagPathError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooLong { length, max_length } => fmt.write_fmt(format_args!("tag path is {length} bytes long, exceeding the limit of {max_length} bytes")),
+            Self::InvalidCharacter(c) => fmt.write_fmt(format_args!("tag path contains invalid character {c:?}"))
+        }
+    }
+}
+
+impl core::error::Error for TagPathError {}
+
+impl EngineTagPaths {
+    /// Check that a tag path (without an extension) can be used by the engine.
+    ///
+    /// Exceeding these limits corrupts cache files rather than failing to build them, so paths
+    /// should be checked before building.
+    pub fn validate(&self, path: &str) -> Result<(), TagPathError> {
+        if let Some(c) = path.chars().find(|c| !self.character_set.allows(*c)) {
+            return Err(TagPathError::InvalidCharacter(c))
+        }
+        if path.len() > self.max_length {
+            return Err(TagPathError::TooLong { length: path.len(), max_length: self.max_length })
+        }
+        Ok(())
+    }
+
+    pub(crate) fn load_from_json(engine_name: &str, object: &Map<String, Value>) -> Result<Self, DefinitionError> {
+        let invalid = |key: &str, expected| DefinitionError::InvalidValue { object: engine_name.to_owned(), key: format!("tag_paths::{key}"), expected };
+        let max_length = object.get("max_length")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| invalid("max_length", "an unsigned integer"))? as usize;
+        let character_set = object.get("character_set")
+            .and_then(|v| v.as_str())
+            .and_then(TagPathCharacterSet::from_name)
+            .ok_or_else(|| invalid("character_set", "a known character set"))?;
+        Ok(Self {
+            max_length,
+            character_set
+        })
+    }
+}
+
+impl Engine {
+    /// Check that a tag path (without an extension) can be used by this engine.
+    ///
+    /// Shorthand for calling [`EngineTagPaths::validate`] on [`Engine::tag_paths`].
+    pub fn validate_tag_path(&self, path: &str) -> Result<(), TagPathError> {
+        self.tag_paths.validate(path)
+    }
+}
+
+#[cfg(all(test, feature = "engines-pc"))]
+mod test {
+    use alloc::string::String;
+    use crate::*;
+
+    #[test]
+    fn tag_paths_are_validated() {
+        let definitions = load_all_definitions();
+        for engine in definitions.engines.values() {
+            assert!(engine.validate_tag_path("levels\\test\\bloodgulch\\bloodgulch").is_ok(), "{} rejects a stock path", engine.name);
+        }
+
+        let engine = definitions.get_engine("pc-custom").unwrap();
+        let max_length = engine.tag_paths.max_length;
+        assert!(engine.validate_tag_path(&"a".repeat(max_length)).is_ok());
+        assert_eq!(engine.validate_tag_path(&"a".repeat(max_length + 1)), Err(TagPathError::TooLong { length: max_length + 1, max_length }));
+        assert_eq!(engine.validate_tag_path("weapons/pistol"), Err(TagPathError::InvalidCharacter('/')));
+        assert_eq!(engine.validate_tag_path(&String::from("caf\u{e9}")), Err(TagPathError::InvalidCharacter('\u{e9}')));
+    }
+}
@@ -0,0 +1,121 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use super::*;
+use super::parse::get_all_child_groups;
+
+/// A tag reference field that can reference a group, found by [`ParsedDefinitions::tag_references_to`].
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct TagReferenceUsage<'a> {
+    /// The tag group the field was reached from.
+    pub group: &'a TagGroup,
+
+    /// Names of the fields leading from the group's base struct to this field, including this
+    /// field's name.
+    pub path: Vec<&'a str>,
+
+    /// The struct the field is in.
+    pub parent: &'a Struct,
+
+    /// The field itself.
+    pub field: &'a StructField
+}
+
+impl ParsedDefinitions {
+    /// List every tag reference field, reachable from any tag group, that can reference the given
+    /// group.
+    ///
+    /// A field can reference the group if [`FieldObject::allows_group`] is true, so references to
+    /// any `object` can reference a `weapon`. Results are ordered by group name, then
+    /// by field order, and fields in structs used by multiple groups are listed once per path. An
+    /// unknown group is referenced by nothing.
+    ///
+    /// ```
+    /// use ringhopper_definitions::load_all_definitions;
+    ///
+    /// let definitions = load_all_definitions();
+    /// let bitmap_references = definitions.tag_references_to("bitmap");
+    /// assert!(bitmap_references.iter().any(|r| r.group.name == "shader_model"));
+    /// ```
+    pub fn tag_references_to(&self, group: &str) -> Vec<TagReferenceUsage<'_>> {
+        let Some(group) = self.groups.get(group) else {
+            return Vec::new()
+        };
+
+        let mut usages = Vec::new();
+        for g in self.groups.values() {
+            self.walk_struct_fields(&g.struct_name, &mut |path, s, field| {
+                if matches!(&field.field_type, StructFieldType::Object(o) if o.allows_group(group, self)) {
+                    usages.push(TagReferenceUsage { group: g, path: path.to_vec(), parent: s, field });
+                }
+            });
+        }
+        usages
+    }
+}
+
+impl FieldObject {
+    /// Get every group a tag reference can reference, in name order, or `None` if this is not a
+    /// tag reference.
+    ///
+    /// Allowed groups are expanded through their subgroups (e.g. allowing `object` allows
+    /// `weapon`), and `*` allows every group. Finalizing already expands allowed groups this way,
+    /// so this mostly matters for definitions that are not finalized.
+    pub fn referenceable_groups<'a>(&self, definitions: &'a ParsedDefinitions) -> Option<Vec<&'a TagGroup>> {
+        let Self::TagReference { allowed_groups } = self else {
+            return None
+        };
+        let mut names = BTreeSet::new();
+        for g in allowed_groups {
+            names.insert(g.to_owned());
+            names.extend(get_all_child_groups(g, &definitions.groups));
+        }
+        Some(names.iter().filter_map(|n| definitions.groups.get(n)).collect())
+    }
+
+    /// Returns true if this is a tag reference that can reference the given group.
+    ///
+    /// See [`FieldObject::referenceable_groups`].
+    pub fn allows_group(&self, group: &TagGroup, definitions: &ParsedDefinitions) -> bool {
+        let Self::TagReference { allowed_groups } = self else {
+            return false
+        };
+        allowed_groups.iter().any(|g| *g == "*" || *g == group.name || get_all_child_groups(g, &definitions.groups).contains(&group.name))
+    }
+}
+
+#[cfg(all(test, feature = "engines-pc", feature = "engines-xbox", feature = "engines-mac", feature = "engines-mcc"))]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn references_through_supergroups() {
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_json(&get_all_definitions());
+        definitions.load_from_str("test.json", r#"[
+            { "name": "ObjectHolder", "type": "struct", "fields": [{ "name": "thing", "type": "TagReference", "groups": ["object"] }], "size": 16 },
+            { "name": "object_holder", "type": "group", "struct": "ObjectHolder", "version": 1, "fourcc_binary": 1330139716 }
+        ]"#).unwrap();
+        definitions.finalize();
+
+        let usages = definitions.tag_references_to("weapon");
+        assert!(usages.iter().any(|u| u.group.name == "object_holder" && u.path == ["thing"] && u.parent.name == "ObjectHolder"));
+        assert!(usages.iter().all(|u| matches!(&u.field.field_type, StructFieldType::Object(FieldObject::TagReference { .. }))));
+        assert!(definitions.tag_references_to("bitmap").iter().all(|u| u.group.name != "object_holder"));
+        assert!(definitions.tag_references_to("nonexistent").is_empty());
+
+        let reference = FieldObject::TagReference { allowed_groups: alloc::vec!["unit".into(), "model".into()] };
+        let groups: alloc::vec::Vec<&str> = reference.referenceable_groups(&definitions).unwrap().iter().map(|g| g.name.as_str()).collect();
+        assert!(groups.contains(&"unit") && groups.contains(&"vehicle") && groups.contains(&"gbxmodel"));
+        assert!(!groups.contains(&"object") && !groups.contains(&"weapon"));
+        assert!(reference.allows_group(&definitions.groups["biped"], &definitions));
+        assert!(!reference.allows_group(&definitions.groups["weapon"], &definitions));
+
+        let any = FieldObject::TagReference { allowed_groups: alloc::vec!["*".into()] };
+        assert_eq!(any.referenceable_groups(&definitions).unwrap().len(), definitions.groups.len());
+        assert!(any.allows_group(&definitions.groups["bitmap"], &definitions));
+        assert!(FieldObject::F32.referenceable_groups(&definitions).is_none());
+    }
+}
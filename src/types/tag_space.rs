@@ -0,0 +1,242 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::borrow::ToOwned;
+
+use super::*;
+
+/// Alignment used for each block of tag data in the simulation.
+const TAG_DATA_ALIGNMENT: u64 = 4;
+
+/// A tag to place in a [`ParsedDefinitions::simulate_tag_space`] simulation.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct PlannedTag {
+    /// Path of the tag, without an extension.
+    pub path: String,
+
+    /// Tag group of the tag.
+    pub group: String,
+
+    /// Number of elements in each reflexive, keyed by the names of the fields leading to the
+    /// reflexive joined with `/` (e.g. `"predicted resources"` or `"triggers/firing effects"`).
+    ///
+    /// Counts apply to every instance of a nested reflexive. Unlisted reflexives are empty.
+    pub reflexive_counts: BTreeMap<String, usize>
+}
+
+impl PlannedTag {
+    /// Plan a tag with every reflexive empty.
+    pub fn new(path: &str, group: &str) -> Self {
+        Self { path: path.to_owned(), group: group.to_owned(), reflexive_counts: BTreeMap::new() }
+    }
+
+    /// Set the number of elements of a reflexive.
+    pub fn with_count(mut self, reflexive_path: &str, count: usize) -> Self {
+        self.reflexive_counts.insert(reflexive_path.to_owned(), count);
+        self
+    }
+}
+
+/// Predicted address of a reflexive's elements.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ReflexivePlacement {
+    /// Names of the fields leading to the reflexive, joined with `/`.
+    pub path: String,
+
+    /// Number of elements.
+    pub count: usize,
+
+    /// Address of the first element.
+    pub address: u64,
+
+    /// Address of the reflexive's pointer (which will be set to [`ReflexivePlacement::address`]).
+    pub pointer_address: u64
+}
+
+/// Predicted placement of a tag's data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TagPlacement {
+    /// Path of the tag.
+    pub path: String,
+
+    /// Tag group of the tag.
+    pub group: String,
+
+    /// Address of the tag path string.
+    pub path_address: u64,
+
+    /// Address of the tag's base struct.
+    pub address: u64,
+
+    /// Total size of the tag's data in bytes, including reflexives and alignment.
+    pub size: u64,
+
+    /// Every non-empty reflexive in the order it was placed.
+    pub reflexives: Vec<ReflexivePlacement>
+}
+
+/// Result of [`ParsedDefinitions::simulate_tag_space`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TagSpaceLayout {
+    /// Base memory address tag data is loaded at.
+    pub base_address: u64,
+
+    /// Address of the tag array.
+    pub tag_array_address: u64,
+
+    /// Placement of every tag, in the order given.
+    pub tags: Vec<TagPlacement>,
+
+    /// Address of the first byte after all tag data.
+    pub end_address: u64,
+
+    /// Maximum tag space of the engine ([`Engine::max_tag_space`]).
+    pub max_tag_space: u64
+}
+
+impl TagSpaceLayout {
+    /// Total tag space used in bytes.
+    pub fn used(&self) -> u64 {
+        self.end_address - self.base_address
+    }
+
+    /// Returns true if the tags do not fit in the engine's tag space.
+    pub fn is_exhausted(&self) -> bool {
+        self.used() > self.max_tag_space
+    }
+}
+
+impl ParsedDefinitions {
+    /// Simulate how the given tags are placed in tag space when building a cache file for an engine.
+    ///
+    /// Tag space starts at [`Engine::base_memory_address`] with the tag data header, followed by
+    /// the tag array, tag paths, and then each tag's data in order. Each tag's base struct is
+    /// followed by its reflexives, depth first, with each block aligned to 4 bytes.
+    ///
+    /// This is entirely definition-driven and is an estimate; data fields (e.g.
+    /// [`FieldObject::Data`]) are not counted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a tag's group does not exist.
+    pub fn simulate_tag_space(&self, engine: &Engine, tags: &[PlannedTag]) -> TagSpaceLayout {
+        let header_struct = if engine.external_models { "CacheFileTagDataHeaderExternalModels" } else { "CacheFileTagDataHeaderInternalModels" };
+        let header_size = self.get_struct(header_struct).map_or(0, |s| s.size as u64);
+        let tag_entry_size = self.get_struct("CacheFileTag").map_or(0, |s| s.size as u64);
+
+        let base_address = engine.base_memory_address.address;
+        let tag_array_address = align(base_address + header_size);
+        let mut cursor = tag_array_address + tag_entry_size * tags.len() as u64;
+
+        let mut path_addresses = Vec::with_capacity(tags.len());
+        for t in tags {
+            path_addresses.push(cursor);
+            cursor += t.path.len() as u64 + 1;
+        }
+
+        let mut placements = Vec::with_capacity(tags.len());
+        for (t, path_address) in tags.iter().zip(path_addresses) {
+            let group = self.get_group(&t.group).unwrap_or_else(|| panic!("planned tag {} has group {} which does not exist", t.path, t.group));
+            let base = group.definition(self).view(self);
+
+            cursor = align(cursor);
+            let address = cursor;
+            cursor += base.size() as u64;
+
+            let mut reflexives = Vec::new();
+            place_reflexives(engine, t, base, address, &mut String::new(), &mut cursor, &mut reflexives);
+
+            placements.push(TagPlacement {
+                path: t.path.to_owned(),
+                group: t.group.to_owned(),
+                path_address,
+                address,
+                size: cursor - address,
+                reflexives
+            });
+        }
+
+        TagSpaceLayout {
+            base_address,
+            tag_array_address,
+            tags: placements,
+            end_address: cursor,
+            max_tag_space: engine.max_tag_space
+        }
+    }
+}
+
+const fn align(address: u64) -> u64 {
+    address.div_ceil(TAG_DATA_ALIGNMENT) * TAG_DATA_ALIGNMENT
+}
+
+/// Place every reflexive in a block of `s` elements located at `address`.
+fn place_reflexives(engine: &Engine, tag: &PlannedTag, s: StructView<'_>, address: u64, path: &mut String, cursor: &mut u64, placements: &mut Vec<ReflexivePlacement>) {
+    for f in s.fields() {
+        if !f.flags.supported_engines.supports_engine(engine) {
+            continue
+        }
+
+        let prefix_length = path.len();
+        if !path.is_empty() {
+            path.push('/');
+        }
+        path.push_str(&f.name);
+
+        match f.resolved_type() {
+            ResolvedFieldType::Struct(inner) => {
+                place_reflexives(engine, tag, inner, address + f.relative_offset as u64, path, cursor, placements);
+            },
+            ResolvedFieldType::Reflexive(inner) => {
+                let count = tag.reflexive_counts.get(path.as_str()).copied().unwrap_or(0);
+                if count > 0 {
+                    *cursor = align(*cursor);
+                    let block_address = *cursor;
+                    *cursor += (inner.size() * count) as u64;
+                    let pointer_address = address + f.relative_offset as u64 + 4;
+                    placements.push(ReflexivePlacement { path: path.to_owned(), count, address: block_address, pointer_address });
+                    for i in 0..count {
+                        place_reflexives(engine, tag, inner, block_address + (inner.size() * i) as u64, path, cursor, placements);
+                    }
+                }
+            },
+            _ => ()
+        }
+
+        path.truncate(prefix_length);
+    }
+}
+
+#[cfg(all(test, feature = "engines-pc"))]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn tag_space_places_reflexives_after_tag() {
+        let definitions = load_all_definitions();
+        let engine = &definitions.engines["pc-retail"];
+        let tags = [
+            PlannedTag::new("weather\\wind", "wind"),
+            PlannedTag::new("effects\\antenna", "antenna").with_count("vertices", 3)
+        ];
+        let layout = definitions.simulate_tag_space(engine, &tags);
+        assert_eq!(layout.base_address, engine.base_memory_address.address);
+        assert_eq!(layout.tags.len(), 2);
+
+        let wind = &layout.tags[0];
+        assert_eq!(wind.size, definitions.struct_view("Wind").unwrap().size() as u64);
+        assert!(wind.reflexives.is_empty());
+
+        let antenna = &layout.tags[1];
+        assert_eq!(antenna.reflexives.len(), 1);
+        assert_eq!(antenna.reflexives[0].count, 3);
+        assert!(antenna.reflexives[0].address >= antenna.address);
+        assert!(antenna.reflexives[0].pointer_address < antenna.reflexives[0].address);
+        assert_eq!(layout.end_address, antenna.address + antenna.size);
+        assert!(!layout.is_exhausted());
+    }
+}
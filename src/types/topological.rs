@@ -0,0 +1,125 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+
+use super::*;
+
+impl ParsedDefinitions {
+    /// Get every struct, enum, and bitfield, with each object after everything it uses.
+    ///
+    /// Objects a struct contains inline always come before it. Structs used through reflexives
+    /// also come first, unless reflexives form a loop back to the struct (e.g. a struct whose
+    /// reflexive holds more of itself), in which case the loop is broken where it is found.
+    ///
+    /// The order is deterministic: objects are visited in name order, and the objects each struct
+    /// uses are visited in field order.
+    pub fn objects_topologically_sorted(&self) -> Vec<&NamedObject> {
+        let mut tarjan = Tarjan {
+            definitions: self,
+            next_index: 0,
+            indices: BTreeMap::new(),
+            stack: Vec::new(),
+            sorted: Vec::with_capacity(self.objects.len())
+        };
+        for name in self.objects.keys() {
+            if !tarjan.indices.contains_key(name.as_str()) {
+                tarjan.visit(name);
+            }
+        }
+        tarjan.sorted
+    }
+}
+
+/// Objects that use each other (through reflexives) form strongly connected components, which
+/// Tarjan's algorithm finds with every component after the components it uses.
+struct Tarjan<'a> {
+    definitions: &'a ParsedDefinitions,
+    next_index: usize,
+
+    /// Index and lowest reachable index of each visited object.
+    indices: BTreeMap<&'a str, (usize, usize)>,
+    stack: Vec<&'a str>,
+    sorted: Vec<&'a NamedObject>
+}
+
+impl<'a> Tarjan<'a> {
+    fn visit(&mut self, name: &'a str) -> usize {
+        let index = self.next_index;
+        self.next_index += 1;
+        self.indices.insert(name, (index, index));
+        self.stack.push(name);
+
+        let mut low = index;
+        for (n, _) in uses(self.definitions, name) {
+            let n_low = match self.indices.get(n) {
+                None => self.visit(n),
+                Some((n_index, _)) if self.stack.contains(&n) => *n_index,
+                Some(_) => continue
+            };
+            low = low.min(n_low);
+        }
+        self.indices.insert(name, (index, low));
+
+        if low == index {
+            let start = self.stack.iter().rposition(|n| *n == name).expect("object should be on the stack");
+            let mut component: Vec<&str> = self.stack.split_off(start);
+            component.sort_unstable();
+
+            // Inline structs can't loop, so the component can always be ordered by them.
+            let mut done = BTreeSet::new();
+            for n in &component {
+                self.push_inline_first(n, &component, &mut done);
+            }
+        }
+        low
+    }
+
+    fn push_inline_first(&mut self, name: &'a str, component: &[&'a str], done: &mut BTreeSet<&'a str>) {
+        if !done.insert(name) {
+            return
+        }
+        for (n, inline) in uses(self.definitions, name) {
+            if inline && component.contains(&n) {
+                self.push_inline_first(n, component, done);
+            }
+        }
+        self.sorted.push(&self.definitions.objects[name]);
+    }
+}
+
+/// Iterate through the objects a struct uses in field order, and whether each is inline.
+fn uses<'a>(definitions: &'a ParsedDefinitions, name: &str) -> impl Iterator<Item = (&'a str, bool)> {
+    let fields = match definitions.objects.get(name) {
+        Some(NamedObject::Struct(s)) => s.fields.as_slice(),
+        _ => &[]
+    };
+    fields.iter().filter_map(|f| match &f.field_type {
+        StructFieldType::Object(FieldObject::NamedObject(n)) => Some((n.as_str(), true)),
+        StructFieldType::Object(FieldObject::Reflexive(n)) => Some((n.as_str(), false)),
+        _ => None
+    }).filter(|(n, _)| definitions.objects.contains_key(*n))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn objects_come_after_their_dependencies() {
+        let definitions = load_all_definitions();
+        let sorted = definitions.objects_topologically_sorted();
+        assert_eq!(sorted.len(), definitions.objects.len());
+        assert!(sorted.iter().zip(definitions.objects_topologically_sorted()).all(|(a, b)| a.name() == b.name()));
+
+        let position = |name: &str| sorted.iter().position(|o| o.name() == name).unwrap();
+        for (index, object) in sorted.iter().enumerate() {
+            let NamedObject::Struct(s) = object else {
+                continue
+            };
+            for f in &s.fields {
+                if let StructFieldType::Object(FieldObject::NamedObject(n)) = &f.field_type {
+                    assert!(position(n) < index, "{n} is after {}", s.name);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::*;
+
+/// Line ending used in [`FieldObject::UTF16String`] data (e.g. in `unicode_string_list` tags).
+///
+/// [`decode_utf16_string`] converts these to `\n`, and [`encode_utf16_string`] converts them back.
+pub const UTF16_STRING_LINE_ENDING: &str = "\r\n";
+
+/// Describes why [`FieldObject::UTF16String`] data is invalid.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum UTF16StringError {
+    /// The data is not a whole number of UTF-16 code units.
+    OddLength,
+
+    /// The data does not end with a null terminator.
+    MissingNullTerminator,
+
+    /// A null character is before the end of the string, at the given byte offset.
+    InteriorNull(usize),
+
+    /// A surrogate is not part of a pair, at the given byte offset.
+    UnpairedSurrogate(usize),
+
+    /// The data is longer than the field's limit.
+    TooLong {
+        /// Length of the data in bytes, including the null terminator.
+        length: usize,
+
+        /// Maximum length of the data in bytes.
+        limit: usize
+    }
+}
+
+impl core::fmt::Display for UTF16StringError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::OddLength => fmt.write_str("UTF-16 string data has an odd length"),
+            Self::MissingNullTerminator => fmt.write_str("UTF-16 string is not null-terminated"),
+            Self::InteriorNull(offset) => fmt.write_fmt(format_args!("UTF-16 string has a null character at offset {offset}")),
+            Self::UnpairedSurrogate(offset) => fmt.write_fmt(format_args!("UTF-16 string has an unpaired surrogate at offset {offset}")),
+            Self::TooLong { length, limit } => fmt.write_fmt(format_args!("UTF-16 string is {length} bytes long, exceeding the limit of {limit} bytes"))
+        }
+    }
+}
+
+impl core::error::Error for UTF16StringError {}
+
+/// Decode the data of a [`FieldObject::UTF16String`] field.
+///
+/// The data is little endian UTF-16 ending with a null terminator, or empty for an empty string.
+/// [`UTF16_STRING_LINE_ENDING`]s are converted to `\n`.
+pub fn decode_utf16_string(data: &[u8]) -> Result<String, UTF16StringError> {
+    if data.is_empty() {
+        return Ok(String::new())
+    }
+    if !data.len().is_multiple_of(2) {
+        return Err(UTF16StringError::OddLength)
+    }
+
+    let units: Vec<u16> = data.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+    let Some((0, string)) = units.split_last() else {
+        return Err(UTF16StringError::MissingNullTerminator)
+    };
+    if let Some(index) = string.iter().position(|u| *u == 0) {
+        return Err(UTF16StringError::InteriorNull(index * 2))
+    }
+
+    let mut decoded = String::with_capacity(string.len());
+    let mut offset = 0;
+    for c in char::decode_utf16(string.iter().copied()) {
+        let c = c.map_err(|_| UTF16StringError::UnpairedSurrogate(offset))?;
+        offset += c.len_utf16() * 2;
+        if c == '\n' && decoded.ends_with('\r') {
+            decoded.pop();
+        }
+        decoded.push(c);
+    }
+    Ok(decoded)
+}
+
+/// Encode a string as the data of a [`FieldObject::UTF16String`] field.
+///
+/// Line endings (`\n` or [`UTF16_STRING_LINE_ENDING`]) are written as
+/// [`UTF16_STRING_LINE_ENDING`], and a null terminator is appended. This returns an error if the
+/// string contains a null character.
+pub fn encode_utf16_string(string: &str) -> Result<Vec<u8>, UTF16StringError> {
+    let mut data = Vec::with_capacity((string.len() + 1) * 2);
+    let mut previous = '\0';
+    for c in string.chars() {
+        if c == '\0' {
+            return Err(UTF16StringError::InteriorNull(data.len()))
+        }
+        if c == '\n' && previous != '\r' {
+            data.extend_from_slice(&u16::from(b'\r').to_le_bytes());
+        }
+        let mut buffer = [0u16; 2];
+        for unit in c.encode_utf16(&mut buffer) {
+            data.extend_from_slice(&unit.to_le_bytes());
+        }
+        previous = c;
+    }
+    data.extend_from_slice(&[0, 0]);
+    Ok(data)
+}
+
+impl FieldView<'_> {
+    /// Decode the data of this [`FieldObject::UTF16String`] field, checking it against the field's
+    /// limit for the given engine (in bytes).
+    ///
+    /// See [`decode_utf16_string`].
+    pub fn decode_utf16_string(&self, engine: &str, data: &[u8]) -> Result<String, UTF16StringError> {
+        assert!(
+            matches!(self.field_type, StructFieldType::Object(FieldObject::UTF16String)),
+            "{} is not a UTF16String",
+            self.name
+        );
+        if let Some(limit) = self.limit_for_engine(engine).filter(|l| data.len() > *l) {
+            return Err(UTF16StringError::TooLong { length: data.len(), limit })
+        }
+        decode_utf16_string(data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn utf16_strings_round_trip() {
+        let data = encode_utf16_string("first line\nsecond line\r\n\u{1F600}").unwrap();
+        assert_eq!(&data[20..24], &[b'\r', 0, b'\n', 0]);
+        assert_eq!(&data[data.len() - 2..], &[0, 0]);
+        assert_eq!(decode_utf16_string(&data).unwrap(), "first line\nsecond line\n\u{1F600}");
+        assert_eq!(decode_utf16_string(&[]).unwrap(), "");
+
+        assert_eq!(encode_utf16_string("a\0b"), Err(UTF16StringError::InteriorNull(2)));
+        assert_eq!(decode_utf16_string(&[b'a', 0, 0]), Err(UTF16StringError::OddLength));
+        assert_eq!(decode_utf16_string(&[b'a', 0]), Err(UTF16StringError::MissingNullTerminator));
+        assert_eq!(decode_utf16_string(&[b'a', 0, 0, 0, b'b', 0, 0, 0]), Err(UTF16StringError::InteriorNull(2)));
+        assert_eq!(decode_utf16_string(&[b'a', 0, 0x00, 0xD8, 0, 0]), Err(UTF16StringError::UnpairedSurrogate(2)));
+
+        let definitions = load_all_definitions();
+        let strings = definitions.struct_view("UnicodeStringListString").unwrap();
+        let field = strings.field("string").unwrap();
+        let limit = field.limit_for_engine("pc-custom").unwrap();
+        assert_eq!(field.decode_utf16_string("pc-custom", &data).unwrap(), "first line\nsecond line\n\u{1F600}");
+        assert_eq!(field.decode_utf16_string("pc-custom", &alloc::vec![0; limit + 2]), Err(UTF16StringError::TooLong { length: limit + 2, limit }));
+    }
+}
@@ -0,0 +1,274 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use super::*;
+use super::parse::BITFIELD_WIDTHS;
+
+/// Every problem found in a set of definitions, created by [`ParsedDefinitions::validate`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct ValidationReport {
+    /// Problems found, grouped by object in alphabetical order.
+    pub problems: Vec<ValidationProblem>
+}
+
+impl ValidationReport {
+    /// Returns true if no problems were found.
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+impl core::fmt::Display for ValidationReport {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for p in &self.problems {
+            fmt.write_fmt(format_args!("{p}\n"))?;
+        }
+        Ok(())
+    }
+}
+
+/// A problem in a [`ValidationReport`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct ValidationProblem {
+    /// Name of the object (or group) with the problem.
+    pub object: String,
+
+    /// Name of the field, option, or key with the problem, if it is not the object itself.
+    pub field: Option<String>,
+
+    /// What is wrong.
+    pub kind: ValidationProblemKind
+}
+
+impl core::fmt::Display for ValidationProblem {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.field {
+            Some(field) => fmt.write_fmt(format_args!("{}::{field}: ", self.object))?,
+            None => fmt.write_fmt(format_args!("{}: ", self.object))?
+        }
+        match &self.kind {
+            ValidationProblemKind::BadSize { expected, actual } => fmt.write_fmt(format_args!("size is incorrect (expected {expected}, got {actual} instead)")),
+            ValidationProblemKind::MissingReference { reference } => fmt.write_fmt(format_args!("references {reference}, which does not exist")),
+            ValidationProblemKind::DuplicateOption => fmt.write_str("is defined more than once"),
+            ValidationProblemKind::DefaultOutOfRange { value } => fmt.write_fmt(format_args!("default {value} is outside of the minimum and maximum")),
+            ValidationProblemKind::OptionDrift(drift) => drift.fmt(fmt),
+            ValidationProblemKind::ReferenceCycle(cycle) => cycle.fmt(fmt)
+        }
+    }
+}
+
+/// Describes what is wrong in a [`ValidationProblem`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ValidationProblemKind {
+    /// A struct's fields do not add up to its size.
+    BadSize {
+        /// Size of the struct, as defined.
+        expected: usize,
+
+        /// Combined size of the struct's fields.
+        actual: usize
+    },
+
+    /// A struct, group, or copy references an object or group that does not exist.
+    MissingReference {
+        /// The name that could not be found.
+        reference: String
+    },
+
+    /// An enum option, bitfield field, or struct field has the same name as an earlier one, so it
+    /// would share its value.
+    DuplicateOption,
+
+    /// A default value is less than the field's minimum or greater than its maximum.
+    DefaultOutOfRange {
+        /// The default value.
+        value: StaticValue
+    },
+
+    /// A copy of an enum or bitfield does not match its original.
+    OptionDrift(OptionDrift),
+
+    /// A struct contains itself through inline struct fields.
+    ReferenceCycle(ReferenceCycle)
+}
+
+impl From<ValidationProblem> for DefinitionError {
+    fn from(problem: ValidationProblem) -> Self {
+        let ValidationProblem { object, field, kind } = problem;
+        match kind {
+            ValidationProblemKind::BadSize { expected, actual } => Self::BadSize { object, expected, actual },
+            ValidationProblemKind::MissingReference { reference } => Self::MissingReference { object, field: field.unwrap_or_default(), reference },
+            ValidationProblemKind::DuplicateOption => Self::Duplicate { name: format!("{object}::{}", field.unwrap_or_default()) },
+            ValidationProblemKind::OptionDrift(drift) => Self::OptionDrift(drift),
+            ValidationProblemKind::ReferenceCycle(cycle) => Self::ReferenceCycle(cycle),
+            kind => {
+                let problem = ValidationProblem { object, field, kind };
+                Self::Invalid { message: problem.to_string(), object: problem.object }
+            }
+        }
+    }
+}
+
+impl ParsedDefinitions {
+    /// Check loaded definitions, returning every problem found instead of stopping at the first.
+    ///
+    /// This is meant for authoring new definitions, and it can be called before
+    /// [`ParsedDefinitions::try_finalize`], which runs the same checks but stops at the first
+    /// problem. Only the problems described by [`ValidationProblemKind`] are checked, so finalizing
+    /// may still fail for other reasons.
+    pub fn validate(&self) -> ValidationReport {
+        ValidationReport { problems: self.validation_problems(false) }
+    }
+
+    // If partial_groups is set, groups may refer to structs that were deliberately not loaded
+    // (see load_groups_from_json)
+    pub(crate) fn validation_problems(&self, partial_groups: bool) -> Vec<ValidationProblem> {
+        let mut problems = Vec::new();
+        let mut report = |object: &str, field: Option<&str>, kind: ValidationProblemKind| problems.push(ValidationProblem {
+            object: object.to_owned(),
+            field: field.map(str::to_owned),
+            kind
+        });
+        let missing = |reference: &str| ValidationProblemKind::MissingReference { reference: reference.to_owned() };
+
+        for group in self.groups.values() {
+            match self.objects.get(&group.struct_name) {
+                Some(NamedObject::Struct(_)) => (),
+                None if partial_groups => (),
+                _ => report(&group.name, Some("struct"), missing(&group.struct_name))
+            }
+            if let Some(s) = group.supergroup.as_ref().filter(|s| !self.groups.contains_key(*s)) {
+                report(&group.name, Some("supergroup"), missing(s));
+            }
+        }
+
+        // The size of a struct that contains itself is meaningless, so only the cycle is reported.
+        let cycles = self.inline_cycles();
+        let cyclic: BTreeSet<&str> = cycles.iter().flat_map(|c| c.path.iter().map(|(s, _)| s.as_str())).collect();
+
+        for object in self.objects.values() {
+            let name = object.name();
+            if let Some(c) = object.copy_of().filter(|c| self.objects.get(*c).is_none_or(|o| o.kind() != object.kind())) {
+                report(name, Some("copy_of"), missing(c));
+            }
+
+            let names: Vec<&str> = match object {
+                NamedObject::Enum(e) => e.options.iter().map(|o| o.name.as_str()).collect(),
+                NamedObject::Bitfield(b) => b.fields.iter().map(|f| f.name.as_str()).collect(),
+                NamedObject::Struct(s) => s.fields
+                    .iter()
+                    .filter(|f| matches!(f.field_type, StructFieldType::Object(_)))
+                    .map(|f| f.name.as_str())
+                    .collect()
+            };
+            for (_, n) in names.iter().enumerate().filter(|(i, n)| names[..*i].contains(n)) {
+                report(name, Some(n), ValidationProblemKind::DuplicateOption);
+            }
+
+            let NamedObject::Struct(s) = object else {
+                continue
+            };
+
+            let mut sizeable = true;
+            for f in &s.fields {
+                let StructFieldType::Object(o) = &f.field_type else {
+                    continue
+                };
+                match o {
+                    FieldObject::NamedObject(n) if !self.objects.contains_key(n) => {
+                        report(name, Some(&f.name), missing(n));
+                        sizeable = false;
+                    },
+                    // Bitfield widths are checked when finalizing, and sizes would be wrong without them
+                    FieldObject::NamedObject(n) if matches!(&self.objects[n], NamedObject::Bitfield(b) if !BITFIELD_WIDTHS.contains(&b.width)) => {
+                        sizeable = false;
+                    },
+                    FieldObject::Custom(c) if !self.custom_objects.contains_key(c) => {
+                        report(name, Some(&f.name), missing(c));
+                        sizeable = false;
+                    },
+                    FieldObject::Reflexive(n) | FieldObject::ReflexiveIndex { struct_name: n, .. } if !matches!(self.objects.get(n), Some(NamedObject::Struct(_))) => {
+                        report(name, Some(&f.name), missing(n));
+                    },
+                    FieldObject::TagReference { allowed_groups } => {
+                        for g in allowed_groups.iter().filter(|g| *g != "*" && !self.groups.contains_key(*g)) {
+                            report(name, Some(&f.name), missing(g));
+                        }
+                    },
+                    _ => ()
+                }
+
+                for value in f.default_value.iter().flatten() {
+                    let below = f.minimum.as_ref().is_some_and(|m| value.numeric_cmp(m) == Some(Ordering::Less));
+                    let above = f.maximum.as_ref().is_some_and(|m| value.numeric_cmp(m) == Some(Ordering::Greater));
+                    if below || above {
+                        report(name, Some(&f.name), ValidationProblemKind::DefaultOutOfRange { value: value.clone() });
+                    }
+                }
+            }
+
+            // Sizes only depend on the sizes of directly referenced objects.
+            if sizeable && !cyclic.contains(name) {
+                let actual = s.fields.iter().map(|f| f.size(self)).sum();
+                if actual != s.size {
+                    report(name, None, ValidationProblemKind::BadSize { expected: s.size, actual });
+                }
+            }
+        }
+
+        for drift in self.option_drift() {
+            let object = drift.copy.to_owned();
+            report(&object, None, ValidationProblemKind::OptionDrift(drift));
+        }
+
+        for cycle in cycles {
+            let object = cycle.path[0].0.to_owned();
+            report(&object, None, ValidationProblemKind::ReferenceCycle(cycle));
+        }
+
+        problems.sort_by(|a, b| a.object.cmp(&b.object));
+        problems
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::borrow::ToOwned;
+
+    use crate::*;
+
+    #[test]
+    fn every_problem_is_reported() {
+        let mut definitions = ParsedDefinitions::default();
+        definitions.load_from_str("test.json", r#"[
+            { "name": "TestEnum", "type": "enum", "options": ["first", "second", "first"] },
+            { "name": "TestStruct", "type": "struct", "size": 12, "fields": [
+                { "name": "scale", "type": "float", "minimum": 0.0, "maximum": 1.0, "default": 2.0 },
+                { "name": "kind", "type": "TestEnum" },
+                { "name": "other", "type": "TestMissing" }
+            ] },
+            { "name": "TestBadSize", "type": "struct", "size": 8, "fields": [{ "name": "value", "type": "uint32" }] }
+        ]"#).unwrap();
+
+        let problem = |object: &str, field: Option<&str>, kind| ValidationProblem { object: object.to_owned(), field: field.map(str::to_owned), kind };
+        let report = definitions.validate();
+        assert_eq!(report.problems, [
+            problem("TestBadSize", None, ValidationProblemKind::BadSize { expected: 8, actual: 4 }),
+            problem("TestEnum", Some("first"), ValidationProblemKind::DuplicateOption),
+            problem("TestStruct", Some("scale"), ValidationProblemKind::DefaultOutOfRange { value: StaticValue::Float(2.0) }),
+            problem("TestStruct", Some("other"), ValidationProblemKind::MissingReference { reference: "TestMissing".to_owned() })
+        ]);
+        assert!(!report.is_valid());
+        assert!(alloc::format!("{report}").contains("TestStruct::other: references TestMissing, which does not exist\n"));
+        assert_eq!(definitions.try_finalize(), Err(report.problems[0].clone().into()));
+
+        assert!(load_all_definitions().validate().is_valid());
+    }
+}
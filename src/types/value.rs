@@ -0,0 +1,205 @@
+use alloc::borrow::ToOwned;
+use core::cmp::Ordering;
+
+use super::*;
+
+impl StaticValue {
+    /// Returns true if the value is zero (or an empty string).
+    ///
+    /// This matches how "default if zero" fields such as [`DefaultBehavior::default_on_cache`] are treated.
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Self::Float(f) => *f == 0.0,
+            Self::Uint(u) => *u == 0,
+            Self::Int(i) => *i == 0,
+            Self::String(s) => s.is_empty()
+        }
+    }
+
+    /// Returns true if the value is numeric (i.e. not a string).
+    pub const fn is_numeric(&self) -> bool {
+        !matches!(self, Self::String(_))
+    }
+
+    /// Returns true if both values can be compared with each other.
+    ///
+    /// Numeric values are comparable with each other regardless of variant, and strings are only
+    /// comparable with strings.
+    pub const fn is_comparable_with(&self, other: &StaticValue) -> bool {
+        self.is_numeric() == other.is_numeric()
+    }
+
+    /// Compare two values numerically, regardless of variant.
+    ///
+    /// Integers are compared exactly, even across [`StaticValue::Uint`] and [`StaticValue::Int`].
+    /// Comparisons involving a float are done as `f64`. Strings are only comparable with strings.
+    ///
+    /// Unlike `==`, which also requires the same variant, `Uint(5)` and `Int(5)` compare as equal.
+    pub fn numeric_cmp(&self, other: &StaticValue) -> Option<Ordering> {
+        match (self, other) {
+            (Self::String(a), Self::String(b)) => Some(a.cmp(b)),
+            (Self::Uint(a), Self::Uint(b)) => Some(a.cmp(b)),
+            (Self::Int(a), Self::Int(b)) => Some(a.cmp(b)),
+            (Self::Uint(a), Self::Int(b)) => Some((*a as i128).cmp(&(*b as i128))),
+            (Self::Int(a), Self::Uint(b)) => Some((*a as i128).cmp(&(*b as i128))),
+            (a, b) => a.as_f64()?.partial_cmp(&b.as_f64()?)
+        }
+    }
+
+    /// Clamp the value to be within `minimum` and `maximum`, if set.
+    ///
+    /// If the value is out of range, the bound it exceeded is returned. Values that cannot be
+    /// compared to a bound are not clamped by it.
+    pub fn clamp(&self, minimum: Option<&StaticValue>, maximum: Option<&StaticValue>) -> StaticValue {
+        if let Some(minimum) = minimum.filter(|m| self.numeric_cmp(m) == Some(Ordering::Less)) {
+            return minimum.to_owned()
+        }
+        if let Some(maximum) = maximum.filter(|m| self.numeric_cmp(m) == Some(Ordering::Greater)) {
+            return maximum.to_owned()
+        }
+        self.to_owned()
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Float(f) => Some(*f as f64),
+            Self::Uint(u) => Some(*u as f64),
+            Self::Int(i) => Some(*i as f64),
+            Self::String(_) => None
+        }
+    }
+}
+
+impl StructField {
+    /// Get the minimum and maximum values of the field, if either is set.
+    ///
+    /// If only one is set, the other is the lowest or highest value the field's type can hold.
+    /// Both values are the same variant as the field's primitive value type. String fields can
+    /// have a minimum and maximum, but have no bounds.
+    ///
+    /// Returns an error if the minimum or maximum does not match the field's type, or if the
+    /// minimum exceeds the maximum.
+    pub fn bounds(&self) -> Result<Option<(StaticValue, StaticValue)>, DefinitionError> {
+        if self.minimum.is_none() && self.maximum.is_none() {
+            return Ok(None)
+        }
+
+        let key = if self.minimum.is_some() { "minimum" } else { "maximum" };
+        let invalid = |key: &str, expected| DefinitionError::InvalidValue { object: self.name.to_owned(), key: key.to_owned(), expected };
+        let (object, value_type) = match &self.field_type {
+            StructFieldType::Object(o) => (o, o.primitive_value_type().ok_or_else(|| invalid(key, "only set on a field with a primitive value type"))?),
+            _ => return Err(invalid(key, "only set on a field with a primitive value type"))
+        };
+        if self.minimum.iter().chain(self.maximum.iter()).any(|v| core::mem::discriminant(v) != core::mem::discriminant(&value_type)) {
+            return Err(invalid(key, "the same type as the field"))
+        }
+        if let (Some(minimum), Some(maximum)) = (&self.minimum, &self.maximum) {
+            if minimum.numeric_cmp(maximum) == Some(Ordering::Greater) {
+                return Err(invalid("minimum", "no greater than the maximum"))
+            }
+        }
+
+        let component_bits = match object {
+            // Reflexive bounds are on the element count, which is 32-bit.
+            FieldObject::Reflexive(_) => 32,
+            o => o.primitive_size() / o.composite_count() * 8
+        };
+        let shift = 64usize.checked_sub(component_bits).filter(|s| *s < 64).ok_or_else(|| invalid(key, "set on a field with a supported integer size"));
+        let (lowest, highest) = match value_type {
+            StaticValue::Float(_) => (StaticValue::Float(f32::MIN), StaticValue::Float(f32::MAX)),
+            StaticValue::Uint(_) => (StaticValue::Uint(0), StaticValue::Uint(u64::MAX >> shift?)),
+            StaticValue::Int(_) => {
+                let shift = shift?;
+                (StaticValue::Int(i64::MIN >> shift), StaticValue::Int(i64::MAX >> shift))
+            },
+            StaticValue::String(_) => return Ok(None)
+        };
+
+        Ok(Some((self.minimum.to_owned().unwrap_or(lowest), self.maximum.to_owned().unwrap_or(highest))))
+    }
+
+    /// How far the squared length of a normalized value can be from 1 for
+    /// [`StructField::is_valid_normal`].
+    pub const NORMAL_TOLERANCE: f64 = 0.001;
+
+    /// Check one element of the field (e.g. the `x`, `y`, and `z` of a vector) against
+    /// [`StructField::normalized`].
+    ///
+    /// Returns true if the field does not need to be normalized, or if the value is unit length
+    /// (within [`StructField::NORMAL_TOLERANCE`]). Zero vectors are not normalized.
+    pub fn is_valid_normal(&self, components: &[f32]) -> bool {
+        if !self.normalized {
+            return true
+        }
+        let squared_length: f64 = components.iter().map(|c| (*c as f64) * (*c as f64)).sum();
+        (squared_length - 1.0).abs() <= Self::NORMAL_TOLERANCE
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::borrow::ToOwned;
+    use core::cmp::Ordering;
+
+    use crate::*;
+
+    #[test]
+    fn compare_and_clamp() {
+        assert_eq!(StaticValue::Uint(5).numeric_cmp(&StaticValue::Int(5)), Some(Ordering::Equal));
+        assert_ne!(StaticValue::Uint(5), StaticValue::Int(5));
+        assert_eq!(StaticValue::Float(2.0).numeric_cmp(&StaticValue::Uint(2)), Some(Ordering::Equal));
+        assert_eq!(StaticValue::Int(-1).numeric_cmp(&StaticValue::Uint(0)), Some(Ordering::Less));
+        assert_eq!(StaticValue::Uint(u64::MAX).numeric_cmp(&StaticValue::Int(i64::MAX)), Some(Ordering::Greater));
+        assert!(StaticValue::String("a".to_owned()).numeric_cmp(&StaticValue::Uint(0)).is_none());
+
+        let (minimum, maximum) = (StaticValue::Float(0.0), StaticValue::Float(1.0));
+        assert_eq!(StaticValue::Float(1.5).clamp(Some(&minimum), Some(&maximum)), maximum);
+        assert_eq!(StaticValue::Float(-0.5).clamp(Some(&minimum), None), minimum);
+        assert_eq!(StaticValue::Float(0.5).clamp(Some(&minimum), Some(&maximum)), StaticValue::Float(0.5));
+
+        assert!(StaticValue::Int(0).is_zero());
+        assert!(StaticValue::String("".to_owned()).is_zero());
+        assert!(!StaticValue::Float(0.1).is_zero());
+    }
+
+    #[test]
+    fn normals_are_unit_length() {
+        let definitions = load_all_definitions();
+        let rotation = definitions.get_struct("ModelNode").unwrap().field("default rotation").unwrap();
+        assert!(rotation.normalized);
+        assert!(rotation.is_valid_normal(&[0.0, 0.0, 0.0, 1.0]));
+        assert!(rotation.is_valid_normal(&[0.5, 0.5, 0.5, 0.5]));
+        assert!(!rotation.is_valid_normal(&[0.0, 0.0, 0.0, 0.0]));
+        assert!(!rotation.is_valid_normal(&[1.0, 0.0, 0.0, 1.0]));
+
+        let translation = definitions.get_struct("ModelNode").unwrap().field("default translation").unwrap();
+        assert!(translation.is_valid_normal(&[2.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn bounds_fill_in_type_range() {
+        let definitions = load_all_definitions();
+        for o in definitions.objects.values() {
+            let NamedObject::Struct(s) = o else { continue };
+            for f in &s.fields {
+                let Some((minimum, maximum)) = f.bounds().unwrap() else { continue };
+                assert_ne!(minimum.numeric_cmp(&maximum), Some(Ordering::Greater));
+                if let Some(m) = &f.minimum {
+                    assert_eq!(&minimum, m);
+                }
+                if let Some(m) = &f.maximum {
+                    assert_eq!(&maximum, m);
+                }
+            }
+        }
+
+        let mut field = definitions.get_struct("ModelNode").unwrap().field("default rotation").unwrap().to_owned();
+        field.minimum = Some(StaticValue::Float(1.0));
+        field.maximum = Some(StaticValue::Float(0.0));
+        assert!(matches!(field.bounds(), Err(DefinitionError::InvalidValue { key, .. }) if key == "minimum"));
+        field.maximum = Some(StaticValue::Uint(2));
+        assert!(matches!(field.bounds(), Err(DefinitionError::InvalidValue { key, .. }) if key == "minimum"));
+        field.field_type = StructFieldType::Object(FieldObject::NamedObject("ModelNode".to_owned()));
+        assert!(field.bounds().is_err());
+    }
+}
@@ -0,0 +1,233 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+use super::*;
+
+/// A borrowed [`Struct`] along with the definitions it belongs to.
+///
+/// This dereferences to the struct, so its fields can be read directly, while methods that need
+/// other definitions (e.g. to resolve field types) do not need them passed in.
+#[derive(Copy, Clone)]
+pub struct StructView<'a> {
+    definitions: &'a ParsedDefinitions,
+    inner: &'a Struct
+}
+
+/// A borrowed [`StructField`] along with its struct and the definitions it belongs to.
+///
+/// This dereferences to the field.
+#[derive(Copy, Clone)]
+pub struct FieldView<'a> {
+    parent: StructView<'a>,
+    inner: &'a StructField
+}
+
+/// What a [`FieldView`] refers to, resolved against its definitions.
+#[derive(Copy, Clone)]
+#[non_exhaustive]
+pub enum ResolvedFieldType<'a> {
+    /// An inline struct.
+    Struct(StructView<'a>),
+
+    /// An enum.
+    Enum(&'a Enum),
+
+    /// A bitfield.
+    Bitfield(&'a Bitfield),
+
+    /// A reflexive of the given struct.
+    Reflexive(StructView<'a>),
+
+    /// Anything else, such as primitives, padding, and editor sections.
+    Other(&'a StructFieldType)
+}
+
+impl ParsedDefinitions {
+    /// Get a view of a struct by name, or `None` if it does not exist or is not a struct.
+    pub fn struct_view(&self, name: &str) -> Option<StructView<'_>> {
+        self.get_struct(name).map(|s| s.view(self))
+    }
+}
+
+impl TagGroup {
+    /// Get the base struct of the group.
+    ///
+    /// This always succeeds with finalized definitions, since finalizing checks that every group's
+    /// struct exists.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`TagGroup::struct_name`] does not refer to a struct in `definitions`.
+    pub fn definition<'a>(&self, definitions: &'a ParsedDefinitions) -> &'a Struct {
+        definitions
+            .get_struct(&self.struct_name)
+            .unwrap_or_else(|| panic!("group {} refers to struct {} which does not exist", self.name, self.struct_name))
+    }
+
+    /// Get every struct, enum, and bitfield reachable from the group's base struct through inline
+    /// structs and reflexives, including the base struct itself, in name order.
+    ///
+    /// Supergroups are included, since their base structs are inline in this group's base struct.
+    pub fn object_closure<'a>(&self, definitions: &'a ParsedDefinitions) -> Vec<&'a NamedObject> {
+        let mut names = BTreeSet::new();
+        definitions.collect_reachable_objects(&self.struct_name, &mut names);
+        names.iter().filter_map(|n| definitions.objects.get(n)).collect()
+    }
+}
+
+impl Struct {
+    /// Get a view of this struct that carries the definitions it belongs to.
+    pub fn view<'a>(&'a self, definitions: &'a ParsedDefinitions) -> StructView<'a> {
+        StructView { definitions, inner: self }
+    }
+}
+
+impl<'a> StructView<'a> {
+    /// Get the definitions this struct belongs to.
+    pub fn definitions(&self) -> &'a ParsedDefinitions {
+        self.definitions
+    }
+
+    /// Get the underlying struct.
+    pub fn get(&self) -> &'a Struct {
+        self.inner
+    }
+
+    /// Get the size of the struct in bytes.
+    pub fn size(&self) -> usize {
+        self.inner.size
+    }
+
+    /// Iterate through all fields, including padding and editor sections.
+    pub fn fields(&self) -> impl Iterator<Item = FieldView<'a>> + 'a {
+        let parent = *self;
+        self.inner.fields.iter().map(move |inner| FieldView { parent, inner })
+    }
+
+    /// Get a field by name.
+    pub fn field(&self, name: &str) -> Option<FieldView<'a>> {
+        self.inner.field(name).map(|inner| FieldView { parent: *self, inner })
+    }
+}
+
+impl<'a> FieldView<'a> {
+    /// Get the struct this field is in.
+    pub fn parent(&self) -> StructView<'a> {
+        self.parent
+    }
+
+    /// Get the underlying field.
+    pub fn get(&self) -> &'a StructField {
+        self.inner
+    }
+
+    /// Get the size of the field in bytes, including all elements if it is an array or bounds.
+    pub fn size(&self) -> usize {
+        self.inner.size(self.parent.definitions)
+    }
+
+    /// Resolve the field's type against its definitions.
+    ///
+    /// Named objects and reflexives that do not exist resolve to [`ResolvedFieldType::Other`]; this
+    /// does not happen with finalized definitions.
+    pub fn resolved_type(&self) -> ResolvedFieldType<'a> {
+        let definitions = self.parent.definitions;
+        let other = ResolvedFieldType::Other(&self.inner.field_type);
+        match &self.inner.field_type {
+            StructFieldType::Object(FieldObject::NamedObject(n)) => match definitions.get_object(n) {
+                Some(NamedObject::Struct(s)) => ResolvedFieldType::Struct(s.view(definitions)),
+                Some(NamedObject::Enum(e)) => ResolvedFieldType::Enum(e),
+                Some(NamedObject::Bitfield(b)) => ResolvedFieldType::Bitfield(b),
+                None => other
+            },
+            StructFieldType::Object(FieldObject::Reflexive(r)) => definitions.struct_view(r).map_or(other, ResolvedFieldType::Reflexive),
+            _ => other
+        }
+    }
+
+    /// Get the maximum count of this field for the given engine.
+    ///
+    /// The engine's own limit is used first, followed by the limits of engines it inherits, and
+    /// then the default limit. Returns `None` if no limit applies.
+    pub fn limit_for_engine(&self, engine: &str) -> Option<usize> {
+        let limits = self.inner.limit.as_ref()?;
+        self.parent
+            .definitions
+            .engine_lineage(engine)
+            .find_map(|e| limits.get(&LimitType::Engine(e.name.clone())))
+            .or_else(|| limits.get(&LimitType::Default))
+            .copied()
+    }
+
+    /// Get the maximum count of this field for the given engine under a limit profile.
+    ///
+    /// If the field has a limit for the profile, that limit is used. Otherwise, or if `profile` is
+    /// `None`, this is the same as [`FieldView::limit_for_engine`].
+    pub fn limit_for_profile(&self, engine: &str, profile: Option<&str>) -> Option<usize> {
+        let profile_limit = profile.and_then(|p| self.inner.limit.as_ref()?.get(&LimitType::Profile(p.to_owned())).copied());
+        profile_limit.or_else(|| self.limit_for_engine(engine))
+    }
+
+    /// Get the number of elements of this field used by the given engine.
+    ///
+    /// This is [`StructField::count`] unless the engine (or an engine it inherits) has its own
+    /// array length in [`StructField::engine_counts`].
+    pub fn count_for_engine(&self, engine: &str) -> usize {
+        self.parent
+            .definitions
+            .engine_lineage(engine)
+            .find_map(|e| self.inner.engine_counts.get(&e.name))
+            .copied()
+            .unwrap_or_else(|| self.inner.count.field_count())
+    }
+
+    /// Get the maximum count of this field allowed by the editor, if any.
+    pub fn editor_limit(&self) -> Option<usize> {
+        self.inner.limit.as_ref()?.get(&LimitType::Editor).copied()
+    }
+}
+
+impl Deref for StructView<'_> {
+    type Target = Struct;
+    fn deref(&self) -> &Self::Target {
+        self.inner
+    }
+}
+
+impl Deref for FieldView<'_> {
+    type Target = StructField;
+    fn deref(&self) -> &Self::Target {
+        self.inner
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn views_resolve_fields() {
+        let definitions = load_all_definitions();
+        let scenario = definitions.struct_view("Scenario").unwrap();
+        assert_eq!(scenario.size(), scenario.fields().map(|f| f.size()).sum::<usize>());
+
+        let reflexive = scenario
+            .fields()
+            .find_map(|f| match f.resolved_type() {
+                ResolvedFieldType::Reflexive(s) => Some((f, s)),
+                _ => None
+            })
+            .expect("scenario should have reflexives");
+        assert!(reflexive.1.size() > 0);
+        assert_eq!(reflexive.0.parent().name, "Scenario");
+        assert_eq!(definitions.groups["scenario"].definition(definitions).name, "Scenario");
+
+        let closure = definitions.groups["weapon"].object_closure(definitions);
+        for name in ["Weapon", "Item", "Object", "WeaponTrigger"] {
+            assert!(closure.iter().any(|o| o.name() == name), "{name} is missing");
+        }
+        assert!(closure.iter().all(|o| o.name() != "Scenario"));
+    }
+}
@@ -0,0 +1,137 @@
+use alloc::vec::Vec;
+
+use super::*;
+
+/// Receives callbacks while walking through a group's fields.
+///
+/// See [`ParsedDefinitions::walk_group`].
+pub trait DefinitionVisitor<'a> {
+    /// Called for every field (excluding padding and editor sections), before descending into it.
+    ///
+    /// `path` contains the names of the fields leading to (and including) this field. `offset` is
+    /// the offset of the field from the start of its block, which is the group's base struct or,
+    /// inside a reflexive, the reflexive's element.
+    ///
+    /// Return false to not descend into the field's struct or reflexive.
+    fn visit_field(&mut self, path: &[&'a str], offset: usize, field: FieldView<'a>) -> bool;
+
+    /// Called before descending into a reflexive's element struct, where offsets restart at 0.
+    fn enter_reflexive(&mut self, path: &[&'a str], element: StructView<'a>) {
+        let _ = (path, element);
+    }
+
+    /// Called after leaving a reflexive's element struct.
+    fn leave_reflexive(&mut self, path: &[&'a str], element: StructView<'a>) {
+        let _ = (path, element);
+    }
+}
+
+impl<'a, F: FnMut(&[&'a str], usize, FieldView<'a>)> DefinitionVisitor<'a> for F {
+    fn visit_field(&mut self, path: &[&'a str], offset: usize, field: FieldView<'a>) -> bool {
+        self(path, offset, field);
+        true
+    }
+}
+
+impl ParsedDefinitions {
+    /// Walk through every field of a group, descending into inline structs and reflexives.
+    ///
+    /// Fields are visited in order. Structs that contain themselves (directly or through
+    /// reflexives) are not descended into again.
+    ///
+    /// ```
+    /// let definitions = ringhopper_definitions::load_all_definitions();
+    /// let mut fields = 0;
+    /// definitions.walk_group(&definitions.groups["wind"], &mut |_: &[&str], _, _| fields += 1);
+    /// assert!(fields > 0);
+    /// ```
+    pub fn walk_group<'a, V: DefinitionVisitor<'a> + ?Sized>(&'a self, group: &TagGroup, visitor: &mut V) {
+        self.walk_struct(group.definition(self).view(self), visitor);
+    }
+
+    /// Walk through every field of a struct, descending into inline structs and reflexives.
+    ///
+    /// See [`ParsedDefinitions::walk_group`].
+    pub fn walk_struct<'a, V: DefinitionVisitor<'a> + ?Sized>(&'a self, base: StructView<'a>, visitor: &mut V) {
+        walk(base, 0, &mut Vec::new(), &mut Vec::new(), visitor);
+    }
+}
+
+fn walk<'a, V: DefinitionVisitor<'a> + ?Sized>(s: StructView<'a>, base_offset: usize, path: &mut Vec<&'a str>, structs: &mut Vec<&'a str>, visitor: &mut V) {
+    let name = s.get().name.as_str();
+    if structs.contains(&name) {
+        return
+    }
+    structs.push(name);
+
+    for f in s.fields() {
+        if !matches!(f.field_type, StructFieldType::Object(_)) {
+            continue
+        }
+
+        let offset = base_offset + f.relative_offset;
+        path.push(f.get().name.as_str());
+        if visitor.visit_field(path, offset, f) {
+            match f.resolved_type() {
+                ResolvedFieldType::Struct(inner) => walk(inner, offset, path, structs, visitor),
+                ResolvedFieldType::Reflexive(element) => {
+                    visitor.enter_reflexive(path, element);
+                    walk(element, 0, path, structs, visitor);
+                    visitor.leave_reflexive(path, element);
+                },
+                _ => ()
+            }
+        }
+        path.pop();
+    }
+
+    structs.pop();
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use crate::*;
+
+    #[derive(Default)]
+    struct Recorder {
+        fields: Vec<(String, usize)>,
+        depth: usize,
+        max_depth: usize
+    }
+
+    impl<'a> DefinitionVisitor<'a> for Recorder {
+        fn visit_field(&mut self, path: &[&'a str], offset: usize, _: FieldView<'a>) -> bool {
+            self.fields.push((path.join("/"), offset));
+            true
+        }
+
+        fn enter_reflexive(&mut self, _: &[&'a str], _: StructView<'a>) {
+            self.depth += 1;
+            self.max_depth = self.max_depth.max(self.depth);
+        }
+
+        fn leave_reflexive(&mut self, _: &[&'a str], _: StructView<'a>) {
+            self.depth -= 1;
+        }
+    }
+
+    #[test]
+    fn walk_group_visits_nested_fields() {
+        let definitions = load_all_definitions();
+        let mut recorder = Recorder::default();
+        definitions.walk_group(&definitions.groups["string_list"], &mut recorder);
+        assert_eq!(recorder.fields[0], ("strings".into(), 0));
+        assert!(recorder.fields.iter().any(|(p, o)| p == "strings/string" && *o == 0));
+        assert_eq!((recorder.depth, recorder.max_depth), (0, 1));
+
+        let mut offsets = Vec::new();
+        definitions.walk_group(&definitions.groups["scenario"], &mut |path: &[&str], offset, field: FieldView<'_>| {
+            if path.len() == 1 {
+                offsets.push((offset, field.relative_offset));
+            }
+        });
+        assert!(offsets.iter().all(|(a, b)| a == b));
+    }
+}
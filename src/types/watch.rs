@@ -0,0 +1,217 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use super::*;
+
+/// Describes which definition files changed between two generations.
+///
+/// All paths are relative to the watched directory and use `/` as a separator, matching
+/// [`Struct::definition_file`] and friends.
+#[derive(Clone, Default, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct DefinitionFileChanges {
+    /// Files that did not exist in the previous generation.
+    pub added: Vec<String>,
+
+    /// Files that existed in the previous generation but no longer exist.
+    pub removed: Vec<String>,
+
+    /// Files whose contents changed.
+    pub modified: Vec<String>
+}
+
+impl DefinitionFileChanges {
+    /// Returns true if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// A freshly loaded generation of definitions.
+#[non_exhaustive]
+pub struct DefinitionsReload {
+    /// Generation number, starting at 1 for the first load and incrementing on every reload.
+    pub generation: u64,
+
+    /// The newly loaded and finalized definitions.
+    pub definitions: ParsedDefinitions,
+
+    /// Which files changed since the previous generation.
+    pub changes: DefinitionFileChanges
+}
+
+/// Loads definitions from a directory of JSON files and reloads them when the files change.
+///
+/// The directory is laid out like the `json` directory of this crate, and every `.json` file in it
+/// (recursively) is loaded.
+///
+/// This does not watch the filesystem on its own; call [`DefinitionsWatcher::reload_if_changed`]
+/// whenever a file watcher (or a timer) says something may have changed.
+pub struct DefinitionsWatcher {
+    directory: PathBuf,
+    include_builtin: bool,
+    files: BTreeMap<String, Vec<u8>>,
+    generation: u64
+}
+
+impl DefinitionsWatcher {
+    /// Watch the given directory.
+    ///
+    /// Nothing is loaded until [`DefinitionsWatcher::reload_if_changed`] is called.
+    pub fn new<P: Into<PathBuf>>(directory: P) -> Self {
+        Self {
+            directory: directory.into(),
+            include_builtin: false,
+            files: BTreeMap::new(),
+            generation: 0
+        }
+    }
+
+    /// Set whether the built-in definitions are loaded alongside the directory.
+    ///
+    /// This is useful for directories that only add new definitions. Definitions in the directory
+    /// cannot replace built-in ones.
+    pub fn with_builtin_definitions(mut self, include_builtin: bool) -> Self {
+        self.include_builtin = include_builtin;
+        self
+    }
+
+    /// Get the watched directory.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Get the generation of the last load, or 0 if nothing has been loaded yet.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Reload the definitions if any definition file was added, removed, or modified since the
+    /// last load.
+    ///
+    /// The first call always loads. Returns `None` if nothing changed.
+    ///
+    /// If the definitions are malformed, an error of kind [`io::ErrorKind::InvalidData`] wrapping a
+    /// [`DefinitionError`] is returned, and the previous generation is kept. The next call tries
+    /// again, so a half-saved file does not need any special handling.
+    pub fn reload_if_changed(&mut self) -> io::Result<Option<DefinitionsReload>> {
+        let files = read_definition_directory(&self.directory)?;
+        let changes = diff_files(&self.files, &files);
+        if self.generation != 0 && changes.is_empty() {
+            return Ok(None)
+        }
+
+        let mut objects: Vec<Map<String, Value>> = if self.include_builtin {
+            get_all_definitions()
+        }
+        else {
+            Vec::new()
+        };
+        let invalid = |e: DefinitionError| io::Error::new(io::ErrorKind::InvalidData, e);
+        for (file, data) in &files {
+            objects.extend(try_parse_definition_file(file, data).map_err(invalid)?);
+        }
+
+        let mut definitions = ParsedDefinitions::default();
+        definitions.try_load_from_json(&objects).map_err(invalid)?;
+        definitions.try_finalize().map_err(invalid)?;
+
+        self.files = files;
+        self.generation += 1;
+
+        Ok(Some(DefinitionsReload {
+            generation: self.generation,
+            definitions,
+            changes
+        }))
+    }
+}
+
+fn diff_files(old: &BTreeMap<String, Vec<u8>>, new: &BTreeMap<String, Vec<u8>>) -> DefinitionFileChanges {
+    let mut changes = DefinitionFileChanges::default();
+    for (file, data) in new {
+        match old.get(file) {
+            None => changes.added.push(file.to_owned()),
+            Some(old_data) if old_data != data => changes.modified.push(file.to_owned()),
+            Some(_) => ()
+        }
+    }
+    for file in old.keys() {
+        if !new.contains_key(file) {
+            changes.removed.push(file.to_owned());
+        }
+    }
+    changes
+}
+
+/// Read every `.json` file in a directory (recursively), keyed by its `/`-separated relative path.
+pub(crate) fn read_definition_directory(directory: &Path) -> io::Result<BTreeMap<String, Vec<u8>>> {
+    fn read_recursively(root: &Path, directory: &Path, files: &mut BTreeMap<String, Vec<u8>>) -> io::Result<()> {
+        for entry in fs::read_dir(directory)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                read_recursively(root, &path, files)?;
+                continue
+            }
+            if path.extension().is_none_or(|e| e != "json") {
+                continue
+            }
+
+            let relative = path.strip_prefix(root).expect("path should be inside of the root directory");
+            let name = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<String>>()
+                .join("/");
+            files.insert(name, fs::read(&path)?);
+        }
+        Ok(())
+    }
+
+    let mut files = BTreeMap::new();
+    read_recursively(directory, directory, &mut files)?;
+    Ok(files)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reload_only_when_changed() {
+        let directory = std::env::temp_dir().join(std::format!("ringhopper-definitions-watch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+        fs::copy(concat!(env!("CARGO_MANIFEST_DIR"), "/json/tag/wind.json"), directory.join("wind.json")).unwrap();
+
+        let mut watcher = DefinitionsWatcher::new(&directory);
+        let first = watcher.reload_if_changed().unwrap().expect("first load should always load");
+        assert_eq!(first.generation, 1);
+        assert_eq!(first.changes.added, ["wind.json"]);
+        assert!(first.definitions.groups.contains_key("wind"));
+
+        assert!(watcher.reload_if_changed().unwrap().is_none());
+
+        fs::write(directory.join("broken.json"), "[{").unwrap();
+        let error = watcher.reload_if_changed().err().expect("malformed definitions should be an error");
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(matches!(error.get_ref().and_then(|e| e.downcast_ref::<DefinitionError>()), Some(DefinitionError::InvalidJson { .. })));
+        assert_eq!(watcher.generation(), 1);
+
+        fs::remove_file(directory.join("broken.json")).unwrap();
+        fs::remove_file(directory.join("wind.json")).unwrap();
+        let second = watcher.reload_if_changed().unwrap().expect("removal should reload");
+        assert_eq!(second.generation, 2);
+        assert_eq!(second.changes.removed, ["wind.json"]);
+        assert!(second.definitions.groups.is_empty());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,136 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::borrow::ToOwned;
+
+use serde_json::Value;
+
+use super::*;
+
+/// Hint for which control an editor should use for a field.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum EditorWidget {
+    /// A slider between the field's minimum and maximum, moving in increments of
+    /// [`StructField::step`] if set.
+    Slider,
+
+    /// A color picker.
+    ColorPicker,
+
+    /// A dial for picking an angle.
+    AngleDial,
+
+    /// A tag path picker.
+    PathPicker {
+        /// Tag groups to filter by.
+        ///
+        /// Each entry references a tag group in [`ParsedDefinitions::groups`].
+        allowed_groups: Vec<String>
+    }
+}
+
+impl EditorWidget {
+    /// Load a widget hint from its JSON representation, which is the widget type (e.g. `"slider"`).
+    ///
+    /// Path pickers are always filtered by the allowed groups of the field's tag reference.
+    pub(crate) fn load_from_json(field_name: &str, widget: &Value, object: &FieldObject) -> Result<Self, DefinitionError> {
+        let invalid = |expected: &'static str| DefinitionError::InvalidValue { object: field_name.to_owned(), key: "widget".to_owned(), expected };
+        let widget_type = widget.as_str().ok_or_else(|| invalid("a string"))?;
+
+        match widget_type {
+            "slider" if object.primitive_value_type().is_some_and(|v| v.is_numeric()) => Ok(Self::Slider),
+            "slider" => Err(invalid("a widget for a numeric field")),
+            "color" if matches!(object, FieldObject::ColorRGB | FieldObject::ColorARGB | FieldObject::Pixel32) => Ok(Self::ColorPicker),
+            "color" => Err(invalid("a widget for a color field")),
+            "angle" if matches!(object, FieldObject::Angle | FieldObject::Euler2D | FieldObject::Euler3D) => Ok(Self::AngleDial),
+            "angle" => Err(invalid("a widget for an angle field")),
+            "path" => match object {
+                FieldObject::TagReference { allowed_groups } => Ok(Self::PathPicker { allowed_groups: allowed_groups.to_owned() }),
+                _ => Err(invalid("a widget for a tag reference"))
+            },
+            _ => Err(invalid("slider, color, angle, or path"))
+        }
+    }
+}
+
+impl StructField {
+    /// Round a value to the nearest multiple of [`StructField::step`].
+    ///
+    /// Values are returned unchanged if the field has no step.
+    pub fn round_to_step(&self, value: f64) -> f64 {
+        match self.step {
+            Some(step) => round_half_away_from_zero(value / step) * step,
+            None => value
+        }
+    }
+
+    /// Get the editor control to use for this field, if any.
+    ///
+    /// This is [`StructField::widget`] if set. Otherwise, colors use a color picker, angles use an
+    /// angle dial, tag references use a path picker, and numeric fields with both a minimum and
+    /// maximum use a slider.
+    pub fn editor_widget(&self) -> Option<EditorWidget> {
+        // Path pickers are derived from the field so they include groups added when finalizing.
+        if let Some(w) = self.widget.as_ref().filter(|w| !matches!(w, EditorWidget::PathPicker { .. })) {
+            return Some(w.to_owned())
+        }
+        let StructFieldType::Object(object) = &self.field_type else {
+            return None
+        };
+        match object {
+            FieldObject::ColorRGB | FieldObject::ColorARGB | FieldObject::Pixel32 => Some(EditorWidget::ColorPicker),
+            FieldObject::Angle | FieldObject::Euler2D | FieldObject::Euler3D => Some(EditorWidget::AngleDial),
+            FieldObject::TagReference { allowed_groups } => Some(EditorWidget::PathPicker { allowed_groups: allowed_groups.to_owned() }),
+            _ if self.minimum.is_some() && self.maximum.is_some() => Some(EditorWidget::Slider),
+            _ => None
+        }
+    }
+}
+
+/// Round half away from zero without needing `std`.
+fn round_half_away_from_zero(value: f64) -> f64 {
+    // Values this large (and infinities and NaN) are already whole, and may not fit in an i64.
+    if value.is_nan() || value.abs() >= (1u64 << f64::MANTISSA_DIGITS) as f64 {
+        return value
+    }
+    let truncated = value as i64 as f64;
+    let fraction = value - truncated;
+    if fraction >= 0.5 {
+        truncated + 1.0
+    }
+    else if fraction <= -0.5 {
+        truncated - 1.0
+    }
+    else {
+        truncated
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::*;
+
+    #[test]
+    fn widgets_are_checked_against_fields() {
+        let widget = |widget, object| EditorWidget::load_from_json("test", &widget, &object);
+        assert_eq!(widget(json!("color"), FieldObject::ColorRGB), Ok(EditorWidget::ColorPicker));
+        assert_eq!(widget(json!("slider"), FieldObject::F32), Ok(EditorWidget::Slider));
+        for (w, object) in [(json!({ "type": "slider" }), FieldObject::F32), (json!("color"), FieldObject::F32), (json!("angle"), FieldObject::U8), (json!("path"), FieldObject::F32), (json!("dial"), FieldObject::F32)] {
+            assert!(matches!(widget(w, object), Err(DefinitionError::InvalidValue { key, .. }) if key == "widget"));
+        }
+    }
+
+    #[test]
+    fn rounding_works_for_every_finite_value() {
+        assert_eq!(super::round_half_away_from_zero(2.5), 3.0);
+        assert_eq!(super::round_half_away_from_zero(-2.5), -3.0);
+        assert_eq!(super::round_half_away_from_zero(-0.4), 0.0);
+        assert_eq!(super::round_half_away_from_zero(1e300), 1e300);
+        assert_eq!(super::round_half_away_from_zero(-1e19), -1e19);
+        assert_eq!(super::round_half_away_from_zero(4503599627370495.5), 4503599627370496.0);
+        assert_eq!(super::round_half_away_from_zero(f64::INFINITY), f64::INFINITY);
+    }
+}